@@ -114,8 +114,8 @@ impl<V: Bounded> BoundingBox<V> {
     /// let mut bdd_box = BoundingBox::new();
     /// bdd_box.push(Vector2::new(-1.0,  1.0));
     /// bdd_box.push(Vector2::new(1.0,  -1.0));
-    /// bdd_box.push(Vector2::new(std::f64::NAN, 1.0));
-    /// bdd_box.push(Vector2::new(-1.0, std::f64::NAN));
+    /// bdd_box.push(Vector2::new(f64::NAN, 1.0));
+    /// bdd_box.push(Vector2::new(-1.0, f64::NAN));
     /// assert_eq!(bdd_box.min(), Vector2::new(-1.0,  -1.0));
     /// assert_eq!(bdd_box.max(), Vector2::new(1.0,  1.0));
     /// ```
@@ -244,7 +244,7 @@ impl<V: Bounded> BoundingBox<V> {
     /// assert_eq!(bdd_box.center(), Vector2::new(2.0,  -1.0));
     /// ```
     /// # Remarks
-    /// If the bounding box is empty, returned vector consists `std::f64::NAN` components.
+    /// If the bounding box is empty, returned vector consists `f64::NAN` components.
     /// ```
     /// use truck_base::{cgmath64::*, bounding_box::*, tolerance::*};
     /// let bdd_box = BoundingBox::<Vector3>::new();
@@ -108,6 +108,7 @@ where
                 SPHint2D::Range(ranges.0, ranges.1)
             }
             SPHint1D::None => SPHint2D::None,
+            SPHint1D::Global => SPHint2D::Global,
         };
         let (x, y) = self.surface.search_parameter(point, shint, trials)?;
         self.curve.search_parameter(Point2::new(x, y), hint, trials)
@@ -133,7 +134,7 @@ where
             SPHint1D::Range(x, y) => {
                 algo::curve::presearch(self, point, (x, y), PRESEARCH_DIVISION)
             }
-            SPHint1D::None => {
+            SPHint1D::None | SPHint1D::Global => {
                 algo::curve::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
         };
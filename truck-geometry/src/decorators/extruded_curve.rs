@@ -90,7 +90,7 @@ impl<C: ParametricCurve2D + BoundedCurve> SearchParameter<D2> for ExtrudedCurve<
             SPHint2D::Range(range0, range1) => {
                 algo::surface::presearch(self, point, (range0, range1), PRESEARCH_DIVISION)
             }
-            SPHint2D::None => {
+            SPHint2D::None | SPHint2D::Global => {
                 algo::surface::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
         };
@@ -112,7 +112,7 @@ impl<C: ParametricCurve3D + BoundedCurve> SearchParameter<D2> for ExtrudedCurve<
             SPHint2D::Range(range0, range1) => {
                 algo::surface::presearch(self, point, (range0, range1), PRESEARCH_DIVISION)
             }
-            SPHint2D::None => {
+            SPHint2D::None | SPHint2D::Global => {
                 algo::surface::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
         };
@@ -134,7 +134,7 @@ impl<C: ParametricCurve3D + BoundedCurve> SearchNearestParameter<D2> for Extrude
             SPHint2D::Range(range0, range1) => {
                 algo::surface::presearch(self, point, (range0, range1), PRESEARCH_DIVISION)
             }
-            SPHint2D::None => {
+            SPHint2D::None | SPHint2D::Global => {
                 algo::surface::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
         };
@@ -140,10 +140,33 @@ pub struct TrimmedCurve<C> {
     range: (f64, f64),
 }
 
+/// trimmed surface for parametric surface, restricted to a rectangle in the parameter space
+/// # Examples
+/// ```
+/// use truck_geometry::prelude::*;
+/// let plane = Plane::new(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// );
+/// assert_eq!(plane.range_tuple(), ((0.0, 1.0), (0.0, 1.0)));
+///
+/// // restrict the plane to another rectangle
+/// let surface = TrimmedSurface::new(plane, ((0.0, 2.0), (-1.0, 1.0)));
+/// assert_eq!(surface.range_tuple(), ((0.0, 2.0), (-1.0, 1.0)));
+/// assert_eq!(surface.subs(1.0, 0.5), plane.subs(1.0, 0.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrimmedSurface<S> {
+    surface: S,
+    range: ((f64, f64), (f64, f64)),
+}
+
 mod curve_on_surface;
 mod extruded_curve;
 mod intersection_curve;
 mod processor;
 mod revolved_curve;
+mod trimmed_surface;
 mod trimmied_curve;
 pub use intersection_curve::double_projection;
@@ -218,7 +218,7 @@ impl<C: ParametricCurve3D + BoundedCurve> SearchParameter<D1> for ProjectedCurve
             SPHint1D::Range(x, y) => {
                 algo::curve::presearch(self, point, (x, y), PRESEARCH_DIVISION)
             }
-            SPHint1D::None => {
+            SPHint1D::None | SPHint1D::Global => {
                 algo::curve::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
         };
@@ -239,7 +239,7 @@ impl<C: ParametricCurve3D + BoundedCurve> SearchNearestParameter<D1> for Project
             SPHint1D::Range(x, y) => {
                 algo::curve::presearch(self, point, (x, y), PRESEARCH_DIVISION)
             }
-            SPHint1D::None => {
+            SPHint1D::None | SPHint1D::Global => {
                 algo::curve::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
         };
@@ -316,13 +316,13 @@ impl<C: ParametricCurve3D + BoundedCurve> SearchParameter<D2> for RevolutedCurve
             match hint.into() {
                 SPHint2D::Parameter(_, y) => Some((t0, y)),
                 SPHint2D::Range((_, y), _) => Some((t0, y)),
-                SPHint2D::None => Some((t0, 0.0)),
+                SPHint2D::None | SPHint2D::Global => Some((t0, 0.0)),
             }
         } else if self.is_back_fixed() && self.curve.back().near(&point) {
             match hint.into() {
                 SPHint2D::Parameter(_, y) => Some((t1, y)),
                 SPHint2D::Range(_, (_, y)) => Some((t1, y)),
-                SPHint2D::None => Some((t1, 2.0 * PI)),
+                SPHint2D::None | SPHint2D::Global => Some((t1, 2.0 * PI)),
             }
         } else {
             let proj_curve = ProjectedCurve {
@@ -334,6 +334,7 @@ impl<C: ParametricCurve3D + BoundedCurve> SearchParameter<D2> for RevolutedCurve
                 SPHint2D::Parameter(x, _) => SPHint1D::Parameter(x),
                 SPHint2D::Range((x0, _), (x1, _)) => SPHint1D::Range(x0, x1),
                 SPHint2D::None => SPHint1D::None,
+                SPHint2D::Global => SPHint1D::Global,
             };
             let t = proj_curve.search_parameter(p, hint0, trials)?;
             let p = self.curve.subs(t);
@@ -363,13 +364,13 @@ impl<C: ParametricCurve3D + BoundedCurve> SearchNearestParameter<D2> for Revolut
             match hint.into() {
                 SPHint2D::Parameter(_, y) => Some((t0, y)),
                 SPHint2D::Range((_, y), _) => Some((t0, y)),
-                SPHint2D::None => Some((t0, 0.0)),
+                SPHint2D::None | SPHint2D::Global => Some((t0, 0.0)),
             }
         } else if self.is_back_fixed() && on_axis(self.curve.back(), self.normal(t1, 0.0)) {
             match hint.into() {
                 SPHint2D::Parameter(_, y) => Some((t1, y)),
                 SPHint2D::Range(_, (_, y)) => Some((t1, y)),
-                SPHint2D::None => Some((t1, 2.0 * PI)),
+                SPHint2D::None | SPHint2D::Global => Some((t1, 2.0 * PI)),
             }
         } else {
             let proj_curve = ProjectedCurve {
@@ -381,6 +382,7 @@ impl<C: ParametricCurve3D + BoundedCurve> SearchNearestParameter<D2> for Revolut
                 SPHint2D::Parameter(x, _) => SPHint1D::Parameter(x),
                 SPHint2D::Range((x0, _), (x1, _)) => SPHint1D::Range(x0, x1),
                 SPHint2D::None => SPHint1D::None,
+                SPHint2D::Global => SPHint1D::Global,
             };
             let t = proj_curve.search_nearest_parameter(p, hint0, trials)?;
             let p = self.curve.subs(t);
@@ -422,7 +424,7 @@ where
         })
 }
 
-impl<'a> IncludeCurve<BSplineCurve<Point3>> for RevolutedCurve<&'a BSplineCurve<Point3>> {
+impl IncludeCurve<BSplineCurve<Point3>> for RevolutedCurve<&BSplineCurve<Point3>> {
     fn include(&self, curve: &BSplineCurve<Point3>) -> bool {
         let knots = curve.knot_vec().to_single_multi().0;
         let degree = usize::max(2, usize::max(curve.degree(), self.curve.degree()));
@@ -438,7 +440,7 @@ impl IncludeCurve<BSplineCurve<Point3>> for RevolutedCurve<BSplineCurve<Point3>>
     }
 }
 
-impl<'a> IncludeCurve<BSplineCurve<Point3>> for RevolutedCurve<&'a NurbsCurve<Vector4>> {
+impl IncludeCurve<BSplineCurve<Point3>> for RevolutedCurve<&NurbsCurve<Vector4>> {
     fn include(&self, curve: &BSplineCurve<Point3>) -> bool {
         let knots = curve.knot_vec().to_single_multi().0;
         let degree = curve.degree() + usize::max(2, self.curve.degree());
@@ -454,7 +456,7 @@ impl IncludeCurve<BSplineCurve<Point3>> for RevolutedCurve<NurbsCurve<Vector4>>
     }
 }
 
-impl<'a> IncludeCurve<NurbsCurve<Vector4>> for RevolutedCurve<&'a BSplineCurve<Point3>> {
+impl IncludeCurve<NurbsCurve<Vector4>> for RevolutedCurve<&BSplineCurve<Point3>> {
     fn include(&self, curve: &NurbsCurve<Vector4>) -> bool {
         let knots = curve.knot_vec().to_single_multi().0;
         let degree = curve.degree() + usize::max(2, self.curve.degree());
@@ -470,7 +472,7 @@ impl IncludeCurve<NurbsCurve<Vector4>> for RevolutedCurve<BSplineCurve<Point3>>
     }
 }
 
-impl<'a> IncludeCurve<NurbsCurve<Vector4>> for RevolutedCurve<&'a NurbsCurve<Vector4>> {
+impl IncludeCurve<NurbsCurve<Vector4>> for RevolutedCurve<&NurbsCurve<Vector4>> {
     fn include(&self, curve: &NurbsCurve<Vector4>) -> bool {
         let knots = curve.knot_vec().to_single_multi().0;
         let degree = curve.degree() + usize::max(2, self.curve.degree());
@@ -0,0 +1,214 @@
+use super::*;
+
+impl<S> TrimmedSurface<S> {
+    /// constructor
+    #[inline(always)]
+    pub const fn new(surface: S, range: ((f64, f64), (f64, f64))) -> Self {
+        Self { surface, range }
+    }
+    /// Returns the reference of non-trimmed surface
+    #[inline(always)]
+    pub const fn surface(&self) -> &S { &self.surface }
+    /// Returns the mutable reference of non-trimmed surface
+    #[inline(always)]
+    pub fn surface_mut(&mut self) -> &mut S { &mut self.surface }
+    /// Returns the trimming range
+    #[inline(always)]
+    pub const fn range(&self) -> ((f64, f64), (f64, f64)) { self.range }
+    /// Returns the mutable reference of the trimming range
+    #[inline(always)]
+    pub fn range_mut(&mut self) -> &mut ((f64, f64), (f64, f64)) { &mut self.range }
+
+    /// Replaces the hint without any information by the trimming range.
+    #[inline(always)]
+    fn hint(&self, hint: SPHint2D) -> SPHint2D {
+        match hint {
+            SPHint2D::None => SPHint2D::Range(self.range.0, self.range.1),
+            _ => hint,
+        }
+    }
+}
+
+impl<S: ParametricSurface> TrimmedSurface<S> {
+    /// Moves the parameter into the trimming range by the periods of the non-trimmed surface.
+    /// Returns `None` if the parameter is out of the range even after the move.
+    fn move_into_range(&self, (u, v): (f64, f64)) -> Option<(f64, f64)> {
+        let shift = |t: f64, (t0, t1): (f64, f64), period: Option<f64>| {
+            let t = match period {
+                Some(p) if t < t0 - TOLERANCE => t + f64::ceil((t0 - TOLERANCE - t) / p) * p,
+                Some(p) if t > t1 + TOLERANCE => t - f64::ceil((t - t1 - TOLERANCE) / p) * p,
+                _ => t,
+            };
+            match t0 - TOLERANCE <= t && t <= t1 + TOLERANCE {
+                true => Some(t),
+                false => None,
+            }
+        };
+        let ((u0, u1), (v0, v1)) = self.range;
+        Some((
+            shift(u, (u0, u1), self.surface.u_period())?,
+            shift(v, (v0, v1), self.surface.v_period())?,
+        ))
+    }
+    /// Clamps the parameter into the trimming range.
+    fn clamp(&self, (u, v): (f64, f64)) -> (f64, f64) {
+        let ((u0, u1), (v0, v1)) = self.range;
+        (u.clamp(u0, u1), v.clamp(v0, v1))
+    }
+}
+
+impl<S: ParametricSurface> ParametricSurface for TrimmedSurface<S> {
+    type Point = S::Point;
+    type Vector = S::Vector;
+    #[inline(always)]
+    fn subs(&self, u: f64, v: f64) -> Self::Point { self.surface.subs(u, v) }
+    #[inline(always)]
+    fn uder(&self, u: f64, v: f64) -> Self::Vector { self.surface.uder(u, v) }
+    #[inline(always)]
+    fn vder(&self, u: f64, v: f64) -> Self::Vector { self.surface.vder(u, v) }
+    #[inline(always)]
+    fn uuder(&self, u: f64, v: f64) -> Self::Vector { self.surface.uuder(u, v) }
+    #[inline(always)]
+    fn uvder(&self, u: f64, v: f64) -> Self::Vector { self.surface.uvder(u, v) }
+    #[inline(always)]
+    fn vvder(&self, u: f64, v: f64) -> Self::Vector { self.surface.vvder(u, v) }
+    #[inline(always)]
+    fn parameter_range(&self) -> (ParameterRange, ParameterRange) {
+        let ((u0, u1), (v0, v1)) = self.range;
+        (
+            (Bound::Included(u0), Bound::Included(u1)),
+            (Bound::Included(v0), Bound::Included(v1)),
+        )
+    }
+    /// Returns the period of the non-trimmed surface only if the trimming range covers the period.
+    #[inline(always)]
+    fn u_period(&self) -> Option<f64> {
+        let (u0, u1) = self.range.0;
+        self.surface.u_period().filter(|p| u1 - u0 >= p - TOLERANCE)
+    }
+    /// Returns the period of the non-trimmed surface only if the trimming range covers the period.
+    #[inline(always)]
+    fn v_period(&self) -> Option<f64> {
+        let (v0, v1) = self.range.1;
+        self.surface.v_period().filter(|p| v1 - v0 >= p - TOLERANCE)
+    }
+}
+
+impl<S: ParametricSurface3D> ParametricSurface3D for TrimmedSurface<S> {
+    #[inline(always)]
+    fn normal(&self, u: f64, v: f64) -> Vector3 { self.surface.normal(u, v) }
+}
+
+impl<S: ParametricSurface> BoundedSurface for TrimmedSurface<S> {}
+
+/// The parameter is searched on the non-trimmed surface and moved into the trimming range by
+/// the periods. If it is still out of the range, the search is retried in the trimming range,
+/// and the result is clamped into the range.
+impl<S> SearchNearestParameter<D2> for TrimmedSurface<S>
+where
+    S: ParametricSurface + SearchNearestParameter<D2, Point = <S as ParametricSurface>::Point>,
+    <S as ParametricSurface>::Point: Copy,
+{
+    type Point = <S as ParametricSurface>::Point;
+    fn search_nearest_parameter<H: Into<SPHint2D>>(
+        &self,
+        pt: Self::Point,
+        hint: H,
+        trials: usize,
+    ) -> Option<(f64, f64)> {
+        let hint = self.hint(hint.into());
+        let res = self.surface.search_nearest_parameter(pt, hint, trials)?;
+        self.move_into_range(res).or_else(|| {
+            let hint = SPHint2D::Range(self.range.0, self.range.1);
+            let res = self.surface.search_nearest_parameter(pt, hint, trials)?;
+            Some(self.clamp(res))
+        })
+    }
+}
+
+/// The parameter is searched on the non-trimmed surface and moved into the trimming range by
+/// the periods. Returns `None` if it is still out of the range.
+impl<S> SearchParameter<D2> for TrimmedSurface<S>
+where S: ParametricSurface + SearchParameter<D2, Point = <S as ParametricSurface>::Point>
+{
+    type Point = <S as ParametricSurface>::Point;
+    fn search_parameter<H: Into<SPHint2D>>(
+        &self,
+        pt: Self::Point,
+        hint: H,
+        trials: usize,
+    ) -> Option<(f64, f64)> {
+        let hint = self.hint(hint.into());
+        let res = self.surface.search_parameter(pt, hint, trials)?;
+        self.move_into_range(res)
+    }
+}
+
+impl<S: ParameterDivision2D> ParameterDivision2D for TrimmedSurface<S> {
+    #[inline(always)]
+    fn parameter_division(
+        &self,
+        range: ((f64, f64), (f64, f64)),
+        tol: f64,
+    ) -> (Vec<f64>, Vec<f64>) {
+        self.surface.parameter_division(range, tol)
+    }
+}
+
+impl<S: Transformed<T>, T> Transformed<T> for TrimmedSurface<S> {
+    #[inline(always)]
+    fn transform_by(&mut self, trans: T) { self.surface.transform_by(trans) }
+}
+
+#[test]
+fn trimmed_plane() {
+    let plane = Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    );
+    let surface = TrimmedSurface::new(plane, ((-1.0, 2.0), (0.5, 3.0)));
+    assert_eq!(surface.range_tuple(), ((-1.0, 2.0), (0.5, 3.0)));
+    assert_near!(surface.subs(1.5, 2.0), Point3::new(1.5, 2.0, 0.0));
+    assert_near!(surface.normal(1.5, 2.0), Vector3::unit_z());
+
+    let pt = Point3::new(0.3, 2.2, 1.0);
+    let (u, v) = surface.search_nearest_parameter(pt, None, 10).unwrap();
+    assert_near!(surface.subs(u, v), Point3::new(0.3, 2.2, 0.0));
+
+    let (udiv, vdiv) = surface.parameter_division(surface.range_tuple(), 0.01);
+    assert_eq!((udiv[0], *udiv.last().unwrap()), (-1.0, 2.0));
+    assert_eq!((vdiv[0], *vdiv.last().unwrap()), (0.5, 3.0));
+
+    let moved = surface.transformed(Matrix4::from_translation(Vector3::unit_z()));
+    assert_near!(moved.subs(1.5, 2.0), Point3::new(1.5, 2.0, 1.0));
+    assert_eq!(moved.range_tuple(), surface.range_tuple());
+}
+
+#[test]
+fn trimmed_sphere() {
+    use std::f64::consts::PI;
+    let sphere = Sphere::new(Point3::origin(), 1.0);
+    let surface = TrimmedSurface::new(sphere, ((0.0, PI), (-PI / 2.0, PI / 2.0)));
+    assert_eq!(surface.v_period(), None);
+    let whole = TrimmedSurface::new(sphere, ((0.0, PI), (0.0, 2.0 * PI)));
+    assert_eq!(whole.v_period(), Some(2.0 * PI));
+
+    // the parameter is moved into the range by the period
+    let pt = surface.subs(PI / 3.0, -PI / 4.0);
+    let (u, v) = surface.search_parameter(pt, None, 10).unwrap();
+    assert_near!(u, PI / 3.0);
+    assert_near!(v, -PI / 4.0);
+    let (u, v) = surface
+        .search_nearest_parameter(pt * 2.0, SPHint2D::Global, 10)
+        .unwrap();
+    assert_near!(u, PI / 3.0);
+    assert_near!(v, -PI / 4.0);
+
+    // the point out of the range
+    let pt = Point3::new(-1.0, 0.0, 0.0);
+    assert_eq!(surface.search_parameter(pt, None, 10), None);
+    let (u, v) = surface.search_nearest_parameter(pt, None, 10).unwrap();
+    assert_near!(u, PI / 2.0);
+    assert!(v.near(&(PI / 2.0)) || v.near(&(-PI / 2.0)));
+}
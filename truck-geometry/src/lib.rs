@@ -19,6 +19,8 @@ use truck_base::bounding_box::Bounded;
 
 const INCLUDE_CURVE_TRIALS: usize = 100;
 const PRESEARCH_DIVISION: usize = 50;
+/// the bound of the deviation of [`BSplineCurve::fair`]
+const FAIRING_TOLERANCE: f64 = 100.0 * truck_base::tolerance::TOLERANCE;
 
 /// re-export `truck_base`
 pub mod base {
@@ -466,7 +466,7 @@ impl<P: ControlPoint<f64> + Tolerance> BSplineCurve<P> {
         let n = self.control_points.len();
 
         let idx = self.knot_vec.add_knot(x);
-        let start = if idx > k { idx - k } else { 0 };
+        let start = idx.saturating_sub(k);
         let end = if idx > n {
             self.control_points.push(P::origin());
             n + 1
@@ -1194,6 +1194,10 @@ where
     /// // the point corresponding the obtained parameter is not
     /// // the globally nearest point in the curve.
     /// assert!((pt0 - pt).magnitude() > (pt1 - pt).magnitude());
+    ///
+    /// // `SPHint1D::Global` guarantees the global solution.
+    /// let t = bspcurve.search_nearest_parameter(pt, SPHint1D::Global, 100).unwrap();
+    /// assert_near!(t, 3.0);
     /// ```
     #[inline(always)]
     fn search_nearest_parameter<H: Into<SPHint1D>>(
@@ -1210,6 +1214,17 @@ where
             SPHint1D::None => {
                 algo::curve::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint1D::Global => {
+                let beziers = subdivision_search::bezier_pieces(self);
+                return subdivision_search::curve_global_nearest(
+                    self,
+                    beziers,
+                    |p| p,
+                    false,
+                    point,
+                    trial,
+                );
+            }
         };
         algo::curve::search_nearest_parameter(self, point, hint, trial)
     }
@@ -1218,7 +1233,8 @@ impl<P> SearchParameter<D1> for BSplineCurve<P>
 where
     P: ControlPoint<f64>
         + EuclideanSpace<Scalar = f64, Diff = <P as ControlPoint<f64>>::Diff>
-        + MetricSpace<Metric = f64>,
+        + MetricSpace<Metric = f64>
+        + Tolerance,
     <P as ControlPoint<f64>>::Diff: InnerSpace<Scalar = f64> + Tolerance,
 {
     type Point = P;
@@ -1232,11 +1248,459 @@ where
             SPHint1D::None => {
                 algo::curve::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint1D::Global => {
+                let beziers = subdivision_search::bezier_pieces(self);
+                let t = subdivision_search::curve_global_nearest(
+                    self,
+                    beziers,
+                    |p| p,
+                    false,
+                    point,
+                    trial,
+                )?;
+                return match point.near(&self.subs(t)) {
+                    true => Some(t),
+                    false => None,
+                };
+            }
         };
         algo::curve::search_parameter(self, point, hint, trial)
     }
 }
 
+impl<P> BSplineCurve<P>
+where
+    P: ControlPoint<f64>
+        + EuclideanSpace<Scalar = f64, Diff = <P as ControlPoint<f64>>::Diff>
+        + MetricSpace<Metric = f64>
+        + Tolerance,
+    <P as ControlPoint<f64>>::Diff: InnerSpace<Scalar = f64> + Tolerance,
+{
+    /// Searches all the parameters `t` such that `self.subs(t)` is locally nearest to `point`.
+    ///
+    /// The curve is decomposed into Bezier curves, and the pieces on which the distance is
+    /// monotone are pruned by the convex hull property. Newton's method with `trial` iterations
+    /// is applied only on the remaining small pieces. The ends of the domain are also returned
+    /// if they are local minima of the distance restricted on the domain.
+    /// The result is sorted in ascending order.
+    ///
+    /// To get only the globally nearest parameter, use `search_nearest_parameter` with `SPHint1D::Global`.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::prelude::*;
+    /// // wave: y = sin(x) like curve
+    /// let knot_vec = KnotVec::uniform_knot(3, 6);
+    /// let ctrl_pts = (0..9)
+    ///     .map(|i| Point2::new(i as f64, if i % 2 == 0 { 0.0 } else { 1.0 }))
+    ///     .collect::<Vec<_>>();
+    /// let bspcurve = BSplineCurve::new(knot_vec, ctrl_pts);
+    ///
+    /// let pt = Point2::new(4.0, 3.0);
+    /// let params = bspcurve.search_all_nearest_parameters(pt, 100);
+    /// assert!(params.len() > 1);
+    /// for t in params.iter().copied() {
+    ///     // the derivative of the distance vanishes, or t is an end of the domain.
+    ///     let f = bspcurve.der(t).dot(bspcurve.subs(t) - pt);
+    ///     assert!(f.so_small() || t == 0.0 || t == 1.0);
+    /// }
+    ///
+    /// // the global nearest point is one of the local solutions.
+    /// let t = bspcurve.search_nearest_parameter(pt, SPHint1D::Global, 100).unwrap();
+    /// let dist = bspcurve.subs(t).distance(pt);
+    /// for s in params {
+    ///     assert!(dist <= bspcurve.subs(s).distance(pt) + TOLERANCE);
+    /// }
+    /// ```
+    pub fn search_all_nearest_parameters(&self, point: P, trial: usize) -> Vec<f64> {
+        let beziers = subdivision_search::bezier_pieces(self);
+        subdivision_search::curve_local_minima(self, beziers, |p| p, false, point, false, trial)
+    }
+
+    /// Removes knots as many as possible while the deviation from the original curve is
+    /// less than or equal to `tol`.
+    ///
+    /// Each interior knot is tried to be removed by `Self::try_remove_knot()` first, and if the
+    /// exact removal fails, the control points are re-computed from both sides of the knot.
+    /// The removal is accepted only if the control points of the result, refined to the
+    /// original knot vector, are in the `tol`-neighborhood of the original control points.
+    /// By the convex hull property, the distance between the curves at the same parameter is
+    /// bounded by `tol`.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::prelude::*;
+    /// let knot_vec = KnotVec::bezier_knot(3);
+    /// let ctrl_pts = vec![
+    ///     Point2::new(0.0, 0.0),
+    ///     Point2::new(1.0, 2.0),
+    ///     Point2::new(2.0, -1.0),
+    ///     Point2::new(3.0, 1.0),
+    /// ];
+    /// let org_curve = BSplineCurve::new(knot_vec, ctrl_pts);
+    ///
+    /// // refine and perturb slightly
+    /// let mut bspcurve = org_curve.clone();
+    /// (1..10).for_each(|i| {
+    ///     bspcurve.add_knot(i as f64 / 10.0);
+    /// });
+    /// bspcurve.control_point_mut(5).y += 1.0e-4;
+    /// assert_eq!(bspcurve.knot_vec().len(), 17);
+    ///
+    /// // the exact removal fails because of the perturbation.
+    /// let mut optimized = bspcurve.clone();
+    /// optimized.optimize();
+    /// assert!(optimized.knot_vec().len() > 8);
+    ///
+    /// // the simplification removes all the interior knots.
+    /// bspcurve.simplify(1.0e-3);
+    /// assert_eq!(bspcurve.knot_vec(), &KnotVec::bezier_knot(3));
+    /// (0..=100).for_each(|i| {
+    ///     let t = i as f64 / 100.0;
+    ///     assert!(bspcurve.subs(t).distance(org_curve.subs(t)) < 1.0e-3);
+    /// });
+    /// ```
+    pub fn simplify(&mut self, tol: f64) -> &mut Self {
+        let original = self.clone();
+        let mut removed = true;
+        while removed {
+            removed = false;
+            let mut idx = self.control_points.len();
+            while idx > self.degree() + 1 {
+                idx -= 1;
+                let mut curve = self.clone();
+                if curve.try_remove_knot(idx).is_err() && !curve.remove_knot_approximately(idx) {
+                    continue;
+                }
+                if knot_removal_deviation(&original, &curve) <= tol {
+                    *self = curve;
+                    removed = true;
+                }
+            }
+        }
+        self
+    }
+
+    /// Removes the `idx`th knot, re-computing the control points by blending the ones
+    /// computed from the left and the right. Returns `false` if `idx` is not interior.
+    fn remove_knot_approximately(&mut self, idx: usize) -> bool {
+        let k = self.degree();
+        let n = self.control_points.len();
+        if k == 0 || idx < k + 1 || idx >= n {
+            return false;
+        }
+        let knot_vec = &self.knot_vec;
+        let alpha = |i: usize| {
+            let delta = knot_vec[i + k + 1] - knot_vec[i];
+            inv_or_zero(delta) * (knot_vec[idx] - knot_vec[i])
+        };
+
+        let mut left = vec![None; k];
+        let mut p = self.control_points[idx - k - 1];
+        for i in (idx - k)..idx {
+            let a = alpha(i);
+            if a.so_small() {
+                break;
+            }
+            p = p + (self.control_points[i] - p) / a;
+            left[i + k - idx] = Some(p);
+        }
+        let mut right = vec![None; k];
+        let mut p = self.control_points[idx];
+        right[k - 1] = Some(p);
+        for i in (idx - k + 1..idx).rev() {
+            let a = alpha(i);
+            if (1.0 - a).so_small() {
+                break;
+            }
+            p = self.control_points[i] + (self.control_points[i] - p) * (a / (1.0 - a));
+            right[i + k - idx - 1] = Some(p);
+        }
+
+        let new_points = left.into_iter().zip(right).enumerate().map(|(i, pair)| {
+            let w = (i + 1) as f64 / (k + 1) as f64;
+            match pair {
+                (Some(l), Some(r)) => Some(l + (r - l) * w),
+                (Some(p), None) | (None, Some(p)) => Some(p),
+                (None, None) => None,
+            }
+        });
+        let new_points = match new_points.collect::<Option<Vec<_>>>() {
+            Some(new_points) => new_points,
+            None => return false,
+        };
+        for (i, p) in new_points.into_iter().enumerate() {
+            self.control_points[idx - k + i] = p;
+        }
+        self.control_points.remove(idx);
+        self.knot_vec.remove(idx);
+        true
+    }
+
+    /// Fairs the curve by minimizing the strain energy, keeping the end points and the knot vector.
+    ///
+    /// The control points are moved to the minimizer of `weight` times the strain energy plus
+    /// the sum of the squared displacements. The strain energy is the sum of the squared second
+    /// divided differences of the control points at the Greville abscissae, so the spacing of
+    /// the knots is taken into account. The deviation from the original curve is bounded by
+    /// `100 * TOLERANCE`, so that only the noise of the curve is removed.
+    /// Use `Self::fair_within()` for the other bound.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::prelude::*;
+    /// // line with zigzag noise
+    /// let knot_vec = KnotVec::uniform_knot(3, 8);
+    /// let ctrl_pts = (0..11)
+    ///     .map(|i| Point2::new(i as f64, if i % 2 == 0 { 0.0 } else { 1.0e-5 }))
+    ///     .collect::<Vec<_>>();
+    /// let org_curve = BSplineCurve::new(knot_vec, ctrl_pts);
+    ///
+    /// let mut bspcurve = org_curve.clone();
+    /// bspcurve.fair(100.0);
+    /// assert_near!(bspcurve.front(), org_curve.front());
+    /// assert_near!(bspcurve.back(), org_curve.back());
+    ///
+    /// let energy = |curve: &BSplineCurve<Point2>| {
+    ///     (0..=100)
+    ///         .map(|i| curve.der2(i as f64 / 100.0).magnitude2())
+    ///         .sum::<f64>()
+    /// };
+    /// assert!(energy(&bspcurve) < energy(&org_curve));
+    /// (0..=100).for_each(|i| {
+    ///     let t = i as f64 / 100.0;
+    ///     assert!(bspcurve.subs(t).distance(org_curve.subs(t)) <= 100.0 * TOLERANCE);
+    /// });
+    /// ```
+    pub fn fair(&mut self, weight: f64) -> &mut Self { self.fair_within(weight, FAIRING_TOLERANCE) }
+
+    /// Fairs the curve as `Self::fair()`, with the deviation from the original curve bounded by `tol`.
+    ///
+    /// If a control point moves farther than `tol`, all the displacements are scaled down.
+    /// By the convex hull property, the distance between the curves at the same parameter is
+    /// bounded by `tol`.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::prelude::*;
+    /// // zigzag line
+    /// let knot_vec = KnotVec::uniform_knot(3, 8);
+    /// let ctrl_pts = (0..11)
+    ///     .map(|i| Point2::new(i as f64, if i % 2 == 0 { 0.0 } else { 0.1 }))
+    ///     .collect::<Vec<_>>();
+    /// let org_curve = BSplineCurve::new(knot_vec, ctrl_pts);
+    ///
+    /// let mut bspcurve = org_curve.clone();
+    /// bspcurve.fair_within(10.0, 0.01);
+    /// assert_near!(bspcurve.front(), org_curve.front());
+    /// assert_near!(bspcurve.back(), org_curve.back());
+    ///
+    /// let energy = |curve: &BSplineCurve<Point2>| {
+    ///     (0..=100)
+    ///         .map(|i| curve.der2(i as f64 / 100.0).magnitude2())
+    ///         .sum::<f64>()
+    /// };
+    /// assert!(energy(&bspcurve) < energy(&org_curve));
+    /// (0..=100).for_each(|i| {
+    ///     let t = i as f64 / 100.0;
+    ///     assert!(bspcurve.subs(t).distance(org_curve.subs(t)) <= 0.01 + TOLERANCE);
+    /// });
+    /// ```
+    pub fn fair_within(&mut self, weight: f64, tol: f64) -> &mut Self {
+        let n = self.control_points.len();
+        let k = self.degree();
+        if n < 3 || k == 0 || weight <= 0.0 {
+            return self;
+        }
+        // the second divided differences at the Greville abscissae, normalized by the mean interval
+        // so that the coefficients are `[1, -2, 1]` for the uniform knot vector.
+        let greville = (0..n)
+            .map(|i| (1..=k).map(|j| self.knot_vec[i + j]).sum::<f64>() / k as f64)
+            .collect::<Vec<_>>();
+        let mean = (greville[n - 1] - greville[0]) / (n - 1) as f64;
+        let coefs = greville.windows(3).map(|g| {
+            let (h0, h1, h) = (g[1] - g[0], g[2] - g[1], g[2] - g[0]);
+            match h0.so_small() || h1.so_small() {
+                true => [0.0; 3],
+                false => {
+                    let c = 2.0 * mean * mean;
+                    [c / (h0 * h), -c / (h0 * h1), c / (h1 * h)]
+                }
+            }
+        });
+        // banded matrix of I + weight * B^T B: `band[i][j + 2 - i]` is the (i, j) component.
+        let mut band = vec![[0.0, 0.0, 1.0, 0.0, 0.0]; n];
+        for (j, coef) in coefs.enumerate() {
+            for a in 0..3 {
+                for b in 0..3 {
+                    band[j + a][b + 2 - a] += weight * coef[a] * coef[b];
+                }
+            }
+        }
+        let mut rhs = self
+            .control_points
+            .iter()
+            .map(|p| EuclideanSpace::to_vec(*p))
+            .collect::<Vec<_>>();
+        // fix the end points
+        for i in [0, n - 1] {
+            for r in i.saturating_sub(2)..usize::min(i + 3, n) {
+                if r != i {
+                    let c = std::mem::take(&mut band[r][i + 2 - r]);
+                    let v = rhs[i] * c;
+                    rhs[r] -= v;
+                }
+            }
+            band[i] = [0.0, 0.0, 1.0, 0.0, 0.0];
+        }
+        // Gaussian elimination without pivoting: the matrix is positive definite.
+        for k in 0..n {
+            for i in k + 1..usize::min(k + 3, n) {
+                let f = band[i][k + 2 - i] / band[k][2];
+                for j in k..usize::min(k + 3, n) {
+                    band[i][j + 2 - i] -= f * band[k][j + 2 - k];
+                }
+                let v = rhs[k] * f;
+                rhs[i] -= v;
+            }
+        }
+        for k in (0..n).rev() {
+            for j in k + 1..usize::min(k + 3, n) {
+                let v = rhs[j] * band[k][j + 2 - k];
+                rhs[k] -= v;
+            }
+            rhs[k] /= band[k][2];
+        }
+
+        let displacements = rhs
+            .into_iter()
+            .zip(&self.control_points)
+            .map(|(v, p)| v - EuclideanSpace::to_vec(*p))
+            .collect::<Vec<_>>();
+        let max = displacements
+            .iter()
+            .fold(0.0, |max: f64, v| f64::max(max, v.magnitude()));
+        let scale = if max > tol { tol / max } else { 1.0 };
+        self.control_points
+            .iter_mut()
+            .zip(displacements)
+            .for_each(|(p, v)| *p += v * scale);
+        self
+    }
+}
+
+/// Returns the maximum distance between the control points of `original` and `curve`,
+/// refined to the knot vector of `original`. The knots of `curve` must be a subset of the ones of `original`.
+fn knot_removal_deviation<P>(original: &BSplineCurve<P>, curve: &BSplineCurve<P>) -> f64
+where
+    P: ControlPoint<f64>
+        + EuclideanSpace<Scalar = f64, Diff = <P as ControlPoint<f64>>::Diff>
+        + MetricSpace<Metric = f64>
+        + Tolerance,
+    <P as ControlPoint<f64>>::Diff: InnerSpace<Scalar = f64> + Tolerance, {
+    let mut curve = curve.clone();
+    for (j, t) in original.knot_vec.iter().copied().enumerate() {
+        if j >= curve.knot_vec.len() || curve.knot_vec[j] != t {
+            curve.add_knot(t);
+        }
+    }
+    original
+        .control_points
+        .iter()
+        .zip(&curve.control_points)
+        .fold(0.0, |max, (p, q)| f64::max(max, p.distance(*q)))
+}
+
+#[test]
+fn global_nearest_random_test() {
+    (0..10).for_each(|_| {
+        let curve = BSplineCurve::new(
+            KnotVec::uniform_knot(3, 6),
+            (0..9)
+                .map(|_| Point3::new(rand::random(), rand::random(), rand::random()))
+                .collect(),
+        );
+        let pt = Point3::new(rand::random(), rand::random(), rand::random());
+        let t = curve
+            .search_nearest_parameter(pt, SPHint1D::Global, 100)
+            .unwrap();
+        let dist = curve.subs(t).distance(pt);
+        (0..=1000).for_each(|i| {
+            let s = i as f64 / 1000.0;
+            assert!(dist <= curve.subs(s).distance(pt) + TOLERANCE);
+        });
+        let params = curve.search_all_nearest_parameters(pt, 100);
+        assert!(params
+            .iter()
+            .any(|s| curve.subs(*s).distance(pt).near(&dist)));
+        params.into_iter().for_each(|s| {
+            let f = curve.der(s).dot(curve.subs(s) - pt);
+            assert!(f.so_small() || s == 0.0 || s == 1.0);
+        });
+    });
+}
+
+#[test]
+fn simplify_and_fair_random_test() {
+    (0..10).for_each(|_| {
+        let org_curve = BSplineCurve::new(
+            KnotVec::uniform_knot(3, 4),
+            (0..7)
+                .map(|_| Point3::new(rand::random(), rand::random(), rand::random()))
+                .collect(),
+        );
+        let mut curve = org_curve.clone();
+        (1..50).for_each(|i| {
+            curve.add_knot(i as f64 / 50.0 + 0.001 * rand::random::<f64>());
+        });
+        curve.control_points.iter_mut().for_each(|p| {
+            *p += Vector3::new(rand::random(), rand::random(), rand::random()) * 1.0e-4;
+        });
+        let noisy = curve.clone();
+        curve.simplify(1.0e-3);
+        assert!(curve.control_points.len() < noisy.control_points.len());
+        (0..=1000).for_each(|i| {
+            let t = i as f64 / 1000.0;
+            assert!(curve.subs(t).distance(noisy.subs(t)) <= 1.0e-3 + TOLERANCE);
+        });
+
+        let mut faired = noisy.clone();
+        faired.fair_within(1.0, 1.0e-3);
+        assert_near!(faired.front(), noisy.front());
+        assert_near!(faired.back(), noisy.back());
+        (0..=1000).for_each(|i| {
+            let t = i as f64 / 1000.0;
+            assert!(faired.subs(t).distance(noisy.subs(t)) <= 1.0e-3 + TOLERANCE);
+        });
+    });
+}
+
+#[test]
+fn fair_non_uniform_test() {
+    // the line `x = t` with the non-uniform knots has no strain energy.
+    let knot_vec = KnotVec::from(vec![0.0, 0.0, 0.0, 0.0, 0.1, 0.15, 0.6, 1.0, 1.0, 1.0, 1.0]);
+    let ctrl_pts = (0..7)
+        .map(|i| {
+            let x = (1..=3).map(|j| knot_vec[i + j]).sum::<f64>() / 3.0;
+            Point2::new(x, 2.0 * x)
+        })
+        .collect::<Vec<_>>();
+    let line = BSplineCurve::new(knot_vec, ctrl_pts);
+    let mut faired = line.clone();
+    faired.fair(10.0);
+    faired
+        .control_points()
+        .iter()
+        .zip(line.control_points())
+        .for_each(|(p, q)| assert_near!(p, q));
+
+    // the strong fairing recovers the line from the perturbed one.
+    let mut curve = line.clone();
+    *curve.control_point_mut(3) += Vector2::new(0.1, -0.1);
+    curve.fair_within(1.0e8, 1.0);
+    curve
+        .control_points()
+        .iter()
+        .zip(line.control_points())
+        .for_each(|(p, q)| assert!(p.distance(*q) < 1.0e-4, "{p:?} {q:?}"));
+}
+
 impl<P: Bounded> BSplineCurve<P> {
     /// Returns the bounding box including all control points.
     #[inline(always)]
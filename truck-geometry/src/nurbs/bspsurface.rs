@@ -733,7 +733,7 @@ impl<P: ControlPoint<f64>> ParametricSurface for BSplineSurface<P> {
             .into_iter()
             .enumerate()
             .fold(P::Diff::zero(), closure)
-            * degree0 as f64
+            * (degree0 * (degree0 - 1)) as f64
     }
 
     /// Substitutes 2nd-ord derived B-spline surface by the second parameter `v`.
@@ -782,7 +782,7 @@ impl<P: ControlPoint<f64>> ParametricSurface for BSplineSurface<P> {
             .into_iter()
             .enumerate()
             .fold(P::Diff::zero(), closure)
-            * degree1 as f64
+            * (degree1 * (degree1 - 1)) as f64
     }
 
     /// Substitutes 2nd-ord derived B-spline surface by the both parameters `u, v`.
@@ -922,7 +922,7 @@ impl<P: ControlPoint<f64> + Tolerance> BSplineSurface<P> {
         }
 
         let idx = uknot_vec.add_knot(x);
-        let start = if idx > k { idx - k } else { 0 };
+        let start = idx.saturating_sub(k);
         let end = if idx > n0 {
             control_points.push(vec![P::origin(); n1]);
             n0 + 1
@@ -974,7 +974,7 @@ impl<P: ControlPoint<f64> + Tolerance> BSplineSurface<P> {
         let n1 = self.control_points[0].len();
 
         let idx = self.knot_vecs.1.add_knot(x);
-        let start = if idx > k { idx - k } else { 0 };
+        let start = idx.saturating_sub(k);
         let end = if idx > n1 {
             self.control_points
                 .iter_mut()
@@ -1875,6 +1875,30 @@ impl<V: Clone> Invertible for BSplineSurface<V> {
     fn invert(&mut self) { self.swap_axes(); }
 }
 
+impl<P: MetricSpace<Metric = f64> + Copy> SameDomain for BSplineSurface<P> {
+    /// Returns whether two surfaces have the same knot vectors and control points within `tol`.
+    fn same_domain(&self, other: &Self, tol: f64) -> bool {
+        let knot_vec_eq = |knot_vec0: &KnotVec, knot_vec1: &KnotVec| {
+            knot_vec0.len() == knot_vec1.len()
+                && knot_vec0
+                    .iter()
+                    .zip(knot_vec1.iter())
+                    .all(|(s, t)| (s - t).abs() <= tol)
+        };
+        knot_vec_eq(self.uknot_vec(), other.uknot_vec())
+            && knot_vec_eq(self.vknot_vec(), other.vknot_vec())
+            && self.control_points.len() == other.control_points.len()
+            && self
+                .control_points
+                .iter()
+                .zip(&other.control_points)
+                .all(|(row0, row1)| {
+                    row0.len() == row1.len()
+                        && row0.iter().zip(row1).all(|(p, q)| p.distance(*q) <= tol)
+                })
+    }
+}
+
 impl SearchParameter<D2> for BSplineSurface<Point2> {
     type Point = Point2;
     fn search_parameter<H: Into<SPHint2D>>(
@@ -1891,6 +1915,21 @@ impl SearchParameter<D2> for BSplineSurface<Point2> {
             SPHint2D::None => {
                 algo::surface::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint2D::Global => {
+                let patches = subdivision_search::bezier_patches(self);
+                let (u, v) = subdivision_search::surface_global_nearest(
+                    self,
+                    patches,
+                    |p| p,
+                    false,
+                    point,
+                    trials,
+                )?;
+                return match self.subs(u, v).near(&point) {
+                    true => Some((u, v)),
+                    false => None,
+                };
+            }
         };
         algo::surface::search_parameter2d(self, point, hint, trials)
     }
@@ -1912,6 +1951,21 @@ impl SearchParameter<D2> for BSplineSurface<Point3> {
             SPHint2D::None => {
                 algo::surface::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint2D::Global => {
+                let patches = subdivision_search::bezier_patches(self);
+                let (u, v) = subdivision_search::surface_global_nearest(
+                    self,
+                    patches,
+                    |p| p,
+                    false,
+                    point,
+                    trials,
+                )?;
+                return match self.subs(u, v).near(&point) {
+                    true => Some((u, v)),
+                    false => None,
+                };
+            }
         };
         algo::surface::search_parameter3d(self, point, hint, trials)
     }
@@ -1921,7 +1975,8 @@ impl<P> SearchNearestParameter<D2> for BSplineSurface<P>
 where
     P: ControlPoint<f64>
         + EuclideanSpace<Scalar = f64, Diff = <P as ControlPoint<f64>>::Diff>
-        + MetricSpace<Metric = f64>,
+        + MetricSpace<Metric = f64>
+        + Tolerance,
     <P as ControlPoint<f64>>::Diff: InnerSpace<Scalar = f64> + Tolerance,
 {
     type Point = P;
@@ -1939,11 +1994,128 @@ where
             SPHint2D::None => {
                 algo::surface::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint2D::Global => {
+                let patches = subdivision_search::bezier_patches(self);
+                return subdivision_search::surface_global_nearest(
+                    self,
+                    patches,
+                    |p| p,
+                    false,
+                    point,
+                    trials,
+                );
+            }
         };
         algo::surface::search_nearest_parameter(self, point, hint, trials)
     }
 }
 
+impl<P> BSplineSurface<P>
+where
+    P: ControlPoint<f64>
+        + EuclideanSpace<Scalar = f64, Diff = <P as ControlPoint<f64>>::Diff>
+        + MetricSpace<Metric = f64>
+        + Tolerance,
+    <P as ControlPoint<f64>>::Diff: InnerSpace<Scalar = f64> + Tolerance,
+{
+    /// Searches all the parameters `(u, v)` such that `self.subs(u, v)` is locally nearest to `point`.
+    ///
+    /// The surface is decomposed into Bezier patches, and the patches on which the distance has
+    /// no critical points are pruned by the convex hull property. Newton's method with `trials`
+    /// iterations is applied only on the remaining small patches. The points on the boundary of
+    /// the domain are also returned if they are local minima of the distance restricted on the domain.
+    ///
+    /// To get only the globally nearest parameter, use `search_nearest_parameter` with `SPHint2D::Global`.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::prelude::*;
+    /// // a saddle surface: z = x^2 - y^2
+    /// let knot_vecs = (KnotVec::bezier_knot(2), KnotVec::bezier_knot(2));
+    /// let ctrl_pts = vec![
+    ///     vec![Point3::new(-1.0, -1.0, 0.0), Point3::new(-1.0, 0.0, 2.0), Point3::new(-1.0, 1.0, 0.0)],
+    ///     vec![Point3::new(0.0, -1.0, -2.0), Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, -2.0)],
+    ///     vec![Point3::new(1.0, -1.0, 0.0), Point3::new(1.0, 0.0, 2.0), Point3::new(1.0, 1.0, 0.0)],
+    /// ];
+    /// let surface = BSplineSurface::new(knot_vecs, ctrl_pts);
+    ///
+    /// // The point is above the saddle, so the nearest points are on the both sides along the x-axis.
+    /// let pt = Point3::new(0.0, 0.0, 2.0);
+    /// let params = surface.search_all_nearest_parameters(pt, 100);
+    /// assert_eq!(params.len(), 2);
+    /// let (p0, p1) = (surface.subs(params[0].0, params[0].1), surface.subs(params[1].0, params[1].1));
+    /// assert_near!(p0.distance(pt), p1.distance(pt));
+    /// assert_near!(p0.y, 0.0);
+    /// assert_near!(p0.x, -p1.x);
+    ///
+    /// let (u, v) = surface.search_nearest_parameter(pt, SPHint2D::Global, 100).unwrap();
+    /// assert_near!(surface.subs(u, v).distance(pt), p0.distance(pt));
+    /// ```
+    pub fn search_all_nearest_parameters(&self, point: P, trials: usize) -> Vec<(f64, f64)> {
+        let patches = subdivision_search::bezier_patches(self);
+        subdivision_search::surface_local_minima(self, patches, |p| p, false, point, false, trials)
+    }
+}
+
+#[test]
+fn second_derivatives_test() {
+    // The degrees are more than two, where the scales of the second derivatives are `d(d - 1)`.
+    let knot_vecs = (KnotVec::uniform_knot(3, 2), KnotVec::uniform_knot(4, 3));
+    let ctrl_pts = (0..5)
+        .map(|_| {
+            (0..7)
+                .map(|_| Point3::new(rand::random(), rand::random(), rand::random()))
+                .collect()
+        })
+        .collect();
+    let surface = BSplineSurface::new(knot_vecs, ctrl_pts);
+    const EPS: f64 = 1.0e-4;
+    (0..10).for_each(|i| {
+        (0..10).for_each(|j| {
+            let (u, v) = ((i as f64 + 0.25) / 10.0, (j as f64 + 0.25) / 10.0);
+            let uuder = (surface.uder(u + EPS, v) - surface.uder(u - EPS, v)) / (2.0 * EPS);
+            let uvder = (surface.uder(u, v + EPS) - surface.uder(u, v - EPS)) / (2.0 * EPS);
+            let vvder = (surface.vder(u, v + EPS) - surface.vder(u, v - EPS)) / (2.0 * EPS);
+            assert!((surface.uuder(u, v) - uuder).magnitude() < EPS);
+            assert!((surface.uvder(u, v) - uvder).magnitude() < EPS);
+            assert!((surface.vvder(u, v) - vvder).magnitude() < EPS);
+        })
+    });
+}
+
+#[test]
+fn global_nearest_random_test() {
+    (0..10).for_each(|_| {
+        let knot_vecs = (KnotVec::uniform_knot(2, 3), KnotVec::uniform_knot(3, 2));
+        let ctrl_pts = (0..5)
+            .map(|i| {
+                (0..5)
+                    .map(|j| Point3::new(i as f64 / 4.0, j as f64 / 4.0, rand::random::<f64>()))
+                    .collect()
+            })
+            .collect();
+        let surface = BSplineSurface::new(knot_vecs, ctrl_pts);
+        let pt = Point3::new(
+            rand::random(),
+            rand::random(),
+            2.0 * rand::random::<f64>() - 0.5,
+        );
+        let (u, v) = surface
+            .search_nearest_parameter(pt, SPHint2D::Global, 100)
+            .unwrap();
+        let dist = surface.subs(u, v).distance(pt);
+        (0..=100).for_each(|i| {
+            (0..=100).for_each(|j| {
+                let (s, t) = (i as f64 / 100.0, j as f64 / 100.0);
+                assert!(dist <= surface.subs(s, t).distance(pt) + TOLERANCE);
+            })
+        });
+        let params = surface.search_all_nearest_parameters(pt, 100);
+        assert!(params
+            .iter()
+            .any(|(s, t)| surface.subs(*s, *t).distance(pt).near(&dist)));
+    });
+}
+
 impl IncludeCurve<BSplineCurve<Point2>> for BSplineSurface<Point2> {
     fn include(&self, curve: &BSplineCurve<Point2>) -> bool {
         let pt = curve.front();
@@ -2228,3 +2400,44 @@ fn test_include_bspcurve3() {
     *curve.control_point_mut(2) += Vector3::new(0.0, 0.0, 0.001);
     assert!(!surface.include(&curve));
 }
+
+#[test]
+fn same_domain() {
+    let knot_vec = KnotVec::bezier_knot(2);
+    let ctrl_pts = vec![
+        vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.5, 0.0, 0.3),
+            Point3::new(1.0, 0.0, 0.0),
+        ],
+        vec![
+            Point3::new(0.0, 0.5, 0.2),
+            Point3::new(0.5, 0.5, 0.6),
+            Point3::new(1.0, 0.5, 0.1),
+        ],
+        vec![
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.5, 1.0, 0.4),
+            Point3::new(1.0, 1.0, 0.0),
+        ],
+    ];
+    let surface = BSplineSurface::new((knot_vec.clone(), knot_vec.clone()), ctrl_pts);
+    assert!(surface.same_domain(&surface.clone(), TOLERANCE));
+
+    // a control point is moved.
+    let mut other = surface.clone();
+    *other.control_point_mut(1, 1) += Vector3::new(0.0, 0.0, 1.0e-3);
+    assert!(!surface.same_domain(&other, TOLERANCE));
+    assert!(surface.same_domain(&other, 1.0e-2));
+
+    // the same surface with the inserted knot is not regarded as the same.
+    let mut other = surface.clone();
+    other.add_uknot(0.5);
+    assert!(!surface.same_domain(&other, TOLERANCE));
+
+    // the knot vectors are scaled.
+    let mut scaled = knot_vec.clone();
+    scaled.transform(2.0, 0.0);
+    let other = BSplineSurface::new((scaled, knot_vec), surface.control_points().clone());
+    assert!(!surface.same_domain(&other, TOLERANCE));
+}
@@ -214,7 +214,7 @@ impl KnotVec {
         res[idx] = 1.0;
 
         for k in 1..=degree {
-            let base = if idx < k { 0 } else { idx - k };
+            let base = idx.saturating_sub(k);
             let delta = self[base + k] - self[base];
             let max = if idx + k < n { idx } else { n - k - 1 };
             let mut a = inv_or_zero(delta) * (t - self[base]);
@@ -498,7 +498,7 @@ impl KnotVec {
     /// ```
     pub fn bezier_knot(degree: usize) -> KnotVec {
         let mut vec = vec![0.0; degree + 1];
-        vec.extend(std::iter::repeat(1.0).take(degree + 1));
+        vec.extend(std::iter::repeat_n(1.0, degree + 1));
         KnotVec(vec)
     }
 
@@ -514,7 +514,7 @@ impl KnotVec {
     pub fn uniform_knot(degree: usize, division: usize) -> KnotVec {
         let mut vec = vec![0.0; degree + 1];
         vec.extend((1..division).map(|i| (i as f64) / (division as f64)));
-        vec.extend(std::iter::repeat(1.0).take(degree + 1));
+        vec.extend(std::iter::repeat_n(1.0, degree + 1));
         KnotVec(vec)
     }
 }
@@ -123,6 +123,7 @@ mod bspsurface;
 mod knot_vec;
 mod nurbscurve;
 mod nurbssurface;
+mod subdivision_search;
 
 #[doc(hidden)]
 #[inline(always)]
@@ -425,7 +425,8 @@ where V::Point: MetricSpace<Metric = f64> + HashGen<f64>
     }
 }
 
-impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V>> SearchNearestParameter<D1> for NurbsCurve<V>
+impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V> + Tolerance> SearchNearestParameter<D1>
+    for NurbsCurve<V>
 where
     V::Point: MetricSpace<Metric = f64>,
     <V::Point as EuclideanSpace>::Diff: InnerSpace + Tolerance,
@@ -484,12 +485,24 @@ where
             SPHint1D::None => {
                 algo::curve::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint1D::Global => {
+                let beziers = subdivision_search::bezier_pieces(&self.0);
+                return subdivision_search::curve_global_nearest(
+                    self,
+                    beziers,
+                    V::to_point,
+                    true,
+                    point,
+                    trial,
+                );
+            }
         };
         algo::curve::search_nearest_parameter(self, point, hint, trial)
     }
 }
 
-impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V>> SearchParameter<D1> for NurbsCurve<V>
+impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V> + Tolerance> SearchParameter<D1>
+    for NurbsCurve<V>
 where
     V::Point: MetricSpace<Metric = f64>,
     <V::Point as EuclideanSpace>::Diff: InnerSpace + Tolerance,
@@ -510,11 +523,74 @@ where
             SPHint1D::None => {
                 algo::curve::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint1D::Global => {
+                let beziers = subdivision_search::bezier_pieces(&self.0);
+                let t = subdivision_search::curve_global_nearest(
+                    self,
+                    beziers,
+                    V::to_point,
+                    true,
+                    point,
+                    trial,
+                )?;
+                return match point.to_vec().near(&self.subs(t).to_vec()) {
+                    true => Some(t),
+                    false => None,
+                };
+            }
         };
         algo::curve::search_parameter(self, point, hint, trial)
     }
 }
 
+impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V> + Tolerance> NurbsCurve<V>
+where
+    V::Point: MetricSpace<Metric = f64>,
+    <V::Point as EuclideanSpace>::Diff: InnerSpace + Tolerance,
+{
+    /// Searches all the parameters `t` such that `self.subs(t)` is locally nearest to `point`.
+    ///
+    /// The same algorithm as `BSplineCurve::search_all_nearest_parameters` is applied.
+    /// The weights of all control points must be positive.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::prelude::*;
+    ///
+    /// // the quarter of the unit circle
+    /// let knot_vec = KnotVec::bezier_knot(2);
+    /// let ctrl_pts = vec![
+    ///     Vector3::new(1.0, 0.0, 1.0),
+    ///     Vector3::new(1.0, 1.0, 1.0) / f64::sqrt(2.0),
+    ///     Vector3::new(0.0, 1.0, 1.0),
+    /// ];
+    /// let curve = NurbsCurve::new(BSplineCurve::new(knot_vec, ctrl_pts));
+    ///
+    /// // The ends of the curve are not local solutions.
+    /// let pt = Point2::new(2.0, 2.0);
+    /// let params = curve.search_all_nearest_parameters(pt, 100);
+    /// assert_eq!(params.len(), 1);
+    /// let ans = Point2::new(1.0, 1.0) / f64::sqrt(2.0);
+    /// assert_near!(curve.subs(params[0]), ans);
+    ///
+    /// // Both of the ends are local solutions.
+    /// let pt = Point2::new(-1.0, -1.0);
+    /// let params = curve.search_all_nearest_parameters(pt, 100);
+    /// assert_eq!(params, vec![0.0, 1.0]);
+    /// ```
+    pub fn search_all_nearest_parameters(&self, point: V::Point, trial: usize) -> Vec<f64> {
+        let beziers = subdivision_search::bezier_pieces(&self.0);
+        subdivision_search::curve_local_minima(
+            self,
+            beziers,
+            V::to_point,
+            true,
+            point,
+            false,
+            trial,
+        )
+    }
+}
+
 impl<V: Homogeneous<f64>> NurbsCurve<V>
 where V::Point: Bounded<Scalar = f64>
 {
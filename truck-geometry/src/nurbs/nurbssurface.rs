@@ -522,7 +522,8 @@ impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V> + Tolerance> NurbsSurface
     pub fn boundary(&self) -> NurbsCurve<V> { NurbsCurve::new(self.0.boundary()) }
 }
 
-impl<V: Homogeneous<f64>> SearchNearestParameter<D2> for NurbsSurface<V>
+impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V> + Tolerance> SearchNearestParameter<D2>
+    for NurbsSurface<V>
 where
     Self: ParametricSurface<Point = V::Point, Vector = <V::Point as EuclideanSpace>::Diff>,
     V::Point: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
@@ -548,6 +549,7 @@ where
     /// ```
     /// # Remarks
     /// It may converge to a local solution depending on the hint.
+    /// Use `SPHint2D::Global` to get the global solution.
     /// cf. [`BSplineCurve::search_rational_nearest_parameter`](struct.BSplineCurve.html#method.search_rational_nearest_parameter)
     #[inline(always)]
     fn search_nearest_parameter<H: Into<SPHint2D>>(
@@ -564,11 +566,65 @@ where
             SPHint2D::None => {
                 algo::surface::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint2D::Global => {
+                let patches = subdivision_search::bezier_patches(&self.0);
+                return subdivision_search::surface_global_nearest(
+                    self,
+                    patches,
+                    V::to_point,
+                    true,
+                    point,
+                    trials,
+                );
+            }
         };
         algo::surface::search_nearest_parameter(self, point, hint, trials)
     }
 }
 
+impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V> + Tolerance> NurbsSurface<V>
+where
+    Self: ParametricSurface<Point = V::Point, Vector = <V::Point as EuclideanSpace>::Diff>,
+    V::Point: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    <V::Point as EuclideanSpace>::Diff: InnerSpace<Scalar = f64> + Tolerance,
+{
+    /// Searches all the parameters `(u, v)` such that `self.subs(u, v)` is locally nearest to `point`.
+    ///
+    /// The same algorithm as `BSplineSurface::search_all_nearest_parameters` is applied.
+    /// The weights of all control points must be positive.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::prelude::*;
+    /// // a quarter of the unit sphere in x > 0, y > 0
+    /// let s = 1.0 / f64::sqrt(2.0);
+    /// let knot_vecs = (KnotVec::bezier_knot(2), KnotVec::bezier_knot(2));
+    /// let ctrl_pts = vec![
+    ///     vec![Vector4::new(0.0, 0.0, 1.0, 1.0), Vector4::new(0.0, 0.0, s, s), Vector4::new(0.0, 0.0, 1.0, 1.0)],
+    ///     vec![Vector4::new(s, 0.0, s, s), Vector4::new(0.5, 0.5, 0.5, 0.5), Vector4::new(0.0, s, s, s)],
+    ///     vec![Vector4::new(1.0, 0.0, 0.0, 1.0), Vector4::new(s, s, 0.0, s), Vector4::new(0.0, 1.0, 0.0, 1.0)],
+    /// ];
+    /// let surface = NurbsSurface::new(BSplineSurface::new(knot_vecs, ctrl_pts));
+    ///
+    /// let pt = Point3::new(0.3, 0.4, 0.2);
+    /// let params = surface.search_all_nearest_parameters(pt, 100);
+    /// assert_eq!(params.len(), 1);
+    /// let (u, v) = params[0];
+    /// assert_near!(surface.subs(u, v), Point3::from_vec(pt.to_vec().normalize()));
+    /// ```
+    pub fn search_all_nearest_parameters(&self, point: V::Point, trials: usize) -> Vec<(f64, f64)> {
+        let patches = subdivision_search::bezier_patches(&self.0);
+        subdivision_search::surface_local_minima(
+            self,
+            patches,
+            V::to_point,
+            true,
+            point,
+            false,
+            trials,
+        )
+    }
+}
+
 impl<V: Homogeneous<f64>> NurbsSurface<V>
 where V::Point: Bounded<Scalar = f64>
 {
@@ -620,6 +676,17 @@ impl SearchParameter<D2> for NurbsSurface<Vector3> {
             SPHint2D::None => {
                 algo::surface::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint2D::Global => {
+                let patches = subdivision_search::bezier_patches(&self.0);
+                let project = |p: Vector3| p.to_point();
+                let (u, v) = subdivision_search::surface_global_nearest(
+                    self, patches, project, true, point, trials,
+                )?;
+                return match self.subs(u, v).near(&point) {
+                    true => Some((u, v)),
+                    false => None,
+                };
+            }
         };
         algo::surface::search_parameter2d(self, point, hint, trials)
     }
@@ -636,6 +703,12 @@ impl<V: Clone> Invertible for NurbsSurface<V> {
     }
 }
 
+impl<V: MetricSpace<Metric = f64> + Copy> SameDomain for NurbsSurface<V> {
+    /// Returns whether the non-rationalized surfaces are the same within `tol`.
+    #[inline(always)]
+    fn same_domain(&self, other: &Self, tol: f64) -> bool { self.0.same_domain(&other.0, tol) }
+}
+
 impl<V: Homogeneous<f64> + ControlPoint<f64, Diff = V>> ParametricSurface for NurbsSurface<V> {
     type Point = V::Point;
     type Vector = <V::Point as EuclideanSpace>::Diff;
@@ -833,6 +906,17 @@ impl SearchParameter<D2> for NurbsSurface<Vector4> {
             SPHint2D::None => {
                 algo::surface::presearch(self, point, self.range_tuple(), PRESEARCH_DIVISION)
             }
+            SPHint2D::Global => {
+                let patches = subdivision_search::bezier_patches(&self.0);
+                let project = |p: Vector4| p.to_point();
+                let (u, v) = subdivision_search::surface_global_nearest(
+                    self, patches, project, true, point, trials,
+                )?;
+                return match self.subs(u, v).near(&point) {
+                    true => Some((u, v)),
+                    false => None,
+                };
+            }
         };
         algo::surface::search_parameter3d(self, point, hint, trials)
     }
@@ -0,0 +1,579 @@
+use super::*;
+
+/// The maximum number of bisections of a Bezier curve.
+const CURVE_MAX_DEPTH: usize = 40;
+/// The maximum number of quadrisections of a Bezier patch.
+const SURFACE_MAX_DEPTH: usize = 10;
+/// The Bezier segment is regarded as straight if all the edges of the control polygon make
+/// an angle whose cosine is greater than `1 - STRAIGHTNESS` with the chord.
+const STRAIGHTNESS: f64 = 1.0e-2;
+/// The number of bisections if Newton's method fails.
+const BISECTION_TRIALS: usize = 64;
+
+/// A Bezier piece of curve: the parameter range and the control points.
+pub(super) type BezierCurvePiece<P> = ((f64, f64), Vec<P>);
+/// A Bezier piece of surface: the parameter ranges and the control points.
+pub(super) type BezierSurfacePiece<P> = (((f64, f64), (f64, f64)), Vec<Vec<P>>);
+
+/// Divides the Bezier control polygon at the middle of the parameter range by de Casteljau's algorithm.
+fn bisect<P: ControlPoint<f64>>(ctrl: &[P]) -> (Vec<P>, Vec<P>) {
+    let mut tmp = ctrl.to_vec();
+    let n = tmp.len();
+    let mut left = Vec::with_capacity(n);
+    let mut right = Vec::with_capacity(n);
+    left.push(tmp[0]);
+    right.push(tmp[n - 1]);
+    for k in 1..n {
+        for i in 0..n - k {
+            tmp[i] = tmp[i] + (tmp[i + 1] - tmp[i]) / 2.0;
+        }
+        left.push(tmp[0]);
+        right.push(tmp[n - k - 1]);
+    }
+    right.reverse();
+    (left, right)
+}
+
+fn ubisect<P: ControlPoint<f64>>(ctrl: &[Vec<P>]) -> (Vec<Vec<P>>, Vec<Vec<P>>) {
+    let (m, n) = (ctrl.len(), ctrl[0].len());
+    let mut left = vec![Vec::with_capacity(n); m];
+    let mut right = vec![Vec::with_capacity(n); m];
+    (0..n).for_each(|j| {
+        let column = ctrl.iter().map(|row| row[j]).collect::<Vec<_>>();
+        let (l, r) = bisect(&column);
+        l.into_iter()
+            .zip(&mut left)
+            .for_each(|(p, row)| row.push(p));
+        r.into_iter()
+            .zip(&mut right)
+            .for_each(|(p, row)| row.push(p));
+    });
+    (left, right)
+}
+
+fn vbisect<P: ControlPoint<f64>>(ctrl: &[Vec<P>]) -> (Vec<Vec<P>>, Vec<Vec<P>>) {
+    ctrl.iter().map(|row| bisect(row)).unzip()
+}
+
+/// Returns `true` if `d.dot(e)` has the same strict sign for all `d` in `dirs` and `e` in `diffs`.
+///
+/// If the derivative of the curve lies in the positive cone of `dirs` and the vectors from the point
+/// to the curve lies in the convex hull of `diffs`, then the derivative of the squared distance
+/// does not vanish.
+fn strictly_signed<V: InnerSpace<Scalar = f64> + Tolerance>(dirs: &[V], diffs: &[V]) -> bool {
+    let mut dots = dirs
+        .iter()
+        .filter(|d| !d.so_small())
+        .flat_map(|d| diffs.iter().map(move |e| d.dot(*e)));
+    match dots.next() {
+        Some(x) if x > 0.0 => dots.all(|y| y > 0.0),
+        Some(x) if x < 0.0 => dots.all(|y| y < 0.0),
+        _ => false,
+    }
+}
+
+/// Returns `true` if all `dirs` are almost parallel to `chord`.
+fn is_straight<V: InnerSpace<Scalar = f64> + Tolerance>(dirs: &[V], chord: V) -> bool {
+    if chord.so_small() {
+        return false;
+    }
+    let chord = chord.normalize();
+    dirs.iter()
+        .all(|d| d.so_small() || d.dot(chord) >= (1.0 - STRAIGHTNESS) * d.magnitude())
+}
+
+/// A lower bound of the distance between `point` and the convex hull of `pts`.
+fn distance_lower_bound<Q>(pts: &[Q], point: Q) -> f64
+where
+    Q: EuclideanSpace<Scalar = f64>,
+    Q::Diff: InnerSpace<Scalar = f64> + Tolerance, {
+    let center = Q::centroid(pts);
+    let dir = center - point;
+    if dir.so_small() {
+        return 0.0;
+    }
+    let dir = dir.normalize();
+    let min = pts
+        .iter()
+        .map(|p| (*p - point).dot(dir))
+        .fold(f64::INFINITY, f64::min);
+    f64::max(min, 0.0)
+}
+
+/// The directions in which the derivative of the Bezier curve with control points `pts` lies.
+///
+/// The derivative of a polynomial Bezier curve is a positive combination of `pts[i + 1] - pts[i]`.
+/// For a rational Bezier curve with positive weights, the derivative is a positive combination
+/// of `pts[j] - pts[i]` with `i < j`.
+fn derivative_cone<Q: EuclideanSpace>(pts: &[Q], rational: bool) -> Vec<Q::Diff> {
+    match rational {
+        true => pts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, p)| pts[i + 1..].iter().map(move |q| *q - *p))
+            .collect(),
+        false => pts.windows(2).map(|p| p[1] - p[0]).collect(),
+    }
+}
+
+/// The directions in which the `u`-derivative of the Bezier patch with control points `pts` lies.
+///
+/// The `u`-derivative of a polynomial Bezier patch is a positive combination of
+/// `pts[i + 1][j] - pts[i][j]`. For a rational Bezier patch with positive weights,
+/// the `u`-derivative is a positive combination of `pts[k][l] - pts[i][j]` with `i < k`.
+fn uderivative_cone<Q: EuclideanSpace>(pts: &[Vec<Q>], rational: bool) -> Vec<Q::Diff> {
+    match rational {
+        true => pts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                pts[i + 1..]
+                    .iter()
+                    .flatten()
+                    .flat_map(move |q| row.iter().map(move |p| *q - *p))
+            })
+            .collect(),
+        false => pts
+            .windows(2)
+            .flat_map(|rows| rows[0].iter().zip(&rows[1]).map(|(p, q)| *q - *p))
+            .collect(),
+    }
+}
+
+fn transpose<Q: Copy>(pts: &[Vec<Q>]) -> Vec<Vec<Q>> {
+    (0..pts[0].len())
+        .map(|j| pts.iter().map(|row| row[j]).collect())
+        .collect()
+}
+
+fn dedup_parameters(params: &mut Vec<f64>) {
+    params.sort_by(|s, t| s.total_cmp(t));
+    params.dedup_by(|s, t| f64::abs(*s - *t) < TOLERANCE);
+}
+
+/// Searches all parameters whose points are locally nearest to `point` on the curve which consists of `beziers`.
+///
+/// * `beziers` - Bezier decomposition of `curve`, in the order of the parameter.
+/// * `project` - the map from control points to the points in the space of `curve`.
+/// * `rational` - whether the control points are homogeneous coordinates or not.
+/// * `global` - if `true`, the pieces which are certainly farther than the found solutions are pruned.
+///
+/// The end points of the domain are also returned if they are local minima of the distance restricted on the domain.
+pub(super) fn curve_local_minima<C, P, Q>(
+    curve: &C,
+    beziers: Vec<BezierCurvePiece<P>>,
+    project: impl Fn(P) -> Q,
+    rational: bool,
+    point: Q,
+    global: bool,
+    trials: usize,
+) -> Vec<f64>
+where
+    C: ParametricCurve<Point = Q, Vector = Q::Diff>,
+    P: ControlPoint<f64>,
+    Q: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    Q::Diff: InnerSpace<Scalar = f64> + Tolerance,
+{
+    let (t0, t1) = match (beziers.first(), beziers.last()) {
+        (Some(first), Some(last)) => (first.0 .0, last.0 .1),
+        _ => return Vec::new(),
+    };
+    let f = |t: f64| curve.der(t).dot(curve.subs(t) - point);
+    let mut best = beziers
+        .iter()
+        .flat_map(|(_, ctrl)| [ctrl[0], ctrl[ctrl.len() - 1]])
+        .map(|p| project(p).distance(point))
+        .fold(f64::INFINITY, f64::min);
+    let mut res = Vec::new();
+    if f(t0) > 0.0 {
+        res.push(t0);
+    }
+    let mut stack: Vec<_> = beziers.into_iter().rev().map(|x| (x, 0)).collect();
+    while let Some((((a, b), ctrl), depth)) = stack.pop() {
+        let pts = ctrl.iter().map(|p| project(*p)).collect::<Vec<_>>();
+        if global && distance_lower_bound(&pts, point) > best + TOLERANCE {
+            continue;
+        }
+        let diffs = pts.iter().map(|p| *p - point).collect::<Vec<_>>();
+        if strictly_signed(&derivative_cone(&pts, rational), &diffs) {
+            continue;
+        }
+        let edges = derivative_cone(&pts, false);
+        // the piece degenerates to a point, e.g. a pole of a surface.
+        if edges.iter().all(|e| e.so_small()) {
+            continue;
+        }
+        let chord = pts[pts.len() - 1] - pts[0];
+        if depth < CURVE_MAX_DEPTH && !is_straight(&edges, chord) {
+            let mid = (a + b) / 2.0;
+            let (left, right) = bisect(&ctrl);
+            stack.push((((mid, b), right), depth + 1));
+            stack.push((((a, mid), left), depth + 1));
+            continue;
+        }
+        let (fa, fb) = (f(a), f(b));
+        if fa > 0.0 || fb < 0.0 {
+            continue;
+        }
+        let hint = match fb - fa > 0.0 {
+            true => a - fa * (b - a) / (fb - fa),
+            false => (a + b) / 2.0,
+        };
+        let t = algo::curve::search_nearest_parameter(curve, point, hint, trials)
+            .filter(|t| a - TOLERANCE <= *t && *t <= b + TOLERANCE)
+            .unwrap_or_else(|| {
+                let (mut a, mut b) = (a, b);
+                (0..BISECTION_TRIALS).for_each(|_| {
+                    let mid = (a + b) / 2.0;
+                    match f(mid) < 0.0 {
+                        true => a = mid,
+                        false => b = mid,
+                    }
+                });
+                (a + b) / 2.0
+            });
+        best = f64::min(best, curve.subs(t).distance(point));
+        res.push(t);
+    }
+    if f(t1) < 0.0 {
+        res.push(t1);
+    }
+    dedup_parameters(&mut res);
+    res
+}
+
+/// The iso-parameter curve of a surface.
+#[derive(Clone, Copy, Debug)]
+struct IsoCurve<'a, S> {
+    surface: &'a S,
+    fixed: f64,
+    u_fixed: bool,
+}
+
+impl<'a, S: ParametricSurface> ParametricCurve for IsoCurve<'a, S> {
+    type Point = S::Point;
+    type Vector = S::Vector;
+    fn subs(&self, t: f64) -> S::Point {
+        match self.u_fixed {
+            true => self.surface.subs(self.fixed, t),
+            false => self.surface.subs(t, self.fixed),
+        }
+    }
+    fn der(&self, t: f64) -> S::Vector {
+        match self.u_fixed {
+            true => self.surface.vder(self.fixed, t),
+            false => self.surface.uder(t, self.fixed),
+        }
+    }
+    fn der2(&self, t: f64) -> S::Vector {
+        match self.u_fixed {
+            true => self.surface.vvder(self.fixed, t),
+            false => self.surface.uuder(t, self.fixed),
+        }
+    }
+}
+
+/// Searches the critical point of the distance in `range` by Newton's method.
+///
+/// If the Hessian is not positive definite, the Gauss-Newton step is used instead,
+/// which always descends the distance.
+fn foot_point_iteration<S>(
+    surface: &S,
+    point: S::Point,
+    (mut u, mut v): (f64, f64),
+    ((a0, a1), (b0, b1)): ((f64, f64), (f64, f64)),
+    trials: usize,
+) -> Option<(f64, f64)>
+where
+    S: ParametricSurface,
+    S::Point: EuclideanSpace<Scalar = f64, Diff = S::Vector>,
+    S::Vector: InnerSpace<Scalar = f64> + Tolerance,
+{
+    for _ in 0..=trials {
+        let diff = surface.subs(u, v) - point;
+        let (ud, vd) = (surface.uder(u, v), surface.vder(u, v));
+        let grad = Vector2::new(ud.dot(diff), vd.dot(diff));
+        let a = surface.uuder(u, v).dot(diff) + ud.dot(ud);
+        let c = surface.uvder(u, v).dot(diff) + ud.dot(vd);
+        let b = surface.vvder(u, v).dot(diff) + vd.dot(vd);
+        let mat = match a > 0.0 && a * b - c * c > 0.0 {
+            true => Matrix2::new(a, c, c, b),
+            false => Matrix2::new(ud.dot(ud), ud.dot(vd), ud.dot(vd), vd.dot(vd)),
+        };
+        let delta = mat.invert()? * grad;
+        if delta.so_small() {
+            return Some((u, v));
+        }
+        u = f64::clamp(u - delta.x, a0, a1);
+        v = f64::clamp(v - delta.y, b0, b1);
+    }
+    None
+}
+
+/// Searches all parameters whose points are locally nearest to `point` on the surface which consists of `patches`.
+///
+/// * `patches` - Bezier decomposition of `surface`, which covers the whole parameter domain.
+/// * `project` - the map from control points to the points in the space of `surface`.
+/// * `rational` - whether the control points are homogeneous coordinates or not.
+/// * `global` - if `true`, the patches which are certainly farther than the found solutions are pruned.
+///
+/// The points on the boundary of the domain are also returned if they are local minima of the distance restricted on the domain.
+pub(super) fn surface_local_minima<S, P, Q>(
+    surface: &S,
+    patches: Vec<BezierSurfacePiece<P>>,
+    project: impl Fn(P) -> Q,
+    rational: bool,
+    point: Q,
+    global: bool,
+    trials: usize,
+) -> Vec<(f64, f64)>
+where
+    S: ParametricSurface<Point = Q, Vector = Q::Diff>,
+    P: ControlPoint<f64>,
+    Q: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    Q::Diff: InnerSpace<Scalar = f64> + Tolerance,
+{
+    if patches.is_empty() {
+        return Vec::new();
+    }
+    let ((u0, u1), (v0, v1)) = patches.iter().fold(
+        (
+            (f64::INFINITY, f64::NEG_INFINITY),
+            (f64::INFINITY, f64::NEG_INFINITY),
+        ),
+        |((u0, u1), (v0, v1)), (((a0, a1), (b0, b1)), _)| {
+            (
+                (f64::min(u0, *a0), f64::max(u1, *a1)),
+                (f64::min(v0, *b0), f64::max(v1, *b1)),
+            )
+        },
+    );
+    let grad = |u: f64, v: f64| {
+        let diff = surface.subs(u, v) - point;
+        (surface.uder(u, v).dot(diff), surface.vder(u, v).dot(diff))
+    };
+
+    let mut res = Vec::new();
+    let mut boundary = |u_fixed: bool, start: bool| {
+        let fixed = match (u_fixed, start) {
+            (true, true) => u0,
+            (true, false) => u1,
+            (false, true) => v0,
+            (false, false) => v1,
+        };
+        let mut pieces = patches
+            .iter()
+            .filter_map(|((urange, vrange), ctrl)| {
+                let (range, end) = match u_fixed {
+                    true => (*vrange, *urange),
+                    false => (*urange, *vrange),
+                };
+                let ctrl = match u_fixed {
+                    true => ctrl.clone(),
+                    false => transpose(ctrl),
+                };
+                match start {
+                    true if end.0 == fixed => Some((range, ctrl[0].clone())),
+                    false if end.1 == fixed => Some((range, ctrl[ctrl.len() - 1].clone())),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        pieces.sort_by(|x, y| x.0 .0.total_cmp(&y.0 .0));
+        let curve = IsoCurve {
+            surface,
+            fixed,
+            u_fixed,
+        };
+        let params = curve_local_minima(&curve, pieces, &project, rational, point, global, trials);
+        params.into_iter().for_each(|t| {
+            let (u, v) = match u_fixed {
+                true => (fixed, t),
+                false => (t, fixed),
+            };
+            let (fu, fv) = grad(u, v);
+            let inward = match (u_fixed, start) {
+                (true, true) => fu,
+                (true, false) => -fu,
+                (false, true) => fv,
+                (false, false) => -fv,
+            };
+            if inward >= 0.0 {
+                res.push((u, v));
+            }
+        });
+    };
+    boundary(true, true);
+    boundary(true, false);
+    boundary(false, true);
+    boundary(false, false);
+
+    let mut best = res
+        .iter()
+        .map(|(u, v)| surface.subs(*u, *v).distance(point))
+        .fold(f64::INFINITY, f64::min);
+    let mut stack: Vec<_> = patches.into_iter().map(|x| (x, 0)).collect();
+    while let Some(((((a0, a1), (b0, b1)), ctrl), depth)) = stack.pop() {
+        let pts = ctrl
+            .iter()
+            .map(|row| row.iter().map(|p| project(*p)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let flat = pts.iter().flatten().copied().collect::<Vec<_>>();
+        if global && distance_lower_bound(&flat, point) > best + TOLERANCE {
+            continue;
+        }
+        let diffs = flat.iter().map(|p| *p - point).collect::<Vec<_>>();
+        let transposed = transpose(&pts);
+        if strictly_signed(&uderivative_cone(&pts, rational), &diffs)
+            || strictly_signed(&uderivative_cone(&transposed, rational), &diffs)
+        {
+            continue;
+        }
+        let (uedges, vedges) = (
+            uderivative_cone(&pts, false),
+            uderivative_cone(&transposed, false),
+        );
+        let (m, n) = (pts.len(), pts[0].len());
+        let uchord = pts[m - 1][0] - pts[0][0] + (pts[m - 1][n - 1] - pts[0][n - 1]);
+        let vchord = pts[0][n - 1] - pts[0][0] + (pts[m - 1][n - 1] - pts[m - 1][0]);
+        if depth < SURFACE_MAX_DEPTH
+            && !(is_straight(&uedges, uchord) && is_straight(&vedges, vchord))
+        {
+            let (umid, vmid) = ((a0 + a1) / 2.0, (b0 + b1) / 2.0);
+            let (left, right) = ubisect(&ctrl);
+            let (c00, c01) = vbisect(&left);
+            let (c10, c11) = vbisect(&right);
+            stack.push(((((a0, umid), (b0, vmid)), c00), depth + 1));
+            stack.push(((((a0, umid), (vmid, b1)), c01), depth + 1));
+            stack.push(((((umid, a1), (b0, vmid)), c10), depth + 1));
+            stack.push(((((umid, a1), (vmid, b1)), c11), depth + 1));
+            continue;
+        }
+        let (i, j) = (0..m)
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .min_by(|(i, j), (k, l)| {
+                let d0 = pts[*i][*j].distance2(point);
+                let d1 = pts[*k][*l].distance2(point);
+                d0.total_cmp(&d1)
+            })
+            .unwrap();
+        let hint = (
+            a0 + (a1 - a0) * i as f64 / usize::max(m - 1, 1) as f64,
+            b0 + (b1 - b0) * j as f64 / usize::max(n - 1, 1) as f64,
+        );
+        let (du, dv) = ((a1 - a0) / 2.0, (b1 - b0) / 2.0);
+        let range = ((a0 - du, a1 + du), (b0 - dv, b1 + dv));
+        let Some((u, v)) = foot_point_iteration(surface, point, hint, range, trials) else {
+            continue;
+        };
+        let inside = range.0 .0 <= u && u <= range.0 .1 && range.1 .0 <= v && v <= range.1 .1;
+        let in_domain = u0 < u && u < u1 && v0 < v && v < v1;
+        if !(inside && in_domain) {
+            continue;
+        }
+        let diff = surface.subs(u, v) - point;
+        let (ud, vd) = (surface.uder(u, v), surface.vder(u, v));
+        let a = surface.uuder(u, v).dot(diff) + ud.dot(ud);
+        let c = surface.uvder(u, v).dot(diff) + ud.dot(vd);
+        let b = surface.vvder(u, v).dot(diff) + vd.dot(vd);
+        if a >= -TOLERANCE && a * b - c * c >= -TOLERANCE {
+            best = f64::min(best, diff.magnitude());
+            res.push((u, v));
+        }
+    }
+
+    let mut params = Vec::<(f64, f64)>::new();
+    res.into_iter().for_each(|(u, v)| {
+        let near = |(s, t): &(f64, f64)| f64::abs(u - s) < TOLERANCE && f64::abs(v - t) < TOLERANCE;
+        if !params.iter().any(near) {
+            params.push((u, v));
+        }
+    });
+    params.sort_by(|x, y| x.0.total_cmp(&y.0).then(x.1.total_cmp(&y.1)));
+    params
+}
+
+/// Returns the parameter of the globally nearest point in the result of `curve_local_minima`.
+pub(super) fn curve_global_nearest<C, P, Q>(
+    curve: &C,
+    beziers: Vec<BezierCurvePiece<P>>,
+    project: impl Fn(P) -> Q,
+    rational: bool,
+    point: Q,
+    trials: usize,
+) -> Option<f64>
+where
+    C: ParametricCurve<Point = Q, Vector = Q::Diff>,
+    P: ControlPoint<f64>,
+    Q: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    Q::Diff: InnerSpace<Scalar = f64> + Tolerance,
+{
+    curve_local_minima(curve, beziers, project, rational, point, true, trials)
+        .into_iter()
+        .map(|t| (t, curve.subs(t).distance2(point)))
+        .min_by(|x, y| x.1.total_cmp(&y.1))
+        .map(|x| x.0)
+}
+
+/// Returns the parameter of the globally nearest point in the result of `surface_local_minima`.
+pub(super) fn surface_global_nearest<S, P, Q>(
+    surface: &S,
+    patches: Vec<BezierSurfacePiece<P>>,
+    project: impl Fn(P) -> Q,
+    rational: bool,
+    point: Q,
+    trials: usize,
+) -> Option<(f64, f64)>
+where
+    S: ParametricSurface<Point = Q, Vector = Q::Diff>,
+    P: ControlPoint<f64>,
+    Q: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    Q::Diff: InnerSpace<Scalar = f64> + Tolerance,
+{
+    surface_local_minima(surface, patches, project, rational, point, true, trials)
+        .into_iter()
+        .map(|(u, v)| ((u, v), surface.subs(u, v).distance2(point)))
+        .min_by(|x, y| x.1.total_cmp(&y.1))
+        .map(|x| x.0)
+}
+
+/// Decomposes the B-spline curve into Bezier pieces.
+pub(super) fn bezier_pieces<P>(curve: &BSplineCurve<P>) -> Vec<BezierCurvePiece<P>>
+where P: ControlPoint<f64> + Tolerance {
+    curve
+        .bezier_decomposition()
+        .into_iter()
+        .map(|bezier| {
+            let knot_vec = &bezier.knot_vec;
+            let range = (knot_vec[0], knot_vec[knot_vec.len() - 1]);
+            (range, bezier.control_points)
+        })
+        .collect()
+}
+
+/// Decomposes the B-spline surface into Bezier patches.
+pub(super) fn bezier_patches<P>(surface: &BSplineSurface<P>) -> Vec<BezierSurfacePiece<P>>
+where P: ControlPoint<f64> + Tolerance {
+    let (uknot_vec, vknot_vec) = surface.knot_vecs();
+    let columns = (0..surface.control_points[0].len())
+        .map(|j| {
+            let column = surface.control_points.iter().map(|row| row[j]).collect();
+            bezier_pieces(&BSplineCurve::new(uknot_vec.clone(), column))
+        })
+        .collect::<Vec<_>>();
+    (0..columns[0].len())
+        .flat_map(|k| {
+            let urange = columns[0][k].0;
+            let strip = transpose(&columns.iter().map(|c| c[k].1.clone()).collect::<Vec<_>>());
+            let rows = strip
+                .into_iter()
+                .map(|row| bezier_pieces(&BSplineCurve::new(vknot_vec.clone(), row)))
+                .collect::<Vec<_>>();
+            (0..rows[0].len()).map(move |l| {
+                let vrange = rows[0][l].0;
+                let ctrl = rows.iter().map(|r| r[l].1.clone()).collect();
+                ((urange, vrange), ctrl)
+            })
+        })
+        .collect()
+}
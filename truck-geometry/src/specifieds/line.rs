@@ -173,6 +173,27 @@ where
     }
 }
 
+impl<P> SameDomain for Line<P>
+where
+    P: ControlPoint<f64>,
+    P::Diff: InnerSpace<Scalar = f64>,
+{
+    /// Returns whether two lines are collinear. The orientations are not considered.
+    #[inline]
+    fn same_domain(&self, other: &Self, tol: f64) -> bool {
+        let b = self.1 - self.0;
+        let len2 = b.dot(b);
+        if len2 < TOLERANCE2 {
+            return false;
+        }
+        let distance2 = |pt: P| {
+            let v = pt - self.0;
+            v.dot(v) - v.dot(b) * v.dot(b) / len2
+        };
+        distance2(other.0) <= tol * tol && distance2(other.1) <= tol * tol
+    }
+}
+
 impl<P: EuclideanSpace, M: Transform<P>> Transformed<M> for Line<P> {
     #[inline]
     fn transform_by(&mut self, trans: M) {
@@ -215,3 +236,23 @@ fn line() {
         .search_parameter(Point2::new(1.0, 1.0), None, 0)
         .is_none());
 }
+
+#[test]
+fn same_domain() {
+    let line = Line(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0));
+    // the collinear lines in the both orientations
+    let other = Line(Point3::new(2.0, 2.0, 0.0), Point3::new(3.0, 3.0, 0.0));
+    assert!(line.same_domain(&other, TOLERANCE));
+    assert!(line.same_domain(&other.inverse(), TOLERANCE));
+    // the parallel line
+    let shift = Vector3::new(0.0, 0.0, 1.0e-3);
+    let other = Line(line.0 + shift, line.1 + shift);
+    assert!(!line.same_domain(&other, TOLERANCE));
+    assert!(line.same_domain(&other, 1.0e-2));
+    // the line crossing at the start point
+    let other = Line(line.0, Point3::new(1.0, 0.0, 0.0));
+    assert!(!line.same_domain(&other, TOLERANCE));
+    // the degenerate line
+    let point = Line(line.0, line.0);
+    assert!(!point.same_domain(&line, TOLERANCE));
+}
@@ -169,6 +169,31 @@ impl Invertible for Plane {
     fn invert(&mut self) { *self = self.inverse(); }
 }
 
+impl SameDomain for Plane {
+    /// Returns whether two planes are coplanar and have the same normal.
+    /// # Examples
+    /// ```
+    /// use truck_geometry::prelude::*;
+    /// let plane0 = Plane::new(
+    ///     Point3::new(0.0, 0.0, 1.0),
+    ///     Point3::new(1.0, 0.0, 1.0),
+    ///     Point3::new(0.0, 1.0, 1.0),
+    /// );
+    /// let plane1 = Plane::new(
+    ///     Point3::new(2.0, 3.0, 1.0),
+    ///     Point3::new(3.0, 4.0, 1.0),
+    ///     Point3::new(1.0, 4.0, 1.0),
+    /// );
+    /// assert!(plane0.same_domain(&plane1, TOLERANCE));
+    /// assert!(!plane0.same_domain(&plane1.inverse(), TOLERANCE));
+    /// ```
+    #[inline(always)]
+    fn same_domain(&self, other: &Self, tol: f64) -> bool {
+        let normal = self.normal();
+        (normal - other.normal()).magnitude() <= tol && (other.o - self.o).dot(normal).abs() <= tol
+    }
+}
+
 impl IncludeCurve<BSplineCurve<Point3>> for Plane {
     #[inline(always)]
     fn include(&self, curve: &BSplineCurve<Point3>) -> bool {
@@ -254,3 +279,36 @@ impl SearchNearestParameter<D2> for Plane {
         Some((v[0], v[1]))
     }
 }
+
+#[test]
+fn same_domain() {
+    let plane = Plane::new(
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(1.0, 0.0, 1.0),
+        Point3::new(0.0, 1.0, 1.0),
+    );
+    // the coplanar plane with the other parametrization
+    let other = Plane::new(
+        Point3::new(5.0, -2.0, 1.0),
+        Point3::new(5.0, -1.0, 1.0),
+        Point3::new(3.0, -2.0, 1.0),
+    );
+    assert!(plane.same_domain(&other, TOLERANCE));
+    // the opposite normal
+    assert!(!plane.same_domain(&plane.inverse(), TOLERANCE));
+    // the parallel plane
+    let other = Plane::new(
+        Point3::new(0.0, 0.0, 1.001),
+        Point3::new(1.0, 0.0, 1.001),
+        Point3::new(0.0, 1.0, 1.001),
+    );
+    assert!(!plane.same_domain(&other, TOLERANCE));
+    assert!(plane.same_domain(&other, 1.0e-2));
+    // the tilted plane through the origin of `plane`
+    let other = Plane::new(
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(1.0, 0.0, 1.1),
+        Point3::new(0.0, 1.0, 1.0),
+    );
+    assert!(!plane.same_domain(&other, TOLERANCE));
+}
@@ -85,6 +85,13 @@ fn sphere_derivation_test() {
 
 impl BoundedSurface for Sphere {}
 
+impl SameDomain for Sphere {
+    #[inline(always)]
+    fn same_domain(&self, other: &Self, tol: f64) -> bool {
+        self.center.distance(other.center) <= tol && (self.radius - other.radius).abs() <= tol
+    }
+}
+
 impl IncludeCurve<BSplineCurve<Point3>> for Sphere {
     #[inline(always)]
     fn include(&self, curve: &BSplineCurve<Point3>) -> bool {
@@ -177,7 +184,7 @@ impl SearchNearestParameter<D2> for Sphere {
         let radius = (point - self.center).normalize();
         let u = f64::acos(radius[2]);
         let sinu = f64::sqrt(1.0 - radius[2] * radius[2]);
-        let cosv = radius[0] / sinu;
+        let cosv = f64::clamp(radius[0] / sinu, -1.0, 1.0);
         let v = if radius[1] > 0.0 {
             f64::acos(cosv)
         } else {
@@ -217,3 +224,16 @@ fn exec_search_parameter_test() {
 
 #[test]
 fn search_parameter_test() { (0..10).for_each(|_| exec_search_parameter_test()) }
+
+#[test]
+fn search_nearest_parameter_on_seam() {
+    let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.5), 1.0);
+    // the rounding error makes the cosine of `v` a little greater than one.
+    let pt = Point3::new(
+        0.8660254037844386,
+        2.362354809609807e-17,
+        8.915941040369674e-17,
+    );
+    let (u, v) = sphere.search_nearest_parameter(pt, None, 100).unwrap();
+    assert_near!(sphere.subs(u, v), pt);
+}
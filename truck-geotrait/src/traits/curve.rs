@@ -83,7 +83,7 @@ impl ParametricCurve for (usize, usize) {
 /// Implementation for the test of topological methods.
 impl BoundedCurve for (usize, usize) {}
 
-impl<'a, C: ParametricCurve> ParametricCurve for &'a C {
+impl<C: ParametricCurve> ParametricCurve for &C {
     type Point = C::Point;
     type Vector = C::Vector;
     fn subs(&self, t: f64) -> Self::Point { (*self).subs(t) }
@@ -97,7 +97,7 @@ impl<'a, C: ParametricCurve> ParametricCurve for &'a C {
     fn period(&self) -> Option<f64> { (*self).period() }
 }
 
-impl<'a, C: BoundedCurve> BoundedCurve for &'a C {
+impl<C: BoundedCurve> BoundedCurve for &C {
     #[inline(always)]
     fn front(&self) -> Self::Point { (*self).front() }
     #[inline(always)]
@@ -149,7 +149,7 @@ pub trait ParameterDivision1D {
     fn parameter_division(&self, range: (f64, f64), tol: f64) -> (Vec<f64>, Vec<Self::Point>);
 }
 
-impl<'a, C: ParameterDivision1D> ParameterDivision1D for &'a C {
+impl<C: ParameterDivision1D> ParameterDivision1D for &C {
     type Point = C::Point;
     fn parameter_division(&self, range: (f64, f64), tol: f64) -> (Vec<f64>, Vec<Self::Point>) {
         (*self).parameter_division(range, tol)
@@ -248,6 +248,9 @@ pub enum ConcatError<Point: Debug> {
     /// Failed to concat curves since the end point of the first curve is different from the start point of the second curve.
     #[error("The end point {0:?} of the first curve is different from the start point {1:?} of the second curve.")]
     DisconnectedPoints(Point, Point),
+    /// Failed to concat curves since the kind of the curves cannot be concatenated, e.g. intersection curves.
+    #[error("The curves cannot be concatenated since their kind is not supported.")]
+    UnsupportedCurve,
 }
 
 impl<T: Debug> ConcatError<T> {
@@ -258,6 +261,7 @@ impl<T: Debug> ConcatError<T> {
         match self {
             ConcatError::DisconnectedParameters(a, b) => ConcatError::DisconnectedParameters(a, b),
             ConcatError::DisconnectedPoints(p, q) => ConcatError::DisconnectedPoints(f(p), f(q)),
+            ConcatError::UnsupportedCurve => ConcatError::UnsupportedCurve,
         }
     }
 }
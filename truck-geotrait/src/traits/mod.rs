@@ -71,3 +71,16 @@ impl<S: Transformed<T>, T> Transformed<T> for Box<S> {
     #[inline(always)]
     fn transformed(&self, trans: T) -> Self { Box::new((**self).transformed(trans)) }
 }
+
+/// Whether two geometries lie on the same domain, e.g. two coplanar planes or two collinear lines.
+pub trait SameDomain {
+    /// Returns whether `self` and `other` lie on the same domain within the tolerance `tol`.
+    ///
+    /// For surfaces, the orientations of the normals must also coincide.
+    fn same_domain(&self, other: &Self, tol: f64) -> bool;
+}
+
+impl<T: SameDomain> SameDomain for Box<T> {
+    #[inline(always)]
+    fn same_domain(&self, other: &Self, tol: f64) -> bool { (**self).same_domain(other, tol) }
+}
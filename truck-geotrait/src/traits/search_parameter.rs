@@ -38,6 +38,9 @@ pub enum SPHint1D {
     /// There are no hint. In the case of `BoundedCurve`, most of the time the parameter range is applied.
     /// Such as planes, no hinting is needed in the first place.
     None,
+    /// Searches the whole domain so that the global solution is obtained.
+    /// B-spline and NURBS curves do this by Bezier subdivision, and the others treat this as `None`.
+    Global,
 }
 
 impl From<f64> for SPHint1D {
@@ -66,6 +69,9 @@ pub enum SPHint2D {
     Range((f64, f64), (f64, f64)),
     /// There are no hint. If the algorithm needed a hint, it always returns None.
     None,
+    /// Searches the whole domain so that the global solution is obtained.
+    /// B-spline and NURBS surfaces do this by Bezier subdivision, and the others treat this as `None`.
+    Global,
 }
 
 impl From<(f64, f64)> for SPHint2D {
@@ -99,7 +105,7 @@ pub trait SearchParameter<Dim: SPDimension> {
     ) -> Option<Dim::Parameter>;
 }
 
-impl<'a, Dim: SPDimension, T: SearchParameter<Dim>> SearchParameter<Dim> for &'a T {
+impl<Dim: SPDimension, T: SearchParameter<Dim>> SearchParameter<Dim> for &T {
     type Point = T::Point;
     fn search_parameter<H: Into<Dim::Hint>>(
         &self,
@@ -137,7 +143,7 @@ pub trait SearchNearestParameter<Dim: SPDimension> {
     ) -> Option<Dim::Parameter>;
 }
 
-impl<'a, Dim: SPDimension, T: SearchNearestParameter<Dim>> SearchNearestParameter<Dim> for &'a T {
+impl<Dim: SPDimension, T: SearchNearestParameter<Dim>> SearchNearestParameter<Dim> for &T {
     type Point = T::Point;
     fn search_nearest_parameter<H: Into<Dim::Hint>>(
         &self,
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use std::ops::Bound;
 use truck_base::cgmath64::*;
 use truck_geotrait::*;
@@ -167,6 +167,20 @@ macro_rules! impl_shape {
                     .map_err(|e| gloo::console::error!(format!("{e}")))
                     .unwrap()
             }
+            /// read shape from the compact binary format
+            pub fn from_binary(data: &[u8]) -> Option<$type> {
+                truck_modeling::binary::from_slice::<truck_modeling::$type>(data)
+                .map_err(|e| println!("{e}"))
+                .ok()
+                .map(|res| res.into_wasm())
+            }
+            /// write shape to the compact binary format
+            pub fn to_binary(&self) -> Vec<u8> {
+                use truck_modeling::binary::{to_vec, Compression};
+                to_vec(&self.0, Compression::Lz)
+                    .map_err(|e| gloo::console::error!(format!("{e}")))
+                    .unwrap()
+            }
             /// write shape to STEP
             pub fn to_step(&self, header: StepHeaderDescriptor) -> String {
                 use truck_stepio::out;
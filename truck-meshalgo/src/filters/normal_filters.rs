@@ -16,7 +16,7 @@ pub trait NormalFilters {
     ///         positions: vec![Point3::new(0.0, 0.0, 0.0)],
     ///         normals: vec![
     ///             Vector3::new(100.0, 20.0, 56.0),
-    ///             Vector3::new(1.0e-12, 3.536e10, std::f64::NAN),
+    ///             Vector3::new(1.0e-12, 3.536e10, f64::NAN),
     ///             Vector3::new(0.0, 1.0, 0.0),
     ///         ],
     ///         ..Default::default()
@@ -76,12 +76,12 @@ pub trait NormalFilters {
     /// # Details
     /// For each vertex, apply the following algorithm:
     /// 1. prepare vectors that enumerate the normals of the faces containing
-    /// the target vertices in order.
+    ///    the target vertices in order.
     /// 1. cluster each normal `n` in turn in the following manner.
     ///  * If there is an existing cluster `A` in which the angle between the weighted
-    /// average of `A` and `n` is less than or equal to `tol_ang`, add `n` to `A`.
+    ///    average of `A` and `n` is less than or equal to `tol_ang`, add `n` to `A`.
     ///  * If cluster `A` as described above does not exist,
-    /// create a new cluster that contains only `n`.
+    ///    create a new cluster that contains only `n`.
     /// # Arguments
     /// - If `overwrite == true`, clear all normals and update all normals in vertices.
     /// - If `overwrite == false`, add normals only for `nor` is `None`.
@@ -10,6 +10,7 @@ pub trait Subdivision {
     /// Confirm:
     /// - All faces are triangles.
     /// - `self.shell_condition()` is `Oriented` or `Closed` before use.
+    ///
     /// This method does NOT check these conditions.
     fn loop_subdivision(&mut self) -> &mut Self;
 }
@@ -87,6 +87,21 @@ where Shell<P, C, S>: MeshedShape
     }
 }
 
+impl<P, C, S> MeshedShape for Compound<P, C, S>
+where Shell<P, C, S>: MeshedShape
+{
+    fn to_polygon(&self) -> PolygonMesh {
+        let mut polygon = PolygonMesh::default();
+        self.solids().iter().for_each(|solid| {
+            polygon.merge(solid.to_polygon());
+        });
+        self.shells().iter().for_each(|shell| {
+            polygon.merge(shell.to_polygon());
+        });
+        polygon
+    }
+}
+
 impl MeshedShape for CompressedShell<Point3, PolylineCurve, PolygonMesh> {
     fn to_polygon(&self) -> PolygonMesh {
         let mut polygon = PolygonMesh::default();
@@ -139,7 +154,7 @@ pub trait MeshableShape {
     /// # Remarks
     ///
     /// - The tessellated mesh is not necessarily closed even if `self` is `Solid`.
-    /// If you want to get closed mesh, use [`OptimizingFilter::put_together_same_attrs`].
+    ///   If you want to get closed mesh, use [`OptimizingFilter::put_together_same_attrs`].
     /// - This method requires that the curve ride strictly on a surface. If not, try [`RobustMeshableShape`].
     ///
     /// [`OptimizingFilter::put_together_same_attrs`]: crate::filters::OptimizingFilter::put_together_same_attrs
@@ -313,6 +328,68 @@ impl<C: PolylineableCurve, S: RobustMeshableSurface> RobustMeshableShape for Sol
     }
 }
 
+/// Makes the edges of wires polylines.
+fn polyline_wire<C: PolylineableCurve>(
+    wire: &Wire<Point3, C>,
+    tol: f64,
+) -> Wire<Point3, PolylineCurve> {
+    wire.mapped(Point3::clone, |curve| {
+        PolylineCurve::from_curve(curve, curve.range_tuple(), tol)
+    })
+}
+
+impl<C: PolylineableCurve, S: MeshableSurface> MeshableShape for Compound<Point3, C, S> {
+    type MeshedShape = Compound<Point3, PolylineCurve, Option<PolygonMesh>>;
+    fn triangulation(&self, tol: f64) -> Self::MeshedShape {
+        nonpositive_tolerance!(tol);
+        Compound::from_elements(
+            self.solids()
+                .iter()
+                .map(|solid| solid.triangulation(tol))
+                .collect(),
+            self.shells()
+                .iter()
+                .map(|shell| shell.triangulation(tol))
+                .collect(),
+            self.wires()
+                .iter()
+                .map(|wire| polyline_wire(wire, tol))
+                .collect(),
+            self.vertices()
+                .iter()
+                .map(|vertex| vertex.mapped(Point3::clone))
+                .collect(),
+        )
+    }
+}
+
+impl<C: PolylineableCurve, S: RobustMeshableSurface> RobustMeshableShape
+    for Compound<Point3, C, S>
+{
+    type MeshedShape = Compound<Point3, PolylineCurve, Option<PolygonMesh>>;
+    fn robust_triangulation(&self, tol: f64) -> Self::MeshedShape {
+        nonpositive_tolerance!(tol);
+        Compound::from_elements(
+            self.solids()
+                .iter()
+                .map(|solid| solid.robust_triangulation(tol))
+                .collect(),
+            self.shells()
+                .iter()
+                .map(|shell| shell.robust_triangulation(tol))
+                .collect(),
+            self.wires()
+                .iter()
+                .map(|wire| polyline_wire(wire, tol))
+                .collect(),
+            self.vertices()
+                .iter()
+                .map(|vertex| vertex.mapped(Point3::clone))
+                .collect(),
+        )
+    }
+}
+
 impl<C: PolylineableCurve, S: MeshableSurface> MeshableShape for CompressedShell<Point3, C, S> {
     type MeshedShape = CompressedShell<Point3, PolylineCurve, Option<PolygonMesh>>;
     fn triangulation(&self, tol: f64) -> Self::MeshedShape {
@@ -48,6 +48,33 @@ where
         .or_else(|| surface.search_nearest_parameter(point, None, 100))
 }
 
+/// Creates the polyline of the edge. The end points are snapped to the vertices
+/// if they are within the tolerances of the edge and the vertices.
+fn edge_polyline<C: PolylineableCurve>(
+    curve: &C,
+    tol: f64,
+    edge_tol: f64,
+    ends: [(Point3, f64); 2],
+) -> PolylineCurve {
+    let mut poly = PolylineCurve::from_curve(curve, curve.range_tuple(), tol);
+    let n = poly.len() - 1;
+    [(0, ends[0]), (n, ends[1])]
+        .into_iter()
+        .for_each(|(i, (pt, vertex_tol))| {
+            if poly[i].distance(pt) <= f64::max(edge_tol, vertex_tol) {
+                poly[i] = pt;
+            }
+        });
+    poly
+}
+
+/// Copies the vertex, keeping the tolerance and the stable id.
+fn tessellate_vertex(vertex: &truck_topology::Vertex<Point3>) -> truck_topology::Vertex<Point3> {
+    let new_vertex = vertex.mapped(Point3::clone);
+    new_vertex.set_stable_id(vertex.stable_id());
+    new_vertex
+}
+
 /// Tessellates faces
 #[cfg(not(target_arch = "wasm32"))]
 pub(super) fn shell_tessellation<'a, C, S>(
@@ -61,7 +88,7 @@ where
 {
     let vmap: HashMap<_, _> = shell
         .vertex_par_iter()
-        .map(|v| (v.id(), v.mapped(Point3::clone)))
+        .map(|v| (v.id(), tessellate_vertex(&v)))
         .collect();
     let eset: HashMap<_, _> = shell.edge_par_iter().map(move |e| (e.id(), e)).collect();
     let edge_map: HashMap<_, _> = eset
@@ -69,9 +96,12 @@ where
         .map(move |(id, edge)| {
             let v0 = vmap.get(&edge.absolute_front().id()).unwrap();
             let v1 = vmap.get(&edge.absolute_back().id()).unwrap();
-            let curve = edge.curve();
-            let poly = PolylineCurve::from_curve(&curve, curve.range_tuple(), tol);
-            (id, Edge::debug_new(v0, v1, poly))
+            let ends = [v0, v1].map(|v| (v.point(), v.tolerance_or_default()));
+            let poly = edge_polyline(&edge.curve(), tol, edge.tolerance_or_default(), ends);
+            let new_edge = Edge::debug_new(v0, v1, poly);
+            new_edge.set_tolerance(edge.tolerance());
+            new_edge.set_stable_id(edge.stable_id());
+            (id, new_edge)
         })
         .collect();
     let create_edge = |edge: &Edge<Point3, C>| -> Edge<_, _> {
@@ -89,7 +119,9 @@ where
             .iter()
             .map(create_boundary)
             .collect();
-        shell_create_polygon(&face.surface(), wires, face.orientation(), tol, &sp)
+        let new_face = shell_create_polygon(&face.surface(), wires, face.orientation(), tol, &sp);
+        new_face.set_stable_id(face.stable_id());
+        new_face
     };
     shell.face_par_iter().map(create_face).collect()
 }
@@ -107,10 +139,7 @@ where
 {
     use truck_base::entry_map::FxEntryMap as EntryMap;
     use truck_topology::Vertex as TVertex;
-    let mut vmap = EntryMap::new(
-        move |v: &TVertex<Point3>| v.id(),
-        move |v| v.mapped(Point3::clone),
-    );
+    let mut vmap = EntryMap::new(move |v: &TVertex<Point3>| v.id(), tessellate_vertex);
     let mut edge_map = EntryMap::new(
         move |edge: &'a Edge<Point3, C>| edge.id(),
         move |edge| {
@@ -118,9 +147,12 @@ where
             let v0 = vmap.entry_or_insert(vf).clone();
             let vb = edge.absolute_back();
             let v1 = vmap.entry_or_insert(vb).clone();
-            let curve = edge.curve();
-            let poly = PolylineCurve::from_curve(&curve, curve.range_tuple(), tol);
-            Edge::debug_new(&v0, &v1, poly)
+            let ends = [&v0, &v1].map(|v| (v.point(), v.tolerance_or_default()));
+            let poly = edge_polyline(&edge.curve(), tol, edge.tolerance_or_default(), ends);
+            let new_edge = Edge::debug_new(&v0, &v1, poly);
+            new_edge.set_tolerance(edge.tolerance());
+            new_edge.set_stable_id(edge.stable_id());
+            new_edge
         },
     );
     let mut create_edge = move |edge: &'a Edge<Point3, C>| -> Edge<_, _> {
@@ -139,7 +171,9 @@ where
             .iter()
             .map(&mut create_boundary)
             .collect();
-        shell_create_polygon(&face.surface(), wires, face.orientation(), tol, &sp)
+        let new_face = shell_create_polygon(&face.surface(), wires, face.orientation(), tol, &sp);
+        new_face.set_stable_id(face.stable_id());
+        new_face
     };
     shell.face_iter().map(create_face).collect()
 }
@@ -155,11 +189,22 @@ where
     S: PreMeshableSurface + 'a,
 {
     let vertices = shell.vertices.clone();
+    let vertex_tolerances = shell.vertex_tolerances.clone();
+    let vertex_end = |i: usize| {
+        let tolerance = match vertex_tolerances.is_empty() {
+            true => TOLERANCE,
+            false => vertex_tolerances[i].unwrap_or(TOLERANCE),
+        };
+        (vertices[i], tolerance)
+    };
     let tessellate_edge = |edge: &CompressedEdge<C>| {
-        let curve = &edge.curve;
+        let ends = [vertex_end(edge.vertices.0), vertex_end(edge.vertices.1)];
+        let edge_tol = edge.tolerance.unwrap_or(TOLERANCE);
         CompressedEdge {
             vertices: edge.vertices,
-            curve: PolylineCurve::from_curve(curve, curve.range_tuple(), tol),
+            curve: edge_polyline(&edge.curve, tol, edge_tol, ends),
+            tolerance: edge.tolerance,
+            stable_id: edge.stable_id,
         }
     };
     #[cfg(not(target_arch = "wasm32"))]
@@ -186,6 +231,7 @@ where
             boundaries,
             orientation: face.orientation,
             surface: polygon,
+            stable_id: face.stable_id,
         }
     };
     #[cfg(not(target_arch = "wasm32"))]
@@ -194,6 +240,8 @@ where
     let faces = shell.faces.iter().map(tessellate_face).collect();
     MeshedCShell {
         vertices,
+        vertex_tolerances,
+        vertex_stable_ids: shell.vertex_stable_ids.clone(),
         edges,
         faces,
     }
@@ -133,3 +133,36 @@ fn special_cylinder_csolid() {
         .remove_unused_attrs();
     assert_eq!(mesh.shell_condition(), ShellCondition::Closed);
 }
+
+#[test]
+fn compound_triangulation() {
+    let v = builder::vertex(Point3::new(-1.0, -1.0, -1.0));
+    let e = builder::tsweep(&v, Vector3::unit_x() * 2.0);
+    let f = builder::tsweep(&e, Vector3::unit_y() * 2.0);
+    let solid = builder::tsweep(&f, Vector3::unit_z() * 2.0);
+    let v = builder::vertex(Point3::new(3.0, 0.0, 0.0));
+    let wire: Wire = builder::rsweep(&v, Point3::new(2.0, 0.0, 0.0), Vector3::unit_z(), Rad(3.0));
+    let mut compound = Compound::from(solid.clone());
+    compound.push_shell(special_cylinder_model());
+    compound.push_wire(wire.clone());
+    compound.push_vertex(builder::vertex(Point3::new(5.0, 0.0, 0.0)));
+
+    let meshed = compound.triangulation(0.01);
+    assert_eq!(meshed.wires()[0].len(), wire.len());
+    assert_eq!(meshed.vertices()[0].point(), Point3::new(5.0, 0.0, 0.0));
+    meshed.wires()[0].iter().for_each(|edge| {
+        let polyline = edge.curve();
+        assert!(polyline.len() > 2);
+        assert!(polyline
+            .iter()
+            .all(|p| p.distance(Point3::new(2.0, 0.0, 0.0)).near(&1.0)));
+    });
+
+    let poly = meshed.to_polygon();
+    let solid_poly = solid.triangulation(0.01).to_polygon();
+    let shell_poly = special_cylinder_model().triangulation(0.01).to_polygon();
+    assert_eq!(
+        poly.positions().len(),
+        solid_poly.positions().len() + shell_poly.positions().len(),
+    );
+}
@@ -0,0 +1,966 @@
+use crate::*;
+use errors::Error;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use std::f64::consts::PI;
+
+const SEARCH_PARAMETER_TRIALS: usize = 100;
+/// the tolerance for the geometry fitted to the approximated curves, e.g. intersection curves
+const FITTING_TOLERANCE: f64 = 10.0 * TOLERANCE;
+
+/// The radius of fillets
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilletRadius {
+    /// the constant radius
+    Constant(f64),
+    /// the radius changing linearly from the front vertex to the back vertex of each edge
+    Variable {
+        /// the radius at the front vertex
+        front: f64,
+        /// the radius at the back vertex
+        back: f64,
+    },
+}
+
+impl From<f64> for FilletRadius {
+    #[inline(always)]
+    fn from(radius: f64) -> Self { Self::Constant(radius) }
+}
+
+impl FilletRadius {
+    /// the radius at the front vertex if `end == 0`, and at the back vertex otherwise.
+    #[inline(always)]
+    fn at(self, end: usize) -> f64 {
+        match (self, end) {
+            (Self::Constant(radius), _) => radius,
+            (Self::Variable { front, .. }, 0) => front,
+            (Self::Variable { back, .. }, _) => back,
+        }
+    }
+}
+
+/// The distances of chamfers
+///
+/// The first face of an edge is the face whose boundary runs along the edge from its front
+/// vertex to its back vertex, and the second face is the other one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChamferDistance {
+    /// the same distance from the edge on both faces
+    Symmetric(f64),
+    /// the distances from the edge on the first face and on the second face
+    TwoDistances(f64, f64),
+    /// the distance from the edge on the first face and the angle between the chamfer and
+    /// the first face
+    DistanceAngle(f64, Rad<f64>),
+}
+
+impl From<f64> for ChamferDistance {
+    #[inline(always)]
+    fn from(distance: f64) -> Self { Self::Symmetric(distance) }
+}
+
+impl From<(f64, f64)> for ChamferDistance {
+    #[inline(always)]
+    fn from((distance0, distance1): (f64, f64)) -> Self { Self::TwoDistances(distance0, distance1) }
+}
+
+impl From<(f64, Rad<f64>)> for ChamferDistance {
+    #[inline(always)]
+    fn from((distance, angle): (f64, Rad<f64>)) -> Self { Self::DistanceAngle(distance, angle) }
+}
+
+/// Rounds `edges` of `solid` by the fillets of `radius`.
+///
+/// Each edge is replaced by the rolling-ball blend face tangent to the two adjacent faces,
+/// and the adjacent faces are trimmed along the tangent curves.
+///
+/// # Details
+/// - The edges must be lines shared by two planar faces, or circular arcs shared by two faces
+///   on which the offset curves of the arcs are coaxial arcs, e.g. a plane and a cylinder
+///   perpendicular to it. The faces may meet at a convex or a concave angle.
+/// - At a vertex where two filleted edges continue smoothly and the other edges are shared by
+///   tangent faces, e.g. on the circular rims of cylinders, the fillets are joined smoothly.
+/// - Otherwise, the vertices of the edges must be shared by exactly three lines and three
+///   planar faces.
+///   - If one filleted edge ends at a vertex, the fillet is trimmed by the plane of the third
+///     face.
+///   - If two filleted edges meet at a vertex, the fillets are trimmed by each other.
+///   - If three filleted edges meet at a vertex, the corner is blended by a spherical patch.
+///     All edges must be convex or all concave, and one of the three faces must be
+///     perpendicular to the other two.
+///
+///   The radii of the edges meeting at a vertex must be the same.
+///
+///   Other vertex blends, e.g. the corners of convex and concave edges or the setback blends
+///   of different radii, are not supported.
+/// - With [`FilletRadius::Variable`], the radius changes linearly along each line.
+///   The fillet is still tangent to the adjacent faces.
+/// # Failures
+/// - Returns [`Error::NotBlendableEdge`] if an edge is not an edge of `solid`, is not one of
+///   the above, or is a circular arc filleted by a variable radius.
+/// - Returns [`Error::UnsupportedBlendVertex`] if the edges meet at a vertex in another
+///   configuration than the above.
+/// - Returns [`Error::InvalidBlendSize`] if the radius is not positive, or so large that
+///   the fillet overruns the adjacent edges.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // the top edge on the plane y = 0
+/// let edge = cube
+///     .edge_iter()
+///     .find(|edge| {
+///         let (p, q) = (edge.front().point(), edge.back().point());
+///         p.y == 0.0 && q.y == 0.0 && p.z == 1.0 && q.z == 1.0
+///     })
+///     .unwrap();
+/// let rounded = blend::fillet_edges(&cube, &[edge], 0.25).unwrap();
+/// let shell = &rounded.boundaries()[0];
+/// assert_eq!(shell.len(), 7);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// ```
+pub fn fillet_edges(
+    solid: &Solid,
+    edges: &[Edge],
+    radius: impl Into<FilletRadius>,
+) -> Result<Solid> {
+    let radius = radius.into();
+    if radius.at(0) < TOLERANCE || radius.at(1) < TOLERANCE {
+        return Err(Error::InvalidBlendSize);
+    }
+    blend_edges(solid, edges, Profile::Fillet(radius))
+}
+
+/// Bevels `edges` of `solid` by the chamfers of `distance`.
+///
+/// Each edge is replaced by the ruled face between the offset curves of the edge on the two
+/// adjacent faces, and the adjacent faces are trimmed along the offset curves. `distance` is
+/// a distance `d`, two distances `(d0, d1)` or a distance and an angle `(d, angle)`,
+/// cf. [`ChamferDistance`].
+///
+/// # Details
+/// The supported edges are the same as [`fillet_edges`]. The chamfer of a line is the planar
+/// face attached by [`builder::try_attach_plane`], and the chamfer of a circular arc is the
+/// conical face of [`builder::homotopy`] between the offset arcs.
+///
+/// The corner where all the edges of a vertex are chamfered is cut by the polygon through the
+/// ends of the chamfers. Unlike fillets, the vertex may be shared by three or more planar faces
+/// meeting at convex and concave edges, and the chamfers may have different distances.
+/// The polygon must be planar, which always holds for three faces. If some edges of the vertex
+/// are not chamfered, the chamfers meeting at the vertex must end at the same points on the
+/// other edges, e.g. chamfers of the same symmetric distance between perpendicular faces.
+/// # Failures
+/// - Returns [`Error::NotBlendableEdge`] if an edge is not an edge of `solid` or is not
+///   supported.
+/// - Returns [`Error::UnsupportedBlendVertex`] if the edges meet at a vertex in an unsupported
+///   configuration.
+/// - Returns [`Error::InvalidBlendSize`] if the distances or the angle are not positive, or
+///   so large that the chamfer overruns the adjacent edges.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+/// let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+/// let disk = builder::try_attach_plane(&[circle]).unwrap();
+/// let cylinder: Solid = builder::tsweep(&disk, Vector3::unit_z());
+///
+/// // the circle on the top face
+/// let top = cylinder.boundaries()[0].iter().last().unwrap();
+/// let edges: Vec<Edge> = top.edge_iter().collect();
+/// let chamfered = blend::chamfer_edges(&cylinder, &edges, 0.25).unwrap();
+/// let shell = &chamfered.boundaries()[0];
+/// assert_eq!(shell.len(), cylinder.boundaries()[0].len() + edges.len());
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// ```
+pub fn chamfer_edges(
+    solid: &Solid,
+    edges: &[Edge],
+    distance: impl Into<ChamferDistance>,
+) -> Result<Solid> {
+    let distance = distance.into();
+    let valid = match distance {
+        ChamferDistance::Symmetric(d) => d > TOLERANCE,
+        ChamferDistance::TwoDistances(d0, d1) => d0 > TOLERANCE && d1 > TOLERANCE,
+        ChamferDistance::DistanceAngle(d, angle) => d > TOLERANCE && angle.0 > TOLERANCE,
+    };
+    match valid {
+        true => blend_edges(solid, edges, Profile::Chamfer(distance)),
+        false => Err(Error::InvalidBlendSize),
+    }
+}
+
+/// The cross section of blends
+#[derive(Clone, Copy, Debug)]
+enum Profile {
+    Fillet(FilletRadius),
+    Chamfer(ChamferDistance),
+}
+
+impl Profile {
+    /// the distances from the edge to the rails on the two faces at `end`, where the faces
+    /// meet at `angle`.
+    fn setbacks(self, end: usize, angle: f64) -> Result<[f64; 2]> {
+        match self {
+            Profile::Fillet(radius) => {
+                let t = radius.at(end) / f64::tan(angle / 2.0);
+                Ok([t, t])
+            }
+            Profile::Chamfer(ChamferDistance::Symmetric(d)) => Ok([d, d]),
+            Profile::Chamfer(ChamferDistance::TwoDistances(d0, d1)) => Ok([d0, d1]),
+            Profile::Chamfer(ChamferDistance::DistanceAngle(d, alpha)) => {
+                // the law of sines on the triangle of the edge and the two rails
+                match PI - angle - alpha.0 > TOLERANCE {
+                    true => Ok([d, d * f64::sin(alpha.0) / f64::sin(angle + alpha.0)]),
+                    false => Err(Error::InvalidBlendSize),
+                }
+            }
+        }
+    }
+}
+
+/// The geometric shape of an edge
+#[derive(Clone, Copy, Debug)]
+enum Shape {
+    Line,
+    /// the arc turning around `axis` through `center` from the front vertex to the back vertex
+    Arc {
+        center: Point3,
+        axis: Vector3,
+    },
+}
+
+fn edge_shape(edge: &Edge) -> Option<Shape> {
+    const N: usize = 12;
+    let curve = edge.curve();
+    let (t0, t1) = curve.range_tuple();
+    let pts = (0..=N)
+        .map(|i| curve.subs(t0 + (t1 - t0) * i as f64 / N as f64))
+        .collect::<Vec<_>>();
+    let (p, q) = (pts[0], pts[N]);
+    let dir = q - p;
+    let on_line = |x: &Point3| (x - p).cross(dir).magnitude() < TOLERANCE * dir.magnitude();
+    if dir.magnitude() > TOLERANCE && pts.iter().all(on_line) {
+        return Some(Shape::Line);
+    }
+    let (center, axis) = geom_impls::fit_circle(&pts, FITTING_TOLERANCE)?;
+    Some(Shape::Arc { center, axis })
+}
+
+/// The geometry around an end of a blended edge
+#[derive(Clone, Copy, Debug)]
+struct Frame {
+    /// the unit tangent vector of the edge
+    tangent: Vector3,
+    /// the outward normals of the faces
+    normals: [Vector3; 2],
+    /// the directions from the edge into the faces
+    dirs: [Vector3; 2],
+    /// the angle between `dirs`
+    angle: f64,
+    /// the distances from the edge to the rails along `dirs`
+    setbacks: [f64; 2],
+}
+
+/// An edge to be blended and the geometry around it
+struct Blended {
+    edge: Edge,
+    /// the face along the edge and the face against it
+    faces: [usize; 2],
+    shape: Shape,
+    /// the geometry at the front vertex and at the back vertex
+    frames: [Frame; 2],
+}
+
+impl Blended {
+    #[inline(always)]
+    fn vertex(&self, end: usize) -> &Vertex {
+        match end {
+            0 => self.edge.absolute_front(),
+            _ => self.edge.absolute_back(),
+        }
+    }
+    #[inline(always)]
+    fn end_of(&self, vertex: VertexID) -> usize { (self.vertex(0).id() != vertex) as usize }
+    /// the direction of the edge going away from the vertex at `end`
+    #[inline(always)]
+    fn outgoing(&self, end: usize) -> Vector3 {
+        match end {
+            0 => self.frames[0].tangent,
+            _ => -self.frames[1].tangent,
+        }
+    }
+    #[inline(always)]
+    fn side(&self, face: usize) -> Option<usize> { self.faces.iter().position(|f| *f == face) }
+    #[inline(always)]
+    fn is_convex(&self) -> bool { self.frames[0].normals[0].dot(self.frames[0].dirs[1]) < 0.0 }
+    /// the point on the rail on the face of `side` at `end`
+    #[inline(always)]
+    fn rail_point(&self, end: usize, side: usize) -> Point3 {
+        let frame = &self.frames[end];
+        self.vertex(end).point() + frame.setbacks[side] * frame.dirs[side]
+    }
+    /// the control points of the cross section of the blend on the plane through the vertex
+    /// at `end` perpendicular to the edge
+    #[inline(always)]
+    fn section(&self, end: usize) -> [Point3; 3] {
+        let pt = self.vertex(end).point();
+        [self.rail_point(end, 0), pt, self.rail_point(end, 1)]
+    }
+}
+
+/// The vertex of blended edges
+struct Corner {
+    faces: Vec<usize>,
+    edges: Vec<Edge>,
+    blended: Vec<usize>,
+    /// whether the blends continue smoothly through the vertex
+    smooth: bool,
+}
+
+fn plane_normal(face: &Face) -> Option<Vector3> {
+    match face.oriented_surface() {
+        Surface::Plane(plane) => Some(plane.normal()),
+        _ => None,
+    }
+}
+
+/// Returns the outward normal of `face` at `pt`.
+fn face_normal(face: &Face, pt: Point3) -> Option<Vector3> {
+    let surface = face.oriented_surface();
+    if let Surface::Plane(plane) = &surface {
+        return Some(plane.normal());
+    }
+    let (u, v) = surface.search_parameter(pt, None, SEARCH_PARAMETER_TRIALS)?;
+    Some(surface.normal(u, v))
+}
+
+fn on_surface(face: &Face, pt: Point3) -> bool {
+    let surface = face.oriented_surface();
+    let uv = surface.search_parameter(pt, None, SEARCH_PARAMETER_TRIALS);
+    uv.is_some_and(|(u, v)| surface.subs(u, v).near(&pt))
+}
+
+#[inline(always)]
+fn line_direction(edge: &Edge) -> Vector3 {
+    (edge.absolute_back().point() - edge.absolute_front().point()).normalize()
+}
+
+fn blend_edges(solid: &Solid, edges: &[Edge], profile: Profile) -> Result<Solid> {
+    let selected: HashSet<EdgeID> = edges.iter().map(Edge::id).collect();
+    let mut found = HashSet::default();
+    let shells = solid
+        .boundaries()
+        .iter()
+        .map(|shell| {
+            let ids = shell.edge_iter().map(|edge| edge.id());
+            found.extend(ids.filter(|id| selected.contains(id)));
+            blend_shell(shell, &selected, profile)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if found.len() != selected.len() {
+        return Err(Error::NotBlendableEdge);
+    }
+    Ok(Solid::try_new(shells)?)
+}
+
+fn blended_edge(
+    shell: &Shell,
+    edge: &Edge,
+    uses: &[(usize, bool)],
+    profile: Profile,
+) -> Result<Blended> {
+    let (a, b) = match uses {
+        [(a, true), (b, false)] | [(b, false), (a, true)] => (*a, *b),
+        _ => return Err(Error::NotBlendableEdge),
+    };
+    if edge.absolute_front() == edge.absolute_back() {
+        return Err(Error::NotBlendableEdge);
+    }
+    let shape = edge_shape(edge).ok_or(Error::NotBlendableEdge)?;
+    let frame = |end: usize| {
+        let pt = [edge.absolute_front(), edge.absolute_back()][end].point();
+        let tangent = match shape {
+            Shape::Line => line_direction(edge),
+            Shape::Arc { center, axis, .. } => axis.cross(pt - center).normalize(),
+        };
+        let na = face_normal(&shell[a], pt).ok_or(Error::NotBlendableEdge)?;
+        let nb = face_normal(&shell[b], pt).ok_or(Error::NotBlendableEdge)?;
+        let dirs = [
+            na.cross(tangent).normalize(),
+            nb.cross(-tangent).normalize(),
+        ];
+        let angle = dirs[0].angle(dirs[1]).0;
+        if angle.is_nan() || angle < TOLERANCE || PI - angle < TOLERANCE {
+            return Err(Error::NotBlendableEdge);
+        }
+        Ok(Frame {
+            tangent,
+            normals: [na, nb],
+            dirs,
+            angle,
+            setbacks: profile.setbacks(end, angle)?,
+        })
+    };
+    let frames = [frame(0)?, frame(1)?];
+    if let Shape::Arc { .. } = shape {
+        let same = |i: usize| (frames[0].setbacks[i] - frames[1].setbacks[i]).abs() < TOLERANCE;
+        if !same(0) || !same(1) {
+            return Err(Error::NotBlendableEdge);
+        }
+    }
+    Ok(Blended {
+        edge: edge.clone(),
+        faces: [a, b],
+        shape,
+        frames,
+    })
+}
+
+fn blend_shell(shell: &Shell, selected: &HashSet<EdgeID>, profile: Profile) -> Result<Shell> {
+    let mut edge_map = HashMap::<EdgeID, (Edge, Vec<(usize, bool)>)>::default();
+    let mut vertex_edges = HashMap::<VertexID, Vec<Edge>>::default();
+    let mut vertex_faces = HashMap::<VertexID, Vec<usize>>::default();
+    shell.iter().enumerate().for_each(|(i, face)| {
+        face.boundaries().iter().flatten().for_each(|edge| {
+            let (absolute, uses) = edge_map.entry(edge.id()).or_insert_with(|| {
+                let absolute = edge.absolute_clone();
+                let ends = [absolute.absolute_front(), absolute.absolute_back()];
+                ends.into_iter().for_each(|v| {
+                    vertex_edges
+                        .entry(v.id())
+                        .or_default()
+                        .push(absolute.clone())
+                });
+                (absolute, Vec::new())
+            });
+            uses.push((i, edge.orientation()));
+            let ends = [absolute.absolute_front(), absolute.absolute_back()];
+            ends.into_iter().for_each(|v| {
+                let faces = vertex_faces.entry(v.id()).or_default();
+                if !faces.contains(&i) {
+                    faces.push(i);
+                }
+            });
+        })
+    });
+
+    let blended = edge_map
+        .iter()
+        .filter(|(id, _)| selected.contains(id))
+        .map(|(_, (edge, uses))| blended_edge(shell, edge, uses, profile))
+        .collect::<Result<Vec<_>>>()?;
+    if blended.is_empty() {
+        return Ok(shell.clone());
+    }
+    let blended_index: HashMap<EdgeID, usize> = blended
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (b.edge.id(), i))
+        .collect();
+    let faces_of = |edge: &Edge| edge_map[&edge.id()].1.iter().map(|(f, _)| *f);
+
+    let mut corners = HashMap::<VertexID, Corner>::default();
+    for (i, b) in blended.iter().enumerate() {
+        for end in 0..2 {
+            let vid = b.vertex(end).id();
+            let corner = corners.entry(vid).or_insert_with(|| Corner {
+                faces: vertex_faces[&vid].clone(),
+                edges: vertex_edges[&vid].clone(),
+                blended: Vec::new(),
+                smooth: false,
+            });
+            corner.blended.push(i);
+        }
+    }
+    for (vid, corner) in &mut corners {
+        let first = &blended[corner.blended[0]];
+        let pt = first.vertex(first.end_of(*vid)).point();
+        // the other edges must be shared by tangent faces
+        let tangent_faces = |edge: &Edge| {
+            let normals = faces_of(edge).map(|f| face_normal(&shell[f], pt));
+            let normals = normals.collect::<Option<Vec<_>>>();
+            matches!(normals.as_deref(), Some([n0, n1]) if n0.near(n1))
+        };
+        corner.smooth = match corner.blended.as_slice() {
+            [i, j] => {
+                let (b0, b1) = (&blended[*i], &blended[*j]);
+                let (u0, u1) = (b0.outgoing(b0.end_of(*vid)), b1.outgoing(b1.end_of(*vid)));
+                (u0 + u1).magnitude() < FITTING_TOLERANCE
+                    && corner
+                        .edges
+                        .iter()
+                        .all(|edge| blended_index.contains_key(&edge.id()) || tangent_faces(edge))
+            }
+            _ => false,
+        };
+        let normals = corner.faces.iter().map(|f| plane_normal(&shell[*f]));
+        let normals = normals.collect::<Option<Vec<_>>>();
+        let lines = corner
+            .edges
+            .iter()
+            .all(|edge| matches!(edge_shape(edge), Some(Shape::Line)));
+        let planar = match profile {
+            Profile::Fillet(_) => {
+                corner.edges.len() == 3
+                    && lines
+                    && matches!(normals.as_deref(), Some([n0, n1, n2])
+                        if n0.cross(*n1).magnitude() > TOLERANCE
+                            && n1.cross(*n2).magnitude() > TOLERANCE
+                            && n2.cross(*n0).magnitude() > TOLERANCE)
+            }
+            // the corner of chamfers is cut by a polygon, whatever the number of the faces
+            Profile::Chamfer(_) => corner.edges.len() >= 3 && lines && normals.is_some(),
+        };
+        // the corner polygon is bounded only by the cross sections
+        let partial = corner.blended.len() >= 3 && corner.blended.len() < corner.edges.len();
+        if (!corner.smooth && !planar) || partial {
+            return Err(Error::UnsupportedBlendVertex);
+        }
+    }
+    let normal = |face: usize| plane_normal(&shell[face]).unwrap();
+
+    // the cross sections at the ends, trimmed at the corners
+    let sections = blended
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            let section = |end: usize| {
+                let vid = b.vertex(end).id();
+                let corner = &corners[&vid];
+                if corner.smooth {
+                    return Ok(b.section(end));
+                }
+                let tangent = b.frames[end].tangent;
+                let project = |pt: Point3, (origin, normal): (Point3, Vector3)| {
+                    let denom = tangent.dot(normal);
+                    match denom.abs() < TOLERANCE {
+                        true => Err(Error::UnsupportedBlendVertex),
+                        false => Ok(pt + tangent * (origin - pt).dot(normal) / denom),
+                    }
+                };
+                let others = corner.blended.iter().filter(|j| **j != i);
+                let others = others.map(|j| &blended[*j]).collect::<Vec<_>>();
+                match profile {
+                    Profile::Fillet(radius) => {
+                        let r = radius.at(end);
+                        if others
+                            .iter()
+                            .any(|c| (radius.at(c.end_of(vid)) - r).abs() > TOLERANCE)
+                        {
+                            return Err(Error::UnsupportedBlendVertex);
+                        }
+                        let plane = match others.as_slice() {
+                            [] => {
+                                let third = corner.faces.iter().find(|f| b.side(**f).is_none());
+                                (b.vertex(end).point(), normal(*third.unwrap()))
+                            }
+                            [other] => meeting_plane(b, other, vid)?,
+                            _ => (corner_center(corner, &blended, &normal, vid)?.0, tangent),
+                        };
+                        let [p, q, s] = b.section(end);
+                        Ok([project(p, plane)?, project(q, plane)?, project(s, plane)?])
+                    }
+                    Profile::Chamfer(_) => {
+                        // each rail is trimmed by the rail of the next chamfered edge on the
+                        // face, or by the face beyond the next edge.
+                        let mut section = b.section(end);
+                        for (side, face) in b.faces.into_iter().enumerate() {
+                            let next = corner.edges.iter().find(|edge| {
+                                edge.id() != b.edge.id() && faces_of(edge).any(|f| f == face)
+                            });
+                            let next = next.ok_or(Error::UnsupportedBlendVertex)?;
+                            let plane = match blended_index.get(&next.id()) {
+                                Some(k) => {
+                                    let c = &blended[*k];
+                                    let (end, side) = (c.end_of(vid), c.side(face).unwrap());
+                                    let normal = normal(face).cross(c.frames[end].tangent);
+                                    (c.rail_point(end, side), normal)
+                                }
+                                None => {
+                                    let beyond = faces_of(next).find(|f| *f != face);
+                                    let beyond = beyond.ok_or(Error::UnsupportedBlendVertex)?;
+                                    (b.vertex(end).point(), normal(beyond))
+                                }
+                            };
+                            section[2 * side] = project(section[2 * side], plane)?;
+                        }
+                        Ok(section)
+                    }
+                }
+            };
+            Ok([section(0)?, section(1)?])
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut new_vertices = HashMap::<VertexID, Vec<Vertex>>::default();
+    let ends = blended
+        .iter()
+        .zip(&sections)
+        .map(|(b, section)| {
+            [0, 1].map(|end| {
+                let list = new_vertices.entry(b.vertex(end).id()).or_default();
+                [0, 2].map(|k| vertex_near(list, section[end][k]))
+            })
+        })
+        .collect::<Vec<_>>();
+    let valid = corners
+        .iter()
+        .all(|(vid, corner)| new_vertices[vid].len() == corner.blended.len().max(2));
+    if !valid {
+        return Err(Error::UnsupportedBlendVertex);
+    }
+
+    // the trimmed edges
+    let mut replaced = HashMap::<EdgeID, Edge>::default();
+    for (id, (edge, _)) in &edge_map {
+        let ends = [edge.absolute_front(), edge.absolute_back()];
+        if blended_index.contains_key(id) || ends.iter().all(|v| !corners.contains_key(&v.id())) {
+            continue;
+        }
+        let Some(Shape::Line) = edge_shape(edge) else {
+            return Err(Error::UnsupportedBlendVertex);
+        };
+        let dir = line_direction(edge);
+        let trimmed = ends.map(|v| {
+            let Some(list) = new_vertices.get(&v.id()) else {
+                return Ok(v.clone());
+            };
+            let on_line = |w: &&Vertex| {
+                let vec: Vector3 = w.point() - v.point();
+                vec.cross(dir).magnitude() < TOLERANCE
+            };
+            list.iter()
+                .find(on_line)
+                .cloned()
+                .ok_or(Error::UnsupportedBlendVertex)
+        });
+        let [front, back] = trimmed;
+        replaced.insert(*id, trimmed_line(&front?, &back?, dir)?);
+    }
+    let rails = blended
+        .iter()
+        .zip(&ends)
+        .map(|(b, [v0, v1])| {
+            let rail = |side: usize| match b.shape {
+                Shape::Line => trimmed_line(&v0[side], &v1[side], line_direction(&b.edge)),
+                Shape::Arc { center, axis, .. } => {
+                    let rail = rail_arc(&v0[side], &v1[side], center, axis)?;
+                    let (t0, t1) = rail.curve().range_tuple();
+                    let middle = rail.curve().subs((t0 + t1) / 2.0);
+                    match on_surface(&shell[b.faces[side]], middle) {
+                        true => Ok(rail),
+                        false => Err(Error::NotBlendableEdge),
+                    }
+                }
+            };
+            Ok([rail(0)?, rail(1)?])
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // the cross sections of the blends, shared by the blends meeting at a vertex
+    let mut cross_sections = HashMap::<(VertexID, VertexID), Edge>::default();
+    let section_edges = blended
+        .iter()
+        .zip(&sections)
+        .zip(&ends)
+        .map(|((b, section), ends)| {
+            [0, 1].map(|end| {
+                let [va, vb] = &ends[end];
+                if let Some(edge) = cross_sections.get(&(va.id(), vb.id())) {
+                    return edge.clone();
+                }
+                let curve = section_curve(profile, section[end], b.frames[end].angle);
+                let edge = Edge::new(va, vb, curve);
+                cross_sections.insert((va.id(), vb.id()), edge.clone());
+                cross_sections.insert((vb.id(), va.id()), edge.clone());
+                edge
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut faces = shell
+        .iter()
+        .enumerate()
+        .map(|(i, face)| {
+            if face.vertex_iter().all(|v| !corners.contains_key(&v.id())) {
+                return Ok(face.clone());
+            }
+            let wires = face
+                .boundaries()
+                .iter()
+                .map(|wire| {
+                    let edges = wire.iter().map(|edge| {
+                        let new = match blended_index.get(&edge.id()) {
+                            Some(k) => rails[*k][blended[*k].side(i).unwrap()].clone(),
+                            None => match replaced.get(&edge.id()) {
+                                Some(new) => new.clone(),
+                                None => edge.absolute_clone(),
+                            },
+                        };
+                        match edge.orientation() {
+                            true => new,
+                            false => new.inverse(),
+                        }
+                    });
+                    connect(edges.collect(), &cross_sections)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Face::try_new(wires, face.oriented_surface())?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for (k, b) in blended.iter().enumerate() {
+        let [[a0, _], [_, b1]] = &ends[k];
+        let wire: Wire = vec![
+            rails[k][0].inverse(),
+            oriented(&section_edges[k][0], a0),
+            rails[k][1].clone(),
+            oriented(&section_edges[k][1], b1),
+        ]
+        .into();
+        // the outward normal at the middle of the front cross section
+        let [n0, n1] = b.frames[0].normals;
+        let face = match (profile, b.shape) {
+            (Profile::Fillet(_), _) => {
+                let surface = fillet_surface(b, &sections[k], &section_edges[k][0]);
+                oriented_face(wire, surface, (0.5, 0.0), |_| n0 + n1)?
+            }
+            (Profile::Chamfer(_), Shape::Line) => planar_face(wire)?,
+            (Profile::Chamfer(_), Shape::Arc { .. }) => {
+                let surface = builder::homotopy(&rails[k][0], &rails[k][1]).oriented_surface();
+                oriented_face(wire, surface, (0.5, 0.0), |_| n0 + n1)?
+            }
+        };
+        faces.push(face);
+    }
+
+    for (vid, corner) in &corners {
+        if corner.blended.len() >= 3 {
+            let pieces = corner
+                .blended
+                .iter()
+                .map(|k| {
+                    let end = blended[*k].end_of(*vid);
+                    oriented(&section_edges[*k][end], &ends[*k][end][1 - end])
+                })
+                .collect::<Vec<_>>();
+            let face = match profile {
+                Profile::Fillet(_) => {
+                    let (origin, offset) = corner_center(corner, &blended, &normal, *vid)?;
+                    spherical_corner(corner, pieces, origin, offset, &normal)?
+                }
+                Profile::Chamfer(_) => planar_face(sort_loop(pieces, 0))?,
+            };
+            faces.push(face);
+        }
+    }
+    Ok(faces.into())
+}
+
+/// Returns the plane where the fillets of `b` and `other` meet at the vertex `vid`.
+fn meeting_plane(b: &Blended, other: &Blended, vid: VertexID) -> Result<(Point3, Vector3)> {
+    let shared = b.faces.iter().find(|f| other.side(**f).is_some());
+    let shared = *shared.ok_or(Error::UnsupportedBlendVertex)?;
+    let (end0, end1) = (b.end_of(vid), other.end_of(vid));
+    let (u0, u1) = (b.outgoing(end0), other.outgoing(end1));
+    let p0 = b.rail_point(end0, b.side(shared).unwrap());
+    let p1 = other.rail_point(end1, other.side(shared).unwrap());
+    // the intersection of the tangent lines on the shared face
+    let (cos, w) = (u0.dot(u1), p0 - p1);
+    let det = 1.0 - cos * cos;
+    let bisector = u0 + u1;
+    if det < TOLERANCE || bisector.magnitude() < TOLERANCE {
+        return Err(Error::UnsupportedBlendVertex);
+    }
+    let origin = p0 + (cos * u1.dot(w) - u0.dot(w)) / det * u0;
+    let normal = b.frames[end0].normals[b.side(shared).unwrap()].cross(bisector);
+    Ok((origin, normal.normalize()))
+}
+
+/// Returns the center of the ball touching the three faces of `corner`, and the signed distance
+/// from the faces to the center along the outward normals.
+fn corner_center(
+    corner: &Corner,
+    blended: &[Blended],
+    normal: &impl Fn(usize) -> Vector3,
+    vid: VertexID,
+) -> Result<(Point3, f64)> {
+    let convex = corner.blended.iter().map(|k| blended[*k].is_convex());
+    let convex = convex.collect::<Vec<_>>();
+    if convex.iter().any(|c| *c != convex[0]) {
+        return Err(Error::UnsupportedBlendVertex);
+    }
+    let b = &blended[corner.blended[0]];
+    let frame = &b.frames[b.end_of(vid)];
+    let radius = frame.setbacks[0] * f64::tan(frame.angle / 2.0);
+    let offset = if convex[0] { -radius } else { radius };
+    let [n0, n1, n2] = [0, 1, 2].map(|i| normal(corner.faces[i]));
+    let mat = Matrix3::from_cols(n0, n1, n2).transpose();
+    let inv = mat.invert().ok_or(Error::UnsupportedBlendVertex)?;
+    let pt = b.vertex(b.end_of(vid)).point();
+    Ok((pt + inv * Vector3::new(offset, offset, offset), offset))
+}
+
+/// Sorts `pieces` into a closed wire starting from `pieces[first]`.
+fn sort_loop(mut pieces: Vec<Edge>, first: usize) -> Wire {
+    pieces.swap(0, first);
+    for i in 1..pieces.len() {
+        let next = (i..pieces.len()).find(|j| pieces[*j].front() == pieces[i - 1].back());
+        if let Some(j) = next {
+            pieces.swap(i, j);
+        }
+    }
+    pieces.into()
+}
+
+/// Returns the spherical patch bounded by the closed sequence of `pieces`.
+fn spherical_corner(
+    corner: &Corner,
+    pieces: Vec<Edge>,
+    origin: Point3,
+    offset: f64,
+    normal: &impl Fn(usize) -> Vector3,
+) -> Result<Face> {
+    let normals = corner.faces.iter().map(|f| normal(*f)).collect::<Vec<_>>();
+    let pole = (0..3).find(|i| {
+        let mut others = (0..3).filter(|j| j != i);
+        others.all(|j| normals[*i].dot(normals[j]).abs() < TOLERANCE)
+    });
+    let pole = origin - offset * normals[pole.ok_or(Error::UnsupportedBlendVertex)?];
+    let first = pieces
+        .iter()
+        .position(|edge| edge.front().point().near(&pole));
+    let wire = sort_loop(pieces, first.ok_or(Error::UnsupportedBlendVertex)?);
+    let (q, s) = (wire[0].back().point(), wire[1].back().point());
+    let axis = (q - origin).cross(s - origin).normalize();
+    let angle = (q - origin).angle(s - origin).0;
+    let surface = RevolutedCurve::by_revolution(wire[0].oriented_curve(), origin, axis);
+    let surface = Surface::RevolutedCurve(Processor::new(surface));
+    let outward = |pt: Point3| (pt - origin) * -offset.signum();
+    oriented_face(wire, surface, (0.5, angle / 2.0), outward)
+}
+
+/// Returns the planar face bounded by `wire`, which is oriented along the shell.
+fn planar_face(wire: Wire) -> Result<Face> {
+    builder::try_attach_plane(&[wire]).map_err(|error| match error {
+        Error::WireNotInOnePlane => Error::UnsupportedBlendVertex,
+        error => error,
+    })
+}
+
+/// Returns the face bounded by `wire` on `surface` whose normal at `(u, v)` is along `normal`.
+fn oriented_face(
+    wire: Wire,
+    surface: Surface,
+    (u, v): (f64, f64),
+    normal: impl Fn(Point3) -> Vector3,
+) -> Result<Face> {
+    if surface.normal(u, v).dot(normal(surface.subs(u, v))) > 0.0 {
+        Ok(Face::try_new(vec![wire], surface)?)
+    } else {
+        let mut face = Face::try_new(vec![wire.inverse()], surface)?;
+        face.invert();
+        Ok(face)
+    }
+}
+
+/// Returns the curve of the cross section whose control points are `points`, where the faces
+/// meet at `angle`.
+fn section_curve(profile: Profile, points: [Point3; 3], angle: f64) -> Curve {
+    match profile {
+        Profile::Fillet(_) => Curve::NurbsCurve(NurbsCurve::new(BSplineCurve::new(
+            KnotVec::bezier_knot(2),
+            vec![
+                points[0].to_homogeneous(),
+                points[1].to_homogeneous() * f64::sin(angle / 2.0),
+                points[2].to_homogeneous(),
+            ],
+        ))),
+        Profile::Chamfer(_) => Curve::Line(Line(points[0], points[2])),
+    }
+}
+
+/// Returns the surface of the fillet of `b` between the cross sections at the ends, where
+/// `front` is the cross section at the front vertex.
+fn fillet_surface(b: &Blended, sections: &[[Point3; 3]; 2], front: &Edge) -> Surface {
+    let [s0, s1] = sections;
+    match b.shape {
+        Shape::Arc { center, axis, .. } => {
+            let surface = RevolutedCurve::by_revolution(front.curve(), center, axis);
+            Surface::RevolutedCurve(Processor::new(surface))
+        }
+        Shape::Line => {
+            let weight = f64::sin(b.frames[0].angle / 2.0);
+            let control_points = (0..3)
+                .map(|j| {
+                    let w = if j == 1 { weight } else { 1.0 };
+                    vec![s0[j].to_homogeneous() * w, s1[j].to_homogeneous() * w]
+                })
+                .collect();
+            let knot_vecs = (KnotVec::bezier_knot(2), KnotVec::bezier_knot(1));
+            NurbsSurface::new(BSplineSurface::new(knot_vecs, control_points)).into()
+        }
+    }
+}
+
+fn vertex_near(list: &mut Vec<Vertex>, pt: Point3) -> Vertex {
+    match list.iter().find(|v| v.point().near(&pt)) {
+        Some(v) => v.clone(),
+        None => {
+            let v = builder::vertex(pt);
+            list.push(v.clone());
+            v
+        }
+    }
+}
+
+/// Returns the line from `front` to `back`, which must be along `dir`.
+fn trimmed_line(front: &Vertex, back: &Vertex, dir: Vector3) -> Result<Edge> {
+    match (back.point() - front.point()).dot(dir) > TOLERANCE {
+        true => Ok(builder::line(front, back)),
+        false => Err(Error::InvalidBlendSize),
+    }
+}
+
+/// Returns the arc from `front` to `back` around the axis through `center`.
+fn rail_arc(front: &Vertex, back: &Vertex, center: Point3, axis: Vector3) -> Result<Edge> {
+    let (p, q) = (front.point(), back.point());
+    let center = center + axis * axis.dot(p - center);
+    let radius = p.distance(center);
+    if radius < TOLERANCE {
+        return Err(Error::InvalidBlendSize);
+    }
+    let height = axis.dot(q - center);
+    if height.abs() > TOLERANCE || (q.distance(center) - radius).abs() > TOLERANCE {
+        return Err(Error::NotBlendableEdge);
+    }
+    let angle = geom_impls::turning_angle(p - center, q - center, axis);
+    let curve = geom_impls::circle_arc(p, center, axis, Rad(angle));
+    Ok(Edge::new(front, back, Curve::NurbsCurve(curve)))
+}
+
+#[inline(always)]
+fn oriented(edge: &Edge, front: &Vertex) -> Edge {
+    match edge.front() == front {
+        true => edge.clone(),
+        false => edge.inverse(),
+    }
+}
+
+/// Connects the consecutive edges of a closed wire by the cross sections of the blends.
+fn connect(edges: Vec<Edge>, cross_sections: &HashMap<(VertexID, VertexID), Edge>) -> Result<Wire> {
+    let mut wire = Wire::new();
+    for (i, edge) in edges.iter().enumerate() {
+        wire.push_back(edge.clone());
+        let next = &edges[(i + 1) % edges.len()];
+        if edge.back() != next.front() {
+            let section = cross_sections.get(&(edge.back().id(), next.front().id()));
+            let section = section.ok_or(Error::UnsupportedBlendVertex)?;
+            wire.push_back(oriented(section, edge.back()));
+        }
+    }
+    Ok(wire)
+}
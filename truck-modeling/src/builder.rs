@@ -528,6 +528,39 @@ pub fn tsweep<T: Sweep<Point3, Curve, Surface>>(elem: &T, vector: Vector3) -> T:
     )
 }
 
+/// Sweeps a vertex, an edge, a wire, a face, or a shell by a vector and returns the history.
+///
+/// The history records the side faces generated from the edges, the edges connecting both ends
+/// generated from the vertices, and the translated copies of the original elements.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let face = builder::tsweep(&e, Vector3::unit_y());
+/// let (cube, history) = builder::tsweep_with_history(&face, Vector3::unit_z());
+///
+/// // the side face generated by the first edge of the profile
+/// let edge = face.boundaries()[0][0].id();
+/// let (side, evolution) = history.faces_from(HistoryOrigin::Edge(edge)).next().unwrap();
+/// assert_eq!(evolution, Evolution::Generated);
+/// assert!(cube.face_iter().any(|face| face.id() == side));
+///
+/// // the top face is the copy of the profile
+/// let top = cube.boundaries()[0].last().unwrap();
+/// let entry = history.face(top.id()).unwrap();
+/// assert_eq!(entry.evolution, Evolution::Copied);
+/// assert_eq!(entry.origins, vec![HistoryOrigin::Face(face.id())]);
+/// ```
+pub fn tsweep_with_history<T: SweepHistory<Point3, Curve, Surface>>(
+    elem: &T,
+    vector: Vector3,
+) -> (T::Swept, History) {
+    let swept = tsweep(elem, vector);
+    let history = elem.sweep_history(&swept);
+    (swept, history)
+}
+
 /// Sweeps a vertex, an edge, a wire, a face, or a shell by the rotation.
 /// # Details
 /// If the absolute value of `angle` is more than 2π rad, then the result is closed shape.
@@ -14,6 +14,43 @@ pub enum Error {
     /// cf. [`builder::try_wire_homotopy`](../builder/fn.try_wire_homotopy.html)
     #[error("The wires must contain the same number of edges to create a homotopy.")]
     NotSameNumberOfEdges,
+    /// tried to convert a control cage which is not an oriented manifold.
+    /// cf. [`subdivision::catmull_clark_limit`](../subdivision/fn.catmull_clark_limit.html)
+    #[error("The control cage must be an oriented manifold.")]
+    NonManifoldControlCage,
+    /// tried to blend an edge which is neither a line shared by two planar faces nor a circular
+    /// arc shared by two faces on which the blend is coaxial, or whose faces are tangent.
+    /// cf. [`blend::fillet_edges`](../blend/fn.fillet_edges.html),
+    /// [`blend::chamfer_edges`](../blend/fn.chamfer_edges.html)
+    #[error("The blended edge must be a line or a circular arc shared by two faces which are not tangent.")]
+    NotBlendableEdge,
+    /// tried to blend edges meeting at a vertex in an unsupported configuration.
+    /// cf. [`blend::fillet_edges`](../blend/fn.fillet_edges.html),
+    /// [`blend::chamfer_edges`](../blend/fn.chamfer_edges.html)
+    #[error("The blended edges meet at a vertex in an unsupported configuration.")]
+    UnsupportedBlendVertex,
+    /// tried to blend edges by a non-positive size or a size too large for the adjacent faces.
+    /// cf. [`blend::fillet_edges`](../blend/fn.fillet_edges.html),
+    /// [`blend::chamfer_edges`](../blend/fn.chamfer_edges.html)
+    #[error("The size of the blend must be positive and small enough for the adjacent faces.")]
+    InvalidBlendSize,
+    /// tried to offset a face whose surface is not supported, or to remove a face which is not
+    /// a face of the solid.
+    /// cf. [`offset::shell_solid`](../offset/fn.shell_solid.html),
+    /// [`offset::thicken`](../offset/fn.thicken.html)
+    #[error("The offset surface must be a plane, a cylinder, a sphere or a surface of revolution of lines and circular arcs.")]
+    NotOffsettableFace,
+    /// tried to offset faces which do not meet along lines or circular arcs, to remove adjacent
+    /// faces, or to stitch intersection curves.
+    /// cf. [`offset::shell_solid`](../offset/fn.shell_solid.html),
+    /// [`offset::thicken`](../offset/fn.thicken.html)
+    #[error("The offset faces must meet along lines or circular arcs, and the removed faces must not be adjacent.")]
+    UnsupportedOffsetShape,
+    /// tried to offset faces by a non-positive distance or a distance too large for the faces.
+    /// cf. [`offset::shell_solid`](../offset/fn.shell_solid.html),
+    /// [`offset::thicken`](../offset/fn.thicken.html)
+    #[error("The offset distance must be positive and small enough for the faces.")]
+    InvalidOffsetSize,
 }
 
 #[test]
@@ -0,0 +1,582 @@
+use crate::*;
+use rustc_hash::FxHashSet as HashSet;
+use std::f64::consts::PI;
+
+/// the angular tolerance for the tangency, the parallelism and the perpendicularity
+const ANGLE_TOLERANCE: f64 = 1.0e-3;
+/// the number of the divisions of each edge and each parameter range for sampling
+const SAMPLE_DIVISION: usize = 4;
+/// the number of the trials of searching parameters
+const SEARCH_PARAMETER_TRIALS: usize = 100;
+
+/// The axis of a cylindrical feature
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Axis {
+    /// the center of the entrance of a hole, either end for a through hole, or of the base of a boss
+    pub origin: Point3,
+    /// the unit vector toward the inside of a hole, or toward the top of a boss
+    pub direction: Vector3,
+}
+
+/// Whether a hole goes through the solid or not
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HoleKind {
+    /// Both ends of the hole are open.
+    Through,
+    /// One end of the hole is closed by a bottom face.
+    Blind,
+}
+
+/// A machining feature recognized by [`recognize_features`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Feature {
+    /// cylindrical hole
+    Hole {
+        /// the cylindrical faces of the hole
+        faces: Vec<FaceID>,
+        /// through or blind
+        kind: HoleKind,
+        /// the axis from the entrance
+        axis: Axis,
+        /// the diameter of the hole
+        diameter: f64,
+        /// the length of the cylindrical part
+        depth: f64,
+    },
+    /// cylindrical boss
+    Boss {
+        /// the cylindrical faces of the boss
+        faces: Vec<FaceID>,
+        /// the axis from the base
+        axis: Axis,
+        /// the diameter of the boss
+        diameter: f64,
+        /// the length of the cylindrical part
+        height: f64,
+    },
+    /// rectangular pocket whose walls are perpendicular to the floor
+    Pocket {
+        /// the floor face
+        floor: FaceID,
+        /// the wall faces in the order of the boundary of the floor
+        walls: Vec<FaceID>,
+        /// the length of the longer sides of the floor
+        length: f64,
+        /// the length of the shorter sides of the floor
+        width: f64,
+        /// the height of the walls from the floor
+        depth: f64,
+    },
+    /// planar chamfer
+    Chamfer {
+        /// the face of the chamfer
+        face: FaceID,
+        /// the distance between the edges shared with the chamfered faces
+        width: f64,
+    },
+    /// fillet connected smoothly to two or more faces
+    Fillet {
+        /// the faces of the fillet, the coaxial cylinders with the same radius or a curved face
+        faces: Vec<FaceID>,
+        /// the radius if the fillet is cylindrical
+        radius: Option<f64>,
+    },
+}
+
+/// the shape of a surface estimated from the sampled points and normals
+#[derive(Clone, Copy, Debug)]
+enum SurfaceKind {
+    Plane {
+        normal: Vector3,
+    },
+    Cylinder {
+        axis: Axis,
+        radius: f64,
+        // `true` if the normals point away from the axis
+        convex: bool,
+    },
+    Other,
+}
+
+/// Samples the points and the oriented unit normals of the surface around the face.
+///
+/// The parameter range of the face is estimated by projecting the boundary to the surface.
+fn sample_face(face: &Face) -> Option<Vec<(Point3, Vector3)>> {
+    let surface = face.surface();
+    let mut urange = (f64::INFINITY, f64::NEG_INFINITY);
+    let mut vrange = (f64::INFINITY, f64::NEG_INFINITY);
+    let mut hint = None;
+    for edge in face.edge_iter() {
+        let curve = edge.curve();
+        let (t0, t1) = curve.range_tuple();
+        for i in 0..=SAMPLE_DIVISION {
+            let p = curve.subs(t0 + (t1 - t0) * i as f64 / SAMPLE_DIVISION as f64);
+            let (u, v) = hint
+                .and_then(|uv| surface.search_nearest_parameter(p, uv, SEARCH_PARAMETER_TRIALS))
+                .or_else(|| {
+                    let hint = SPHint2D::Global;
+                    surface.search_nearest_parameter(p, hint, SEARCH_PARAMETER_TRIALS)
+                })?;
+            hint = Some((u, v));
+            urange = (f64::min(urange.0, u), f64::max(urange.1, u));
+            vrange = (f64::min(vrange.0, v), f64::max(vrange.1, v));
+        }
+    }
+    if urange.0 > urange.1 {
+        return None;
+    }
+    let division = SAMPLE_DIVISION as f64;
+    let samples = (0..=SAMPLE_DIVISION)
+        .flat_map(|i| (0..=SAMPLE_DIVISION).map(move |j| (i as f64, j as f64)))
+        .map(|(i, j)| {
+            let u = urange.0 + (urange.1 - urange.0) * i / division;
+            let v = vrange.0 + (vrange.1 - vrange.0) * j / division;
+            let normal = match face.orientation() {
+                true => surface.normal(u, v),
+                false => -surface.normal(u, v),
+            };
+            (surface.subs(u, v), normal)
+        })
+        .collect();
+    Some(samples)
+}
+
+/// Classifies the surface by the samples: a plane if the normals are parallel, a cylinder if the
+/// normals are perpendicular to a common axis and the points are equidistant from the axis.
+fn classify(samples: &[(Point3, Vector3)], tol: f64) -> SurfaceKind {
+    let (p0, n0) = samples[0];
+    let (pk, nk) = samples
+        .iter()
+        .max_by(|(_, n1), (_, n2)| {
+            let (c1, c2) = (n0.cross(*n1).magnitude2(), n0.cross(*n2).magnitude2());
+            c1.total_cmp(&c2)
+        })
+        .copied()
+        .unwrap();
+    let cross = n0.cross(nk);
+    if cross.magnitude() <= ANGLE_TOLERANCE {
+        let planar = samples
+            .iter()
+            .all(|(p, n)| (p - p0).dot(n0).abs() <= tol && n.dot(n0) > 0.0);
+        return match planar {
+            true => SurfaceKind::Plane { normal: n0 },
+            false => SurfaceKind::Other,
+        };
+    }
+    let direction = cross.normalize();
+    if samples
+        .iter()
+        .any(|(_, n)| n.dot(direction).abs() > ANGLE_TOLERANCE)
+    {
+        return SurfaceKind::Other;
+    }
+    // the nearest points of the normal lines through `p0` and `pk`
+    let w = p0 - pk;
+    let (b, d, e) = (n0.dot(nk), n0.dot(w), nk.dot(w));
+    let s = (b * e - d) / (1.0 - b * b);
+    let origin = p0 + s * n0;
+    let (radius, convex) = (s.abs(), s < 0.0);
+    let on_cylinder = samples.iter().all(|(p, n)| {
+        let v = p - origin;
+        let radial = v - v.dot(direction) * direction;
+        let r = radial.magnitude();
+        let sign = if convex { 1.0 } else { -1.0 };
+        (r - radius).abs() <= tol && (sign * radial / r - n).magnitude() <= ANGLE_TOLERANCE
+    });
+    match on_cylinder && radius > tol {
+        true => SurfaceKind::Cylinder {
+            axis: Axis { origin, direction },
+            radius,
+            convex,
+        },
+        false => SurfaceKind::Other,
+    }
+}
+
+fn coaxial(axis0: &Axis, axis1: &Axis, tol: f64) -> bool {
+    let v = axis1.origin - axis0.origin;
+    axis0.direction.cross(axis1.direction).magnitude() <= ANGLE_TOLERANCE
+        && (v - v.dot(axis0.direction) * axis0.direction).magnitude() <= tol
+}
+
+/// the faces of a solid with their shapes and neighbors
+struct Recognizer<'a> {
+    graph: &'a FaceAdjacencyGraph,
+    kinds: Vec<SurfaceKind>,
+    tol: f64,
+}
+
+impl Recognizer<'_> {
+    fn face(&self, i: usize) -> &Face { &self.graph.faces()[i] }
+
+    /// the indices of the neighbors with the shared edges
+    fn neighbors(&self, i: usize) -> Vec<(usize, &AdjacencyEdge)> {
+        let neighbors = self.graph.adjacent_faces(self.face(i).id());
+        neighbors
+            .into_iter()
+            .map(|(face, edge)| (self.graph.face_index(face.id()).unwrap(), edge))
+            .collect()
+    }
+
+    /// the edges of the loops of the face with their orientations in the face
+    fn loops(&self, i: usize) -> Vec<Vec<Edge>> {
+        let face = self.face(i);
+        face.boundary_iters()
+            .into_iter()
+            .map(|iter| iter.collect())
+            .collect()
+    }
+
+    fn face_ids(&self, indices: &[usize]) -> Vec<FaceID> {
+        indices.iter().map(|i| self.face(*i).id()).collect()
+    }
+
+    /// Collects the coaxial cylinders with the same radius connected to the `i`-th face.
+    fn cylinder_group(&self, i: usize, grouped: &mut [bool]) -> Vec<usize> {
+        let SurfaceKind::Cylinder {
+            axis,
+            radius,
+            convex,
+        } = self.kinds[i]
+        else {
+            return Vec::new();
+        };
+        let mut group = vec![i];
+        grouped[i] = true;
+        let mut k = 0;
+        while k < group.len() {
+            for (j, _) in self.neighbors(group[k]) {
+                if grouped[j] {
+                    continue;
+                }
+                if let SurfaceKind::Cylinder {
+                    axis: axis1,
+                    radius: radius1,
+                    convex: convex1,
+                } = self.kinds[j]
+                {
+                    let same = coaxial(&axis, &axis1, self.tol)
+                        && (radius - radius1).abs() <= self.tol
+                        && convex == convex1;
+                    if same {
+                        grouped[j] = true;
+                        group.push(j);
+                    }
+                }
+            }
+            k += 1;
+        }
+        group
+    }
+
+    /// Recognizes a hole or a boss from a group of cylinders.
+    fn cylindrical_feature(&self, group: &[usize]) -> Option<Feature> {
+        let SurfaceKind::Cylinder {
+            axis,
+            radius,
+            convex,
+        } = self.kinds[group[0]]
+        else {
+            return None;
+        };
+        // the edges shared with the other faces
+        let outer: Vec<(usize, &AdjacencyEdge)> = group
+            .iter()
+            .flat_map(|i| self.neighbors(*i))
+            .filter(|(j, _)| !group.contains(j))
+            .collect();
+        // The cylinders connected smoothly to other faces are fillets.
+        if outer
+            .iter()
+            .any(|(_, edge)| edge.convexity == Some(Convexity::Smooth))
+        {
+            return self.fillet(group, Some(radius));
+        }
+        let parameter = |p: Point3| (p - axis.origin).dot(axis.direction);
+        let ts: Vec<f64> = group
+            .iter()
+            .flat_map(|i| self.face(*i).vertex_iter())
+            .map(|v| parameter(v.point()))
+            .collect();
+        let t_min = ts.iter().copied().fold(f64::INFINITY, f64::min);
+        let t_max = ts.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mean = |points: &[Point3]| {
+            let sum = points.iter().map(|p| parameter(*p)).sum::<f64>();
+            sum / points.len() as f64
+        };
+        // the axis from the end nearer to `t`, or from the other end if `reverse`
+        let axis_from = |t: f64, reverse: bool| {
+            let from_min = (t - t_min < t_max - t) != reverse;
+            let (t0, direction) = match from_min {
+                true => (t_min, axis.direction),
+                false => (t_max, -axis.direction),
+            };
+            Axis {
+                origin: axis.origin + t0 * axis.direction,
+                direction,
+            }
+        };
+        let group_edges: HashSet<EdgeID> = group
+            .iter()
+            .flat_map(|i| self.face(*i).edge_iter())
+            .map(|edge| edge.id())
+            .collect();
+        match convex {
+            false => {
+                // The face is a bottom if all its edges shared with other faces are of the hole.
+                let bottom = outer.iter().map(|(j, _)| *j).find(|j| {
+                    self.face(*j).edge_iter().all(|edge| {
+                        group_edges.contains(&edge.id())
+                            || self.graph.faces_around_edge(edge.id()).len() < 2
+                    })
+                });
+                let (kind, axis) = match bottom {
+                    Some(j) => {
+                        let points: Vec<Point3> =
+                            self.face(j).vertex_iter().map(|v| v.point()).collect();
+                        (HoleKind::Blind, axis_from(mean(&points), true))
+                    }
+                    None => (HoleKind::Through, axis_from(t_min, false)),
+                };
+                Some(Feature::Hole {
+                    faces: self.face_ids(group),
+                    kind,
+                    axis,
+                    diameter: 2.0 * radius,
+                    depth: t_max - t_min,
+                })
+            }
+            true => {
+                let base: Vec<Point3> = outer
+                    .iter()
+                    .filter(|(_, edge)| edge.convexity == Some(Convexity::Concave))
+                    .flat_map(|(_, edge)| [edge.edge.front().point(), edge.edge.back().point()])
+                    .collect();
+                if base.is_empty() {
+                    return None;
+                }
+                Some(Feature::Boss {
+                    faces: self.face_ids(group),
+                    axis: axis_from(mean(&base), false),
+                    diameter: 2.0 * radius,
+                    height: t_max - t_min,
+                })
+            }
+        }
+    }
+
+    fn is_line(&self, edge: &Edge) -> bool {
+        let curve = edge.curve();
+        let (t0, t1) = curve.range_tuple();
+        let (p, q) = (curve.front(), curve.back());
+        let middle = curve.subs((t0 + t1) / 2.0);
+        middle.distance(p.midpoint(q)) <= self.tol
+    }
+
+    fn is_plane(&self, i: usize) -> bool { matches!(self.kinds[i], SurfaceKind::Plane { .. }) }
+
+    /// Recognizes a rectangular pocket whose floor is the `i`-th face.
+    fn pocket(&self, i: usize) -> Option<Feature> {
+        let SurfaceKind::Plane { normal } = self.kinds[i] else {
+            return None;
+        };
+        let [boundary] = &self.loops(i)[..] else {
+            return None;
+        };
+        if boundary.len() != 4 || !boundary.iter().all(|edge| self.is_line(edge)) {
+            return None;
+        }
+        let walls = boundary
+            .iter()
+            .map(|edge| {
+                let shared = self.graph.edge(edge.id())?;
+                let angle = shared.dihedral_angle?;
+                if shared.convexity != Some(Convexity::Concave)
+                    || (angle - 1.5 * PI).abs() > ANGLE_TOLERANCE
+                {
+                    return None;
+                }
+                let wall = shared.faces.iter().copied().find(|j| *j != i)?;
+                Some(wall).filter(|j| self.is_plane(*j))
+            })
+            .collect::<Option<Vec<usize>>>()?;
+        // The adjacent walls meet at concave edges.
+        let corners_concave = (0..4).all(|k| {
+            let (w0, w1) = (walls[k], walls[(k + 1) % 4]);
+            w0 != w1
+                && self
+                    .neighbors(w0)
+                    .iter()
+                    .any(|(j, edge)| *j == w1 && edge.convexity == Some(Convexity::Concave))
+        });
+        let p: Vec<Point3> = boundary.iter().map(|edge| edge.front().point()).collect();
+        let sides: Vec<Vector3> = (0..4).map(|k| p[(k + 1) % 4] - p[k]).collect();
+        let rectangular = (0..4).all(|k| {
+            let (v0, v1) = (sides[k], sides[(k + 1) % 4]);
+            v0.normalize().dot(v1.normalize()).abs() <= ANGLE_TOLERANCE
+        });
+        if !corners_concave || !rectangular {
+            return None;
+        }
+        let (a, b) = (sides[0].magnitude(), sides[1].magnitude());
+        let depth = walls
+            .iter()
+            .flat_map(|j| self.face(*j).vertex_iter())
+            .map(|v| (v.point() - p[0]).dot(normal))
+            .fold(0.0, f64::max);
+        Some(Feature::Pocket {
+            floor: self.face(i).id(),
+            walls: self.face_ids(&walls),
+            length: f64::max(a, b),
+            width: f64::min(a, b),
+            depth,
+        })
+    }
+
+    /// Recognizes a chamfer, a quadrilateral face meeting two planes at obtuse angles
+    /// on its opposite sides.
+    fn chamfer(&self, i: usize) -> Option<Feature> {
+        if !self.is_plane(i) {
+            return None;
+        }
+        let [boundary] = &self.loops(i)[..] else {
+            return None;
+        };
+        if boundary.len() != 4 {
+            return None;
+        }
+        let obtuse = |edge: &Edge| {
+            self.graph.edge(edge.id()).is_some_and(|shared| {
+                let planar = shared.faces.iter().all(|j| self.is_plane(*j));
+                let angle = shared.dihedral_angle.unwrap_or(0.0);
+                shared.convexity == Some(Convexity::Convex)
+                    && planar
+                    && angle > PI / 2.0 + ANGLE_TOLERANCE
+            })
+        };
+        let k = (0..2).find(|k| obtuse(&boundary[*k]) && obtuse(&boundary[k + 2]))?;
+        let width = [&boundary[k + 1], &boundary[(k + 3) % 4]]
+            .iter()
+            .map(|edge| edge.front().point().distance(edge.back().point()))
+            .fold(0.0, f64::max);
+        Some(Feature::Chamfer {
+            face: self.face(i).id(),
+            width,
+        })
+    }
+
+    /// Recognizes a fillet, curved faces connected smoothly to two or more other faces.
+    fn fillet(&self, group: &[usize], radius: Option<f64>) -> Option<Feature> {
+        if group.iter().any(|i| self.is_plane(*i)) {
+            return None;
+        }
+        let mut smooth_neighbors: Vec<usize> = group
+            .iter()
+            .flat_map(|i| self.neighbors(*i))
+            .filter(|(j, edge)| edge.convexity == Some(Convexity::Smooth) && !group.contains(j))
+            .map(|(j, _)| j)
+            .collect();
+        smooth_neighbors.sort_unstable();
+        smooth_neighbors.dedup();
+        match smooth_neighbors.len() < 2 {
+            true => None,
+            false => Some(Feature::Fillet {
+                faces: self.face_ids(group),
+                radius,
+            }),
+        }
+    }
+}
+
+/// Recognizes the machining features of the solid: cylindrical holes and bosses, rectangular
+/// pockets, chamfers and fillets.
+///
+/// The features are recognized from the face adjacency graph attributed by the convexity,
+/// and the shapes of the surfaces estimated from the sampled points and normals, so that the
+/// features on the surfaces of any representation, e.g. NURBS surfaces imported from STEP files,
+/// are recognized.
+///
+/// - A hole is a group of the coaxial cylindrical faces with the same radius facing the axis.
+///   It is blind if a face is bounded only by the edges of the hole, e.g. the flat bottom face or
+///   the cone of a drill point, and through otherwise.
+/// - A boss is a group of the coaxial cylindrical faces with the same radius facing outward,
+///   meeting the other faces at concave edges at its base.
+/// - A pocket is a rectangular planar floor whose sides meet planar walls at right concave angles,
+///   the adjacent walls meeting at concave edges. The floor has no islands.
+/// - A chamfer is a quadrilateral planar face meeting two planes at obtuse convex angles
+///   on its opposite sides.
+/// - A fillet is a group of the coaxial cylindrical faces with the same radius, or another curved
+///   face, connected smoothly to two or more faces. It is not regarded as a hole nor a boss.
+///
+/// `tol` is the tolerance of distances. The features are returned in the order of holes and
+/// bosses, pockets, chamfers and fillets.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_modeling::features::*;
+///
+/// // a plate with a hole through it
+/// let v = builder::vertex(Point3::new(0.0, 0.0, 0.0));
+/// let edge = builder::tsweep(&v, Vector3::new(4.0, 0.0, 0.0));
+/// let mut face = builder::tsweep(&edge, Vector3::new(0.0, 4.0, 0.0));
+/// let v = builder::vertex(Point3::new(3.0, 2.0, 0.0));
+/// let circle = builder::rsweep(&v, Point3::new(2.0, 2.0, 0.0), Vector3::unit_z(), Rad(7.0));
+/// face.add_boundary(circle.inverse());
+/// let plate: Solid = builder::tsweep(&face, Vector3::unit_z());
+///
+/// let features = recognize_features(&plate, TOLERANCE);
+/// assert_eq!(features.len(), 1);
+/// let Feature::Hole { kind, diameter, depth, axis, .. } = &features[0] else {
+///     panic!("not a hole");
+/// };
+/// assert_eq!(*kind, HoleKind::Through);
+/// assert_near!(*diameter, 2.0);
+/// assert_near!(*depth, 1.0);
+/// // The axis of a through hole starts from either end.
+/// assert_near!(Point2::new(axis.origin.x, axis.origin.y), Point2::new(2.0, 2.0));
+/// assert_near!(axis.direction.z.abs(), 1.0);
+/// ```
+pub fn recognize_features(solid: &Solid, tol: f64) -> Vec<Feature> {
+    nonpositive_tolerance!(tol);
+    let mut graph = solid.face_adjacency_graph();
+    graph.compute_dihedral_angles(ANGLE_TOLERANCE);
+    let kinds = graph
+        .faces()
+        .iter()
+        .map(|face| match sample_face(face) {
+            Some(samples) => classify(&samples, tol),
+            None => SurfaceKind::Other,
+        })
+        .collect();
+    let recognizer = Recognizer {
+        graph: &graph,
+        kinds,
+        tol,
+    };
+
+    let len = graph.faces().len();
+    let mut grouped = vec![false; len];
+    let (mut features, mut fillets) = (Vec::new(), Vec::new());
+    for i in 0..len {
+        if grouped[i] {
+            continue;
+        }
+        let group = match recognizer.kinds[i] {
+            SurfaceKind::Cylinder { .. } => recognizer.cylinder_group(i, &mut grouped),
+            _ => vec![i],
+        };
+        let feature = match recognizer.kinds[i] {
+            SurfaceKind::Cylinder { .. } => recognizer.cylindrical_feature(&group),
+            _ => recognizer.fillet(&group, None),
+        };
+        match feature {
+            Some(feature @ Feature::Fillet { .. }) => fillets.push(feature),
+            Some(feature) => features.push(feature),
+            None => {}
+        }
+    }
+    features.extend((0..len).filter_map(|i| recognizer.pocket(i)));
+    features.extend((0..len).filter_map(|i| recognizer.chamfer(i)));
+    features.extend(fillets);
+    features
+}
@@ -55,6 +55,51 @@ pub(super) fn circle_arc(
     unit_curve
 }
 
+/// Returns the angle in `(0, 2π]` turning from `u` to `w` around `axis`.
+pub(super) fn turning_angle(u: Vector3, w: Vector3, axis: Vector3) -> f64 {
+    let angle = f64::atan2(axis.dot(u.cross(w)), u.dot(w));
+    match angle < TOLERANCE {
+        true => angle + 2.0 * PI,
+        false => angle,
+    }
+}
+
+/// Returns the center and the axis of the circle through `pts` within `tol`, where the points
+/// turn around the axis in the right-handed direction.
+///
+/// The circle is fitted by the least squares, which is stable for the approximated curves,
+/// e.g. intersection curves.
+pub(super) fn fit_circle(pts: &[Point3], tol: f64) -> Option<(Point3, Vector3)> {
+    let mean = Point3::centroid(pts);
+    let axis = pts
+        .windows(2)
+        .map(|x| (x[0] - mean).cross(x[1] - mean))
+        .sum::<Vector3>()
+        .normalize();
+    let e0 = (pts[0] - mean).cross(axis).normalize();
+    let e1 = axis.cross(e0);
+    let (mat, vec) = pts
+        .iter()
+        .fold((Matrix3::zero(), Vector3::zero()), |(mat, vec), x| {
+            let row = Vector3::new((x - mean).dot(e0), (x - mean).dot(e1), 1.0);
+            let square = row.x * row.x + row.y * row.y;
+            let outer = Matrix3::from_cols(row * row.x, row * row.y, row * row.z);
+            (mat + outer, vec - row * square)
+        });
+    let sol = mat.invert()? * vec;
+    let center = mean - (sol.x * e0 + sol.y * e1) / 2.0;
+    let radius = center.distance(pts[0] - axis * axis.dot(pts[0] - mean));
+    let on_circle = |x: &Point3| {
+        let height = (x - center).dot(axis);
+        let radial = (x - axis * height).distance(center) - radius;
+        height.abs() < tol && radial.abs() < tol
+    };
+    match pts.iter().all(on_circle) {
+        true => Some((center, axis)),
+        false => None,
+    }
+}
+
 fn closed_polyline_orientation<'a>(pts: impl IntoIterator<Item = &'a Vec<Point3>>) -> bool {
     pts.into_iter()
         .flat_map(|vec| vec.windows(2))
@@ -149,6 +149,132 @@ impl Curve {
     }
 }
 
+impl ParameterTransform for Leader {
+    fn parameter_transform(&mut self, scalar: f64, r#move: f64) -> &mut Self {
+        if let Leader::Polyline(polyline) = self {
+            let n = polyline.len();
+            let knot_vec: KnotVec = std::iter::once(0.0)
+                .chain((0..n).map(|i| i as f64))
+                .chain(std::iter::once((n - 1) as f64))
+                .collect();
+            *self = Leader::BSpline(BSplineCurve::new(knot_vec, polyline.0.clone()));
+        }
+        if let Leader::BSpline(curve) = self {
+            curve.parameter_transform(scalar, r#move);
+        }
+        self
+    }
+}
+
+impl ParameterTransform for Curve {
+    /// Transforms the parameter of the curve.
+    /// The lines and the polyline leaders are converted into B-spline curves.
+    fn parameter_transform(&mut self, scalar: f64, r#move: f64) -> &mut Self {
+        if scalar == 1.0 && r#move == 0.0 {
+            return self;
+        }
+        if let Curve::Line(line) = self {
+            *self = Curve::BSplineCurve(line.to_bspline());
+        }
+        match self {
+            Curve::Line(_) => {}
+            Curve::BSplineCurve(curve) => {
+                curve.parameter_transform(scalar, r#move);
+            }
+            Curve::NurbsCurve(curve) => {
+                curve.parameter_transform(scalar, r#move);
+            }
+            Curve::IntersectionCurve(curve) => {
+                curve.editor().leader.parameter_transform(scalar, r#move);
+            }
+        }
+        self
+    }
+}
+
+impl Curve {
+    /// Returns the line segment if the curve is straight.
+    fn straight_line(&self) -> Option<Line<Point3>> {
+        match self {
+            Curve::Line(line) => Some(*line),
+            Curve::BSplineCurve(curve) => {
+                let control_points = curve.control_points();
+                let line = Line(control_points[0], *control_points.last().unwrap());
+                let dir = line.1 - line.0;
+                let straight = control_points.windows(2).all(|pts| {
+                    let v = pts[1] - pts[0];
+                    v.cross(dir).so_small() && v.dot(dir) >= 0.0
+                });
+                match straight {
+                    true => Some(line),
+                    false => None,
+                }
+            }
+            _ => None,
+        }
+    }
+    /// Returns the NURBS curve, `None` if the curve is an intersection curve.
+    fn to_nurbs(&self) -> Option<NurbsCurve<Vector4>> {
+        match self {
+            Curve::Line(line) => Some(NurbsCurve::from(line.to_bspline())),
+            Curve::BSplineCurve(curve) => Some(NurbsCurve::from(curve.clone())),
+            Curve::NurbsCurve(curve) => Some(curve.clone()),
+            Curve::IntersectionCurve(_) => None,
+        }
+    }
+}
+
+impl Concat<Curve> for Curve {
+    type Output = Curve;
+    /// Concats two curves. Two collinear straight curves are concatenated into a line.
+    ///
+    /// Returns [`ConcatError::UnsupportedCurve`] if one of the curves is an intersection curve.
+    fn try_concat(&self, rhs: &Curve) -> std::result::Result<Curve, ConcatError<Point3>> {
+        let (t0, t1) = (self.range_tuple().1, rhs.range_tuple().0);
+        if !t0.near(&t1) {
+            return Err(ConcatError::DisconnectedParameters(t0, t1));
+        }
+        let (front, back) = (self.back(), rhs.front());
+        if !front.near(&back) {
+            return Err(ConcatError::DisconnectedPoints(front, back));
+        }
+        if let (Some(line0), Some(line1)) = (self.straight_line(), rhs.straight_line()) {
+            let collinear = line0.same_domain(&line1, TOLERANCE);
+            if collinear && (line0.1 - line0.0).dot(line1.1 - line1.0) > 0.0 {
+                return Ok(Curve::Line(Line(line0.0, line1.1)));
+            }
+        }
+        match (self, rhs) {
+            (Curve::NurbsCurve(_), _) | (_, Curve::NurbsCurve(_)) => {
+                let to_nurbs =
+                    |curve: &Curve| curve.to_nurbs().ok_or(ConcatError::UnsupportedCurve);
+                let curve = to_nurbs(self)?.try_concat(&to_nurbs(rhs)?)?;
+                Ok(Curve::NurbsCurve(curve))
+            }
+            _ => {
+                let to_bspline = |curve: &Curve| match curve {
+                    Curve::Line(line) => Ok(line.to_bspline()),
+                    Curve::BSplineCurve(curve) => Ok(curve.clone()),
+                    _ => Err(ConcatError::UnsupportedCurve),
+                };
+                let curve = to_bspline(self)?.try_concat(&to_bspline(rhs)?)?;
+                Ok(Curve::BSplineCurve(curve))
+            }
+        }
+    }
+}
+
+impl SameDomain for Curve {
+    /// Returns whether two curves are collinear straight curves.
+    #[inline(always)]
+    fn same_domain(&self, other: &Self, tol: f64) -> bool {
+        match (self.straight_line(), other.straight_line()) {
+            (Some(line0), Some(line1)) => line0.same_domain(&line1, tol),
+            _ => false,
+        }
+    }
+}
+
 /// 3-dimensional surfaces
 #[derive(
     Clone,
@@ -267,6 +393,80 @@ impl IncludeCurve<Curve> for Surface {
     }
 }
 
+/// Returns whether two revoluted surfaces have the same axis and the same profile within `tol`,
+/// and their normals have the same orientation.
+///
+/// The profiles are compared by the distances from the axis and the heights along the axis
+/// at the same parameters, so that the profiles rotated around the axis are the same.
+fn same_revolution(
+    surface0: &Processor<RevolutedCurve<Curve>, Matrix4>,
+    surface1: &Processor<RevolutedCurve<Curve>, Matrix4>,
+    tol: f64,
+) -> bool {
+    let axis = |surface: &Processor<RevolutedCurve<Curve>, Matrix4>| {
+        let (entity, matrix) = (surface.entity(), surface.transform());
+        let origin = matrix.transform_point(entity.origin());
+        (origin, matrix.transform_vector(entity.axis()).normalize())
+    };
+    let ((origin, axis0), (origin1, axis1)) = (axis(surface0), axis(surface1));
+    if axis0.cross(axis1).magnitude() > tol || (origin1 - origin).cross(axis0).magnitude() > tol {
+        return false;
+    }
+    let profile = |surface: &Processor<RevolutedCurve<Curve>, Matrix4>| {
+        let curve = surface.entity().entity_curve();
+        curve.transformed(*surface.transform())
+    };
+    let (profile0, profile1) = (profile(surface0), profile(surface1));
+    let ((t0, t1), (s0, s1)) = (profile0.range_tuple(), profile1.range_tuple());
+    if (t0 - s0).abs() > tol || (t1 - s1).abs() > tol {
+        return false;
+    }
+    let radius_height = |p: Point3| {
+        let height = (p - origin).dot(axis0);
+        ((p - origin - axis0 * height).magnitude(), height)
+    };
+    let (params, points) = profile0.parameter_division((t0, t1), tol);
+    let same_profile = params.iter().zip(&points).all(|(t, p)| {
+        let ((r0, h0), (r1, h1)) = (radius_height(*p), radius_height(profile1.subs(*t)));
+        (r0 - r1).abs() <= tol && (h0 - h1).abs() <= tol
+    });
+    // the normals are compared at the farthest point from the axis
+    let farthest = points
+        .iter()
+        .max_by(|p, q| radius_height(**p).0.total_cmp(&radius_height(**q).0));
+    same_profile
+        && farthest.is_some_and(|p| {
+            let normal = |surface: &Processor<RevolutedCurve<Curve>, Matrix4>| {
+                let (u, v) = surface.search_nearest_parameter(*p, None, 100)?;
+                Some(surface.normal(u, v))
+            };
+            matches!((normal(surface0), normal(surface1)), (Some(n0), Some(n1)) if n0.dot(n1) > 0.0)
+        })
+}
+
+impl SameDomain for Surface {
+    /// Returns whether two surfaces are the same planes, B-spline surfaces or NURBS surfaces,
+    /// or revoluted surfaces with the same axis and the same profile.
+    #[inline(always)]
+    fn same_domain(&self, other: &Self, tol: f64) -> bool {
+        match (self, other) {
+            (Surface::Plane(surface0), Surface::Plane(surface1)) => {
+                surface0.same_domain(surface1, tol)
+            }
+            (Surface::BSplineSurface(surface0), Surface::BSplineSurface(surface1)) => {
+                surface0.same_domain(surface1, tol)
+            }
+            (Surface::NurbsSurface(surface0), Surface::NurbsSurface(surface1)) => {
+                surface0.same_domain(surface1, tol)
+            }
+            (Surface::RevolutedCurve(surface0), Surface::RevolutedCurve(surface1)) => {
+                same_revolution(surface0, surface1, tol)
+            }
+            _ => false,
+        }
+    }
+}
+
 impl SearchNearestParameter<D2> for Surface {
     type Point = Point3;
     fn search_nearest_parameter<H: Into<SPHint2D>>(
@@ -285,7 +485,7 @@ impl SearchNearestParameter<D2> for Surface {
                 let hint = match hint.into() {
                     SPHint2D::Parameter(hint0, hint1) => (hint0, hint1),
                     SPHint2D::Range(x, y) => algo::surface::presearch(rotted, point, (x, y), 100),
-                    SPHint2D::None => {
+                    SPHint2D::None | SPHint2D::Global => {
                         algo::surface::presearch(rotted, point, rotted.range_tuple(), 100)
                     }
                 };
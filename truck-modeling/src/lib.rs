@@ -43,6 +43,8 @@ pub mod topology {
     pub type Shell = truck_topology::Shell<Point3, Curve, Surface>;
     /// Solid, attached to a closed shells.
     pub type Solid = truck_topology::Solid<Point3, Curve, Surface>;
+    /// Compound, a collection of solids, shells, wires and free vertices.
+    pub type Compound = truck_topology::Compound<Point3, Curve, Surface>;
 
     /// The id of vertex. `Copy` trait is implemented.
     pub type VertexID = truck_topology::VertexID<Point3>;
@@ -51,14 +53,59 @@ pub mod topology {
     /// The id that does not depend on the direction of the face.
     pub type FaceID = truck_topology::FaceID<Surface>;
 
-    pub use truck_topology::shell::ShellCondition;
+    /// Typed attributes of vertices, edges and faces.
+    ///
+    /// The faces created by sweeping a face refer to the attributes of the swept face.
+    /// ```
+    /// use truck_modeling::*;
+    /// let v = builder::vertex(Point3::origin());
+    /// let e = builder::tsweep(&v, Vector3::unit_x());
+    /// let face = builder::tsweep(&e, Vector3::unit_y());
+    ///
+    /// let mut attributes = AttributeStore::new();
+    /// attributes.set_face(&face, "blue");
+    /// let cube = builder::tsweep(&face, Vector3::unit_z());
+    ///
+    /// let shell = &cube.boundaries()[0];
+    /// assert_eq!(attributes.face(&shell[0]), Some(&"blue"));
+    /// assert_eq!(attributes.face(&shell[5]), Some(&"blue"));
+    /// assert_eq!(attributes.face(&shell[1]), None);
+    /// ```
+    pub type AttributeStore<A> =
+        truck_topology::attributes::AttributeStore<Point3, Curve, Surface, A>;
+
+    /// History of modeling operations.
+    pub type History = truck_topology::history::History<Point3, Curve, Surface>;
+    /// The original element from which another element is derived.
+    pub type HistoryOrigin = truck_topology::history::Origin<Point3, Curve, Surface>;
+
+    /// A problem of topology or geometry reported by `check`.
+    pub type ValidityIssue = truck_topology::validity::ValidityIssue<Point3, Curve, Surface>;
+    /// The differences of two shapes reported by `diff`.
+    pub type ShapeDiff = truck_topology::diff::ShapeDiff<Curve, Surface>;
+    /// Adjacency graph of faces, attributed by the convexity of the shared edges.
+    pub type FaceAdjacencyGraph =
+        truck_topology::adjacency::FaceAdjacencyGraph<Point3, Curve, Surface>;
+    /// An edge as an arc of the face adjacency graph.
+    pub type AdjacencyEdge = truck_topology::adjacency::AdjacencyEdge<Point3, Curve>;
+
+    pub use truck_topology::{
+        adjacency::Convexity,
+        attributes::CompressedAttributes,
+        binary,
+        history::Evolution,
+        journal::{Journal, Transaction},
+        mass_properties::MassProperties,
+        shell::ShellCondition,
+    };
 }
 pub use topology::*;
 
-/// topological utility: [`Mapped`], [`Sweep`], and [`ClosedSweep`].
+/// topological utility: [`Mapped`], [`Sweep`], [`SweepHistory`], and [`ClosedSweep`].
 ///
 /// [`Mapped`]: ./topo_traits/trait.Mapped.html
 /// [`Sweep`]: ./topo_traits/trait.Sweep.html
+/// [`SweepHistory`]: ./topo_traits/trait.SweepHistory.html
 /// [`ClosedSweep`]: ./topo_traits/trait.ClosedSweep.html
 pub mod topo_traits {
     /// Mapping, duplicates and moves a topological element.
@@ -127,6 +174,16 @@ pub mod topo_traits {
         ) -> Self::Swept;
     }
 
+    /// Records the history of sweeping, which elements are generated from or copied from the original ones.
+    pub trait SweepHistory<P, C, S>: Sweep<P, C, S> {
+        /// Returns the history of `swept`, the result of sweeping `self`.
+        ///
+        /// The side faces are generated from the original edges, the connecting edges are generated
+        /// from the original vertices, and the transformed copies of the original elements are copied
+        /// from them. The original elements contained in `swept` are not recorded.
+        fn sweep_history(&self, swept: &Self::Swept) -> truck_topology::history::History<P, C, S>;
+    }
+
     /// closed sweep, builds a closed torus, and so on.
     pub trait ClosedSweep<P, C, S>: MultiSweep<P, C, S> {
         /// Transform topologies and connect vertices and edges in boundaries.
@@ -152,13 +209,23 @@ pub use topo_traits::*;
 /// `Result` with crate's errors.
 pub type Result<T> = std::result::Result<T, errors::Error>;
 
+/// fillets and chamfers of the edges of solids
+pub mod blend;
 /// the building model utility API
 pub mod builder;
 mod closed_sweep;
 /// declare errors
 pub mod errors;
+/// recognition of machining features
+pub mod features;
 mod geom_impls;
 mod mapped;
 mod multi_sweep;
+/// exact offsets of planes, cylinders, spheres and surfaces of revolution: hollowing solids and
+/// thickening shells
+pub mod offset;
+/// conversion from subdivision surfaces
+pub mod subdivision;
 mod sweep;
+mod sweep_history;
 mod topo_impls;
@@ -79,3 +79,17 @@ impl<P: Clone, C: Clone, S: Clone> Mapped<P, C, S> for Solid<P, C, S> {
         )
     }
 }
+
+impl<P: Clone, C: Clone, S: Clone> Mapped<P, C, S> for Compound<P, C, S> {
+    /// Returns a new compound whose surfaces are mapped by `surface_mapping`,
+    /// curves are mapped by `curve_mapping` and points are mapped by `point_mapping`.
+    #[inline(always)]
+    fn mapped<FP: Fn(&P) -> P, FC: Fn(&C) -> C, FS: Fn(&S) -> S>(
+        &self,
+        point_mapping: &FP,
+        curve_mapping: &FC,
+        surface_mapping: &FS,
+    ) -> Self {
+        self.mapped(point_mapping, curve_mapping, surface_mapping)
+    }
+}
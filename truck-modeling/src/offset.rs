@@ -0,0 +1,552 @@
+use crate::*;
+use errors::Error;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+/// the number of the divisions of each edge for sampling
+const SAMPLE_DIVISION: usize = 8;
+const SEARCH_PARAMETER_TRIALS: usize = 100;
+/// the number of the bisections in the approximation of intersection curves
+const APPROXIMATION_TRIALS: usize = 20;
+
+/// Hollows `solid` into the walls of `thickness`, opened at `faces_to_remove`, where the faces
+/// are on planes, cylinders, spheres or surfaces of revolution.
+///
+/// Each face of `solid` except `faces_to_remove` is offset inward by `thickness`, and the offset
+/// faces bound the inside of the walls. Each removed face is replaced by the rims between its
+/// boundaries and the offset boundaries, which open the inside of the walls.
+///
+/// # Details
+/// - The surfaces of the offset faces must be planes, cylinders, spheres, or surfaces of
+///   revolution of lines and circular arcs, whose offsets are of the same kinds. The offsets of
+///   the other surfaces, e.g. B-spline surfaces, are not approximated, so that such solids
+///   cannot be hollowed.
+/// - The offset faces must meet along lines, circular arcs, or the curves moved with the
+///   offset surfaces, e.g. the seams of cylinders, as the original faces.
+/// - The removed faces must not be adjacent to each other or tangent to the other faces.
+/// - If no face is removed, the inside of the walls is a closed void bounded by another
+///   boundary shell of the returned solid.
+/// # Failures
+/// - Returns [`Error::NotOffsettableFace`] if a face to be removed is not a face of `solid`,
+///   or the surface of an offset face is not one of the above.
+/// - Returns [`Error::UnsupportedOffsetShape`] if the offset faces do not meet as above, or
+///   the removed faces are adjacent or tangent to the other faces.
+/// - Returns [`Error::InvalidOffsetSize`] if `thickness` is not positive, or so large that the
+///   offset faces degenerate, e.g. larger than the radius of a cylinder or the width of a wall.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// let v = builder::vertex(Point3::origin());
+/// let e = builder::tsweep(&v, Vector3::unit_x());
+/// let f = builder::tsweep(&e, Vector3::unit_y());
+/// let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+///
+/// // the box opened at the top face
+/// let top = cube.boundaries()[0].iter().last().unwrap().clone();
+/// let hollowed = offset::shell_solid(&cube, 0.1, &[top]).unwrap();
+/// let shell = &hollowed.boundaries()[0];
+/// assert_eq!(hollowed.boundaries().len(), 1);
+/// // the outer faces, the rim and the inner faces
+/// assert_eq!(shell.len(), 5 + 1 + 5);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// ```
+pub fn shell_solid(solid: &Solid, thickness: f64, faces_to_remove: &[Face]) -> Result<Solid> {
+    if thickness < TOLERANCE {
+        return Err(Error::InvalidOffsetSize);
+    }
+    let removed: HashSet<FaceID> = faces_to_remove.iter().map(Face::id).collect();
+    let found = solid
+        .face_iter()
+        .filter(|face| removed.contains(&face.id()));
+    if found.count() != removed.len() {
+        return Err(Error::NotOffsettableFace);
+    }
+    let shells = solid
+        .boundaries()
+        .iter()
+        .map(|shell| hollow_shell(shell, thickness, &removed))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Solid::try_new(shells.into_iter().flatten().collect())?)
+}
+
+fn hollow_shell(shell: &Shell, thickness: f64, removed: &HashSet<FaceID>) -> Result<Vec<Shell>> {
+    let mut removed_edges = HashSet::default();
+    let adjacent = shell
+        .iter()
+        .filter(|face| removed.contains(&face.id()))
+        .flat_map(|face| face.edge_iter())
+        .any(|edge| !removed_edges.insert(edge.id()));
+    if adjacent {
+        return Err(Error::UnsupportedOffsetShape);
+    }
+    let distances = shell
+        .iter()
+        .map(|face| match removed.contains(&face.id()) {
+            true => 0.0,
+            false => thickness,
+        })
+        .collect::<Vec<_>>();
+    let offset = offset_shell(shell, &distances)?;
+
+    let mut faces = Shell::new();
+    for face in shell.iter() {
+        if !removed.contains(&face.id()) {
+            faces.push(face.clone());
+            continue;
+        }
+        for wire in face.boundaries() {
+            let inner: Wire = wire.iter().map(|edge| offset.edge(edge)).collect();
+            let boundaries = vec![wire, inner.inverse()];
+            faces.push(Face::try_new(boundaries, face.oriented_surface())?);
+        }
+    }
+    offset.faces.into_iter().flatten().for_each(|mut face| {
+        face.invert();
+        faces.push(face);
+    });
+    Ok(faces.connected_components())
+}
+
+/// Thickens `shell` on planes, cylinders, spheres or surfaces of revolution into a solid by
+/// `distance` along the normals of the faces.
+///
+/// The faces of `shell` are offset by `distance`, or by the half of `distance` to each side if
+/// `both_sides` is true, and the boundaries of the offset faces are stitched by the ruled side
+/// faces. To thicken `shell` against the normals, invert the faces in advance.
+///
+/// # Details
+/// - The faces are offset as [`shell_solid`]: the surfaces must be planes, cylinders, spheres, or
+///   surfaces of revolution of lines and circular arcs, and the faces must meet along lines,
+///   circular arcs, or the curves moved with the offset surfaces. The offsets of the other
+///   surfaces, e.g. B-spline surfaces, are not approximated.
+/// - The side faces along the intersection curves, e.g. on the boundaries of the shells made by
+///   boolean operations, are the ruled surfaces between the cubic B-spline approximations of
+///   the curves.
+/// - If `shell` is closed, the returned solid has two boundary shells without side faces.
+/// # Failures
+/// - Returns [`Error::InvalidOffsetSize`] if `distance` is not positive, or so large that the
+///   offset faces degenerate, e.g. larger than the radius of a cylinder curved toward the offset.
+/// - Returns [`Error::NotOffsettableFace`] or [`Error::UnsupportedOffsetShape`] if the faces are
+///   not supported as above, or [`Error::UnsupportedOffsetShape`] if an intersection curve on
+///   the boundary cannot be approximated.
+/// - Returns [`Error::FromTopology`] if the boundaries of the offset faces cannot be stitched,
+///   e.g. `shell` is not oriented.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// let v = builder::vertex(Point3::new(-1.0, -1.0, 0.0));
+/// let e = builder::tsweep(&v, 2.0 * Vector3::unit_x());
+/// let plate: Face = builder::tsweep(&e, 2.0 * Vector3::unit_y());
+///
+/// // the plate on the xy-plane thickened to the both sides
+/// let shell: Shell = vec![plate].into();
+/// let solid = offset::thicken(&shell, 0.5, true).unwrap();
+/// let shell = &solid.boundaries()[0];
+/// // the offset faces and the side faces
+/// assert_eq!(shell.len(), 2 + 4);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+/// let props = solid.mass_properties(TOLERANCE).unwrap();
+/// assert_near!(props.volume, 2.0);
+/// assert_near!(props.centroid, Point3::origin());
+/// ```
+pub fn thicken(shell: &Shell, distance: f64, both_sides: bool) -> Result<Solid> {
+    if distance < TOLERANCE {
+        return Err(Error::InvalidOffsetSize);
+    }
+    let (upper, lower) = match both_sides {
+        true => (-0.5 * distance, 0.5 * distance),
+        false => (-distance, 0.0),
+    };
+    let offset = |distance: f64| offset_shell(shell, &vec![distance; shell.len()]);
+    let upper = offset(upper)?;
+    let lower = match lower == 0.0 {
+        true => None,
+        false => Some(offset(lower)?),
+    };
+
+    let boundaries = shell.extract_boundaries();
+    let sides = boundaries
+        .iter()
+        .map(|wire| {
+            let upper_wire: Wire = wire.iter().map(|edge| upper.edge(edge)).collect();
+            let lower_wire: Wire = match &lower {
+                Some(lower) => wire.iter().map(|edge| lower.edge(edge)).collect(),
+                None => wire.clone(),
+            };
+            side_faces(&lower_wire, &upper_wire)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut faces: Shell = upper.faces.into_iter().flatten().collect();
+    let lower_faces = match lower {
+        Some(lower) => lower.faces.into_iter().flatten().collect(),
+        None => shell.iter().cloned().collect::<Vec<_>>(),
+    };
+    lower_faces.into_iter().for_each(|mut face| {
+        face.invert();
+        faces.push(face);
+    });
+    sides.into_iter().for_each(|side| faces.extend(side));
+    Ok(Solid::try_new(faces.connected_components())?)
+}
+
+/// Returns the ruled faces between the edges of `wire0` and `wire1` as
+/// [`builder::try_wire_homotopy`], where the intersection curves are approximated by cubic
+/// B-spline curves.
+fn side_faces(wire0: &Wire, wire1: &Wire) -> Result<Shell> {
+    let lift = |edge: &Edge| match edge.oriented_curve() {
+        Curve::IntersectionCurve(curve) => {
+            // only the points are fitted, so that the edge is on the side face
+            let (range, trials) = (curve.range_tuple(), APPROXIMATION_TRIALS);
+            BSplineCurve::cubic_approximation(&curve, range, TOLERANCE, f64::INFINITY, trials)
+                .map(|bspcurve| Curve::BSplineCurve(bspcurve).lift_up())
+                .ok_or(Error::UnsupportedOffsetShape)
+        }
+        curve => Ok(curve.lift_up()),
+    };
+    let mut vemap = truck_base::entry_map::FxEntryMap::new(
+        |(v0, v1): (&Vertex, &Vertex)| (v0.id(), v1.id()),
+        |(v0, v1)| builder::line(v0, v1),
+    );
+    wire0
+        .edge_iter()
+        .zip(wire1.edge_iter())
+        .map(|(edge0, edge1)| {
+            let edge2 = vemap
+                .entry_or_insert((edge0.front(), edge1.front()))
+                .inverse();
+            let edge3 = vemap.entry_or_insert((edge0.back(), edge1.back())).clone();
+            let wire: Wire = vec![edge0.clone(), edge3, edge1.inverse(), edge2].into();
+            let surface = BSplineSurface::homotopy(lift(edge0)?, lift(edge1)?);
+            let surface = Surface::NurbsSurface(NurbsSurface::new(surface));
+            Ok(Face::try_new(vec![wire], surface)?)
+        })
+        .collect()
+}
+
+/// The offsets of the edges and the faces of a shell
+struct ShellOffset {
+    /// the offset edges in the directions of the absolute original edges
+    edges: HashMap<EdgeID, Edge>,
+    /// the offset faces, `None` for the faces not to be offset
+    faces: Vec<Option<Face>>,
+}
+
+impl ShellOffset {
+    /// the offset of `edge` in the same direction as `edge`
+    fn edge(&self, edge: &Edge) -> Edge {
+        let offset = &self.edges[&edge.id()];
+        match edge.orientation() {
+            true => offset.clone(),
+            false => offset.inverse(),
+        }
+    }
+}
+
+/// Offsets each face of `shell` by the corresponding distance against its outward normal.
+///
+/// The vertices and the edges of the faces with non-zero distances are moved to the
+/// intersections of the offset surfaces, where the surfaces with zero distances stay.
+fn offset_shell(shell: &Shell, distances: &[f64]) -> Result<ShellOffset> {
+    let originals = shell.iter().map(Face::oriented_surface).collect::<Vec<_>>();
+    let surfaces = shell
+        .iter()
+        .zip(distances)
+        .map(|(face, distance)| match *distance == 0.0 {
+            true => Ok((face.oriented_surface(), None)),
+            false => offset_surface(face, *distance),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut edge_faces = HashMap::<EdgeID, (Edge, Vec<usize>)>::default();
+    let mut vertex_faces = HashMap::<VertexID, (Vertex, Vec<usize>)>::default();
+    shell.iter().enumerate().for_each(|(i, face)| {
+        face.edge_iter().for_each(|edge| {
+            let entry = edge_faces
+                .entry(edge.id())
+                .or_insert_with(|| (edge.absolute_clone(), Vec::new()));
+            if !entry.1.contains(&i) {
+                entry.1.push(i);
+            }
+        });
+        face.vertex_iter().for_each(|vertex| {
+            let entry = vertex_faces
+                .entry(vertex.id())
+                .or_insert_with(|| (vertex.clone(), Vec::new()));
+            if !entry.1.contains(&i) {
+                entry.1.push(i);
+            }
+        });
+    });
+    // the tangent planes at `pt` with the distances
+    let offsets = |pt: Point3, faces: &[usize]| {
+        let offsets = faces.iter().map(|f| {
+            let (_, normal) = surface_point(&originals[*f], pt)?;
+            Some((normal, distances[*f]))
+        });
+        offsets.collect::<Option<Vec<_>>>()
+    };
+    let moved = |faces: &[usize]| faces.iter().any(|f| distances[*f] != 0.0);
+
+    let vertices = vertex_faces
+        .iter()
+        .filter(|(_, (_, faces))| moved(faces))
+        .map(|(id, (vertex, faces))| {
+            let pt = vertex.point();
+            let new = offsets(pt, faces)
+                .and_then(|offsets| offset_point(pt, &offsets))
+                .filter(|pt| faces.iter().all(|f| on_surface(&surfaces[*f].0, *pt)))
+                .ok_or(Error::UnsupportedOffsetShape)?;
+            Ok((*id, builder::vertex(new)))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    let edges = edge_faces
+        .iter()
+        .filter(|(_, (_, faces))| moved(faces))
+        .map(|(id, (edge, faces))| {
+            let curve = edge.curve();
+            let (t0, t1) = curve.range_tuple();
+            let params = (0..=SAMPLE_DIVISION)
+                .map(|i| t0 + (t1 - t0) * i as f64 / SAMPLE_DIVISION as f64)
+                .collect::<Vec<_>>();
+            let targets = params
+                .iter()
+                .map(|t| {
+                    let pt = curve.subs(*t);
+                    offset_point(pt, &offsets(pt, faces)?)
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or(Error::UnsupportedOffsetShape)?;
+            let front = &vertices[&edge.absolute_front().id()];
+            let back = &vertices[&edge.absolute_back().id()];
+            let maps = faces.iter().filter_map(|f| surfaces[*f].1);
+            let new = offset_curve(&curve, &params, &targets, maps, front, back)?;
+            Ok((*id, new))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    let mut offset = ShellOffset {
+        edges,
+        faces: Vec::new(),
+    };
+    offset.faces = shell
+        .iter()
+        .zip(surfaces)
+        .zip(distances)
+        .map(|((face, (surface, _)), distance)| {
+            if *distance == 0.0 {
+                return Ok(None);
+            }
+            let wires = face
+                .boundaries()
+                .iter()
+                .map(|wire| wire.iter().map(|edge| offset.edge(edge)).collect())
+                .collect();
+            Ok(Some(Face::try_new(wires, surface)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(offset)
+}
+
+/// Returns the point and the normal of `surface` nearest to `pt`.
+fn surface_point(surface: &Surface, pt: Point3) -> Option<(Point3, Vector3)> {
+    let (u, v) = surface
+        .search_parameter(pt, None, SEARCH_PARAMETER_TRIALS)
+        .or_else(|| surface.search_nearest_parameter(pt, None, SEARCH_PARAMETER_TRIALS))?;
+    let normal = surface.normal(u, v);
+    match normal.magnitude().near(&1.0) {
+        true => Some((surface.subs(u, v), normal)),
+        false => None,
+    }
+}
+
+#[inline(always)]
+fn on_surface(surface: &Surface, pt: Point3) -> bool {
+    surface_point(surface, pt).is_some_and(|(q, _)| q.near(&pt))
+}
+
+/// Returns the point whose distances from the tangent planes through `pt` are the given ones
+/// against the normals, where `offsets` are the pairs of the normals and the distances.
+fn offset_point(pt: Point3, offsets: &[(Vector3, f64)]) -> Option<Point3> {
+    let mut basis = Vec::<(Vector3, f64)>::new();
+    offsets.iter().for_each(|(n, d)| {
+        let independent = match basis.as_slice() {
+            [] => true,
+            [(n0, _)] => n0.cross(*n).magnitude() > TOLERANCE,
+            [(n0, _), (n1, _)] => n0.cross(*n1).dot(*n).abs() > TOLERANCE,
+            _ => false,
+        };
+        if independent {
+            basis.push((*n, *d));
+        }
+    });
+    let vec = match basis.as_slice() {
+        [(n0, d0)] => -d0 * n0,
+        [(n0, d0), (n1, d1)] => {
+            let cos = n0.dot(*n1);
+            let det = 1.0 - cos * cos;
+            (cos * d1 - d0) / det * n0 + (cos * d0 - d1) / det * n1
+        }
+        [(n0, d0), (n1, d1), (n2, d2)] => {
+            let mat = Matrix3::from_cols(*n0, *n1, *n2).transpose();
+            mat.invert()? * Vector3::new(-d0, -d1, -d2)
+        }
+        _ => return None,
+    };
+    match offsets
+        .iter()
+        .all(|(n, d)| (vec.dot(*n) + d).abs() < TOLERANCE)
+    {
+        true => Some(pt + vec),
+        false => None,
+    }
+}
+
+/// Returns the affine map moving the sampled points by `distance` against the normals and its
+/// ratio of scaling: a translation for the parallel normals, the scaling around the center for
+/// spheres and circles, and the scaling around the axis for cylinders.
+fn offset_map(samples: &[(Point3, Vector3)], distance: f64) -> Option<(Matrix4, f64)> {
+    let (p0, n0) = *samples.first()?;
+    let (pk, nk) = samples
+        .iter()
+        .max_by(|(_, n1), (_, n2)| {
+            let (d1, d2) = ((n1 - n0).magnitude2(), (n2 - n0).magnitude2());
+            d1.partial_cmp(&d2).unwrap()
+        })
+        .copied()?;
+    let dn = n0 - nk;
+    let candidates = if dn.magnitude() < TOLERANCE {
+        vec![(Matrix4::from_translation(-distance * n0), 1.0)]
+    } else {
+        // the signed radius, positive if the normals point away from the center
+        let radius = (p0 - pk).dot(dn) / dn.magnitude2();
+        let scale = (radius - distance) / radius;
+        let center = (p0 - radius * n0).to_vec();
+        let around = |mat: Matrix3| {
+            Matrix4::from_translation(center)
+                * Matrix4::from(mat)
+                * Matrix4::from_translation(-center)
+        };
+        let axis = n0.cross(nk).normalize();
+        let projection = Matrix3::from_cols(axis * axis.x, axis * axis.y, axis * axis.z);
+        let axial = Matrix3::identity() * scale + projection * (1.0 - scale);
+        vec![
+            (around(Matrix3::identity() * scale), scale),
+            (around(axial), scale),
+        ]
+    };
+    candidates.into_iter().find(|(mat, _)| {
+        samples
+            .iter()
+            .all(|(p, n)| mat.transform_point(*p).near(&(p - distance * n)))
+    })
+}
+
+/// Returns the surface of `face` offset by `distance` against the outward normal, and the
+/// affine map which moves the surface to the offset one if exists.
+fn offset_surface(face: &Face, distance: f64) -> Result<(Surface, Option<Matrix4>)> {
+    let surface = face.oriented_surface();
+    let samples = face
+        .edge_iter()
+        .flat_map(|edge| {
+            let curve = edge.curve();
+            let (t0, t1) = curve.range_tuple();
+            (0..=SAMPLE_DIVISION)
+                .map(move |i| curve.subs(t0 + (t1 - t0) * i as f64 / SAMPLE_DIVISION as f64))
+        })
+        .filter_map(|pt| surface_point(&surface, pt))
+        .collect::<Vec<_>>();
+    let (mut offset, map) = match &surface {
+        Surface::RevolutedCurve(processor) => {
+            let revolution = processor.entity();
+            let trans = *processor.transform();
+            let profile = revolution.entity_curve().transformed(trans);
+            let (t0, t1) = profile.range_tuple();
+            // the interior points avoiding the poles on the axis
+            let samples = (1..SAMPLE_DIVISION)
+                .map(|i| profile.subs(t0 + (t1 - t0) * i as f64 / SAMPLE_DIVISION as f64))
+                .filter_map(|pt| surface_point(&surface, pt))
+                .collect::<Vec<_>>();
+            let (map, scale) = offset_map(&samples, distance).ok_or(Error::NotOffsettableFace)?;
+            if scale < TOLERANCE {
+                return Err(Error::InvalidOffsetSize);
+            }
+            let origin = trans.transform_point(revolution.origin());
+            let axis = trans.transform_vector(revolution.axis()).normalize();
+            // the offset profile must not cross the axis
+            let radial = |pt: Point3| {
+                let vec = pt - origin;
+                vec - axis * vec.dot(axis)
+            };
+            let crossed = samples
+                .iter()
+                .any(|(p, n)| radial(p - distance * n).dot(radial(*p)) < TOLERANCE);
+            if crossed {
+                return Err(Error::InvalidOffsetSize);
+            }
+            let revolution = RevolutedCurve::by_revolution(profile.transformed(map), origin, axis);
+            (Surface::RevolutedCurve(Processor::new(revolution)), None)
+        }
+        _ => {
+            let (map, scale) = offset_map(&samples, distance).ok_or(Error::NotOffsettableFace)?;
+            if scale < TOLERANCE {
+                return Err(Error::InvalidOffsetSize);
+            }
+            (surface.transformed(map), Some(map))
+        }
+    };
+    let moved = samples.iter().map(|(p, n)| (p - distance * n, *n));
+    let moved = moved.collect::<Vec<_>>();
+    if samples.is_empty() || !moved.iter().all(|(p, _)| on_surface(&offset, *p)) {
+        return Err(Error::NotOffsettableFace);
+    }
+    let (p, n) = moved[0];
+    if surface_point(&offset, p).is_some_and(|(_, normal)| normal.dot(n) < 0.0) {
+        offset.invert();
+    }
+    Ok((offset, map))
+}
+
+/// Returns the offset of `curve` from `front` to `back` through `targets` at `params`.
+///
+/// The candidates are the curve moved by the affine maps of the faces, the line and
+/// the circular arc. The moved curve is taken only if its ends are `front` and `back`, since
+/// the offset edge may be shorter or longer than the original one.
+fn offset_curve(
+    curve: &Curve,
+    params: &[f64],
+    targets: &[Point3],
+    maps: impl IntoIterator<Item = Matrix4>,
+    front: &Vertex,
+    back: &Vertex,
+) -> Result<Edge> {
+    let (p, q) = (front.point(), back.point());
+    for map in maps {
+        let moved = curve.transformed(map);
+        let (t0, t1) = moved.range_tuple();
+        let ends = moved.subs(t0).near(&p) && moved.subs(t1).near(&q);
+        if ends
+            && params
+                .iter()
+                .zip(targets)
+                .all(|(t, x)| moved.subs(*t).near(x))
+        {
+            return Ok(Edge::new(front, back, moved));
+        }
+    }
+    let dir = q - p;
+    let on_line = |x: &Point3| (x - p).cross(dir).magnitude() < TOLERANCE * dir.magnitude();
+    if dir.magnitude() > TOLERANCE && targets.iter().all(on_line) {
+        let (t0, t1) = curve.range_tuple();
+        return match dir.dot(curve.subs(t1) - curve.subs(t0)) > 0.0 {
+            true => Ok(builder::line(front, back)),
+            false => Err(Error::InvalidOffsetSize),
+        };
+    }
+    if let Some((center, axis)) = geom_impls::fit_circle(targets, TOLERANCE) {
+        let angle = geom_impls::turning_angle(p - center, q - center, axis);
+        let arc = geom_impls::circle_arc(p, center, axis, Rad(angle));
+        return Ok(Edge::new(front, back, Curve::NurbsCurve(arc)));
+    }
+    Err(Error::UnsupportedOffsetShape)
+}
@@ -0,0 +1,381 @@
+use crate::*;
+use errors::Error;
+use rustc_hash::FxHashMap as HashMap;
+use truck_polymesh::PolygonMesh;
+
+type Quad = [usize; 4];
+
+/// Converts the Catmull-Clark subdivision surface whose control cage is `cage` into a shell
+/// consisting of bicubic Bezier patches.
+///
+/// Each quadrangle of the cage is converted to one patch.
+/// If the cage has a face which is not a quadrangle, it is subdivided once before the conversion,
+/// so that all faces are quadrangles.
+///
+/// # Details
+/// - Around regular vertices, the patches coincide with the limit surface of the subdivision.
+/// - Around extraordinary vertices, the patches approximate the limit surface by the method of
+///   Loop and Schaefer, "Approximating Catmull-Clark subdivision surfaces with bicubic patches".
+///   The shell is watertight but not always tangent continuous on the edges near such vertices.
+/// - The boundary of an open cage is converted to the cubic B-spline curve of the boundary
+///   polyline, and the vertices included in only one face are interpolated.
+///
+/// The vertices of the cage are identified by the indices of positions,
+/// so the same position must not be registered twice.
+/// # Failures
+/// Returns [`Error::NonManifoldControlCage`] if an edge of the cage is shared by more than two
+/// faces or the orientations of the faces are not compatible.
+/// # Examples
+/// ```
+/// use truck_modeling::*;
+/// use truck_polymesh::*;
+///
+/// // the control cage of a cube
+/// let positions = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+///     Point3::new(1.0, 0.0, 1.0),
+///     Point3::new(1.0, 1.0, 1.0),
+///     Point3::new(0.0, 1.0, 1.0),
+/// ];
+/// let faces = Faces::from_iter(&[
+///     [0, 3, 2, 1],
+///     [0, 1, 5, 4],
+///     [1, 2, 6, 5],
+///     [2, 3, 7, 6],
+///     [3, 0, 4, 7],
+///     [4, 5, 6, 7],
+/// ]);
+/// let cage = PolygonMesh::new(
+///     StandardAttributes {
+///         positions,
+///         ..Default::default()
+///     },
+///     faces,
+/// );
+///
+/// let shell = subdivision::catmull_clark_limit(&cage).unwrap();
+/// assert_eq!(shell.len(), 6);
+/// assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+///
+/// // the limit point of a corner of the cube
+/// let pt = Point3::new(0.25, 0.25, 0.25);
+/// assert!(shell.vertex_iter().any(|v| v.point().near(&pt)));
+/// ```
+pub fn catmull_clark_limit(cage: &PolygonMesh) -> Result<Shell> {
+    let mut positions = cage.positions().to_vec();
+    let mut faces = cage
+        .face_iter()
+        .map(|face| face.iter().map(|v| v.pos).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    if faces.iter().any(|face| face.len() != 4) {
+        (positions, faces) = subdivide(&positions, &faces)?;
+    }
+    let quads = faces
+        .into_iter()
+        .map(|face| [face[0], face[1], face[2], face[3]])
+        .collect::<Vec<_>>();
+    bezier_shell(&positions, &quads)
+}
+
+/// Returns the map from the edges `(v, w)` with `v < w` to the faces including the edges.
+fn edge_faces<F: AsRef<[usize]>>(faces: &[F]) -> Result<HashMap<(usize, usize), Vec<usize>>> {
+    let mut oriented = HashMap::<(usize, usize), usize>::default();
+    let mut map = HashMap::<(usize, usize), Vec<usize>>::default();
+    for (i, face) in faces.iter().enumerate() {
+        let face = face.as_ref();
+        for (j, v) in face.iter().enumerate() {
+            let w = face[(j + 1) % face.len()];
+            if oriented.insert((*v, w), i).is_some() {
+                return Err(Error::NonManifoldControlCage);
+            }
+            map.entry((usize::min(*v, w), usize::max(*v, w)))
+                .or_default()
+                .push(i);
+        }
+    }
+    Ok(map)
+}
+
+/// Returns the neighbors of each vertex and whether the edges to them are on the boundary.
+fn neighbors(len: usize, edges: &HashMap<(usize, usize), Vec<usize>>) -> Vec<Vec<(usize, bool)>> {
+    let mut res = vec![Vec::new(); len];
+    for ((v, w), faces) in edges {
+        res[*v].push((*w, faces.len() == 1));
+        res[*w].push((*v, faces.len() == 1));
+    }
+    res
+}
+
+/// Applies one step of Catmull-Clark subdivision.
+fn subdivide(positions: &[Point3], faces: &[Vec<usize>]) -> Result<(Vec<Point3>, Vec<Vec<usize>>)> {
+    let edges = edge_faces(faces)?;
+    let neighbors = neighbors(positions.len(), &edges);
+    let face_points = faces
+        .iter()
+        .map(|face| Point3::centroid(&face.iter().map(|i| positions[*i]).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    let mut incident_faces = vec![Vec::new(); positions.len()];
+    faces.iter().enumerate().for_each(|(i, face)| {
+        face.iter().for_each(|v| incident_faces[*v].push(i));
+    });
+
+    let mut new_positions = positions
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let boundary = neighbors[i]
+                .iter()
+                .filter(|(_, is_boundary)| *is_boundary)
+                .map(|(w, _)| positions[*w])
+                .collect::<Vec<_>>();
+            let n = incident_faces[i].len() as f64;
+            match (boundary.len(), incident_faces[i].len()) {
+                (_, 0) | (_, 1) => *p,
+                (0, _) => {
+                    let f = incident_faces[i]
+                        .iter()
+                        .fold(Vector3::zero(), |sum, j| sum + face_points[*j].to_vec())
+                        / n;
+                    let r = neighbors[i].iter().fold(Vector3::zero(), |sum, (w, _)| {
+                        sum + p.midpoint(positions[*w]).to_vec()
+                    }) / n;
+                    Point3::from_vec((f + 2.0 * r + (n - 3.0) * p.to_vec()) / n)
+                }
+                (2, _) => Point3::from_vec(
+                    (boundary[0].to_vec() + 6.0 * p.to_vec() + boundary[1].to_vec()) / 8.0,
+                ),
+                _ => *p,
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut edge_index = HashMap::<(usize, usize), usize>::default();
+    edges.iter().for_each(|((v, w), adjacent)| {
+        let midpoint = positions[*v].midpoint(positions[*w]);
+        let pt = match adjacent.len() {
+            2 => {
+                let center = face_points[adjacent[0]].midpoint(face_points[adjacent[1]]);
+                midpoint.midpoint(center)
+            }
+            _ => midpoint,
+        };
+        edge_index.insert((*v, *w), new_positions.len());
+        new_positions.push(pt);
+    });
+    let face_offset = new_positions.len();
+    new_positions.extend(face_points);
+
+    let edge = |v: usize, w: usize| edge_index[&(usize::min(v, w), usize::max(v, w))];
+    let new_faces = faces
+        .iter()
+        .enumerate()
+        .flat_map(|(i, face)| {
+            let len = face.len();
+            (0..len).map(move |j| {
+                let (prev, v, next) = (face[(j + len - 1) % len], face[j], face[(j + 1) % len]);
+                vec![v, edge(v, next), face_offset + i, edge(prev, v)]
+            })
+        })
+        .collect();
+    Ok((new_positions, new_faces))
+}
+
+/// Creates the shell of bicubic Bezier patches from the quadrangle control cage.
+fn bezier_shell(positions: &[Point3], quads: &[Quad]) -> Result<Shell> {
+    let edges = edge_faces(quads)?;
+    let neighbors = neighbors(positions.len(), &edges);
+
+    // the inner control points of the patches, indexed by the faces and their corners.
+    let inner_points = quads
+        .iter()
+        .map(|quad| {
+            let mut pts = [Point3::origin(); 4];
+            pts.iter_mut().enumerate().for_each(|(i, pt)| {
+                let v = quad[i];
+                let n = neighbors[v].len() as f64;
+                let sum = n * positions[v].to_vec()
+                    + 2.0 * positions[quad[(i + 1) % 4]].to_vec()
+                    + 2.0 * positions[quad[(i + 3) % 4]].to_vec()
+                    + positions[quad[(i + 2) % 4]].to_vec();
+                *pt = Point3::from_vec(sum / (n + 5.0));
+            });
+            pts
+        })
+        .collect::<Vec<_>>();
+    let inner_point = |face: usize, v: usize| {
+        let i = quads[face].iter().position(|w| *w == v).unwrap();
+        inner_points[face][i]
+    };
+    let mut incident_faces = vec![Vec::new(); positions.len()];
+    quads.iter().enumerate().for_each(|(i, quad)| {
+        quad.iter().for_each(|v| incident_faces[*v].push(i));
+    });
+
+    let corner_point = |v: usize| {
+        let boundary = neighbors[v]
+            .iter()
+            .filter(|(_, is_boundary)| *is_boundary)
+            .map(|(w, _)| positions[*w])
+            .collect::<Vec<_>>();
+        match (boundary.len(), incident_faces[v].len()) {
+            (_, 1) => positions[v],
+            (0, _) => {
+                let pts = incident_faces[v]
+                    .iter()
+                    .map(|face| inner_point(*face, v))
+                    .collect::<Vec<_>>();
+                Point3::centroid(&pts)
+            }
+            (2, _) => Point3::from_vec(
+                (boundary[0].to_vec() + 4.0 * positions[v].to_vec() + boundary[1].to_vec()) / 6.0,
+            ),
+            _ => positions[v],
+        }
+    };
+    // the control point of the edge `(v, w)` next to `v`.
+    let edge_point = |v: usize, w: usize| {
+        let adjacent = &edges[&(usize::min(v, w), usize::max(v, w))];
+        match adjacent.len() {
+            2 => inner_point(adjacent[0], v).midpoint(inner_point(adjacent[1], v)),
+            _ => positions[v] + (positions[w] - positions[v]) / 3.0,
+        }
+    };
+
+    let mut vertices = HashMap::<usize, Vertex>::default();
+    quads.iter().flatten().for_each(|v| {
+        vertices
+            .entry(*v)
+            .or_insert_with(|| Vertex::new(corner_point(*v)));
+    });
+    let mut edge_map = HashMap::<(usize, usize), Edge>::default();
+    edges.keys().for_each(|(v, w)| {
+        let ctrl_pts = vec![
+            vertices[v].point(),
+            edge_point(*v, *w),
+            edge_point(*w, *v),
+            vertices[w].point(),
+        ];
+        let curve = BSplineCurve::new(KnotVec::bezier_knot(3), ctrl_pts);
+        let edge = Edge::new(&vertices[v], &vertices[w], Curve::BSplineCurve(curve));
+        edge_map.insert((*v, *w), edge);
+    });
+    let edge = |v: usize, w: usize| match v < w {
+        true => edge_map[&(v, w)].clone(),
+        false => edge_map[&(w, v)].inverse(),
+    };
+
+    let shell = quads
+        .iter()
+        .zip(&inner_points)
+        .map(|(quad, inner)| {
+            let [q0, q1, q2, q3] = *quad;
+            let ctrl_pts = vec![
+                vec![
+                    vertices[&q0].point(),
+                    edge_point(q0, q3),
+                    edge_point(q3, q0),
+                    vertices[&q3].point(),
+                ],
+                vec![edge_point(q0, q1), inner[0], inner[3], edge_point(q3, q2)],
+                vec![edge_point(q1, q0), inner[1], inner[2], edge_point(q2, q3)],
+                vec![
+                    vertices[&q1].point(),
+                    edge_point(q1, q2),
+                    edge_point(q2, q1),
+                    vertices[&q2].point(),
+                ],
+            ];
+            let knot_vecs = (KnotVec::bezier_knot(3), KnotVec::bezier_knot(3));
+            let surface = BSplineSurface::new(knot_vecs, ctrl_pts);
+            let wire = Wire::from(vec![edge(q0, q1), edge(q1, q2), edge(q2, q3), edge(q3, q0)]);
+            Face::new(vec![wire], Surface::BSplineSurface(surface))
+        })
+        .collect();
+    Ok(shell)
+}
+
+#[test]
+fn regular_patch_test() {
+    use truck_polymesh::{Faces, StandardAttributes};
+    const N: usize = 6;
+    let positions = (0..N)
+        .flat_map(|i| (0..N).map(move |j| (i, j)))
+        .map(|(i, j)| Point3::new(i as f64, j as f64, f64::sin((i * j) as f64)))
+        .collect::<Vec<_>>();
+    let faces = (0..N - 1)
+        .flat_map(|i| (0..N - 1).map(move |j| (i, j)))
+        .map(|(i, j)| {
+            let k = i * N + j;
+            [k, k + N, k + N + 1, k + 1]
+        })
+        .collect::<Faces>();
+    let cage = PolygonMesh::new(
+        StandardAttributes {
+            positions: positions.clone(),
+            ..Default::default()
+        },
+        faces,
+    );
+    let shell = catmull_clark_limit(&cage).unwrap();
+    assert_eq!(shell.len(), (N - 1) * (N - 1));
+    assert_eq!(shell.shell_condition(), ShellCondition::Oriented);
+
+    // the patch on the face [2 * N + 2, 3 * N + 2, 3 * N + 3, 2 * N + 3] is regular.
+    let ctrl_pts = (1..5)
+        .map(|i| (1..5).map(|j| positions[i * N + j]).collect())
+        .collect();
+    let knot_vec = KnotVec::from((0..8).map(|i| i as f64).collect::<Vec<_>>());
+    let bspline = BSplineSurface::new((knot_vec.clone(), knot_vec), ctrl_pts);
+    let face = &shell[2 * (N - 1) + 2];
+    let surface = face.oriented_surface();
+    (0..=10).for_each(|i| {
+        (0..=10).for_each(|j| {
+            let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+            assert_near!(surface.subs(u, v), bspline.subs(3.0 + u, 3.0 + v));
+        })
+    });
+}
+
+#[test]
+fn triangle_cage_test() {
+    use truck_polymesh::{Faces, StandardAttributes};
+    // tetrahedron
+    let positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+    ];
+    let faces = Faces::from_iter(&[[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]]);
+    let cage = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    );
+    let shell = catmull_clark_limit(&cage).unwrap();
+    assert_eq!(shell.len(), 12);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    shell.edge_iter().for_each(|edge| {
+        let curve = edge.oriented_curve();
+        assert_near!(curve.front(), edge.front().point());
+        assert_near!(curve.back(), edge.back().point());
+    });
+
+    let faces = Faces::from_iter(&[[0, 2, 1], [0, 2, 3]]);
+    let cage = PolygonMesh::new(
+        StandardAttributes {
+            positions: cage.positions().to_vec(),
+            ..Default::default()
+        },
+        faces,
+    );
+    assert_eq!(
+        catmull_clark_limit(&cage).unwrap_err(),
+        Error::NonManifoldControlCage,
+    );
+}
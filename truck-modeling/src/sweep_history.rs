@@ -0,0 +1,166 @@
+use crate::topo_traits::*;
+use rustc_hash::FxHashSet as HashSet;
+use truck_topology::{history::*, *};
+
+struct HistoryRecorder<P, C, S> {
+    vertices: HashSet<VertexID<P>>,
+    edges: HashSet<EdgeID<C>>,
+    faces: HashSet<FaceID<S>>,
+    history: History<P, C, S>,
+}
+
+impl<P, C, S> HistoryRecorder<P, C, S> {
+    fn new<'a>(
+        vertices: impl IntoIterator<Item = Vertex<P>>,
+        edges: impl IntoIterator<Item = Edge<P, C>>,
+        faces: impl IntoIterator<Item = &'a Face<P, C, S>>,
+    ) -> Self
+    where
+        P: 'a,
+        C: 'a,
+        S: 'a,
+    {
+        Self {
+            vertices: vertices.into_iter().map(|v| v.id()).collect(),
+            edges: edges.into_iter().map(|e| e.id()).collect(),
+            faces: faces.into_iter().map(|f| f.id()).collect(),
+            history: History::new(),
+        }
+    }
+
+    fn is_new_vertex(&self, vertex: &Vertex<P>) -> bool {
+        !self.vertices.contains(&vertex.id()) && self.history.vertex(vertex.id()).is_none()
+    }
+
+    fn is_new_edge(&self, edge: &Edge<P, C>) -> bool {
+        !self.edges.contains(&edge.id()) && self.history.edge(edge.id()).is_none()
+    }
+
+    /// Records the edges and the vertices of `to` as the copies of the ones of `from`.
+    fn copied_face(&mut self, from: &Face<P, C, S>, to: &Face<P, C, S>) {
+        self.history
+            .set_face(to.id(), Evolution::Copied, [Origin::Face(from.id())]);
+        let edges0 = from.absolute_boundaries().iter().flatten();
+        let edges1 = to.absolute_boundaries().iter().flatten();
+        edges0.zip(edges1).for_each(|(edge0, edge1)| {
+            self.copied_edge(edge0, edge1);
+        });
+    }
+
+    fn copied_edge(&mut self, from: &Edge<P, C>, to: &Edge<P, C>) {
+        if self.is_new_edge(to) {
+            self.history
+                .set_edge(to.id(), Evolution::Copied, [Origin::Edge(from.id())]);
+        }
+        let pairs = [
+            (from.absolute_front(), to.absolute_front()),
+            (from.absolute_back(), to.absolute_back()),
+        ];
+        pairs.into_iter().for_each(|(v0, v1)| {
+            if self.is_new_vertex(v1) {
+                self.history
+                    .set_vertex(v1.id(), Evolution::Copied, [Origin::Vertex(v0.id())]);
+            }
+        });
+    }
+
+    /// Records the edge connecting an original vertex and its copy.
+    fn connecting_edge(&mut self, edge: &Edge<P, C>) {
+        if !self.is_new_edge(edge) {
+            return;
+        }
+        let (front, back) = (edge.absolute_front(), edge.absolute_back());
+        let (origin, copy) = match (
+            self.vertices.contains(&front.id()),
+            self.vertices.contains(&back.id()),
+        ) {
+            (true, false) => (front, back),
+            (false, true) => (back, front),
+            _ => return,
+        };
+        let origin = Origin::Vertex(origin.id());
+        self.history
+            .set_edge(edge.id(), Evolution::Generated, [origin]);
+        if self.is_new_vertex(copy) {
+            self.history
+                .set_vertex(copy.id(), Evolution::Copied, [origin]);
+        }
+    }
+
+    /// Records the face generated by sweeping an original edge.
+    fn side_face(&mut self, face: &Face<P, C, S>) {
+        if self.faces.contains(&face.id()) || self.history.face(face.id()).is_some() {
+            return;
+        }
+        let edges: Vec<_> = face.absolute_boundaries().iter().flatten().collect();
+        let Some(origin) = edges.iter().find(|edge| self.edges.contains(&edge.id())) else {
+            return;
+        };
+        self.history
+            .set_face(face.id(), Evolution::Generated, [Origin::Edge(origin.id())]);
+        edges.iter().for_each(|edge| self.connecting_edge(edge));
+        let copy = edges.iter().find(|edge| {
+            self.is_new_edge(edge)
+                && !self.vertices.contains(&edge.front().id())
+                && !self.vertices.contains(&edge.back().id())
+        });
+        if let Some(copy) = copy {
+            self.copied_edge(origin, copy);
+        }
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> SweepHistory<P, C, S> for Vertex<P> {
+    fn sweep_history(&self, swept: &Edge<P, C>) -> History<P, C, S> {
+        let mut recorder = HistoryRecorder::new([self.clone()], [], []);
+        recorder.connecting_edge(swept);
+        recorder.history
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> SweepHistory<P, C, S> for Edge<P, C> {
+    fn sweep_history(&self, swept: &Face<P, C, S>) -> History<P, C, S> {
+        let vertices = [self.front().clone(), self.back().clone()];
+        let mut recorder = HistoryRecorder::new(vertices, [self.clone()], []);
+        recorder.side_face(swept);
+        recorder.history
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> SweepHistory<P, C, S> for Wire<P, C> {
+    fn sweep_history(&self, swept: &Shell<P, C, S>) -> History<P, C, S> {
+        let mut recorder = HistoryRecorder::new(self.vertex_iter(), self.edge_iter().cloned(), []);
+        swept.iter().for_each(|face| recorder.side_face(face));
+        recorder.history
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> SweepHistory<P, C, S> for Face<P, C, S> {
+    fn sweep_history(&self, swept: &Solid<P, C, S>) -> History<P, C, S> {
+        let mut recorder = HistoryRecorder::new(self.vertex_iter(), self.edge_iter(), [self]);
+        let shell = &swept.boundaries()[0];
+        recorder.copied_face(self, shell.last().unwrap());
+        shell.iter().for_each(|face| recorder.side_face(face));
+        recorder.history
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> SweepHistory<P, C, S> for Shell<P, C, S> {
+    fn sweep_history(&self, swept: &Vec<Result<Solid<P, C, S>>>) -> History<P, C, S> {
+        let mut recorder = HistoryRecorder::new(self.vertex_iter(), self.edge_iter(), self.iter());
+        let components = self.connected_components();
+        components.iter().zip(swept).for_each(|(component, solid)| {
+            let Ok(solid) = solid else {
+                return;
+            };
+            let shell = &solid.boundaries()[0];
+            let seiling = &shell[shell.len() - component.len()..];
+            component
+                .iter()
+                .zip(seiling)
+                .for_each(|(face0, face1)| recorder.copied_face(face0, face1));
+            shell.iter().for_each(|face| recorder.side_face(face));
+        });
+        recorder.history
+    }
+}
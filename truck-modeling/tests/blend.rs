@@ -0,0 +1,253 @@
+use std::f64::consts::PI;
+use truck_modeling::blend::*;
+use truck_modeling::errors::Error;
+use truck_modeling::*;
+
+/// the cube [0, 2]^3
+fn cube() -> Solid {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, 2.0 * Vector3::unit_x());
+    let f = builder::tsweep(&e, 2.0 * Vector3::unit_y());
+    builder::tsweep(&f, 2.0 * Vector3::unit_z())
+}
+
+/// the edges of `solid` whose both ends satisfy `pred`
+fn edges(solid: &Solid, pred: impl Fn(Point3) -> bool) -> Vec<Edge> {
+    let mut edges: Vec<Edge> = Vec::new();
+    solid.edge_iter().for_each(|edge| {
+        let ends = [edge.front().point(), edge.back().point()];
+        if ends.into_iter().all(&pred) && edges.iter().all(|e| !e.is_same(&edge)) {
+            edges.push(edge);
+        }
+    });
+    edges
+}
+
+/// the cylinder of radius 1 and height 1
+fn cylinder() -> Solid {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    builder::tsweep(&disk, Vector3::unit_z())
+}
+
+fn volume(solid: &Solid) -> f64 {
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    solid.mass_properties(TOLERANCE).unwrap().volume
+}
+
+#[test]
+fn fillet_one_edge() {
+    let cube = cube();
+    let edges = edges(&cube, |p| p.y == 0.0 && p.z == 2.0);
+    let solid = fillet_edges(&cube, &edges, 0.5).unwrap();
+    assert_eq!(solid.boundaries()[0].len(), 7);
+    assert_near!(volume(&solid), 8.0 - (1.0 - PI / 4.0) * 0.25 * 2.0);
+
+    let radius = FilletRadius::Variable {
+        front: 0.25,
+        back: 0.5,
+    };
+    let solid = fillet_edges(&cube, &edges, radius).unwrap();
+    let mean_square = (0.0625 + 0.125 + 0.25) / 3.0;
+    assert_near!(volume(&solid), 8.0 - (1.0 - PI / 4.0) * mean_square * 2.0);
+}
+
+#[test]
+fn fillet_meeting_edges() {
+    let cube = cube();
+    let edges = edges(&cube, |p| p.z == 2.0);
+    assert_eq!(edges.len(), 4);
+    let solid = fillet_edges(&cube, &edges, 0.5).unwrap();
+    assert_eq!(solid.boundaries()[0].len(), 10);
+    // the horizontal section at the height 1.5 + h is the square shrunk by 0.5 - sqrt(0.25 - h^2)
+    let top = 4.0 * (0.125 + PI / 16.0 + 1.0 / 12.0);
+    assert_near!(volume(&solid), 6.0 + top);
+}
+
+#[test]
+fn fillet_corners() {
+    let cube = cube();
+    let edges = edges(&cube, |_| true);
+    assert_eq!(edges.len(), 12);
+    let solid = fillet_edges(&cube, &edges, 0.5).unwrap();
+    assert_eq!(solid.boundaries()[0].len(), 26);
+    // the Minkowski sum of the unit cube and the ball of radius 0.5
+    assert_near!(volume(&solid), 1.0 + 6.0 * 0.5 + 3.0 * PI * 0.25 + PI / 6.0);
+}
+
+#[test]
+fn fillet_concave_edge() {
+    let points = [
+        (0.0, 0.0),
+        (2.0, 0.0),
+        (2.0, 1.0),
+        (1.0, 1.0),
+        (1.0, 2.0),
+        (0.0, 2.0),
+    ];
+    let v: Vec<Vertex> = points
+        .iter()
+        .map(|(x, y)| builder::vertex(Point3::new(*x, *y, 0.0)))
+        .collect();
+    let wire: Wire = (0..v.len())
+        .map(|i| builder::line(&v[i], &v[(i + 1) % v.len()]))
+        .collect();
+    let face = builder::try_attach_plane(&[wire]).unwrap();
+    let solid: Solid = builder::tsweep(&face, Vector3::unit_z());
+    let edges = edges(&solid, |p| p.x == 1.0 && p.y == 1.0);
+    let filleted = fillet_edges(&solid, &edges, 0.5).unwrap();
+    assert_near!(volume(&filleted), 3.0 + (1.0 - PI / 4.0) * 0.25);
+}
+
+#[test]
+fn fillet_circular_edges() {
+    let cylinder = cylinder();
+    let edges = edges(&cylinder, |p| p.z == 1.0);
+    let solid = fillet_edges(&cylinder, &edges, 0.25).unwrap();
+    assert_eq!(
+        solid.boundaries()[0].len(),
+        cylinder.boundaries()[0].len() + edges.len()
+    );
+    // Pappus's theorem on the section cut off by the fillet
+    let area = (1.0 - PI / 4.0) * 0.0625;
+    let centroid = 1.0 - 0.25 * (10.0 - 3.0 * PI) / (3.0 * (4.0 - PI));
+    assert_near!(volume(&solid), PI - 2.0 * PI * centroid * area);
+}
+
+#[test]
+fn fillet_failures() {
+    let cube = cube();
+    let edges = edges(&cube, |p| p.y == 0.0 && p.z == 2.0);
+    assert_eq!(
+        fillet_edges(&cube, &edges, 2.5).unwrap_err(),
+        Error::InvalidBlendSize
+    );
+    assert_eq!(
+        fillet_edges(&cube, &edges, -0.5).unwrap_err(),
+        Error::InvalidBlendSize
+    );
+
+    let cylinder = cylinder();
+    let edges = cylinder.edge_iter().take(1).collect::<Vec<_>>();
+    assert_eq!(
+        fillet_edges(&cylinder, &edges, 0.1).unwrap_err(),
+        Error::UnsupportedBlendVertex
+    );
+    assert_eq!(
+        fillet_edges(&cube, &edges, 0.1).unwrap_err(),
+        Error::NotBlendableEdge
+    );
+    let edges = self::edges(&cylinder, |p| p.z == 1.0);
+    let radius = FilletRadius::Variable {
+        front: 0.1,
+        back: 0.2,
+    };
+    assert_eq!(
+        fillet_edges(&cylinder, &edges, radius).unwrap_err(),
+        Error::NotBlendableEdge
+    );
+}
+
+#[test]
+fn chamfer_one_edge() {
+    let cube = cube();
+    let edges = edges(&cube, |p| p.y == 0.0 && p.z == 2.0);
+    let solid = chamfer_edges(&cube, &edges, 0.5).unwrap();
+    assert_eq!(solid.boundaries()[0].len(), 7);
+    assert_near!(volume(&solid), 8.0 - 0.125 * 2.0);
+
+    let solid = chamfer_edges(&cube, &edges, (0.5, 1.0)).unwrap();
+    assert_near!(volume(&solid), 8.0 - 0.25 * 2.0);
+
+    let solid = chamfer_edges(&cube, &edges, (0.5, Rad(PI / 3.0))).unwrap();
+    let distance = f64::sqrt(3.0) / 2.0;
+    assert_near!(volume(&solid), 8.0 - 0.25 * distance * 2.0);
+}
+
+#[test]
+fn chamfer_corners() {
+    let cube = cube();
+    let edges = edges(&cube, |p| p.z == 2.0);
+    let solid = chamfer_edges(&cube, &edges, 0.5).unwrap();
+    assert_eq!(solid.boundaries()[0].len(), 10);
+    // the horizontal section at the height 1.5 + h is the square shrunk by h
+    assert_near!(volume(&solid), 6.0 + 7.0 / 6.0);
+
+    let edges = self::edges(&cube, |_| true);
+    let solid = chamfer_edges(&cube, &edges, 0.5).unwrap();
+    assert_eq!(solid.boundaries()[0].len(), 26);
+    // the center, the slabs on the faces, the prisms on the edges and the corners
+    assert_near!(volume(&solid), 1.0 + 3.0 + 1.5 + 8.0 / 48.0);
+}
+
+#[test]
+fn chamfer_pyramid() {
+    // the square pyramid whose apex is shared by four edges
+    let points = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+    let v: Vec<Vertex> = points
+        .iter()
+        .map(|(x, y)| builder::vertex(Point3::new(*x, *y, 0.0)))
+        .collect();
+    let apex = builder::vertex(Point3::new(0.0, 0.0, 1.0));
+    let base: Vec<Edge> = (0..4)
+        .map(|i| builder::line(&v[i], &v[(i + 1) % 4]))
+        .collect();
+    let slant: Vec<Edge> = v.iter().map(|v| builder::line(v, &apex)).collect();
+    let mut shell: Shell = (0..4)
+        .map(|i| {
+            let wire = vec![
+                base[i].clone(),
+                slant[(i + 1) % 4].clone(),
+                slant[i].inverse(),
+            ];
+            builder::try_attach_plane(&[wire.into()]).unwrap()
+        })
+        .collect();
+    let bottom: Wire = base.iter().rev().map(Edge::inverse).collect();
+    shell.push(builder::try_attach_plane(&[bottom]).unwrap());
+    let pyramid = Solid::new(vec![shell]);
+
+    let solid = chamfer_edges(&pyramid, &slant, 0.1).unwrap();
+    assert_eq!(solid.boundaries()[0].len(), 10);
+    // the corner is cut by a square parallel to the base
+    let top = solid.boundaries()[0].iter().find(|face| {
+        let surface = face.oriented_surface();
+        surface.normal(0.0, 0.0).near(&Vector3::unit_z())
+    });
+    assert_eq!(top.unwrap().boundaries()[0].len(), 4);
+    let volume = volume(&solid);
+    assert!(volume > 1.2 && volume < 4.0 / 3.0);
+
+    // the fillets cannot be joined at the apex
+    assert_eq!(
+        fillet_edges(&pyramid, &slant, 0.1).unwrap_err(),
+        Error::UnsupportedBlendVertex
+    );
+}
+
+#[test]
+fn chamfer_circular_edges() {
+    let cylinder = cylinder();
+    let edges = edges(&cylinder, |p| p.z == 1.0);
+    let solid = chamfer_edges(&cylinder, &edges, 0.25).unwrap();
+    // Pappus's theorem on the triangle cut off by the chamfer
+    let centroid = 1.0 - 0.25 / 3.0;
+    assert_near!(volume(&solid), PI - 2.0 * PI * centroid * 0.03125);
+
+    let points = [(1.0, 0.0), (1.0, 1.0), (2.0, 1.0), (2.0, 0.0)];
+    let v: Vec<Vertex> = points
+        .iter()
+        .map(|(x, z)| builder::vertex(Point3::new(*x, 0.0, *z)))
+        .collect();
+    let wire: Wire = (0..v.len())
+        .map(|i| builder::line(&v[i], &v[(i + 1) % v.len()]))
+        .collect();
+    let face = builder::try_attach_plane(&[wire]).unwrap();
+    let ring: Solid = builder::rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let edges = self::edges(&ring, |p| p.z == 1.0 && p.x * p.x + p.y * p.y > 2.0);
+    let solid = chamfer_edges(&ring, &edges, 0.25).unwrap();
+    let centroid = 2.0 - 0.25 / 3.0;
+    assert_near!(volume(&solid), 3.0 * PI - 2.0 * PI * centroid * 0.03125);
+}
@@ -0,0 +1,170 @@
+use std::f64::consts::PI;
+use truck_modeling::features::*;
+use truck_modeling::*;
+
+/// the closed wire through the points on the plane z = `z`, counter-clockwise from +z.
+fn polygon(points: &[(f64, f64)], z: f64) -> Wire {
+    let v: Vec<Vertex> = points
+        .iter()
+        .map(|(x, y)| builder::vertex(Point3::new(*x, *y, z)))
+        .collect();
+    (0..v.len())
+        .map(|i| builder::line(&v[i], &v[(i + 1) % v.len()]))
+        .collect()
+}
+
+/// the plate [0, 4] x [0, 4] x [0, 1]
+fn plate() -> Solid {
+    let face = builder::try_attach_plane(&[polygon(
+        &[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)],
+        0.0,
+    )]);
+    builder::tsweep(&face.unwrap(), Vector3::unit_z())
+}
+
+fn circle(center: Point3, radius: f64) -> Wire {
+    let v = builder::vertex(center + radius * Vector3::unit_x());
+    builder::rsweep(&v, center, Vector3::unit_z(), Rad(7.0))
+}
+
+/// Cuts `profile`, a counter-clockwise wire on the top face of `plate`, as a hole of the top face,
+/// and attaches the prism swept from it by `height`, a boss if positive and a cavity if negative.
+fn attach(plate: &Solid, profile: &Wire, height: f64) -> Solid {
+    let mut shell = plate.boundaries()[0].clone();
+    let top = shell
+        .iter_mut()
+        .find(|face| face.vertex_iter().all(|v| v.point().z == 1.0))
+        .unwrap();
+    top.add_boundary(profile.inverse());
+
+    let profile_face = builder::try_attach_plane(std::slice::from_ref(profile)).unwrap();
+    let prism: Solid = builder::tsweep(&profile_face, height * Vector3::unit_z());
+    let profile_edges: Vec<EdgeID> = profile.edge_iter().map(|edge| edge.id()).collect();
+    let mut faces: Vec<Face> = prism
+        .face_iter()
+        .filter(|face| {
+            !face
+                .edge_iter()
+                .all(|edge| profile_edges.contains(&edge.id()))
+        })
+        .cloned()
+        .collect();
+    let mut attached = shell.clone();
+    attached.extend(faces.iter().cloned());
+    if attached.shell_condition() != ShellCondition::Closed {
+        faces.iter_mut().for_each(|face| {
+            face.invert();
+        });
+        attached = shell;
+        attached.extend(faces);
+    }
+    Solid::new(vec![attached])
+}
+
+#[test]
+fn blind_hole() {
+    let solid = attach(&plate(), &circle(Point3::new(2.0, 2.0, 1.0), 0.5), -0.6);
+    let features = recognize_features(&solid, TOLERANCE);
+    assert_eq!(features.len(), 1);
+    let Feature::Hole {
+        kind,
+        axis,
+        diameter,
+        depth,
+        ..
+    } = &features[0]
+    else {
+        panic!("{features:?}");
+    };
+    assert_eq!(*kind, HoleKind::Blind);
+    assert_near!(*diameter, 1.0);
+    assert_near!(*depth, 0.6);
+    assert_near!(axis.origin, Point3::new(2.0, 2.0, 1.0));
+    assert_near!(axis.direction, -Vector3::unit_z());
+}
+
+#[test]
+fn boss() {
+    let solid = attach(&plate(), &circle(Point3::new(2.0, 2.0, 1.0), 0.5), 0.8);
+    let features = recognize_features(&solid, TOLERANCE);
+    assert_eq!(features.len(), 1);
+    let Feature::Boss {
+        faces,
+        axis,
+        diameter,
+        height,
+    } = &features[0]
+    else {
+        panic!("{features:?}");
+    };
+    assert!(!faces.is_empty());
+    assert_near!(*diameter, 1.0);
+    assert_near!(*height, 0.8);
+    assert_near!(axis.origin, Point3::new(2.0, 2.0, 1.0));
+    assert_near!(axis.direction, Vector3::unit_z());
+}
+
+#[test]
+fn pocket() {
+    let profile = polygon(&[(1.0, 1.0), (3.0, 1.0), (3.0, 2.0), (1.0, 2.0)], 1.0);
+    let solid = attach(&plate(), &profile, -0.5);
+    let features = recognize_features(&solid, TOLERANCE);
+    assert_eq!(features.len(), 1);
+    let Feature::Pocket {
+        walls,
+        length,
+        width,
+        depth,
+        ..
+    } = &features[0]
+    else {
+        panic!("{features:?}");
+    };
+    assert_eq!(walls.len(), 4);
+    assert_near!(*length, 2.0);
+    assert_near!(*width, 1.0);
+    assert_near!(*depth, 0.5);
+}
+
+#[test]
+fn chamfer_and_fillet() {
+    // a square with a chamfered corner at (4, 0) and a filleted corner at (0, 4)
+    let v = [
+        (0.0, 0.0),
+        (3.0, 0.0),
+        (4.0, 1.0),
+        (4.0, 4.0),
+        (1.0, 4.0),
+        (0.0, 3.0),
+    ]
+    .map(|(x, y)| builder::vertex(Point3::new(x, y, 0.0)));
+    let arc = builder::rsweep(
+        &v[4],
+        Point3::new(1.0, 3.0, 0.0),
+        Vector3::unit_z(),
+        Rad(PI / 2.0),
+    );
+    let mut wire: Wire = (0..4).map(|i| builder::line(&v[i], &v[i + 1])).collect();
+    wire.extend(arc.iter().cloned());
+    wire.push_back(builder::line(arc.back_vertex().unwrap(), &v[0]));
+    let face = builder::try_attach_plane(&[wire]).unwrap();
+    let solid: Solid = builder::tsweep(&face, Vector3::unit_z());
+
+    let features = recognize_features(&solid, TOLERANCE);
+    assert_eq!(features.len(), 2, "{features:?}");
+    let Feature::Chamfer { width, .. } = &features[0] else {
+        panic!("{features:?}");
+    };
+    assert_near!(*width, f64::sqrt(2.0));
+    // The quarter arc is divided into two cylindrical faces of one fillet.
+    let Feature::Fillet { faces, radius } = &features[1] else {
+        panic!("{features:?}");
+    };
+    assert_eq!(faces.len(), 2);
+    assert_near!(radius.unwrap(), 1.0);
+}
+
+#[test]
+fn no_features() {
+    assert!(recognize_features(&plate(), TOLERANCE).is_empty());
+}
@@ -0,0 +1,167 @@
+use std::f64::consts::PI;
+use truck_modeling::errors::Error;
+use truck_modeling::offset::*;
+use truck_modeling::*;
+
+/// the cube [0, 2]^3
+fn cube() -> Solid {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, 2.0 * Vector3::unit_x());
+    let f = builder::tsweep(&e, 2.0 * Vector3::unit_y());
+    builder::tsweep(&f, 2.0 * Vector3::unit_z())
+}
+
+/// the cylinder of radius 1 and height 1
+fn cylinder() -> Solid {
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let circle = builder::rsweep(&v, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    let disk = builder::try_attach_plane(&[circle]).unwrap();
+    builder::tsweep(&disk, Vector3::unit_z())
+}
+
+/// the faces of `solid` whose vertices satisfy `pred`
+fn faces(solid: &Solid, pred: impl Fn(Point3) -> bool) -> Vec<Face> {
+    solid
+        .face_iter()
+        .filter(|face| face.vertex_iter().all(|v| pred(v.point())))
+        .cloned()
+        .collect()
+}
+
+fn volume(solid: &Solid) -> f64 {
+    solid.boundaries().iter().for_each(|shell| {
+        assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    });
+    solid.mass_properties(TOLERANCE).unwrap().volume
+}
+
+#[test]
+fn shell_cube() {
+    let cube = cube();
+    let top = faces(&cube, |p| p.z == 2.0);
+    let solid = shell_solid(&cube, 0.25, &top).unwrap();
+    assert_eq!(solid.boundaries().len(), 1);
+    assert_eq!(solid.boundaries()[0].len(), 11);
+    assert_near!(volume(&solid), 8.0 - 1.5 * 1.5 * 1.75);
+
+    let mut sides = faces(&cube, |p| p.x == 0.0);
+    sides.extend(faces(&cube, |p| p.x == 2.0));
+    assert_eq!(sides.len(), 2);
+    let solid = shell_solid(&cube, 0.25, &sides).unwrap();
+    assert_near!(volume(&solid), 8.0 - 2.0 * 1.5 * 1.5);
+
+    // a closed void inside the walls
+    let solid = shell_solid(&cube, 0.25, &[]).unwrap();
+    assert_eq!(solid.boundaries().len(), 2);
+    assert_near!(volume(&solid), 8.0 - 1.5 * 1.5 * 1.5);
+}
+
+#[test]
+fn shell_cylinder() {
+    let cylinder = cylinder();
+    let top = faces(&cylinder, |p| p.z == 1.0);
+    let solid = shell_solid(&cylinder, 0.1, &top).unwrap();
+    assert_eq!(solid.boundaries().len(), 1);
+    assert_near!(volume(&solid), PI - PI * 0.81 * 0.9);
+
+    let points = [(1.0, 0.0), (1.0, 1.0), (2.0, 1.0), (2.0, 0.0)];
+    let v: Vec<Vertex> = points
+        .iter()
+        .map(|(x, z)| builder::vertex(Point3::new(*x, 0.0, *z)))
+        .collect();
+    let wire: Wire = (0..v.len())
+        .map(|i| builder::line(&v[i], &v[(i + 1) % v.len()]))
+        .collect();
+    let face = builder::try_attach_plane(&[wire]).unwrap();
+    let ring: Solid = builder::rsweep(&face, Point3::origin(), Vector3::unit_z(), Rad(7.0));
+    // the faces of revolution are offset to the faces of revolution
+    let solid = shell_solid(&ring, 0.1, &[]).unwrap();
+    assert_eq!(solid.boundaries().len(), 2);
+    assert_near!(
+        volume(&solid),
+        3.0 * PI - PI * (1.9 * 1.9 - 1.1 * 1.1) * 0.8
+    );
+}
+
+#[test]
+fn shell_failures() {
+    let cube = cube();
+    let top = faces(&cube, |p| p.z == 2.0);
+    assert_eq!(
+        shell_solid(&cube, 0.0, &top).unwrap_err(),
+        Error::InvalidOffsetSize,
+    );
+    assert_eq!(
+        shell_solid(&cube, 1.5, &top).unwrap_err(),
+        Error::InvalidOffsetSize,
+    );
+    let adjacent = faces(&cube, |p| p.z == 2.0 || p.x == 2.0);
+    assert_eq!(
+        shell_solid(&cube, 0.25, &adjacent).unwrap_err(),
+        Error::UnsupportedOffsetShape,
+    );
+    let other = faces(&cylinder(), |p| p.z == 1.0);
+    assert_eq!(
+        shell_solid(&cube, 0.25, &other).unwrap_err(),
+        Error::NotOffsettableFace,
+    );
+    let cylinder = cylinder();
+    assert_eq!(
+        shell_solid(&cylinder, 1.5, &[]).unwrap_err(),
+        Error::InvalidOffsetSize,
+    );
+
+    // the offsets of B-spline surfaces are not approximated
+    let v = [
+        (0.0, 0.0, 0.0),
+        (1.0, 0.0, 0.0),
+        (0.0, 1.0, 0.0),
+        (1.0, 1.0, 0.5),
+    ]
+    .map(|(x, y, z)| builder::vertex(Point3::new(x, y, z)));
+    let line0 = builder::line(&v[0], &v[1]);
+    let line1 = builder::line(&v[2], &v[3]);
+    let twisted: Solid = builder::tsweep(&builder::homotopy(&line0, &line1), Vector3::unit_z());
+    assert_eq!(
+        shell_solid(&twisted, 0.1, &[]).unwrap_err(),
+        Error::NotOffsettableFace,
+    );
+}
+
+#[test]
+fn thicken_shells() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, 2.0 * Vector3::unit_x());
+    let plate: Shell = vec![builder::tsweep(&e, 2.0 * Vector3::unit_y())].into();
+    let solid = thicken(&plate, 0.5, false).unwrap();
+    assert_near!(volume(&solid), 2.0);
+
+    // the corner of two plates, thickened outside and to the both sides
+    let v =
+        [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0)].map(|(x, y)| builder::vertex(Point3::new(x, y, 0.0)));
+    let wire: Wire = vec![builder::line(&v[0], &v[1]), builder::line(&v[1], &v[2])].into();
+    let corner: Shell = builder::tsweep(&wire, Vector3::unit_z());
+    let solid = thicken(&corner, 0.25, false).unwrap();
+    assert_eq!(solid.boundaries()[0].len(), 2 + 2 + 6);
+    assert_near!(volume(&solid), 2.25 * 2.25 - 2.0 * 2.0);
+    let solid = thicken(&corner, 0.25, true).unwrap();
+    assert_near!(volume(&solid), 2.125 * 2.125 - 1.875 * 1.875);
+
+    // the half of the cylinder whose normals are toward the axis
+    let v = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let line = builder::tsweep(&v, Vector3::unit_z());
+    let half: Shell = builder::rsweep(&line, Point3::origin(), Vector3::unit_z(), Rad(PI));
+    let solid = thicken(&half, 0.1, false).unwrap();
+    assert_near!(volume(&solid), PI / 2.0 * (1.0 - 0.9 * 0.9));
+    let solid = thicken(&half, 0.1, true).unwrap();
+    assert_near!(volume(&solid), PI / 2.0 * (1.05 * 1.05 - 0.95 * 0.95));
+
+    assert_eq!(
+        thicken(&plate, 0.0, false).unwrap_err(),
+        Error::InvalidOffsetSize,
+    );
+    assert_eq!(
+        thicken(&half, 1.5, false).unwrap_err(),
+        Error::InvalidOffsetSize,
+    );
+}
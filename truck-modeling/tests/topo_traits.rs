@@ -826,3 +826,53 @@ fn shell_closed_sweep() {
         assert_near!(face_top_normal(face), expected_area * expected_normal);
     });
 }
+
+#[test]
+fn sweep_history() {
+    use truck_topology::history::*;
+    let shell = test_shell();
+    let solids = sweep(&shell);
+    let history: History<_, _, _> = shell.sweep_history(&solids);
+    let solid = solids[0].as_ref().unwrap();
+
+    let vertices: HashSet<_> = shell.vertex_iter().map(|v| v.id()).collect();
+    let edges: HashSet<_> = shell.edge_iter().map(|e| e.id()).collect();
+    assert!(solid
+        .vertex_iter()
+        .all(|v| vertices.contains(&v.id()) || history.vertex(v.id()).is_some()));
+    assert!(solid
+        .edge_iter()
+        .all(|e| edges.contains(&e.id()) || history.edge(e.id()).is_some()));
+    assert!(solid.face_iter().all(|f| shell[0].id() == f.id()
+        || shell[1].id() == f.id()
+        || history.face(f.id()).is_some()));
+
+    let shared: HashSet<_> = shell[0]
+        .edge_iter()
+        .filter(|e0| shell[1].edge_iter().any(|e1| e0.id() == e1.id()))
+        .map(|e| e.id())
+        .collect();
+    assert_eq!(shared.len(), 1);
+    shell.edge_iter().for_each(|edge| {
+        let origin = Origin::Edge(edge.id());
+        let copies: Vec<_> = history.edges_from(origin).collect();
+        assert_eq!(copies.len(), 1);
+        assert_eq!(copies[0].1, Evolution::Copied);
+        // the edge shared by two faces is not on the boundary and does not generate a face.
+        let faces = history.faces_from(origin).count();
+        assert_eq!(faces, !shared.contains(&edge.id()) as usize);
+    });
+    shell[0].vertex_iter().for_each(|vertex| {
+        let origin = Origin::Vertex(vertex.id());
+        let generated = history
+            .edges_from(origin)
+            .filter(|(_, evolution)| *evolution == Evolution::Generated)
+            .count();
+        assert_eq!(generated, 1);
+    });
+    shell.face_iter().for_each(|face| {
+        let copies: Vec<_> = history.faces_from(Origin::Face(face.id())).collect();
+        assert_eq!(copies.len(), 1);
+        assert_eq!(copies[0].1, Evolution::Copied);
+    });
+}
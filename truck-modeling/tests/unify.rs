@@ -0,0 +1,78 @@
+use truck_modeling::*;
+
+#[test]
+fn unify_same_domain() {
+    let v = [0.0, 1.0, 3.0].map(|x| builder::vertex(Point3::new(x, 0.0, 0.0)));
+    let wire: Wire = vec![builder::line(&v[0], &v[1]), builder::line(&v[1], &v[2])].into();
+    let shell: Shell = builder::tsweep(&wire, Vector3::unit_y());
+    let mut solid = builder::tsweep(&shell, Vector3::unit_z())
+        .pop()
+        .unwrap()
+        .unwrap();
+    assert_eq!(solid.face_iter().count(), 10);
+
+    solid.unify_same_domain(TOLERANCE);
+    let shell = &solid.boundaries()[0];
+    assert_eq!(shell.len(), 6);
+    assert_eq!(shell.edge_iter().count(), 24);
+    assert_eq!(shell.vertex_iter().count(), 24);
+    assert!(shell
+        .edge_iter()
+        .all(|edge| matches!(edge.curve(), Curve::Line(_))));
+    assert!(solid.is_geometric_consistent());
+    assert!(solid.check(TOLERANCE).is_empty());
+}
+
+#[test]
+fn concat_intersection_curve() {
+    let plane0 = Plane::new(
+        Point3::origin(),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    );
+    let plane1 = Plane::new(
+        Point3::origin(),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+    );
+    let leader = BSplineCurve::new(
+        KnotVec::from(vec![-1.0, -1.0, 0.0, 0.0]),
+        vec![Point3::new(-1.0, 0.0, 0.0), Point3::origin()],
+    );
+    let curve0 = Curve::IntersectionCurve(IntersectionCurve::new_unchecked(
+        Box::new(Surface::Plane(plane0)),
+        Box::new(Surface::Plane(plane1)),
+        Leader::BSpline(leader),
+        TOLERANCE,
+    ));
+    let line = Curve::Line(Line(Point3::origin(), Point3::new(1.0, 0.0, 0.0)));
+    let err = curve0.try_concat(&line).unwrap_err();
+    assert_eq!(err, ConcatError::UnsupportedCurve);
+    let nurbs = Curve::NurbsCurve(NurbsCurve::new(line.lift_up()));
+    let err = curve0.try_concat(&nurbs).unwrap_err();
+    assert_eq!(err, ConcatError::UnsupportedCurve);
+}
+
+#[test]
+fn same_domain_revoluted_surfaces() {
+    let v0 = builder::vertex(Point3::new(1.0, 0.0, 0.0));
+    let v1 = builder::vertex(Point3::new(1.0, 0.0, 1.0));
+    let edge = builder::line(&v0, &v1);
+    let revolute = |angle: f64| {
+        let edge = builder::rotated(&edge, Point3::origin(), Vector3::unit_z(), Rad(angle));
+        let shell = builder::rsweep(&edge, Point3::origin(), Vector3::unit_z(), Rad(1.0));
+        shell[0].surface()
+    };
+    let surface = revolute(0.0);
+    assert!(matches!(surface, Surface::RevolutedCurve(_)));
+    // the profile rotated around the axis
+    assert!(surface.same_domain(&revolute(2.0), TOLERANCE));
+    assert!(!surface.same_domain(&surface.inverse(), TOLERANCE));
+    // the other axis or the other radius
+    let moved = builder::translated(&edge, Vector3::new(0.5, 0.0, 0.0));
+    let origin = Point3::new(0.5, 0.0, 0.0);
+    let shell = builder::rsweep(&moved, origin, Vector3::unit_z(), Rad(1.0));
+    assert!(!surface.same_domain(&shell[0].surface(), TOLERANCE));
+    let shell = builder::rsweep(&moved, Point3::origin(), Vector3::unit_z(), Rad(1.0));
+    assert!(!surface.same_domain(&shell[0].surface(), TOLERANCE));
+}
@@ -13,7 +13,7 @@ impl From<&Vertex> for Vertex {
     fn from(v: &Vertex) -> Vertex { *v }
 }
 
-impl<'a, T: AsVertexSlice> AsVertexSlice for &'a T {
+impl<T: AsVertexSlice> AsVertexSlice for &T {
     type V = T::V;
 }
 
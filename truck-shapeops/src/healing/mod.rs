@@ -24,6 +24,7 @@ use split_closed_faces::split_closed_faces;
 /// The topology of the shapes handled by truck has the following rules
 /// - The endpoints of the edges must be different.
 /// - The boundaries of the faces must be a simple wire.
+///
 /// Shapes created in other CAD systems do not necessarily follow these rules.
 /// When such shapes are handled by truck, this method is applied at the stage
 /// of `CompressedShell` and `CompressedSolid`, which are intermediate forms.
@@ -53,6 +54,12 @@ where
         }
         split_closed_edges(self);
         split_closed_faces(self, tol, sp);
+        if !self.vertex_tolerances.is_empty() {
+            self.vertex_tolerances.resize(self.vertices.len(), None);
+        }
+        if !self.vertex_stable_ids.is_empty() {
+            self.vertex_stable_ids.resize(self.vertices.len(), None);
+        }
     }
 }
 
@@ -106,6 +113,12 @@ where
         }
         split_closed_edges(self);
         split_closed_faces(self, tol, sp);
+        if !self.vertex_tolerances.is_empty() {
+            self.vertex_tolerances.resize(self.vertices.len(), None);
+        }
+        if !self.vertex_stable_ids.is_empty() {
+            self.vertex_stable_ids.resize(self.vertices.len(), None);
+        }
     }
 }
 
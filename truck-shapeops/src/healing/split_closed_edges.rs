@@ -6,6 +6,8 @@ fn cut_edge<P, C>(
     Edge {
         vertices: (_, v1),
         curve,
+        tolerance,
+        ..
     }: &mut Edge<C>,
 ) -> Edge<C>
 where
@@ -22,6 +24,8 @@ where
     Edge {
         vertices: (v1, v2),
         curve: curve0,
+        tolerance: *tolerance,
+        stable_id: None,
     }
 }
 
@@ -104,7 +104,7 @@ fn non_simple_wire_divisor<C>(
         .find_map(find_loop())
         .unwrap_or((j0, boundary.len()));
 
-    let (k0, k1) = ((j0 + 1) / 2, (i1 + j1 + 1) / 2);
+    let (k0, k1) = (j0.div_ceil(2), (i1 + j1).div_ceil(2));
     let f = closure_take_front(edges);
     let pre_divisor = (f(boundary[k0]), f(boundary[k1]));
     nearest_correction(
@@ -130,6 +130,7 @@ fn non_closed_wires_in_param_divisor<C, S>(
         edges,
         faces,
         ref vertices,
+        ..
     }: &mut Shell<Point3, C, S>,
     poly_edges: &[PolylineCurve<Point3>],
     sp: impl SP<S>,
@@ -264,6 +265,7 @@ fn take_vertices_to_intersections<C, S>(
         vertices,
         edges,
         faces,
+        ..
     }: &mut Shell<Point3, C, S>,
     poly_edges: &mut Vec<PolylineCurve<Point3>>,
     sp: impl SP<S>,
@@ -436,6 +438,8 @@ where
     let closure = |(i, (t, _))| Edge {
         vertices: (vfirst + i, vfirst + i + 1),
         curve: edge.curve.cut(t),
+        tolerance: edge.tolerance,
+        stable_id: None,
     };
     let mut new_edges: Vec<_> = intersections_iter.map(closure).collect();
     let to_poly = closure_to_poly(tol);
@@ -686,6 +690,8 @@ where
         Some(Edge {
             vertices: (v0, v1),
             curve: C::try_from(pcurve).ok()?,
+            tolerance: None,
+            stable_id: None,
         })
     };
     vertices_on_divisor.chunks(2).map(make_edge).collect()
@@ -772,6 +778,7 @@ fn divide_face<S: ParametricSurface3D>(
         ref mut boundaries,
         ref surface,
         ref orientation,
+        ..
     }: &mut Face<S>,
     new_boundaries: Vec<Wire>,
     poly_edges: &[PolylineCurve<Point3>],
@@ -783,6 +790,7 @@ fn divide_face<S: ParametricSurface3D>(
         boundaries,
         surface: surface.clone(),
         orientation: *orientation,
+        stable_id: None,
     };
     Some(face_boundaries.map(create_face).collect())
 }
@@ -6,10 +6,7 @@ use truck_topology::Shell;
 fn test_split_closed_edges() {
     let vertices = vec![Point2::new(1.0, 0.0)];
     let curve = TrimmedCurve::new(UnitCircle::<Point2>::new(), (0.0, 2.0 * PI));
-    let edges = vec![CompressedEdge {
-        vertices: (0, 0),
-        curve,
-    }];
+    let edges = vec![CompressedEdge::new((0, 0), curve)];
     let faces = vec![
         CompressedFace {
             surface: (),
@@ -18,6 +15,7 @@ fn test_split_closed_edges() {
                 index: 0,
                 orientation: true,
             }]],
+            stable_id: None,
         },
         CompressedFace {
             surface: (),
@@ -26,13 +24,10 @@ fn test_split_closed_edges() {
                 index: 0,
                 orientation: false,
             }]],
+            stable_id: None,
         },
     ];
-    let mut shell = CompressedShell {
-        vertices,
-        edges,
-        faces,
-    };
+    let mut shell = CompressedShell::new(vertices, edges, faces);
     assert!(Shell::extract(shell.clone()).is_err());
 
     split_closed_edges(&mut shell);
@@ -42,6 +37,7 @@ fn test_split_closed_edges() {
         vertices,
         edges,
         faces,
+        ..
     } = &shell;
     assert_eq!(vertices.len(), 2);
     assert_near!(vertices[0], Point2::new(1.0, 0.0));
@@ -67,6 +63,7 @@ fn test_split_closed_edges() {
                         orientation: true,
                     }
                 ]],
+                stable_id: None,
             },
             CompressedFace {
                 surface: (),
@@ -81,6 +78,7 @@ fn test_split_closed_edges() {
                         orientation: false,
                     }
                 ]],
+                stable_id: None,
             },
         ]
     );
@@ -117,38 +115,35 @@ fn test_split_closed_face_simple_cylinder_case() {
 
     let translate = Matrix4::from_translation(Vector3::unit_z());
     let edges = vec![
-        CompressedEdge {
-            vertices: (0, 1),
-            curve: Curve::Arc(TrimmedCurve::new(
+        CompressedEdge::new(
+            (0, 1),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()),
                 (0.0, PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (1, 0),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new(
+            (1, 0),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()),
                 (PI, 2.0 * PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (0, 2),
-            curve: Curve::Line(Line(vertices[0], vertices[2])),
-        },
-        CompressedEdge {
-            vertices: (2, 3),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new((0, 2), Curve::Line(Line(vertices[0], vertices[2]))),
+        CompressedEdge::new(
+            (2, 3),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()).transformed(translate),
                 (0.0, PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (3, 2),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new(
+            (3, 2),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()).transformed(translate),
                 (PI, 2.0 * PI),
             )),
-        },
+        ),
     ];
     let surface = RevolutedCurve::by_revolution(
         Line(vertices[2], vertices[0]),
@@ -184,12 +179,9 @@ fn test_split_closed_face_simple_cylinder_case() {
             },
         ]],
         orientation: true,
+        stable_id: None,
     }];
-    let mut shell = CompressedShell {
-        vertices,
-        edges,
-        faces,
-    };
+    let mut shell = CompressedShell::new(vertices, edges, faces);
 
     assert!(Shell::extract(shell.clone()).is_err());
 
@@ -200,6 +192,7 @@ fn test_split_closed_face_simple_cylinder_case() {
         ref vertices,
         ref edges,
         ref mut faces,
+        ..
     } = shell;
     assert_eq!(vertices.len(), 4);
     assert_eq!(edges.len(), 6);
@@ -270,6 +263,7 @@ fn test_split_closed_face_simple_cylinder_case() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
             Face {
                 boundaries: vec![vec![
@@ -292,6 +286,7 @@ fn test_split_closed_face_simple_cylinder_case() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
         ]
     );
@@ -352,58 +347,55 @@ fn test_split_closed_face_cylinder_with_hole() {
     let translate = Matrix4::from_translation(Vector3::unit_z());
     let transform = Matrix3::from_translation(Vector2::new(0.5, PI)) * Matrix3::from_scale(0.25);
     let edges = vec![
-        CompressedEdge {
-            vertices: (0, 1),
-            curve: Curve::Arc(TrimmedCurve::new(
+        CompressedEdge::new(
+            (0, 1),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()),
                 (0.0, PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (1, 0),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new(
+            (1, 0),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()),
                 (PI, 2.0 * PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (0, 2),
-            curve: Curve::Line(Line(vertices[0], vertices[2])),
-        },
-        CompressedEdge {
-            vertices: (2, 3),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new((0, 2), Curve::Line(Line(vertices[0], vertices[2]))),
+        CompressedEdge::new(
+            (2, 3),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()).transformed(translate),
                 (0.0, PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (3, 2),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new(
+            (3, 2),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()).transformed(translate),
                 (PI, 2.0 * PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (4, 5),
-            curve: Curve::PCurve(PCurve::new(
+        ),
+        CompressedEdge::new(
+            (4, 5),
+            Curve::PCurve(PCurve::new(
                 ParameterCurve::Arc(TrimmedCurve::new(
                     Processor::new(UnitCircle::new()).transformed(transform),
                     (0.0, PI),
                 )),
                 surface,
             )),
-        },
-        CompressedEdge {
-            vertices: (5, 4),
-            curve: Curve::PCurve(PCurve::new(
+        ),
+        CompressedEdge::new(
+            (5, 4),
+            Curve::PCurve(PCurve::new(
                 ParameterCurve::Arc(TrimmedCurve::new(
                     Processor::new(UnitCircle::new()).transformed(transform),
                     (PI, 2.0 * PI),
                 )),
                 surface,
             )),
-        },
+        ),
     ];
     let faces = vec![Face {
         surface,
@@ -446,12 +438,9 @@ fn test_split_closed_face_cylinder_with_hole() {
             ],
         ],
         orientation: true,
+        stable_id: None,
     }];
-    let mut shell = CompressedShell {
-        vertices,
-        edges,
-        faces,
-    };
+    let mut shell = CompressedShell::new(vertices, edges, faces);
 
     assert!(Shell::extract(shell.clone()).is_err());
     split_closed_faces(&mut shell, 0.01, sp);
@@ -461,6 +450,7 @@ fn test_split_closed_face_cylinder_with_hole() {
         ref vertices,
         ref edges,
         ref mut faces,
+        ..
     } = shell;
     assert_eq!(vertices.len(), 6);
     assert_eq!(edges.len(), 9);
@@ -546,6 +536,7 @@ fn test_split_closed_face_cylinder_with_hole() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
             Face {
                 boundaries: vec![vec![
@@ -576,6 +567,7 @@ fn test_split_closed_face_cylinder_with_hole() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
         ]
     );
@@ -636,58 +628,55 @@ fn test_split_closed_face_cylinder_with_rotated_hole() {
     let translate = Matrix4::from_translation(Vector3::unit_z());
     let transform = Matrix3::from_translation(Vector2::new(0.5, PI)) * Matrix3::from_scale(0.25);
     let edges = vec![
-        CompressedEdge {
-            vertices: (0, 1),
-            curve: Curve::Arc(TrimmedCurve::new(
+        CompressedEdge::new(
+            (0, 1),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()),
                 (0.0, PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (1, 0),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new(
+            (1, 0),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()),
                 (PI, 2.0 * PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (0, 2),
-            curve: Curve::Line(Line(vertices[0], vertices[2])),
-        },
-        CompressedEdge {
-            vertices: (2, 3),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new((0, 2), Curve::Line(Line(vertices[0], vertices[2]))),
+        CompressedEdge::new(
+            (2, 3),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()).transformed(translate),
                 (0.0, PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (3, 2),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new(
+            (3, 2),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()).transformed(translate),
                 (PI, 2.0 * PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (4, 5),
-            curve: Curve::PCurve(PCurve::new(
+        ),
+        CompressedEdge::new(
+            (4, 5),
+            Curve::PCurve(PCurve::new(
                 ParameterCurve::Arc(TrimmedCurve::new(
                     Processor::new(UnitCircle::new()).transformed(transform),
                     (0.5 * PI, 1.5 * PI),
                 )),
                 surface,
             )),
-        },
-        CompressedEdge {
-            vertices: (5, 4),
-            curve: Curve::PCurve(PCurve::new(
+        ),
+        CompressedEdge::new(
+            (5, 4),
+            Curve::PCurve(PCurve::new(
                 ParameterCurve::Arc(TrimmedCurve::new(
                     Processor::new(UnitCircle::new()).transformed(transform),
                     (1.5 * PI, 2.5 * PI),
                 )),
                 surface,
             )),
-        },
+        ),
     ];
     let faces = vec![Face {
         surface,
@@ -730,12 +719,9 @@ fn test_split_closed_face_cylinder_with_rotated_hole() {
             ],
         ],
         orientation: true,
+        stable_id: None,
     }];
-    let mut shell = CompressedShell {
-        vertices,
-        edges,
-        faces,
-    };
+    let mut shell = CompressedShell::new(vertices, edges, faces);
 
     assert!(Shell::extract(shell.clone()).is_err());
     split_closed_faces(&mut shell, 0.01, sp);
@@ -745,6 +731,7 @@ fn test_split_closed_face_cylinder_with_rotated_hole() {
         ref vertices,
         ref edges,
         ref mut faces,
+        ..
     } = shell;
     assert_eq!(vertices.len(), 8);
     assert_eq!(edges.len(), 11);
@@ -838,6 +825,7 @@ fn test_split_closed_face_cylinder_with_rotated_hole() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
             Face {
                 boundaries: vec![vec![
@@ -872,6 +860,7 @@ fn test_split_closed_face_cylinder_with_rotated_hole() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
         ]
     );
@@ -906,14 +895,8 @@ fn too_simple_cylinder() {
         (0.0, 2.0 * PI),
     );
     let edges = vec![
-        CompressedEdge {
-            vertices: (0, 0),
-            curve: Curve::Arc(circle0),
-        },
-        CompressedEdge {
-            vertices: (1, 1),
-            curve: Curve::Arc(circle1),
-        },
+        CompressedEdge::new((0, 0), Curve::Arc(circle0)),
+        CompressedEdge::new((1, 1), Curve::Arc(circle1)),
     ];
 
     let surface = RevolutedCurve::by_revolution(
@@ -934,13 +917,10 @@ fn too_simple_cylinder() {
         ],
         surface,
         orientation: true,
+        stable_id: None,
     }];
 
-    let mut shell = CompressedShell {
-        vertices,
-        edges,
-        faces,
-    };
+    let mut shell = CompressedShell::new(vertices, edges, faces);
 
     assert!(Shell::extract(shell.clone()).is_err());
     split_closed_edges(&mut shell);
@@ -951,6 +931,7 @@ fn too_simple_cylinder() {
         ref vertices,
         ref edges,
         ref mut faces,
+        ..
     } = shell;
 
     assert_eq!(vertices.len(), 4);
@@ -1025,6 +1006,7 @@ fn too_simple_cylinder() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
             Face {
                 boundaries: vec![vec![
@@ -1047,6 +1029,7 @@ fn too_simple_cylinder() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
         ]
     );
@@ -1102,24 +1085,24 @@ fn double_closed_boundary_cylinder() {
         Vector3::unit_z(),
     );
     let edges = vec![
-        CompressedEdge {
-            vertices: (0, 0),
-            curve: Curve::Arc(TrimmedCurve::new(
+        CompressedEdge::new(
+            (0, 0),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new()),
                 (0.0, 2.0 * PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (1, 1),
-            curve: Curve::Arc(TrimmedCurve::new(
+        ),
+        CompressedEdge::new(
+            (1, 1),
+            Curve::Arc(TrimmedCurve::new(
                 Processor::new(UnitCircle::new())
                     .transformed(Matrix4::from_translation(Vector3::unit_z())),
                 (0.0, 2.0 * PI),
             )),
-        },
-        CompressedEdge {
-            vertices: (2, 2),
-            curve: Curve::PCurve(PCurve::new(
+        ),
+        CompressedEdge::new(
+            (2, 2),
+            Curve::PCurve(PCurve::new(
                 ParameterCurve::Arc(TrimmedCurve::new(
                     Processor::new(UnitCircle::new()).transformed(
                         Matrix3::from_translation(Vector2::new(0.5, 0.0))
@@ -1129,10 +1112,10 @@ fn double_closed_boundary_cylinder() {
                 )),
                 surface,
             )),
-        },
-        CompressedEdge {
-            vertices: (3, 3),
-            curve: Curve::PCurve(PCurve::new(
+        ),
+        CompressedEdge::new(
+            (3, 3),
+            Curve::PCurve(PCurve::new(
                 ParameterCurve::Arc(TrimmedCurve::new(
                     Processor::new(UnitCircle::new()).transformed(
                         Matrix3::from_translation(Vector2::new(0.5, PI))
@@ -1142,7 +1125,7 @@ fn double_closed_boundary_cylinder() {
                 )),
                 surface,
             )),
-        },
+        ),
     ];
     let faces = vec![CompressedFace {
         boundaries: vec![
@@ -1165,12 +1148,9 @@ fn double_closed_boundary_cylinder() {
         ],
         surface,
         orientation: true,
+        stable_id: None,
     }];
-    let mut shell = CompressedShell {
-        vertices,
-        edges,
-        faces,
-    };
+    let mut shell = CompressedShell::new(vertices, edges, faces);
 
     assert!(Shell::extract(shell.clone()).is_err());
     split_closed_edges(&mut shell);
@@ -1181,6 +1161,7 @@ fn double_closed_boundary_cylinder() {
         ref vertices,
         ref edges,
         ref mut faces,
+        ..
     } = shell;
 
     assert_eq!(vertices.len(), 8);
@@ -1282,6 +1263,7 @@ fn double_closed_boundary_cylinder() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
             CompressedFace {
                 boundaries: vec![vec![
@@ -1320,6 +1302,7 @@ fn double_closed_boundary_cylinder() {
                 ]],
                 surface,
                 orientation: true,
+                stable_id: None,
             },
         ]
     );
@@ -1379,27 +1362,27 @@ fn many_closed_boundary_cylinder() {
     );
     let edges = {
         let mut edges = vec![
-            CompressedEdge {
-                vertices: (0, 0),
-                curve: Curve::Arc(TrimmedCurve::new(
+            CompressedEdge::new(
+                (0, 0),
+                Curve::Arc(TrimmedCurve::new(
                     Processor::new(UnitCircle::new()),
                     (0.0, 2.0 * PI),
                 )),
-            },
-            CompressedEdge {
-                vertices: (1, 1),
-                curve: Curve::Arc(TrimmedCurve::new(
+            ),
+            CompressedEdge::new(
+                (1, 1),
+                Curve::Arc(TrimmedCurve::new(
                     Processor::new(UnitCircle::new())
                         .transformed(Matrix4::from_translation(Vector3::unit_z())),
                     (0.0, 2.0 * PI),
                 )),
-            },
+            ),
         ];
         edges.extend((0..NUM_OF_CIRCLES).map(|i| {
             let t = 2.0 * PI * i as f64 / NUM_OF_CIRCLES as f64;
-            CompressedEdge {
-                vertices: (2 + i, 2 + i),
-                curve: Curve::PCurve(PCurve::new(
+            CompressedEdge::new(
+                (2 + i, 2 + i),
+                Curve::PCurve(PCurve::new(
                     ParameterCurve::Arc(TrimmedCurve::new(
                         Processor::new(UnitCircle::new()).transformed(
                             Matrix3::from_translation(Vector2::new(0.5, t))
@@ -1409,7 +1392,7 @@ fn many_closed_boundary_cylinder() {
                     )),
                     surface,
                 )),
-            }
+            )
         }));
         edges
     };
@@ -1427,12 +1410,9 @@ fn many_closed_boundary_cylinder() {
         boundaries,
         surface,
         orientation: true,
+        stable_id: None,
     }];
-    let mut shell = CompressedShell {
-        vertices,
-        edges,
-        faces,
-    };
+    let mut shell = CompressedShell::new(vertices, edges, faces);
 
     assert!(Shell::extract(shell.clone()).is_err());
     split_closed_edges(&mut shell);
@@ -1443,6 +1423,7 @@ fn many_closed_boundary_cylinder() {
         ref vertices,
         ref edges,
         ref mut faces,
+        ..
     } = shell;
 
     assert_eq!(vertices.len(), (2 + NUM_OF_CIRCLES) * 2);
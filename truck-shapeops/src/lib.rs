@@ -16,5 +16,9 @@
 mod healing;
 pub use healing::{RobustSplitClosedEdgesAndFaces, SplitClosedEdgesAndFaces};
 mod transversal;
-pub use transversal::{and, or, ShapeOpsCurve, ShapeOpsSurface};
+pub use transversal::{
+    and, and_with_attributes, and_with_history, intersect_surfaces, or, or_with_attributes,
+    or_with_history, ShapeOpsCurve, ShapeOpsSurface,
+};
+#[cfg(test)]
 mod alternative;
@@ -120,8 +120,9 @@ where
                 res.push(face.clone(), ShapesOpStatus::Unknown);
             } else {
                 let vec = divide_one_face(face, loops, tol)?;
-                vec.into_iter()
-                    .for_each(|(face, status)| res.push(face, status));
+                vec.into_iter().for_each(|(new_face, status)| {
+                    res.push_with_origin(new_face, status, face.id())
+                });
             }
             Some(())
         })?;
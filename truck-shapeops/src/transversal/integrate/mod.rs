@@ -1,7 +1,18 @@
 use super::*;
+use rustc_hash::FxHashMap as HashMap;
 use truck_geometry::prelude::IntersectionCurve;
 use truck_meshalgo::prelude::*;
-use truck_topology::*;
+use truck_topology::{
+    attributes::AttributeStore,
+    history::{Evolution, History, Origin},
+    *,
+};
+
+type FaceOrigins<S> = HashMap<FaceID<S>, FaceID<S>>;
+type ShellsWithOrigins<C, S> = ([Shell<Point3, C, S>; 2], FaceOrigins<S>);
+type SolidWithOrigins<C, S> = (Solid<Point3, C, S>, FaceOrigins<S>);
+type SolidWithAttributes<C, S, A> = (Solid<Point3, C, S>, AttributeStore<Point3, C, S, A>);
+type SolidWithHistory<C, S> = (Solid<Point3, C, S>, History<Point3, C, S>);
 
 /// Only solids consisting of faces whose surface is implemented this trait can be used for set operations.
 pub trait ShapeOpsSurface:
@@ -51,8 +62,16 @@ fn process_one_pair_of_shells<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
     shell0: &Shell<Point3, C, S>,
     shell1: &Shell<Point3, C, S>,
     tol: f64,
-) -> Option<[Shell<Point3, C, S>; 2]> {
+) -> Option<ShellsWithOrigins<C, S>> {
     nonpositive_tolerance!(tol);
+    let tol = [shell0, shell1]
+        .into_iter()
+        .flat_map(|shell| {
+            let vtols = shell.vertex_iter().map(|v| v.tolerance());
+            vtols.chain(shell.edge_iter().map(|e| e.tolerance()))
+        })
+        .flatten()
+        .fold(tol, f64::max);
     let poly_shell0 = shell0.triangulation(tol);
     let poly_shell1 = shell1.triangulation(tol);
     let loops_store::LoopsStoreQuadruple {
@@ -96,53 +115,250 @@ fn process_one_pair_of_shells<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
     })?;
     and0.append(&mut and1);
     or0.append(&mut or1);
-    Some([and0, or0])
+    let mut origins = cls0.origins().clone();
+    origins.extend(cls1.origins());
+    Some(([and0, or0], origins))
 }
 
-/// AND operation between two solids.
-pub fn and<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
+/// Executes the boolean operation and returns the map from the resulting faces to the original faces.
+/// `index` is `0` for AND and `1` for OR.
+fn boolean_with_origins<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
     solid0: &Solid<Point3, C, S>,
     solid1: &Solid<Point3, C, S>,
     tol: f64,
-) -> Option<Solid<Point3, C, S>> {
+    index: usize,
+) -> Option<SolidWithOrigins<C, S>> {
     let mut iter0 = solid0.boundaries().iter();
     let mut iter1 = solid1.boundaries().iter();
     let shell0 = iter0.next().unwrap();
     let shell1 = iter1.next().unwrap();
-    let [mut and_shell, _] = process_one_pair_of_shells(shell0, shell1, tol)?;
-    for shell in iter0 {
-        let [res, _] = process_one_pair_of_shells(&and_shell, shell, tol)?;
-        and_shell = res;
+    let (shells, mut origins) = process_one_pair_of_shells(shell0, shell1, tol)?;
+    let mut res_shell = shells.into_iter().nth(index).unwrap();
+    for shell in iter0.chain(iter1) {
+        let (shells, step_origins) = process_one_pair_of_shells(&res_shell, shell, tol)?;
+        res_shell = shells.into_iter().nth(index).unwrap();
+        origins = step_origins
+            .into_iter()
+            .map(|(id, step_origin)| (id, *origins.get(&step_origin).unwrap_or(&step_origin)))
+            .collect();
     }
-    for shell in iter1 {
-        let [res, _] = process_one_pair_of_shells(&and_shell, shell, tol)?;
-        and_shell = res;
-    }
-    let boundaries = and_shell.connected_components();
-    Some(Solid::new(boundaries))
+    let boundaries = res_shell.connected_components();
+    Some((Solid::new(boundaries), origins))
+}
+
+/// Creates the attributes of the result of the boolean operation.
+fn result_attributes<C, S, A: Clone>(
+    solid0: &Solid<Point3, C, S>,
+    solid1: &Solid<Point3, C, S>,
+    solid: &Solid<Point3, C, S>,
+    origins: &FaceOrigins<S>,
+    attributes: &AttributeStore<Point3, C, S, A>,
+) -> AttributeStore<Point3, C, S, A> {
+    let input_faces: HashMap<_, _> = solid0
+        .face_iter()
+        .chain(solid1.face_iter())
+        .map(|face| (face.id(), face))
+        .collect();
+    let mut res = AttributeStore::new();
+    solid.face_iter().for_each(|face| {
+        let origin = origins.get(&face.id()).and_then(|id| input_faces.get(id));
+        if let Some(a) = attributes.face(origin.copied().unwrap_or(face)) {
+            res.set_face(face, a.clone());
+        }
+    });
+    solid.edge_iter().for_each(|edge| {
+        if let Some(a) = attributes.edge(&edge) {
+            res.set_edge(&edge, a.clone());
+        }
+    });
+    solid.vertex_iter().for_each(|vertex| {
+        if let Some(a) = attributes.vertex(&vertex) {
+            res.set_vertex(&vertex, a.clone());
+        }
+    });
+    res
+}
+
+/// Creates the history of the result of the boolean operation.
+fn result_history<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
+    solid0: &Solid<Point3, C, S>,
+    solid1: &Solid<Point3, C, S>,
+    solid: &Solid<Point3, C, S>,
+    origins: &FaceOrigins<S>,
+) -> History<Point3, C, S> {
+    let input_faces: HashMap<_, _> = solid0
+        .face_iter()
+        .chain(solid1.face_iter())
+        .map(|face| (face.id(), face))
+        .collect();
+    let input_edges: HashMap<_, _> = input_faces
+        .values()
+        .flat_map(|face| face.edge_iter())
+        .map(|edge| (edge.id(), edge))
+        .collect();
+    let mut history = History::new();
+
+    // faces and the origins of faces around each edge
+    let mut edge_faces = HashMap::<EdgeID<C>, (Edge<Point3, C>, Vec<FaceID<S>>)>::default();
+    solid.face_iter().for_each(|face| {
+        let origin = *origins.get(&face.id()).unwrap_or(&face.id());
+        if origin != face.id() {
+            history.set_face(face.id(), Evolution::Modified, [Origin::Face(origin)]);
+        }
+        face.edge_iter()
+            .filter(|edge| !input_edges.contains_key(&edge.id()))
+            .for_each(|edge| {
+                let entry = edge_faces
+                    .entry(edge.id())
+                    .or_insert_with(|| (edge.clone(), Vec::new()));
+                if !entry.1.contains(&origin) {
+                    entry.1.push(origin);
+                }
+            });
+    });
+
+    // pieces of original edges and intersection edges
+    edge_faces.into_values().for_each(|(edge, faces)| {
+        let curve = edge.curve();
+        let (t0, t1) = curve.range_tuple();
+        let pt = curve.subs((t0 + t1) / 2.0);
+        let original = input_faces[&faces[0]]
+            .edge_iter()
+            .filter(|e| {
+                faces[1..]
+                    .iter()
+                    .all(|f| input_faces[f].edge_iter().any(|e1| e1.id() == e.id()))
+            })
+            .find(|e| e.curve().search_parameter(pt, None, 100).is_some());
+        match original {
+            Some(original) => history.set_edge(
+                edge.id(),
+                Evolution::Modified,
+                [Origin::Edge(original.id())],
+            ),
+            None => history.set_edge(
+                edge.id(),
+                Evolution::Generated,
+                faces.into_iter().map(Origin::Face),
+            ),
+        }
+    });
+
+    // vertices created by the intersection
+    let mut vertex_origins = HashMap::<VertexID<Point3>, Vec<Origin<Point3, C, S>>>::default();
+    let input_vertices: HashMap<_, _> = input_edges
+        .values()
+        .flat_map(|edge| [edge.front().id(), edge.back().id()])
+        .map(|id| (id, ()))
+        .collect();
+    solid.edge_iter().for_each(|edge| {
+        let Some(entry) = history.edge(edge.id()) else {
+            return;
+        };
+        [edge.front(), edge.back()]
+            .into_iter()
+            .filter(|v| !input_vertices.contains_key(&v.id()))
+            .for_each(|v| {
+                let vec = vertex_origins.entry(v.id()).or_default();
+                entry.origins.iter().for_each(|origin| {
+                    if !vec.contains(origin) {
+                        vec.push(*origin);
+                    }
+                });
+            });
+    });
+    vertex_origins.into_iter().for_each(|(id, origins)| {
+        history.set_vertex(id, Evolution::Generated, origins);
+    });
+    history
+}
+
+/// AND operation between two solids.
+///
+/// If some vertices or edges of the solids have their own tolerances,
+/// the largest of them and `tol` is used as the tolerance of the operation.
+pub fn and<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
+    solid0: &Solid<Point3, C, S>,
+    solid1: &Solid<Point3, C, S>,
+    tol: f64,
+) -> Option<Solid<Point3, C, S>> {
+    boolean_with_origins(solid0, solid1, tol, 0).map(|x| x.0)
 }
 
 /// OR operation between two solids.
+///
+/// The tolerances of the vertices and edges are taken into account in the same way as [`and`].
 pub fn or<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
     solid0: &Solid<Point3, C, S>,
     solid1: &Solid<Point3, C, S>,
     tol: f64,
 ) -> Option<Solid<Point3, C, S>> {
-    let mut iter0 = solid0.boundaries().iter();
-    let mut iter1 = solid1.boundaries().iter();
-    let shell0 = iter0.next().unwrap();
-    let shell1 = iter1.next().unwrap();
-    let [_, mut or_shell] = process_one_pair_of_shells(shell0, shell1, tol)?;
-    for shell in iter0 {
-        let [_, res] = process_one_pair_of_shells(&or_shell, shell, tol)?;
-        or_shell = res;
-    }
-    for shell in iter1 {
-        let [_, res] = process_one_pair_of_shells(&or_shell, shell, tol)?;
-        or_shell = res;
-    }
-    let boundaries = or_shell.connected_components();
-    Some(Solid::new(boundaries))
+    boolean_with_origins(solid0, solid1, tol, 1).map(|x| x.0)
+}
+
+/// AND operation between two solids, carrying the attributes of the elements.
+///
+/// `attributes` contains the attributes of the elements of both solids.
+/// Returns the resulting solid and the attributes of its elements.
+///
+/// The faces divided by the operation inherit the attributes of the original faces.
+/// The vertices and edges which remain in the result keep their attributes.
+/// The edges cut by the intersection curves and the newly created intersection edges
+/// and vertices have no attributes.
+pub fn and_with_attributes<C: ShapeOpsCurve<S>, S: ShapeOpsSurface, A: Clone>(
+    solid0: &Solid<Point3, C, S>,
+    solid1: &Solid<Point3, C, S>,
+    attributes: &AttributeStore<Point3, C, S, A>,
+    tol: f64,
+) -> Option<SolidWithAttributes<C, S, A>> {
+    let (solid, origins) = boolean_with_origins(solid0, solid1, tol, 0)?;
+    let attributes = result_attributes(solid0, solid1, &solid, &origins, attributes);
+    Some((solid, attributes))
+}
+
+/// OR operation between two solids, carrying the attributes of the elements.
+///
+/// The attributes are carried in the same way as [`and_with_attributes`].
+pub fn or_with_attributes<C: ShapeOpsCurve<S>, S: ShapeOpsSurface, A: Clone>(
+    solid0: &Solid<Point3, C, S>,
+    solid1: &Solid<Point3, C, S>,
+    attributes: &AttributeStore<Point3, C, S, A>,
+    tol: f64,
+) -> Option<SolidWithAttributes<C, S, A>> {
+    let (solid, origins) = boolean_with_origins(solid0, solid1, tol, 1)?;
+    let attributes = result_attributes(solid0, solid1, &solid, &origins, attributes);
+    Some((solid, attributes))
+}
+
+/// AND operation between two solids, returning the history of the elements.
+///
+/// The history records the following elements of the result.
+/// The elements remaining in the result as they are have the same ids and are not recorded.
+///
+/// - The pieces of the divided faces and edges are `Modified` from the original ones.
+/// - The intersection edges are `Generated` from the two original faces.
+/// - The intersection vertices are `Generated` from the origins of the edges incident to them.
+pub fn and_with_history<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
+    solid0: &Solid<Point3, C, S>,
+    solid1: &Solid<Point3, C, S>,
+    tol: f64,
+) -> Option<SolidWithHistory<C, S>> {
+    let (solid, origins) = boolean_with_origins(solid0, solid1, tol, 0)?;
+    let history = result_history(solid0, solid1, &solid, &origins);
+    Some((solid, history))
+}
+
+/// OR operation between two solids, returning the history of the elements.
+///
+/// The history is recorded in the same way as [`and_with_history`].
+pub fn or_with_history<C: ShapeOpsCurve<S>, S: ShapeOpsSurface>(
+    solid0: &Solid<Point3, C, S>,
+    solid1: &Solid<Point3, C, S>,
+    tol: f64,
+) -> Option<SolidWithHistory<C, S>> {
+    let (solid, origins) = boolean_with_origins(solid0, solid1, tol, 1)?;
+    let history = result_history(solid0, solid1, &solid, &origins);
+    Some((solid, history))
 }
 
 #[cfg(test)]
@@ -19,3 +19,191 @@ fn punched_cube() {
     let file = std::fs::File::create("punched-cube.obj").unwrap();
     obj::write(&poly, file).unwrap();
 }
+
+#[test]
+fn punched_cube_with_attributes() {
+    use truck_topology::attributes::AttributeStore;
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+
+    let v = builder::vertex(Point3::new(0.5, 0.25, -0.5));
+    let w = builder::rsweep(&v, Point3::new(0.5, 0.5, 0.0), Vector3::unit_z(), Rad(7.0));
+    let f = builder::try_attach_plane(&[w]).unwrap();
+    let mut cylinder = builder::tsweep(&f, Vector3::unit_z() * 2.0);
+    cylinder.not();
+
+    let mut attributes = AttributeStore::new();
+    cube.face_iter().for_each(|face| {
+        attributes.set_face(face, "cube");
+    });
+    cylinder.face_iter().for_each(|face| {
+        attributes.set_face(face, "cylinder");
+    });
+    cube.vertex_iter().for_each(|vertex| {
+        attributes.set_vertex(&vertex, "corner");
+    });
+
+    let (and, attrs) = crate::and_with_attributes(&cube, &cylinder, &attributes, 0.05).unwrap();
+    assert!(and.face_iter().all(|face| attrs.face(face).is_some()));
+    let count = |name| {
+        and.face_iter()
+            .filter(|face| attrs.face(face) == Some(&name))
+            .count()
+    };
+    assert_eq!(count("cube"), 6);
+    assert!(count("cylinder") > 0);
+    let corners: std::collections::HashSet<_> = and
+        .vertex_iter()
+        .filter(|vertex| attrs.vertex(vertex) == Some(&"corner"))
+        .map(|vertex| vertex.id())
+        .collect();
+    assert_eq!(corners.len(), 8);
+}
+
+#[test]
+fn punched_cube_with_history() {
+    use truck_topology::history::{Evolution, Origin};
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+
+    let v = builder::vertex(Point3::new(0.5, 0.25, -0.5));
+    let w = builder::rsweep(&v, Point3::new(0.5, 0.5, 0.0), Vector3::unit_z(), Rad(7.0));
+    let f = builder::try_attach_plane(&[w]).unwrap();
+    let mut cylinder = builder::tsweep(&f, Vector3::unit_z() * 2.0);
+    cylinder.not();
+
+    let (and, history) = crate::and_with_history(&cube, &cylinder, 0.05).unwrap();
+    let input_faces: Vec<_> = cube.face_iter().chain(cylinder.face_iter()).collect();
+    and.face_iter()
+        .for_each(|face| match history.face(face.id()) {
+            Some(entry) => {
+                assert_eq!(entry.evolution, Evolution::Modified);
+                assert_eq!(entry.origins.len(), 1);
+            }
+            None => assert!(input_faces.iter().any(|f| f.id() == face.id())),
+        });
+
+    // the top and bottom faces of the cube are punched
+    let top = cube.boundaries()[0].last().unwrap();
+    let punched: Vec<_> = history.faces_from(Origin::Face(top.id())).collect();
+    assert_eq!(punched.len(), 1);
+
+    // the intersection edges are generated by the faces of both solids
+    let generated: Vec<_> = history
+        .edge_iter()
+        .filter(|(_, entry)| entry.evolution == Evolution::Generated)
+        .collect();
+    assert!(!generated.is_empty());
+    generated.iter().for_each(|(_, entry)| {
+        let [Origin::Face(f0), Origin::Face(f1)] = entry.origins[..] else {
+            panic!("invalid origins: {entry:?}");
+        };
+        assert!(cube.face_iter().any(|f| f.id() == f0) != cube.face_iter().any(|f| f.id() == f1));
+    });
+}
+
+#[test]
+fn punched_cube_chamfer() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+
+    let v = builder::vertex(Point3::new(0.5, 0.25, -0.5));
+    let w = builder::rsweep(&v, Point3::new(0.5, 0.5, 0.0), Vector3::unit_z(), Rad(7.0));
+    let f = builder::try_attach_plane(&[w]).unwrap();
+    let mut cylinder = builder::tsweep(&f, Vector3::unit_z() * 2.0);
+    cylinder.not();
+    let and = crate::and(&cube, &cylinder, 0.05).unwrap();
+
+    // the rim of the hole on the top face
+    let mut rim = Vec::<Edge>::new();
+    and.edge_iter().for_each(|edge| {
+        let (p, q) = (edge.front().point(), edge.back().point());
+        let on_rim = p.z.near(&1.0) && q.z.near(&1.0) && p.distance(q) < 0.5;
+        if on_rim && rim.iter().all(|e| !e.is_same(&edge)) {
+            rim.push(edge);
+        }
+    });
+    let chamfered = blend::chamfer_edges(&and, &rim, 0.05).unwrap();
+    let shell = &chamfered.boundaries()[0];
+    assert_eq!(shell.len(), and.boundaries()[0].len() + rim.len());
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+
+    // Pappus's theorem on the triangle cut off by the chamfer
+    let removed = 2.0 * std::f64::consts::PI * (0.25 + 0.05 / 3.0) * 0.05 * 0.05 / 2.0;
+    let volume = |solid: &Solid| solid.triangulation(0.001).to_polygon().volume();
+    assert!((volume(&and) - volume(&chamfered) - removed).abs() < 1.0e-4);
+}
+
+#[test]
+fn punched_cube_shell() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+
+    let v = builder::vertex(Point3::new(0.5, 0.25, -0.5));
+    let w = builder::rsweep(&v, Point3::new(0.5, 0.5, 0.0), Vector3::unit_z(), Rad(7.0));
+    let f = builder::try_attach_plane(&[w]).unwrap();
+    let mut cylinder = builder::tsweep(&f, Vector3::unit_z() * 2.0);
+    cylinder.not();
+    let and = crate::and(&cube, &cylinder, 0.05).unwrap();
+
+    // the housing opened at the bottom face
+    let bottom = and
+        .face_iter()
+        .filter(|face| face.vertex_iter().all(|v| v.point().z.near(&0.0)))
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(bottom.len(), 1);
+    let hollowed = offset::shell_solid(&and, 0.05, &bottom).unwrap();
+    let shell = &hollowed.boundaries()[0];
+    // the rims around the outer boundary and the hole instead of the bottom face
+    assert_eq!(shell.len(), 2 * and.boundaries()[0].len());
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+
+    // the box around the thickened hole
+    let void = 0.95 * (0.81 - std::f64::consts::PI * 0.09);
+    let volume = |solid: &Solid| solid.triangulation(0.001).to_polygon().volume();
+    assert!((volume(&and) - volume(&hollowed) - void).abs() < 1.0e-3);
+}
+
+#[test]
+fn punched_plate_thicken() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+
+    let v = builder::vertex(Point3::new(0.5, 0.25, -0.5));
+    let w = builder::rsweep(&v, Point3::new(0.5, 0.5, 0.0), Vector3::unit_z(), Rad(7.0));
+    let f = builder::try_attach_plane(&[w]).unwrap();
+    let mut cylinder = builder::tsweep(&f, Vector3::unit_z() * 2.0);
+    cylinder.not();
+    let and = crate::and(&cube, &cylinder, 0.05).unwrap();
+
+    // the top face, whose hole is bounded by the intersection curves
+    let top = and
+        .face_iter()
+        .find(|face| face.vertex_iter().all(|v| v.point().z.near(&1.0)))
+        .unwrap();
+    let intersection = top
+        .edge_iter()
+        .any(|edge| matches!(edge.curve(), Curve::IntersectionCurve(_)));
+    assert!(intersection);
+    let plate: Shell = vec![top.clone()].into();
+    let thickened = offset::thicken(&plate, 0.1, false).unwrap();
+    let shell = &thickened.boundaries()[0];
+    // the offset face, the plate and the side faces along the outer boundary and the hole
+    assert_eq!(shell.len(), 2 + top.edge_iter().count());
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+
+    let area = 1.0 - std::f64::consts::PI * 0.0625;
+    let volume = thickened.triangulation(0.001).to_polygon().volume();
+    assert!((volume - 0.1 * area).abs() < 1.0e-3);
+}
@@ -170,5 +170,73 @@ where
         .collect()
 }
 
+/// Calculates the intersection curves of `surface0` and `surface1`.
+///
+/// Both surfaces are triangulated with the tolerance `tol`, and the polylines of the interference
+/// of the triangulations are projected onto the both surfaces, as in the boolean operations.
+/// The leaders of the returned curves are the projected polylines with the parameters `0, 1, 2, ...`
+/// at the vertices, represented by B-spline curves of degree one.
+///
+/// - Closed intersection curves are returned as curves whose front and back are the same point.
+/// - The polylines which cannot be projected onto the both surfaces, e.g. on the tangential
+///   intersections, are skipped.
+/// # Examples
+/// ```
+/// use truck_geometry::prelude::*;
+/// use truck_shapeops::intersect_surfaces;
+///
+/// let sphere0 = Sphere::new(Point3::new(0.0, 0.0, 0.5), 1.0);
+/// let sphere1 = Sphere::new(Point3::new(0.0, 0.0, -0.5), 1.0);
+/// let curves = intersect_surfaces(&sphere0, &sphere1, 0.01);
+///
+/// // the intersection is a circle
+/// assert_eq!(curves.len(), 1);
+/// let curve = &curves[0];
+/// assert_near!(curve.front(), curve.back());
+/// let (t0, t1) = curve.range_tuple();
+/// const N: usize = 100;
+/// for i in 0..=N {
+///     let t = t0 + (t1 - t0) * i as f64 / N as f64;
+///     let pt = curve.subs(t);
+///     assert!(pt.z.abs() < 1.0e-5);
+///     assert!((pt.to_vec().magnitude2() - 0.75).abs() < 1.0e-5);
+/// }
+/// ```
+pub fn intersect_surfaces<S>(
+    surface0: &S,
+    surface1: &S,
+    tol: f64,
+) -> Vec<IntersectionCurve<BSplineCurve<Point3>, S>>
+where
+    S: ParametricSurface3D
+        + BoundedSurface
+        + ParameterDivision2D
+        + SearchNearestParameter<D2, Point = Point3>,
+{
+    let polygon =
+        |surface: &S| StructuredMesh::from_surface(surface, surface.range_tuple(), tol).destruct();
+    let (polygon0, polygon1) = (polygon(surface0), polygon(surface1));
+    intersection_curves(
+        surface0.clone(),
+        &polygon0,
+        surface1.clone(),
+        &polygon1,
+        tol,
+    )
+    .into_iter()
+    .filter_map(|(_, curve)| {
+        let curve = IntersectionCurve::from(curve?);
+        Some(curve.change_leader(|PolylineCurve(points)| {
+            let n = points.len();
+            let knots = std::iter::once(0.0)
+                .chain((0..n).map(|i| i as f64))
+                .chain(std::iter::once((n - 1) as f64))
+                .collect::<Vec<_>>();
+            BSplineCurve::new(KnotVec::from(knots), points)
+        }))
+    })
+    .collect()
+}
+
 #[cfg(test)]
 mod tests;
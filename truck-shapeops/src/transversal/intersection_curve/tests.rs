@@ -171,3 +171,69 @@ fn collide_parabola() {
         assert_near!(pt.distance(Point3::origin()) * 0.5, f64::sqrt(0.5) * 0.5);
     }
 }
+
+#[test]
+fn plane_plane_test() {
+    let plane0 = Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    );
+    let plane1 = Plane::new(
+        Point3::new(0.5, -0.5, -0.5),
+        Point3::new(0.5, 1.5, -0.5),
+        Point3::new(0.5, -0.5, 0.5),
+    );
+    let curves = intersect_surfaces(&plane0, &plane1, 0.01);
+    assert_eq!(curves.len(), 1);
+    let curve = &curves[0];
+    let (front, back) = (curve.front(), curve.back());
+    assert!(
+        (front.near(&Point3::new(0.5, 0.0, 0.0)) && back.near(&Point3::new(0.5, 1.0, 0.0)))
+            || (front.near(&Point3::new(0.5, 1.0, 0.0)) && back.near(&Point3::new(0.5, 0.0, 0.0)))
+    );
+
+    let plane2 = Plane::new(
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(1.0, 0.0, 1.0),
+        Point3::new(0.0, 1.0, 1.0),
+    );
+    assert!(intersect_surfaces(&plane0, &plane2, 0.01).is_empty());
+}
+
+#[test]
+fn bspline_branch_test() {
+    // saddle z = xy on [-1, 1.5]^2
+    let ctrl_pts = vec![
+        vec![Point3::new(-1.0, -1.0, 1.0), Point3::new(-1.0, 1.5, -1.5)],
+        vec![Point3::new(1.5, -1.0, -1.5), Point3::new(1.5, 1.5, 2.25)],
+    ];
+    let knot_vecs = (KnotVec::bezier_knot(1), KnotVec::bezier_knot(1));
+    let saddle = BSplineSurface::new(knot_vecs.clone(), ctrl_pts);
+    let ctrl_pts = vec![
+        vec![Point3::new(-2.0, -2.0, 0.0), Point3::new(-2.0, 2.0, 0.0)],
+        vec![Point3::new(2.0, -2.0, 0.0), Point3::new(2.0, 2.0, 0.0)],
+    ];
+    let plane = BSplineSurface::new(knot_vecs, ctrl_pts);
+    // the intersection is the union of the x-axis and the y-axis.
+    let curves = intersect_surfaces(&saddle, &plane, 0.01);
+    let mut ends = Vec::new();
+    curves.iter().for_each(|curve| {
+        let (t0, t1) = curve.range_tuple();
+        (0..=10).for_each(|i| {
+            let pt = curve.subs(t0 + (t1 - t0) * i as f64 / 10.0);
+            assert_near!(pt.z, 0.0);
+            assert!(f64::min(pt.x.abs(), pt.y.abs()) < 0.05, "{pt:?}");
+        });
+        ends.push(curve.front());
+        ends.push(curve.back());
+    });
+    [
+        Point3::new(-1.0, 0.0, 0.0),
+        Point3::new(1.5, 0.0, 0.0),
+        Point3::new(0.0, -1.0, 0.0),
+        Point3::new(0.0, 1.5, 0.0),
+    ]
+    .iter()
+    .for_each(|p| assert!(ends.iter().any(|q| p.near(q))));
+}
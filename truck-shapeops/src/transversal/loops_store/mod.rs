@@ -433,8 +433,10 @@ where
 
 pub struct LoopsStoreQuadruple<C> {
     pub geom_loops_store0: LoopsStore<Point3, C>,
+    #[allow(dead_code)]
     pub poly_loops_store0: LoopsStore<Point3, PolylineCurve>,
     pub geom_loops_store1: LoopsStore<Point3, C>,
+    #[allow(dead_code)]
     pub poly_loops_store1: LoopsStore<Point3, PolylineCurve>,
 }
 
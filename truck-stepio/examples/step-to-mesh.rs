@@ -102,6 +102,8 @@ fn output_vtk(
                  vertices,
                  edges,
                  faces,
+                 vertex_tolerances,
+                 vertex_stable_ids,
              }| {
                 let faces = faces
                     .into_iter()
@@ -110,6 +112,7 @@ fn output_vtk(
                             boundaries: face.boundaries,
                             orientation: face.orientation,
                             surface: face.surface?,
+                            stable_id: face.stable_id,
                         })
                     })
                     .collect();
@@ -117,6 +120,8 @@ fn output_vtk(
                     vertices,
                     edges,
                     faces,
+                    vertex_tolerances,
+                    vertex_stable_ids,
                 }
                 .to_data_set() else {
                     unreachable!()
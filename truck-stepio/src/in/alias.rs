@@ -17,6 +17,7 @@ pub type ToroidalSurface = Processor<Torus, Matrix4>;
 pub type ConicalSurface = Processor<RevolutedCurve<Line<Point3>>, Matrix4>;
 pub type StepExtrudedCurve = ExtrudedCurve<Curve3D, Vector3>;
 pub type StepRevolutedCurve = Processor<RevolutedCurve<Curve3D>, Matrix4>;
+pub type RectangularTrimmedSurface = Processor<TrimmedSurface<Surface>, Matrix4>;
 pub type PCurve = truck_geometry::prelude::PCurve<Box<Curve2D>, Box<Surface>>;
 
 #[derive(
@@ -193,6 +194,7 @@ pub enum Surface {
     SweptCurve(Box<SweptCurve>),
     BSplineSurface(Box<BSplineSurface<Point3>>),
     NurbsSurface(Box<NurbsSurface<Vector4>>),
+    TrimmedSurface(Box<RectangularTrimmedSurface>),
 }
 
 impl truck_stepio::out::DisplayByStep for Surface {
@@ -203,10 +205,40 @@ impl truck_stepio::out::DisplayByStep for Surface {
             SweptCurve(x) => x.fmt(idx, f),
             BSplineSurface(x) => x.fmt(idx, f),
             NurbsSurface(x) => x.fmt(idx, f),
+            TrimmedSurface(x) => x.fmt(idx, f),
         }
     }
 }
 
+impl truck_stepio::out::StepLength for RectangularTrimmedSurface {
+    fn step_length(&self) -> usize {
+        1 + truck_stepio::out::StepLength::step_length(self.entity().surface())
+    }
+}
+impl truck_stepio::out::DisplayByStep for RectangularTrimmedSurface {
+    fn fmt(&self, idx: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use truck_stepio::out::{FloatDisplay, StepDisplay};
+        let surface_idx = idx + 1;
+        let surface = self.entity().surface().transformed(*self.transform());
+        let ((u0, u1), (v0, v1)) = self.entity().range();
+        f.write_fmt(format_args!(
+            "#{idx} = RECTANGULAR_TRIMMED_SURFACE('', #{surface_idx}, {u0}, {u1}, {v0}, {v1}, .T., .T.);\n{surface}",
+            u0 = FloatDisplay(u0),
+            u1 = FloatDisplay(u1),
+            v0 = FloatDisplay(v0),
+            v1 = FloatDisplay(v1),
+            surface = StepDisplay::new(&surface, surface_idx),
+        ))
+    }
+}
+impl truck_stepio::out::StepSurface for RectangularTrimmedSurface {
+    #[inline(always)]
+    fn same_sense(&self) -> bool {
+        let same_sense = truck_stepio::out::StepSurface::same_sense(self.entity().surface());
+        self.orientation() == same_sense
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, StepSurface)]
 pub struct Sphere(pub truck_geometry::prelude::Sphere);
 
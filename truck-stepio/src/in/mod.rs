@@ -55,6 +55,7 @@ pub struct Table {
     pub rational_b_spline_surface: HashMap<u64, RationalBSplineSurfaceHolder>,
     pub surface_of_linear_extrusion: HashMap<u64, SurfaceOfLinearExtrusionHolder>,
     pub surface_of_revolution: HashMap<u64, SurfaceOfRevolutionHolder>,
+    pub rectangular_trimmed_surface: HashMap<u64, RectangularTrimmedSurfaceHolder>,
 
     // topology
     pub vertex_point: HashMap<u64, VertexPointHolder>,
@@ -195,6 +196,10 @@ impl Table {
                     self.surface_of_revolution
                         .insert(*id, Deserialize::deserialize(record)?);
                 }
+                "RECTANGULAR_TRIMMED_SURFACE" => {
+                    self.rectangular_trimmed_surface
+                        .insert(*id, Deserialize::deserialize(record)?);
+                }
 
                 "VERTEX_POINT" => {
                     self.vertex_point
@@ -1506,6 +1511,8 @@ pub enum SurfaceAny {
     BSplineSurface(Box<BSplineSurfaceAny>),
     #[holder(use_place_holder)]
     SweptSurface(Box<SweptSurfaceAny>),
+    #[holder(use_place_holder)]
+    RectangularTrimmedSurface(Box<RectangularTrimmedSurface>),
 }
 
 impl TryFrom<&SurfaceAny> for Surface {
@@ -1517,6 +1524,7 @@ impl TryFrom<&SurfaceAny> for Surface {
             ElementarySurface(x) => Self::ElementarySurface(Box::new(x.as_ref().into())),
             BSplineSurface(x) => x.as_ref().try_into()?,
             SweptSurface(x) => Self::SweptCurve(Box::new(x.as_ref().try_into()?)),
+            RectangularTrimmedSurface(x) => Self::TrimmedSurface(Box::new(x.as_ref().try_into()?)),
         })
     }
 }
@@ -1989,6 +1997,46 @@ impl TryFrom<&SurfaceOfRevolution> for StepRevolutedCurve {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Holder)]
+#[holder(table = Table)]
+#[holder(field = rectangular_trimmed_surface)]
+#[holder(generate_deserialize)]
+pub struct RectangularTrimmedSurface {
+    label: String,
+    #[holder(use_place_holder)]
+    basis_surface: SurfaceAny,
+    u1: f64,
+    u2: f64,
+    v1: f64,
+    v2: f64,
+    usense: bool,
+    vsense: bool,
+}
+
+impl TryFrom<&RectangularTrimmedSurface> for alias::RectangularTrimmedSurface {
+    type Error = ExpressParseError;
+    fn try_from(value: &RectangularTrimmedSurface) -> Result<Self, Self::Error> {
+        let surface = Surface::try_from(&value.basis_surface)?;
+        // If the sense disagrees with the basis surface, the range runs from the second parameter
+        // to the first one. The reversed range passes through the seam of the periodic surface.
+        let range = |t1: f64, t2: f64, sense: bool, period: Option<f64>| {
+            let (t0, t1) = if sense { (t1, t2) } else { (t2, t1) };
+            match (t0 < t1, period) {
+                (true, _) => Ok((t0, t1)),
+                (false, Some(period)) => Ok((t0, t1 + period)),
+                (false, None) => Err("the trimming range of a non-periodic surface is reversed."),
+            }
+        };
+        let urange = range(value.u1, value.u2, value.usense, surface.u_period())?;
+        let vrange = range(value.v1, value.v2, value.vsense, surface.v_period())?;
+        let mut res = Processor::new(TrimmedSurface::new(surface, (urange, vrange)));
+        if value.usense != value.vsense {
+            res.invert();
+        }
+        Ok(res)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Holder)]
 #[holder(table = Table)]
 #[holder(field = vertex_point)]
@@ -2589,10 +2637,8 @@ impl Table {
             let Ref(Name::Entity(back_idx)) = edge.edge_end else {
                 return None;
             };
-            Some(CompressedEdge {
-                vertices: (*vidx_map.get(&front_idx)?, *vidx_map.get(&back_idx)?),
-                curve,
-            })
+            let vertices = (*vidx_map.get(&front_idx)?, *vidx_map.get(&back_idx)?);
+            Some(CompressedEdge::new(vertices, curve))
         };
         let edges: Vec<CompressedEdge<Curve3D>> = shell
             .cfs_faces_holder(self)
@@ -2666,11 +2712,7 @@ impl Table {
                     .into_iter()
                     .filter_map(|bound| self.face_bound_to_edges(bound?, eidx_map))
                     .collect();
-                Some(CompressedFace {
-                    surface,
-                    boundaries,
-                    orientation,
-                })
+                Some(CompressedFace::new(boundaries, orientation, surface))
             })
             .collect()
     }
@@ -2681,10 +2723,7 @@ impl Table {
     ) -> Result<CompressedShell<Point3, Curve3D, Surface>, ExpressParseError> {
         let (vertices, vidx_map) = self.shell_vertices(shell);
         let (edges, eidx_map) = self.shell_edges(shell, &vidx_map);
-        Ok(CompressedShell {
-            vertices,
-            edges,
-            faces: self.shell_faces(shell, &eidx_map),
-        })
+        let faces = self.shell_faces(shell, &eidx_map);
+        Ok(CompressedShell::new(vertices, edges, faces))
     }
 }
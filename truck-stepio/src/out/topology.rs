@@ -409,6 +409,33 @@ where
         self.next_idx += model.step_length();
         self.models.push(model)
     }
+    /// push the solids and shells of a compound to step models
+    ///
+    /// # Remarks
+    /// The wires and the free vertices of the compound are not output.
+    pub fn push_compound(&mut self, compound: &'a CompressedCompound<P, C, S>) {
+        compound
+            .solids
+            .iter()
+            .for_each(|solid| self.push_solid(solid));
+        compound
+            .shells
+            .iter()
+            .for_each(|shell| self.push_shell(shell));
+    }
+}
+
+impl<'a, P, C, S> From<&'a CompressedCompound<P, C, S>> for StepModels<'a, P, C, S>
+where
+    P: Copy,
+    C: StepLength,
+    S: StepLength,
+{
+    fn from(compound: &'a CompressedCompound<P, C, S>) -> Self {
+        let mut models = Self::default();
+        models.push_compound(compound);
+        models
+    }
 }
 
 impl<'a, P, C, S> FromIterator<&'a CompressedShell<P, C, S>> for StepModels<'a, P, C, S>
@@ -1751,3 +1751,41 @@ proptest! {
         exec_surface_of_revolution(point0_coord, point1_coord, org_coord, axis_array)
     }
 }
+
+#[test]
+fn rectangular_trimmed_surface() {
+    // the range passes through the seam of the cylinder
+    let step_str = "DATA;
+#1 = RECTANGULAR_TRIMMED_SURFACE('', #2, 4.71238898038469, 1.5707963267948966, 0.0, 2.0, .T., .T.);
+#2 = CYLINDRICAL_SURFACE('', #3, 1.0);
+#3 = AXIS2_PLACEMENT_3D('', #4, #5, #6);
+#4 = CARTESIAN_POINT('', (0.0, 0.0, 0.0));
+#5 = DIRECTION('', (0.0, 0.0, 1.0));
+#6 = DIRECTION('', (1.0, 0.0, 0.0));
+ENDSEC;";
+    let step_surface = step_to_entity::<RectangularTrimmedSurfaceHolder>(step_str);
+    let surface: alias::RectangularTrimmedSurface = (&step_surface).try_into().unwrap();
+    let ((u0, u1), (v0, v1)) = surface.range_tuple();
+    assert_near!(u0, 1.5 * PI);
+    assert_near!(u1, 2.5 * PI);
+    assert_near!(v0, 0.0);
+    assert_near!(v1, 2.0);
+    assert_eq!(surface.u_period(), None);
+    assert_near!(surface.subs(2.0 * PI, 1.0), Point3::new(1.0, 0.0, 1.0));
+
+    let display = StepDisplay::new(&surface, 1).to_string();
+    assert!(display.starts_with("#1 = RECTANGULAR_TRIMMED_SURFACE('', #2, "));
+
+    // the range of the non-periodic surface must not be reversed
+    let step_str = "DATA;
+#1 = RECTANGULAR_TRIMMED_SURFACE('', #2, 1.0, 0.0, 0.0, 1.0, .T., .T.);
+#2 = PLANE('', #3);
+#3 = AXIS2_PLACEMENT_3D('', #4, #5, #6);
+#4 = CARTESIAN_POINT('', (0.0, 0.0, 0.0));
+#5 = DIRECTION('', (0.0, 0.0, 1.0));
+#6 = DIRECTION('', (1.0, 0.0, 0.0));
+ENDSEC;";
+    let step_surface = step_to_entity::<RectangularTrimmedSurfaceHolder>(step_str);
+    let res: Result<alias::RectangularTrimmedSurface, _> = (&step_surface).try_into();
+    assert!(res.is_err());
+}
@@ -59,3 +59,27 @@ fn parse_solids() {
         panic!("failed to parse step\n[Error Message]\n{e}[STEP file]\n{step_string}")
     });
 }
+
+#[test]
+fn parse_compound() {
+    let v = builder::vertex(Point3::origin());
+    let e = builder::tsweep(&v, Vector3::unit_x());
+    let f = builder::tsweep(&e, Vector3::unit_y());
+    let cube = builder::tsweep(&f, Vector3::unit_z());
+    let mut shell = builder::translated(&cube, Vector3::new(2.0, 0.0, 0.0))
+        .into_boundaries()
+        .pop()
+        .unwrap();
+    shell.pop();
+    let mut compound = Compound::from(cube);
+    compound.push_shell(shell);
+    compound.push_vertex(builder::vertex(Point3::new(5.0, 0.0, 0.0)));
+    let compound = compound.compress();
+    let step_string =
+        CompleteStepDisplay::new(StepModels::from(&compound), Default::default()).to_string();
+    ruststep::parser::parse(&step_string).unwrap_or_else(|e| {
+        panic!("failed to parse step\n[Error Message]\n{e}[STEP file]\n{step_string}")
+    });
+    assert!(step_string.contains("MANIFOLD_SOLID_BREP"));
+    assert!(step_string.contains("SHELL_BASED_SURFACE_MODEL"));
+}
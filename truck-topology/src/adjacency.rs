@@ -0,0 +1,334 @@
+//! Attributed adjacency graph of faces
+//!
+//! [`FaceAdjacencyGraph`] has the faces of a shape as its nodes and the edges of the shape as its
+//! arcs. Each [`AdjacencyEdge`] knows the faces sharing it, and, after
+//! [`FaceAdjacencyGraph::compute_dihedral_angles`], the dihedral angle and the convexity there.
+//! The graph can be exported in the Graphviz DOT format by [`FaceAdjacencyGraph::to_dot`].
+//!
+//! # Examples
+//! ```
+//! use truck_topology::*;
+//! let v = Vertex::news([(); 4]);
+//! let edge = [
+//!     Edge::new(&v[0], &v[1], ()),
+//!     Edge::new(&v[1], &v[2], ()),
+//!     Edge::new(&v[2], &v[0], ()),
+//!     Edge::new(&v[1], &v[3], ()),
+//!     Edge::new(&v[3], &v[2], ()),
+//! ];
+//! let wire0 = Wire::from(vec![edge[0].clone(), edge[1].clone(), edge[2].clone()]);
+//! let wire1 = Wire::from(vec![edge[3].clone(), edge[4].clone(), edge[1].inverse()]);
+//! let shell: Shell<_, _, _> = vec![Face::new(vec![wire0], ()), Face::new(vec![wire1], ())].into();
+//!
+//! let graph = shell.face_adjacency_graph();
+//! assert_eq!(graph.faces_around_edge(edge[1].id()).len(), 2);
+//! assert_eq!(graph.faces_around_edge(edge[0].id()).len(), 1);
+//! assert_eq!(graph.faces_around_vertex(v[3].id()).len(), 1);
+//! let neighbors = graph.adjacent_faces(shell[0].id());
+//! assert_eq!(neighbors.len(), 1);
+//! assert_eq!(neighbors[0].0.id(), shell[1].id());
+//! assert_eq!(neighbors[0].1.edge.id(), edge[1].id());
+//!
+//! let dot = graph.to_dot();
+//! assert!(dot.starts_with("graph {"));
+//! assert!(dot.contains("f0 -- f1"));
+//! ```
+
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use std::f64::consts::PI;
+use std::fmt::Write;
+use truck_base::cgmath64::*;
+
+/// Convexity of the faces at their common edge, seen from the outside of the shape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Convexity {
+    /// The dihedral angle is less than π, e.g. the edges of a cube.
+    Convex,
+    /// The dihedral angle is greater than π, e.g. the inner corner of an L-shaped solid.
+    Concave,
+    /// The faces are tangent at the edge, e.g. the boundary between a fillet and a plane.
+    Smooth,
+}
+
+/// An edge of the shape as an arc of [`FaceAdjacencyGraph`]
+#[derive(Clone, Debug)]
+pub struct AdjacencyEdge<P, C> {
+    /// the edge in its absolute orientation
+    pub edge: Edge<P, C>,
+    /// the indices of the faces whose boundaries contain the edge, in the order of the faces
+    /// in the graph. The face appears twice if the edge is a seam of the face.
+    pub faces: Vec<usize>,
+    /// the dihedral angle in radians measured inside the shape, at the middle point of the edge.
+    /// `None` if not computed, or if the edge is not shared by exactly two faces.
+    pub dihedral_angle: Option<f64>,
+    /// the convexity at the middle point of the edge. `None` as well as `dihedral_angle`.
+    pub convexity: Option<Convexity>,
+}
+
+/// an adjacent face and the shared edge
+type Neighbor<'a, P, C, S> = (&'a Face<P, C, S>, &'a AdjacencyEdge<P, C>);
+
+/// Adjacency graph of faces, attributed by the convexity of the shared edges
+#[derive(Clone, Debug)]
+pub struct FaceAdjacencyGraph<P, C, S> {
+    faces: Vec<Face<P, C, S>>,
+    edges: Vec<AdjacencyEdge<P, C>>,
+    face_indices: HashMap<FaceID<S>, usize>,
+    edge_indices: HashMap<EdgeID<C>, usize>,
+    vertex_faces: HashMap<VertexID<P>, Vec<usize>>,
+}
+
+impl<P, C, S> FaceAdjacencyGraph<P, C, S> {
+    /// Creates the graph of the faces. The attributes of the edges are not computed.
+    pub fn new<'a>(faces: impl IntoIterator<Item = &'a Face<P, C, S>>) -> Self
+    where
+        P: 'a,
+        C: 'a,
+        S: 'a, {
+        let mut graph = Self {
+            faces: Vec::new(),
+            edges: Vec::new(),
+            face_indices: HashMap::default(),
+            edge_indices: HashMap::default(),
+            vertex_faces: HashMap::default(),
+        };
+        faces.into_iter().for_each(|face| {
+            if graph.face_indices.contains_key(&face.id()) {
+                return;
+            }
+            let index = graph.faces.len();
+            graph.face_indices.insert(face.id(), index);
+            graph.faces.push(face.clone());
+            face.absolute_boundaries()
+                .iter()
+                .flatten()
+                .for_each(|edge| {
+                    let edges = &mut graph.edges;
+                    let i = *graph.edge_indices.entry(edge.id()).or_insert_with(|| {
+                        edges.push(AdjacencyEdge {
+                            edge: edge.absolute_clone(),
+                            faces: Vec::new(),
+                            dihedral_angle: None,
+                            convexity: None,
+                        });
+                        edges.len() - 1
+                    });
+                    graph.edges[i].faces.push(index);
+                    let faces = graph.vertex_faces.entry(edge.front().id()).or_default();
+                    if faces.last() != Some(&index) {
+                        faces.push(index);
+                    }
+                });
+        });
+        graph
+    }
+
+    /// Returns the faces, the nodes of the graph.
+    #[inline(always)]
+    pub fn faces(&self) -> &[Face<P, C, S>] { &self.faces }
+
+    /// Returns the edges, the arcs of the graph.
+    #[inline(always)]
+    pub fn edges(&self) -> &[AdjacencyEdge<P, C>] { &self.edges }
+
+    /// Returns the index of the face in [`FaceAdjacencyGraph::faces`].
+    #[inline(always)]
+    pub fn face_index(&self, face: FaceID<S>) -> Option<usize> {
+        self.face_indices.get(&face).copied()
+    }
+
+    /// Returns the edge of the graph with the id.
+    #[inline(always)]
+    pub fn edge(&self, edge: EdgeID<C>) -> Option<&AdjacencyEdge<P, C>> {
+        self.edge_indices.get(&edge).map(|i| &self.edges[*i])
+    }
+
+    /// Returns the faces adjacent to `face` with the shared edges.
+    /// A face sharing several edges with `face` appears once for each shared edge.
+    pub fn adjacent_faces(&self, face: FaceID<S>) -> Vec<Neighbor<'_, P, C, S>> {
+        let Some(index) = self.face_index(face) else {
+            return Vec::new();
+        };
+        let mut adjacent_faces = Vec::new();
+        self.faces[index].edge_iter().for_each(|edge| {
+            let edge = &self.edges[self.edge_indices[&edge.id()]];
+            // the seams are not counted
+            if edge.faces.iter().filter(|i| **i == index).count() > 1 {
+                return;
+            }
+            edge.faces
+                .iter()
+                .filter(|i| **i != index)
+                .for_each(|i| adjacent_faces.push((&self.faces[*i], edge)));
+        });
+        adjacent_faces
+    }
+
+    /// Returns the faces whose boundaries contain the edge.
+    pub fn faces_around_edge(&self, edge: EdgeID<C>) -> Vec<&Face<P, C, S>> {
+        let mut faces: Vec<usize> = self.edge(edge).map(|e| e.faces.clone()).unwrap_or_default();
+        faces.dedup();
+        faces.into_iter().map(|i| &self.faces[i]).collect()
+    }
+
+    /// Returns the faces whose boundaries contain the vertex.
+    pub fn faces_around_vertex(&self, vertex: VertexID<P>) -> Vec<&Face<P, C, S>> {
+        let faces = self.vertex_faces.get(&vertex).into_iter().flatten();
+        faces.map(|i| &self.faces[*i]).collect()
+    }
+
+    /// Returns the loops of the edges of the graph bounding `face`, in the order of
+    /// [`Face::boundaries`]. Returns the empty vector if `face` is not in the graph.
+    pub fn edge_loops(&self, face: FaceID<S>) -> Vec<Vec<&AdjacencyEdge<P, C>>> {
+        let Some(index) = self.face_index(face) else {
+            return Vec::new();
+        };
+        self.faces[index]
+            .boundary_iters()
+            .into_iter()
+            .map(|iter| {
+                iter.map(|edge| &self.edges[self.edge_indices[&edge.id()]])
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Exports the graph in the Graphviz DOT format.
+    ///
+    /// The node `f{i}` is the `i`-th face, labeled by its stable id if any. An arc is drawn for each
+    /// pair of the faces sharing an edge, labeled by the dihedral angle in degrees if computed.
+    /// The concave arcs are dashed and the smooth arcs are dotted.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph {\n");
+        self.faces.iter().enumerate().for_each(|(i, face)| {
+            let _ = match face.stable_id() {
+                Some(id) => writeln!(dot, "    f{i} [label=\"#{id}\"];"),
+                None => writeln!(dot, "    f{i} [label=\"{i}\"];"),
+            };
+        });
+        self.edges.iter().for_each(|edge| {
+            let mut attributes = Vec::new();
+            if let Some(angle) = edge.dihedral_angle {
+                attributes.push(format!("label=\"{:.1}\"", angle.to_degrees()));
+            }
+            match edge.convexity {
+                Some(Convexity::Concave) => attributes.push("style=dashed".to_string()),
+                Some(Convexity::Smooth) => attributes.push("style=dotted".to_string()),
+                _ => {}
+            }
+            let attributes = match attributes.is_empty() {
+                true => String::new(),
+                false => format!(" [{}]", attributes.join(", ")),
+            };
+            edge.faces.iter().enumerate().for_each(|(k, i)| {
+                edge.faces[k + 1..]
+                    .iter()
+                    .filter(|j| *j != i)
+                    .for_each(|j| {
+                        let _ = writeln!(dot, "    f{i} -- f{j}{attributes};");
+                    });
+            });
+        });
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<C, S> FaceAdjacencyGraph<Point3, C, S>
+where
+    C: ParametricCurve3D + BoundedCurve,
+    S: ParametricSurface3D + SearchNearestParameter<D2, Point = Point3>,
+{
+    /// Computes the dihedral angles and the convexities of the edges shared by exactly two faces.
+    ///
+    /// The angles are measured at the middle points of the edges, assuming that the normals of
+    /// the faces point the outside of the shape. The edges whose dihedral angles differ from π
+    /// by no more than `angle_tol` are regarded as smooth ones.
+    pub fn compute_dihedral_angles(&mut self, angle_tol: f64) {
+        nonpositive_tolerance!(angle_tol);
+        let faces = &self.faces;
+        self.edges.iter_mut().for_each(|edge| {
+            edge.dihedral_angle = None;
+            edge.convexity = None;
+            let [i, j] = edge.faces[..] else {
+                return;
+            };
+            // the orientations of the edge in the boundaries of the faces
+            let orientations = |face: &Face<Point3, C, S>| -> Vec<bool> {
+                face.edge_iter()
+                    .filter(|e| e.id() == edge.edge.id())
+                    .map(|e| e.orientation())
+                    .collect()
+            };
+            let (o0, o1) = match i == j {
+                true => match orientations(&faces[i])[..] {
+                    [o0, o1] => (o0, o1),
+                    _ => return,
+                },
+                false => (orientations(&faces[i])[0], orientations(&faces[j])[0]),
+            };
+            let curve = edge.edge.curve();
+            let (t0, t1) = curve.range_tuple();
+            let t = (t0 + t1) / 2.0;
+            let (point, tangent) = (curve.subs(t), curve.der(t));
+            // the normal of the face, and the direction from the edge toward the inside of the face
+            let directions = |face: &Face<Point3, C, S>, orientation: bool| {
+                let surface = face.surface();
+                let (u, v) = surface.search_nearest_parameter(
+                    point,
+                    SPHint2D::Global,
+                    SEARCH_PARAMETER_TRIALS,
+                )?;
+                let normal = match face.orientation() {
+                    true => surface.normal(u, v),
+                    false => -surface.normal(u, v),
+                };
+                let tangent = match orientation {
+                    true => tangent,
+                    false => -tangent,
+                };
+                Some((normal, normal.cross(tangent)))
+            };
+            let (Some((_, inward0)), Some((normal1, inward1))) =
+                (directions(&faces[i], o0), directions(&faces[j], o1))
+            else {
+                return;
+            };
+            if inward0.so_small() || inward1.so_small() {
+                return;
+            }
+            let angle = inward0.angle(inward1).0;
+            let angle = match normal1.dot(inward0) > 0.0 {
+                true => 2.0 * PI - angle,
+                false => angle,
+            };
+            edge.dihedral_angle = Some(angle);
+            edge.convexity = Some(if (angle - PI).abs() <= angle_tol {
+                Convexity::Smooth
+            } else if angle < PI {
+                Convexity::Convex
+            } else {
+                Convexity::Concave
+            });
+        });
+    }
+}
+
+impl<P, C, S> Shell<P, C, S> {
+    /// Returns the adjacency graph of the faces of the shell.
+    /// See [the module document](self) for the details.
+    #[inline(always)]
+    pub fn face_adjacency_graph(&self) -> FaceAdjacencyGraph<P, C, S> {
+        FaceAdjacencyGraph::new(self.face_iter())
+    }
+}
+
+impl<P, C, S> Solid<P, C, S> {
+    /// Returns the adjacency graph of the faces of all the boundary shells of the solid.
+    /// See [the module document](self) for the details.
+    #[inline(always)]
+    pub fn face_adjacency_graph(&self) -> FaceAdjacencyGraph<P, C, S> {
+        FaceAdjacencyGraph::new(self.face_iter())
+    }
+}
@@ -0,0 +1,460 @@
+//! Typed attributes attached to topological elements
+//!
+//! [`AttributeStore`] maps the stable ids of vertices, edges and faces to user data, e.g. colors,
+//! names or manufacturing tags. Setting an attribute assigns the stable id to the element if it
+//! has none. Since the stable ids are shared by all clones and inverses of an element and are
+//! never reused by other elements, an attribute never moves to a different element.
+//!
+//! The elements created by `mapped`, e.g. the transformed copies and the tops of sweeps,
+//! refer to the attributes of their origins unless they have their own attributes.
+//! The attributes set after mapping are not referred to by the copies.
+//!
+//! # Examples
+//! ```
+//! use truck_topology::*;
+//! use truck_topology::attributes::*;
+//! let v = Vertex::news([(); 3]);
+//! let wire: Wire<(), ()> = vec![
+//!     Edge::new(&v[0], &v[1], ()),
+//!     Edge::new(&v[1], &v[2], ()),
+//!     Edge::new(&v[2], &v[0], ()),
+//! ].into();
+//! let face = Face::new(vec![wire], ());
+//!
+//! let mut attributes = AttributeStore::new();
+//! attributes.set_face(&face, "red");
+//! // the inversed face has the same stable id
+//! assert_eq!(attributes.face(&face.inverse()), Some(&"red"));
+//!
+//! // the copy of the face refers to the attribute of the original face
+//! let copy = face.mapped(|_| (), |_| (), |_| ());
+//! assert_eq!(attributes.face(&copy), Some(&"red"));
+//! attributes.set_face(&copy, "blue");
+//! assert_eq!(attributes.face(&copy), Some(&"blue"));
+//! assert_eq!(attributes.face(&face), Some(&"red"));
+//! ```
+
+use crate::{compress::*, errors::Error, *};
+use rustc_hash::FxHashMap as HashMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// Typed attributes of vertices, edges and faces
+pub struct AttributeStore<P, C, S, A> {
+    vertices: HashMap<StableID, A>,
+    edges: HashMap<StableID, A>,
+    faces: HashMap<StableID, A>,
+    _phantom: PhantomData<(P, C, S)>,
+}
+
+/// Serialized attributes attached to the indices of the elements in `CompressedShell`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompressedAttributes<A> {
+    /// attributes of vertices, pairs of indices of vertices and attributes
+    pub vertices: Vec<(usize, A)>,
+    /// attributes of edges, pairs of indices of edges and attributes
+    pub edges: Vec<(usize, A)>,
+    /// attributes of faces, pairs of indices of faces and attributes
+    pub faces: Vec<(usize, A)>,
+}
+
+/// Returns the attribute of the element itself, or the one of its origin.
+#[inline(always)]
+fn lookup<A>(
+    map: &HashMap<StableID, A>,
+    stable_id: Option<StableID>,
+    origin: Option<StableID>,
+) -> Option<&A> {
+    stable_id
+        .and_then(|id| map.get(&id))
+        .or_else(|| map.get(&origin?))
+}
+
+/// Keeps the stable id by which the attribute of the element is looked up.
+#[inline(always)]
+fn retained_id<A>(
+    map: &HashMap<StableID, A>,
+    stable_id: Option<StableID>,
+    origin: Option<StableID>,
+) -> Option<StableID> {
+    match stable_id {
+        Some(id) if map.contains_key(&id) => Some(id),
+        _ => origin,
+    }
+}
+
+impl<P, C, S, A> AttributeStore<P, C, S, A> {
+    /// Creates the empty store.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            vertices: HashMap::default(),
+            edges: HashMap::default(),
+            faces: HashMap::default(),
+            _phantom: PhantomData,
+        }
+    }
+    /// Returns `true` if no attribute is stored.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty() && self.edges.is_empty() && self.faces.is_empty()
+    }
+    /// Removes all attributes.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.edges.clear();
+        self.faces.clear();
+    }
+
+    /// Returns the attribute of the vertex, or the one of its origin if the vertex has none.
+    #[inline(always)]
+    pub fn vertex(&self, vertex: &Vertex<P>) -> Option<&A> {
+        lookup(&self.vertices, vertex.stable_id(), vertex.point.origin())
+    }
+    /// Returns the mutable reference to the attribute of the vertex itself.
+    #[inline(always)]
+    pub fn vertex_mut(&mut self, vertex: &Vertex<P>) -> Option<&mut A> {
+        self.vertices.get_mut(&vertex.stable_id()?)
+    }
+    /// Sets the attribute of the vertex and returns the old one.
+    /// Assigns the stable id to the vertex if it has none.
+    #[inline(always)]
+    pub fn set_vertex(&mut self, vertex: &Vertex<P>, attribute: A) -> Option<A> {
+        self.vertices.insert(vertex.assign_stable_id(), attribute)
+    }
+    /// Removes the attribute of the vertex itself.
+    #[inline(always)]
+    pub fn remove_vertex(&mut self, vertex: &Vertex<P>) -> Option<A> {
+        self.vertices.remove(&vertex.stable_id()?)
+    }
+    /// Returns the iterator over the pairs of stable ids of vertices and attributes.
+    #[inline(always)]
+    pub fn vertex_iter(&self) -> impl Iterator<Item = (StableID, &A)> {
+        self.vertices.iter().map(|(id, a)| (*id, a))
+    }
+
+    /// Returns the attribute of the edge, or the one of its origin if the edge has none.
+    #[inline(always)]
+    pub fn edge(&self, edge: &Edge<P, C>) -> Option<&A> {
+        lookup(&self.edges, edge.stable_id(), edge.curve.origin())
+    }
+    /// Returns the mutable reference to the attribute of the edge itself.
+    #[inline(always)]
+    pub fn edge_mut(&mut self, edge: &Edge<P, C>) -> Option<&mut A> {
+        self.edges.get_mut(&edge.stable_id()?)
+    }
+    /// Sets the attribute of the edge and returns the old one.
+    /// Assigns the stable id to the edge if it has none.
+    #[inline(always)]
+    pub fn set_edge(&mut self, edge: &Edge<P, C>, attribute: A) -> Option<A> {
+        self.edges.insert(edge.assign_stable_id(), attribute)
+    }
+    /// Removes the attribute of the edge itself.
+    #[inline(always)]
+    pub fn remove_edge(&mut self, edge: &Edge<P, C>) -> Option<A> {
+        self.edges.remove(&edge.stable_id()?)
+    }
+    /// Returns the iterator over the pairs of stable ids of edges and attributes.
+    #[inline(always)]
+    pub fn edge_iter(&self) -> impl Iterator<Item = (StableID, &A)> {
+        self.edges.iter().map(|(id, a)| (*id, a))
+    }
+
+    /// Returns the attribute of the face, or the one of its origin if the face has none.
+    #[inline(always)]
+    pub fn face(&self, face: &Face<P, C, S>) -> Option<&A> {
+        lookup(&self.faces, face.stable_id(), face.surface.origin())
+    }
+    /// Returns the mutable reference to the attribute of the face itself.
+    #[inline(always)]
+    pub fn face_mut(&mut self, face: &Face<P, C, S>) -> Option<&mut A> {
+        self.faces.get_mut(&face.stable_id()?)
+    }
+    /// Sets the attribute of the face and returns the old one.
+    /// Assigns the stable id to the face if it has none.
+    #[inline(always)]
+    pub fn set_face(&mut self, face: &Face<P, C, S>, attribute: A) -> Option<A> {
+        self.faces.insert(face.assign_stable_id(), attribute)
+    }
+    /// Removes the attribute of the face itself.
+    #[inline(always)]
+    pub fn remove_face(&mut self, face: &Face<P, C, S>) -> Option<A> {
+        self.faces.remove(&face.stable_id()?)
+    }
+    /// Returns the iterator over the pairs of stable ids of faces and attributes.
+    #[inline(always)]
+    pub fn face_iter(&self) -> impl Iterator<Item = (StableID, &A)> {
+        self.faces.iter().map(|(id, a)| (*id, a))
+    }
+
+    /// Removes the attributes which are not referred to by the elements of `faces`.
+    ///
+    /// The attributes of the origins are kept for the elements which have no attributes of their own.
+    pub fn retain_elements<'a>(&mut self, faces: impl IntoIterator<Item = &'a Face<P, C, S>>)
+    where
+        P: 'a,
+        C: 'a,
+        S: 'a, {
+        let mut vertices = HashMap::<StableID, ()>::default();
+        let mut edges = HashMap::<StableID, ()>::default();
+        let mut face_ids = HashMap::<StableID, ()>::default();
+        faces.into_iter().for_each(|face| {
+            let (id, origin) = (face.stable_id(), face.surface.origin());
+            if let Some(id) = retained_id(&self.faces, id, origin) {
+                face_ids.insert(id, ());
+            }
+            face.boundaries.iter().flatten().for_each(|edge| {
+                let (id, origin) = (edge.stable_id(), edge.curve.origin());
+                if let Some(id) = retained_id(&self.edges, id, origin) {
+                    edges.insert(id, ());
+                }
+                let vertex = edge.front();
+                let (id, origin) = (vertex.stable_id(), vertex.point.origin());
+                if let Some(id) = retained_id(&self.vertices, id, origin) {
+                    vertices.insert(id, ());
+                }
+            });
+        });
+        self.vertices.retain(|id, _| vertices.contains_key(id));
+        self.edges.retain(|id, _| edges.contains_key(id));
+        self.faces.retain(|id, _| face_ids.contains_key(id));
+    }
+}
+
+impl<P, C, S, A: Clone> AttributeStore<P, C, S, A> {
+    /// Copies the attributes of the elements of `from` to the corresponding elements of `to`.
+    ///
+    /// The faces of `from` and `to` are compared in order, and so are their boundaries, edges
+    /// and end vertices. Traversal stops at the first pair of faces whose boundary structures
+    /// disagree. The copies created by `mapped` refer to the attributes of their origins without
+    /// this method, which is needed for the attributes set after mapping or the elements rebuilt
+    /// in other ways.
+    pub fn inherit<'a, 'b>(
+        &mut self,
+        from: impl IntoIterator<Item = &'a Face<P, C, S>>,
+        to: impl IntoIterator<Item = &'b Face<P, C, S>>,
+    ) where
+        P: 'a + 'b,
+        C: 'a + 'b,
+        S: 'a + 'b,
+    {
+        let store = self.mapped(from, to);
+        self.vertices.extend(store.vertices);
+        self.edges.extend(store.edges);
+        self.faces.extend(store.faces);
+    }
+
+    /// Creates the store of the attributes of `to` copied from the corresponding elements of `from`.
+    ///
+    /// This is the version of [`AttributeStore::inherit`] for the shapes with the different types.
+    pub fn mapped<'a, 'b, Q, D, T>(
+        &self,
+        from: impl IntoIterator<Item = &'a Face<P, C, S>>,
+        to: impl IntoIterator<Item = &'b Face<Q, D, T>>,
+    ) -> AttributeStore<Q, D, T, A>
+    where
+        P: 'a,
+        C: 'a,
+        S: 'a,
+        Q: 'b,
+        D: 'b,
+        T: 'b,
+    {
+        let mut res = AttributeStore::new();
+        for (face0, face1) in from.into_iter().zip(to) {
+            let same_structure = face0.boundaries.len() == face1.boundaries.len()
+                && face0
+                    .boundaries
+                    .iter()
+                    .zip(&face1.boundaries)
+                    .all(|(wire0, wire1)| wire0.len() == wire1.len());
+            if !same_structure {
+                break;
+            }
+            if let Some(a) = self.face(face0) {
+                res.set_face(face1, a.clone());
+            }
+            let edges0 = face0.boundaries.iter().flatten();
+            let edges1 = face1.boundaries.iter().flatten();
+            for (edge0, edge1) in edges0.zip(edges1) {
+                if let Some(a) = self.edge(edge0) {
+                    res.set_edge(edge1, a.clone());
+                }
+                let (v0, v1) = (edge0.absolute_front(), edge1.absolute_front());
+                if let Some(a) = self.vertex(v0) {
+                    res.set_vertex(v1, a.clone());
+                }
+                let (v0, v1) = (edge0.absolute_back(), edge1.absolute_back());
+                if let Some(a) = self.vertex(v0) {
+                    res.set_vertex(v1, a.clone());
+                }
+            }
+        }
+        res
+    }
+}
+
+impl<P, C, S, A> Default for AttributeStore<P, C, S, A> {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+impl<P, C, S, A: Clone> Clone for AttributeStore<P, C, S, A> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            vertices: self.vertices.clone(),
+            edges: self.edges.clone(),
+            faces: self.faces.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<P, C, S, A: Debug> Debug for AttributeStore<P, C, S, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttributeStore")
+            .field("vertices", &self.vertices)
+            .field("edges", &self.edges)
+            .field("faces", &self.faces)
+            .finish()
+    }
+}
+
+impl<A> CompressedAttributes<A> {
+    #[inline(always)]
+    fn sorted(mut self) -> Self {
+        self.vertices.sort_by_key(|x| x.0);
+        self.edges.sort_by_key(|x| x.0);
+        self.faces.sort_by_key(|x| x.0);
+        self.vertices.dedup_by_key(|x| x.0);
+        self.edges.dedup_by_key(|x| x.0);
+        self
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> Shell<P, C, S> {
+    /// Compresses the shell together with the attributes of its elements.
+    ///
+    /// The attributes of the elements which are not contained in the shell are ignored,
+    /// and the ones referred to from the origins are recorded as the attributes of the elements.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// use truck_topology::attributes::*;
+    /// let v = Vertex::news([(); 3]);
+    /// let wire: Wire<(), ()> = vec![
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[2], &v[0], ()),
+    /// ].into();
+    /// let shell: Shell<_, _, _> = vec![Face::new(vec![wire], ())].into();
+    ///
+    /// let mut attributes = AttributeStore::new();
+    /// attributes.set_vertex(&v[1], 1);
+    /// attributes.set_face(&shell[0], 2);
+    ///
+    /// // `cshell` and `cattrs` are serializable.
+    /// let (cshell, cattrs) = shell.compress_with_attributes(&attributes);
+    /// let (shell, attributes) = Shell::extract_with_attributes(cshell, cattrs).unwrap();
+    /// assert_eq!(attributes.face(&shell[0]), Some(&2));
+    /// let v = shell[0].vertex_iter().collect::<Vec<_>>();
+    /// assert_eq!(attributes.vertex(&v[1]), Some(&1));
+    /// ```
+    pub fn compress_with_attributes<A: Clone>(
+        &self,
+        attributes: &AttributeStore<P, C, S, A>,
+    ) -> (CompressedShell<P, C, S>, CompressedAttributes<A>) {
+        let (cshell, vindices, eindices) = self.compress_with_indices();
+        let cattrs = CompressedAttributes {
+            vertices: self
+                .vertex_iter()
+                .filter_map(|v| Some((*vindices.get(&v.id())?, attributes.vertex(&v)?.clone())))
+                .collect(),
+            edges: self
+                .edge_iter()
+                .filter_map(|e| Some((*eindices.get(&e.id())?, attributes.edge(&e)?.clone())))
+                .collect(),
+            faces: self
+                .iter()
+                .enumerate()
+                .filter_map(|(i, face)| Some((i, attributes.face(face)?.clone())))
+                .collect(),
+        };
+        (cshell, cattrs.sorted())
+    }
+
+    /// Extracts the compressed shell and the compressed attributes of its elements.
+    /// # Failures
+    /// Returns [`Error::AttributeIndexOutOfRange`] if an attribute refers to a non-existent element,
+    /// in addition to the errors of [`Shell::extract`].
+    ///
+    /// [`Error::AttributeIndexOutOfRange`]: crate::errors::Error::AttributeIndexOutOfRange
+    pub fn extract_with_attributes<A>(
+        cshell: CompressedShell<P, C, S>,
+        cattrs: CompressedAttributes<A>,
+    ) -> Result<(Self, AttributeStore<P, C, S, A>)> {
+        let (shell, vertices, edges) = Self::extract_with_elements(cshell)?;
+        let mut attributes = AttributeStore::new();
+        let error = || Error::AttributeIndexOutOfRange;
+        for (i, a) in cattrs.vertices {
+            attributes.set_vertex(vertices.get(i).ok_or_else(error)?, a);
+        }
+        for (i, a) in cattrs.edges {
+            attributes.set_edge(edges.get(i).ok_or_else(error)?, a);
+        }
+        for (i, a) in cattrs.faces {
+            attributes.set_face(shell.get(i).ok_or_else(error)?, a);
+        }
+        Ok((shell, attributes))
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> Solid<P, C, S> {
+    /// Compresses the solid together with the attributes of its elements.
+    ///
+    /// The attributes are compressed for each boundary shell.
+    /// The attributes of the elements shared by several boundaries are recorded in each boundary.
+    pub fn compress_with_attributes<A: Clone>(
+        &self,
+        attributes: &AttributeStore<P, C, S, A>,
+    ) -> (CompressedSolid<P, C, S>, Vec<CompressedAttributes<A>>) {
+        let (boundaries, cattrs) = self
+            .boundaries()
+            .iter()
+            .map(|shell| shell.compress_with_attributes(attributes))
+            .unzip();
+        (CompressedSolid { boundaries }, cattrs)
+    }
+
+    /// Extracts the compressed solid and the compressed attributes of its elements.
+    /// # Failures
+    /// Returns [`Error::AttributeIndexOutOfRange`] if an attribute refers to a non-existent element
+    /// or the number of attributes does not match the number of boundaries,
+    /// in addition to the errors of [`Solid::extract`].
+    ///
+    /// [`Error::AttributeIndexOutOfRange`]: crate::errors::Error::AttributeIndexOutOfRange
+    pub fn extract_with_attributes<A>(
+        csolid: CompressedSolid<P, C, S>,
+        cattrs: Vec<CompressedAttributes<A>>,
+    ) -> Result<(Self, AttributeStore<P, C, S, A>)> {
+        if csolid.boundaries.len() != cattrs.len() {
+            return Err(Error::AttributeIndexOutOfRange);
+        }
+        let mut attributes = AttributeStore::new();
+        let shells = csolid
+            .boundaries
+            .into_iter()
+            .zip(cattrs)
+            .map(|(cshell, cattrs)| {
+                let (shell, attrs) = Shell::extract_with_attributes(cshell, cattrs)?;
+                attributes.vertices.extend(attrs.vertices);
+                attributes.edges.extend(attrs.edges);
+                attributes.faces.extend(attrs.faces);
+                Ok(shell)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok((Solid::try_new(shells)?, attributes))
+    }
+}
@@ -0,0 +1,251 @@
+use super::*;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+pub(super) struct Deserializer<'de> {
+    input: &'de [u8],
+    strings: Vec<&'de str>,
+}
+
+impl<'de> Deserializer<'de> {
+    pub(super) fn new(input: &'de [u8]) -> Self {
+        Self {
+            input,
+            strings: Vec::new(),
+        }
+    }
+
+    pub(super) fn end(&self) -> Result<()> {
+        match self.input.is_empty() {
+            true => Ok(()),
+            false => Err(Error::Corrupted("trailing bytes after the value".into())),
+        }
+    }
+
+    fn peek(&self) -> Result<u8> { self.input.first().copied().ok_or_else(unexpected_end) }
+
+    fn next(&mut self) -> Result<u8> {
+        let tag = self.peek()?;
+        self.input = &self.input[1..];
+        Ok(tag)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'de [u8]> {
+        if self.input.len() < len {
+            return Err(unexpected_end());
+        }
+        let (bytes, input) = self.input.split_at(len);
+        self.input = input;
+        Ok(bytes)
+    }
+
+    fn varint(&mut self) -> Result<u64> { read_varint(&mut self.input) }
+
+    fn len(&mut self) -> Result<usize> {
+        let len = self.varint()? as usize;
+        match len <= self.input.len() {
+            true => Ok(len),
+            false => Err(unexpected_end()),
+        }
+    }
+
+    fn string(&mut self, tag: u8) -> Result<&'de str> {
+        match tag {
+            tags::STRING => {
+                let len = self.len()?;
+                let s = std::str::from_utf8(self.bytes(len)?)
+                    .map_err(|e| Error::Corrupted(e.to_string()))?;
+                self.strings.push(s);
+                Ok(s)
+            }
+            _ => {
+                let index = self.varint()? as usize;
+                let s = self.strings.get(index);
+                s.copied()
+                    .ok_or_else(|| Error::Corrupted(format!("undefined string index {index}")))
+            }
+        }
+    }
+}
+
+fn unexpected_end() -> Error { Error::Corrupted("unexpected end of data".into()) }
+
+fn unexpected_tag(tag: u8) -> Error { Error::Corrupted(format!("unexpected tag 0x{tag:02x}")) }
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.next()? {
+            tags::UNIT => visitor.visit_unit(),
+            tags::SOME => visitor.visit_some(self),
+            tags::FALSE => visitor.visit_bool(false),
+            tags::TRUE => visitor.visit_bool(true),
+            tags::UNSIGNED => visitor.visit_u64(self.varint()?),
+            tags::NEGATIVE => visitor.visit_i64(!self.varint()? as i64),
+            tags::F32 => {
+                let bytes = self.bytes(4)?;
+                visitor.visit_f32(f32::from_le_bytes(bytes.try_into().unwrap()))
+            }
+            tags::F64 => {
+                let bytes = self.bytes(8)?;
+                visitor.visit_f64(f64::from_le_bytes(bytes.try_into().unwrap()))
+            }
+            tags::F64_INTEGRAL => {
+                let x = self.varint()?;
+                visitor.visit_f64(((x >> 1) as i64 ^ -((x & 1) as i64)) as f64)
+            }
+            tags::F64_SHORT => {
+                let bytes = self.bytes(4)?;
+                visitor.visit_f64(f32::from_le_bytes(bytes.try_into().unwrap()) as f64)
+            }
+            tag @ (tags::STRING | tags::STRING_REF) => {
+                visitor.visit_borrowed_str(self.string(tag)?)
+            }
+            tags::BYTES => {
+                let len = self.len()?;
+                visitor.visit_borrowed_bytes(self.bytes(len)?)
+            }
+            tags::SEQ => {
+                let remaining = Some(self.varint()?);
+                visitor.visit_seq(Compound {
+                    de: self,
+                    remaining,
+                })
+            }
+            tags::SEQ_OPEN => visitor.visit_seq(Compound {
+                de: self,
+                remaining: None,
+            }),
+            tags::MAP => {
+                let remaining = Some(self.varint()?);
+                visitor.visit_map(Compound {
+                    de: self,
+                    remaining,
+                })
+            }
+            tags::MAP_OPEN => visitor.visit_map(Compound {
+                de: self,
+                remaining: None,
+            }),
+            tag => Err(unexpected_tag(tag)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.next()? {
+            tags::UNIT => visitor.visit_none(),
+            tags::SOME => visitor.visit_some(self),
+            tag => Err(unexpected_tag(tag)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.next()? {
+            tag @ (tags::STRING | tags::STRING_REF) => {
+                visitor.visit_enum(self.string(tag)?.into_deserializer())
+            }
+            tags::MAP => match self.varint()? {
+                1 => visitor.visit_enum(self),
+                len => Err(Error::Corrupted(format!(
+                    "enum variant map of length {len}"
+                ))),
+            },
+            tag => Err(unexpected_tag(tag)),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool { false }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct Compound<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    // `None` if the elements are closed by the tag `CLOSE`.
+    remaining: Option<u64>,
+}
+
+impl Compound<'_, '_> {
+    fn has_next(&mut self) -> Result<bool> {
+        match &mut self.remaining {
+            Some(0) => Ok(false),
+            Some(remaining) => {
+                *remaining -= 1;
+                Ok(true)
+            }
+            None => match self.de.peek()? {
+                tags::CLOSE => {
+                    self.de.next()?;
+                    Ok(false)
+                }
+                _ => Ok(true),
+            },
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Compound<'_, 'de> {
+    type Error = Error;
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.has_next()? {
+            true => seed.deserialize(&mut *self.de).map(Some),
+            false => Ok(None),
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Compound<'_, 'de> {
+    type Error = Error;
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.has_next()? {
+            true => seed.deserialize(&mut *self.de).map(Some),
+            false => Ok(None),
+        }
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let variant = seed.deserialize(&mut *self)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+    fn unit_variant(self) -> Result<()> { de::Deserialize::deserialize(self) }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+}
@@ -0,0 +1,115 @@
+//! A simple LZ77 compression without external dependencies.
+//!
+//! The compressed data is the varint of the length of the original data followed by the
+//! sequences. A sequence consists of the varint of the number of literals, the literals,
+//! and, unless the data ends, the varints of the offset and the length of the match.
+
+use super::{read_varint, write_varint, Error, Result};
+
+const MIN_MATCH: usize = 4;
+const HASH_BITS: u32 = 16;
+
+#[inline(always)]
+fn hash(bytes: &[u8]) -> usize {
+    let x = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    (x.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize
+}
+
+pub(super) fn compress(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len() / 2);
+    write_varint(&mut output, input.len() as u64);
+    let mut table = vec![usize::MAX; 1 << HASH_BITS];
+    let (mut i, mut literal_start) = (0, 0);
+    while i + MIN_MATCH <= input.len() {
+        let h = hash(&input[i..]);
+        let candidate = table[h];
+        table[h] = i;
+        let matched = match candidate {
+            usize::MAX => 0,
+            j => input[j..]
+                .iter()
+                .zip(&input[i..])
+                .take_while(|(a, b)| a == b)
+                .count(),
+        };
+        if matched < MIN_MATCH {
+            i += 1;
+            continue;
+        }
+        write_varint(&mut output, (i - literal_start) as u64);
+        output.extend_from_slice(&input[literal_start..i]);
+        write_varint(&mut output, (i - candidate) as u64);
+        write_varint(&mut output, (matched - MIN_MATCH) as u64);
+        i += matched;
+        literal_start = i;
+    }
+    write_varint(&mut output, (input.len() - literal_start) as u64);
+    output.extend_from_slice(&input[literal_start..]);
+    output
+}
+
+pub(super) fn decompress(mut input: &[u8]) -> Result<Vec<u8>> {
+    let corrupted = || Error::Corrupted("invalid compressed sequence".into());
+    let read_usize =
+        |input: &mut &[u8]| usize::try_from(read_varint(input)?).map_err(|_| corrupted());
+    let len = read_usize(&mut input)?;
+    // `len` is not trusted: the output grows on demand beyond the bound by the input.
+    let mut output = Vec::with_capacity(len.min(input.len().saturating_mul(255)));
+    loop {
+        let literals = read_usize(&mut input)?;
+        let end = output.len().checked_add(literals).ok_or_else(corrupted)?;
+        if literals > input.len() || end > len {
+            return Err(corrupted());
+        }
+        output.extend_from_slice(&input[..literals]);
+        input = &input[literals..];
+        if output.len() == len {
+            return Ok(output);
+        }
+        let offset = read_usize(&mut input)?;
+        let matched = read_usize(&mut input)?
+            .checked_add(MIN_MATCH)
+            .ok_or_else(corrupted)?;
+        let end = output.len().checked_add(matched).ok_or_else(corrupted)?;
+        if offset == 0 || offset > output.len() || end > len {
+            return Err(corrupted());
+        }
+        let start = output.len() - offset;
+        (start..start + matched).for_each(|k| output.push(output[k]));
+    }
+}
+
+#[test]
+fn compress_decompress() {
+    let data: Vec<u8> = (0..10000).map(|i| ((i % 97) * (i % 13)) as u8).collect();
+    let compressed = compress(&data);
+    assert!(compressed.len() < data.len() / 2);
+    assert_eq!(decompress(&compressed).unwrap(), data);
+    assert_eq!(decompress(&compress(&[])).unwrap(), Vec::<u8>::new());
+    assert!(decompress(&compressed[..compressed.len() / 2]).is_err());
+}
+
+#[test]
+fn decompress_hostile_lengths() {
+    // the huge length of the original data
+    let mut data = Vec::new();
+    write_varint(&mut data, u64::MAX);
+    write_varint(&mut data, 1);
+    data.push(0);
+    assert!(decompress(&data).is_err());
+
+    // the huge length of a match
+    let mut data = Vec::new();
+    write_varint(&mut data, 1 << 40);
+    write_varint(&mut data, 1);
+    data.push(0);
+    write_varint(&mut data, 1);
+    write_varint(&mut data, u64::MAX);
+    assert!(decompress(&data).is_err());
+
+    // the huge number of literals
+    let mut data = Vec::new();
+    write_varint(&mut data, 1 << 40);
+    write_varint(&mut data, u64::MAX);
+    assert!(decompress(&data).is_err());
+}
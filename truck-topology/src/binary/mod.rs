@@ -0,0 +1,374 @@
+//! Compact binary format for serializable data, e.g. compressed shapes
+//!
+//! The JSON representation of shapes is huge since each floating point number is written in decimal
+//! and each field name is repeated. This module provides a binary format which stores the numbers
+//! in binary and each field name only once, and can be additionally compressed by a simple LZ77
+//! algorithm without external dependencies.
+//!
+//! # Format
+//! A record consists of the header and the payload.
+//! The header is the magic bytes `b"TRKB"`, the format version (`u16`), the flags (`u8`),
+//! and the length of the payload (`u64`), in little endian.
+//! The payload is the serialized value, compressed if the lowest bit of the flags is set.
+//!
+//! The payload is self-describing, i.e. it holds the types and the field names of the values,
+//! and the field names are stored once and referred by the indices afterwards.
+//! So the data written by older versions of truck are read as long as the data structures are
+//! compatible in serde, e.g. the fields added later have default values.
+//! The records of versions newer than [`FORMAT_VERSION`] are rejected by
+//! [`Error::UnsupportedVersion`].
+//!
+//! # Migration
+//! The payload is decoded by the reader of the version in the header. When the encoding of the
+//! payload is changed, [`FORMAT_VERSION`] is bumped and the reader of the previous version is
+//! kept, which migrates the old records into the current values. Only the version 1 exists so far.
+//!
+//! Several records can be written in a stream by [`Writer`] and read one by one by [`Reader`].
+//!
+//! # Examples
+//! ```
+//! use truck_topology::*;
+//! use truck_topology::{binary::{self, Compression}, compress::CompressedShell};
+//! let v = Vertex::news([0, 1, 2]);
+//! let wire: Wire<usize, usize> = vec![
+//!     Edge::new(&v[0], &v[1], 0),
+//!     Edge::new(&v[1], &v[2], 1),
+//!     Edge::new(&v[2], &v[0], 2),
+//! ].into();
+//! let face = Face::new(vec![wire], 0);
+//! let shell: Shell<usize, usize, usize> = vec![face.clone(), face.inverse()].into();
+//!
+//! let bytes = binary::to_vec(&shell.compress(), Compression::Lz).unwrap();
+//! let cshell: CompressedShell<usize, usize, usize> = binary::from_slice(&bytes).unwrap();
+//! assert_eq!(cshell, shell.compress());
+//! ```
+
+use rustc_hash::FxHashMap as HashMap;
+use serde::{de::DeserializeOwned, Serialize};
+use std::io::{Read, Write};
+use thiserror::Error;
+
+mod de;
+mod lz;
+mod ser;
+
+/// The current version of the binary format.
+pub const FORMAT_VERSION: u16 = 1;
+
+const MAGIC: [u8; 4] = *b"TRKB";
+const HEADER_LEN: usize = 15;
+const COMPRESSED: u8 = 1;
+
+/// The tags of the values in the payload
+mod tags {
+    pub const UNIT: u8 = 0x00;
+    pub const SOME: u8 = 0x01;
+    pub const FALSE: u8 = 0x02;
+    pub const TRUE: u8 = 0x03;
+    pub const UNSIGNED: u8 = 0x04;
+    pub const NEGATIVE: u8 = 0x05;
+    pub const F32: u8 = 0x06;
+    pub const F64: u8 = 0x07;
+    pub const STRING: u8 = 0x08;
+    pub const STRING_REF: u8 = 0x09;
+    pub const BYTES: u8 = 0x0a;
+    pub const SEQ: u8 = 0x0b;
+    pub const SEQ_OPEN: u8 = 0x0c;
+    pub const MAP: u8 = 0x0d;
+    pub const MAP_OPEN: u8 = 0x0e;
+    pub const CLOSE: u8 = 0x0f;
+    // `f64` with an integral value, stored as a zigzag varint
+    pub const F64_INTEGRAL: u8 = 0x10;
+    // `f64` exactly representable by `f32`, stored as `f32`
+    pub const F64_SHORT: u8 = 0x11;
+}
+
+/// Errors of reading and writing the binary format
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The error of the underlying reader or writer.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The data does not start with the magic bytes.
+    #[error("The data is not in the truck binary format.")]
+    InvalidMagic,
+    /// The data is written in an unsupported version of the format.
+    #[error("The format version {found} is not supported. The supported version is up to {FORMAT_VERSION}.")]
+    UnsupportedVersion {
+        /// the version of the data
+        found: u16,
+    },
+    /// The data is broken.
+    #[error("The data is corrupted: {0}")]
+    Corrupted(String),
+    /// The error reported by the serialized or deserialized value.
+    #[error("{0}")]
+    Message(String),
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self { Error::Message(msg.to_string()) }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self { Error::Message(msg.to_string()) }
+}
+
+/// `Result` with the errors of the binary format.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Whether the payload is compressed or not.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// The payload is stored as is.
+    #[default]
+    None,
+    /// The payload is compressed by a simple LZ77 algorithm.
+    Lz,
+}
+
+fn write_varint(output: &mut Vec<u8>, mut x: u64) {
+    while x >= 0x80 {
+        output.push(x as u8 | 0x80);
+        x >>= 7;
+    }
+    output.push(x as u8);
+}
+
+fn read_varint(input: &mut &[u8]) -> Result<u64> {
+    let mut x = 0;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input
+            .split_first()
+            .ok_or_else(|| Error::Corrupted("unexpected end of data".into()))?;
+        *input = rest;
+        x |= ((byte & 0x7f) as u64) << shift;
+        if byte < 0x80 {
+            return Ok(x);
+        }
+    }
+    Err(Error::Corrupted("too long varint".into()))
+}
+
+fn encode<T: ?Sized + Serialize>(value: &T, compression: Compression) -> Result<Vec<u8>> {
+    let mut payload = Vec::new();
+    value.serialize(&mut ser::Serializer::new(&mut payload))?;
+    let (flags, payload) = match compression {
+        Compression::None => (0, payload),
+        Compression::Lz => (COMPRESSED, lz::compress(&payload)),
+    };
+    let mut record = Vec::with_capacity(HEADER_LEN + payload.len());
+    record.extend_from_slice(&MAGIC);
+    record.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    record.push(flags);
+    record.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    record.extend_from_slice(&payload);
+    Ok(record)
+}
+
+struct Header {
+    version: u16,
+    flags: u8,
+    len: u64,
+}
+
+impl Header {
+    fn parse(bytes: &[u8; HEADER_LEN]) -> Result<Self> {
+        if bytes[..4] != MAGIC {
+            return Err(Error::InvalidMagic);
+        }
+        let header = Header {
+            version: u16::from_le_bytes([bytes[4], bytes[5]]),
+            flags: bytes[6],
+            len: u64::from_le_bytes(bytes[7..].try_into().unwrap()),
+        };
+        if header.version == 0 || header.version > FORMAT_VERSION {
+            return Err(Error::UnsupportedVersion {
+                found: header.version,
+            });
+        }
+        if header.flags & !COMPRESSED != 0 {
+            return Err(Error::Corrupted(format!(
+                "unknown flags 0x{:02x}",
+                header.flags
+            )));
+        }
+        Ok(header)
+    }
+}
+
+fn decode<T: DeserializeOwned>(header: &Header, payload: Vec<u8>) -> Result<T> {
+    let payload = match header.flags & COMPRESSED {
+        0 => payload,
+        _ => lz::decompress(&payload)?,
+    };
+    match header.version {
+        1 => read_v1(&payload),
+        found => Err(Error::UnsupportedVersion { found }),
+    }
+}
+
+/// Reads the payload of the version 1, the current version.
+fn read_v1<T: DeserializeOwned>(payload: &[u8]) -> Result<T> {
+    let mut deserializer = de::Deserializer::new(payload);
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Serializes `value` into a record of the binary format.
+#[inline(always)]
+pub fn to_vec<T: ?Sized + Serialize>(value: &T, compression: Compression) -> Result<Vec<u8>> {
+    encode(value, compression)
+}
+
+/// Writes `value` to `writer` as a record of the binary format.
+#[inline(always)]
+pub fn to_writer<W: Write, T: ?Sized + Serialize>(
+    writer: W,
+    value: &T,
+    compression: Compression,
+) -> Result<()> {
+    Writer::new(writer, compression).write(value)
+}
+
+/// Deserializes a record of the binary format.
+/// # Failures
+/// Returns an error if `bytes` is not exactly one valid record.
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    let mut reader = Reader::new(bytes);
+    let value = reader.read()?.ok_or(Error::Corrupted("no record".into()))?;
+    match reader.reader.is_empty() {
+        true => Ok(value),
+        false => Err(Error::Corrupted("trailing bytes after the record".into())),
+    }
+}
+
+/// Reads a record of the binary format from `reader`.
+#[inline(always)]
+pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T> {
+    Reader::new(reader)
+        .read()?
+        .ok_or(Error::Corrupted("no record".into()))
+}
+
+/// Writes the records of the binary format to a stream.
+#[derive(Debug)]
+pub struct Writer<W> {
+    writer: W,
+    compression: Compression,
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates the writer compressing the records by `compression`.
+    #[inline(always)]
+    pub fn new(writer: W, compression: Compression) -> Self {
+        Self {
+            writer,
+            compression,
+        }
+    }
+    /// Writes `value` as a record.
+    #[inline(always)]
+    pub fn write<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        let record = encode(value, self.compression)?;
+        self.writer.write_all(&record)?;
+        Ok(())
+    }
+    /// Returns the underlying writer.
+    #[inline(always)]
+    pub fn into_inner(self) -> W { self.writer }
+}
+
+/// Reads the records of the binary format from a stream one by one.
+/// # Examples
+/// ```
+/// use truck_topology::binary::*;
+/// let mut writer = Writer::new(Vec::new(), Compression::None);
+/// writer.write(&vec![1.0, 2.0]).unwrap();
+/// writer.write(&vec![3.0]).unwrap();
+/// let bytes = writer.into_inner();
+///
+/// let mut reader = Reader::new(bytes.as_slice());
+/// assert!(reader.skip().unwrap());
+/// assert_eq!(reader.read::<Vec<f64>>().unwrap(), Some(vec![3.0]));
+/// assert_eq!(reader.read::<Vec<f64>>().unwrap(), None);
+/// ```
+#[derive(Debug)]
+pub struct Reader<R> {
+    reader: R,
+}
+
+impl<R: Read> Reader<R> {
+    /// Creates the reader.
+    #[inline(always)]
+    pub fn new(reader: R) -> Self { Self { reader } }
+
+    fn read_header(&mut self) -> Result<Option<Header>> {
+        let mut bytes = [0; HEADER_LEN];
+        let mut filled = 0;
+        while filled < HEADER_LEN {
+            match self.reader.read(&mut bytes[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(Error::Corrupted("unexpected end of header".into())),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Header::parse(&bytes).map(Some)
+    }
+
+    fn read_payload(&mut self, header: &Header) -> Result<Vec<u8>> {
+        let mut payload = Vec::new();
+        (&mut self.reader)
+            .take(header.len)
+            .read_to_end(&mut payload)?;
+        match payload.len() as u64 == header.len {
+            true => Ok(payload),
+            false => Err(Error::Corrupted("unexpected end of payload".into())),
+        }
+    }
+
+    /// Reads the next record. Returns `None` if the stream ends.
+    pub fn read<T: DeserializeOwned>(&mut self) -> Result<Option<T>> {
+        let Some(header) = self.read_header()? else {
+            return Ok(None);
+        };
+        let payload = self.read_payload(&header)?;
+        decode(&header, payload).map(Some)
+    }
+
+    /// Skips the next record without deserializing it. Returns `false` if the stream ends.
+    pub fn skip(&mut self) -> Result<bool> {
+        let Some(header) = self.read_header()? else {
+            return Ok(false);
+        };
+        let skipped = std::io::copy(
+            &mut (&mut self.reader).take(header.len),
+            &mut std::io::sink(),
+        )?;
+        match skipped == header.len {
+            true => Ok(true),
+            false => Err(Error::Corrupted("unexpected end of payload".into())),
+        }
+    }
+
+    /// Returns the iterator reading the records until the stream ends.
+    /// The iteration stops after an error is returned.
+    pub fn iter<T: DeserializeOwned>(&mut self) -> impl Iterator<Item = Result<T>> + '_ {
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let res = self.read().transpose();
+            failed = matches!(res, Some(Err(_)));
+            res
+        })
+    }
+
+    /// Returns the underlying reader.
+    #[inline(always)]
+    pub fn into_inner(self) -> R { self.reader }
+}
@@ -0,0 +1,247 @@
+use super::*;
+use serde::ser::{self, Serialize};
+
+pub(super) struct Serializer<'a> {
+    output: &'a mut Vec<u8>,
+    strings: HashMap<String, u64>,
+}
+
+impl<'a> Serializer<'a> {
+    pub(super) fn new(output: &'a mut Vec<u8>) -> Self {
+        Self {
+            output,
+            strings: HashMap::default(),
+        }
+    }
+
+    #[inline(always)]
+    fn tag(&mut self, tag: u8) { self.output.push(tag) }
+
+    #[inline(always)]
+    fn varint(&mut self, tag: u8, x: u64) {
+        self.tag(tag);
+        write_varint(self.output, x);
+    }
+
+    /// Writes the string. The second and subsequent occurrences are written by the indices.
+    fn string(&mut self, s: &str) {
+        match self.strings.get(s) {
+            Some(&index) => self.varint(tags::STRING_REF, index),
+            None => {
+                let index = self.strings.len() as u64;
+                self.strings.insert(s.to_string(), index);
+                self.varint(tags::STRING, s.len() as u64);
+                self.output.extend_from_slice(s.as_bytes());
+            }
+        }
+    }
+
+    fn length_or_open(&mut self, len: Option<usize>, tag: u8, open_tag: u8) {
+        match len {
+            Some(len) => self.varint(tag, len as u64),
+            None => self.tag(open_tag),
+        }
+    }
+
+    fn variant_map(&mut self, variant: &str) {
+        self.varint(tags::MAP, 1);
+        self.string(variant);
+    }
+}
+
+impl<'b, 'a> ser::Serializer for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'b, 'a>;
+    type SerializeTuple = Compound<'b, 'a>;
+    type SerializeTupleStruct = Compound<'b, 'a>;
+    type SerializeTupleVariant = Compound<'b, 'a>;
+    type SerializeMap = Compound<'b, 'a>;
+    type SerializeStruct = Compound<'b, 'a>;
+    type SerializeStructVariant = Compound<'b, 'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.tag(if v { tags::TRUE } else { tags::FALSE });
+        Ok(())
+    }
+    fn serialize_i8(self, v: i8) -> Result<()> { self.serialize_i64(v as i64) }
+    fn serialize_i16(self, v: i16) -> Result<()> { self.serialize_i64(v as i64) }
+    fn serialize_i32(self, v: i32) -> Result<()> { self.serialize_i64(v as i64) }
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        match v < 0 {
+            true => self.varint(tags::NEGATIVE, !(v as u64)),
+            false => self.varint(tags::UNSIGNED, v as u64),
+        }
+        Ok(())
+    }
+    fn serialize_u8(self, v: u8) -> Result<()> { self.serialize_u64(v as u64) }
+    fn serialize_u16(self, v: u16) -> Result<()> { self.serialize_u64(v as u64) }
+    fn serialize_u32(self, v: u32) -> Result<()> { self.serialize_u64(v as u64) }
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.varint(tags::UNSIGNED, v);
+        Ok(())
+    }
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.tag(tags::F32);
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        const MAX_INTEGRAL: f64 = (1u64 << 53) as f64;
+        if v.trunc() == v && v.abs() < MAX_INTEGRAL && !(v == 0.0 && v.is_sign_negative()) {
+            let x = v as i64;
+            self.varint(tags::F64_INTEGRAL, ((x << 1) ^ (x >> 63)) as u64);
+        } else if (v as f32) as f64 == v {
+            self.tag(tags::F64_SHORT);
+            self.output.extend_from_slice(&(v as f32).to_le_bytes());
+        } else {
+            self.tag(tags::F64);
+            self.output.extend_from_slice(&v.to_le_bytes());
+        }
+        Ok(())
+    }
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.string(v);
+        Ok(())
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.varint(tags::BYTES, v.len() as u64);
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+    fn serialize_none(self) -> Result<()> { self.serialize_unit() }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<()> {
+        self.tag(tags::SOME);
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<()> {
+        self.tag(tags::UNIT);
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<()> { self.serialize_unit() }
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<()> {
+        self.serialize_str(variant)
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.variant_map(variant);
+        value.serialize(self)
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<Compound<'b, 'a>> {
+        self.length_or_open(len, tags::SEQ, tags::SEQ_OPEN);
+        Ok(Compound::new(self, len.is_none()))
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'b, 'a>> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<Compound<'b, 'a>> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'b, 'a>> {
+        self.variant_map(variant);
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_map(self, len: Option<usize>) -> Result<Compound<'b, 'a>> {
+        self.length_or_open(len, tags::MAP, tags::MAP_OPEN);
+        Ok(Compound::new(self, len.is_none()))
+    }
+    // The number of fields is not known in advance because of `skip_serializing_if`.
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Compound<'b, 'a>> {
+        self.serialize_map(None)
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Compound<'b, 'a>> {
+        self.variant_map(variant);
+        self.serialize_map(None)
+    }
+}
+
+pub(super) struct Compound<'b, 'a> {
+    ser: &'b mut Serializer<'a>,
+    open: bool,
+}
+
+impl<'b, 'a> Compound<'b, 'a> {
+    fn new(ser: &'b mut Serializer<'a>, open: bool) -> Self { Self { ser, open } }
+    fn end(self) -> Result<()> {
+        if self.open {
+            self.ser.tag(tags::CLOSE);
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_compound {
+    ($trait: ident, $method: ident) => {
+        impl<'b, 'a> ser::$trait for Compound<'b, 'a> {
+            type Ok = ();
+            type Error = Error;
+            fn $method<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+                value.serialize(&mut *self.ser)
+            }
+            fn end(self) -> Result<()> { Compound::end(self) }
+        }
+    };
+}
+impl_compound!(SerializeSeq, serialize_element);
+impl_compound!(SerializeTuple, serialize_element);
+impl_compound!(SerializeTupleStruct, serialize_field);
+impl_compound!(SerializeTupleVariant, serialize_field);
+
+impl<'b, 'a> ser::SerializeMap for Compound<'b, 'a> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut *self.ser)
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut *self.ser)
+    }
+    fn end(self) -> Result<()> { Compound::end(self) }
+}
+
+macro_rules! impl_struct_compound {
+    ($trait: ident) => {
+        impl<'b, 'a> ser::$trait for Compound<'b, 'a> {
+            type Ok = ();
+            type Error = Error;
+            fn serialize_field<T: ?Sized + Serialize>(
+                &mut self,
+                key: &'static str,
+                value: &T,
+            ) -> Result<()> {
+                self.ser.string(key);
+                value.serialize(&mut *self.ser)
+            }
+            fn end(self) -> Result<()> { Compound::end(self) }
+        }
+    };
+}
+impl_struct_compound!(SerializeStruct);
+impl_struct_compound!(SerializeStructVariant);
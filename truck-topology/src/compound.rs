@@ -0,0 +1,280 @@
+use crate::*;
+use truck_base::cgmath64::MetricSpace;
+
+type CompoundElements<P, C, S> = (
+    Vec<Solid<P, C, S>>,
+    Vec<Shell<P, C, S>>,
+    Vec<Wire<P, C>>,
+    Vec<Vertex<P>>,
+);
+
+impl<P, C, S> Compound<P, C, S> {
+    /// Creates the empty compound.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Compound {
+            solids: Vec::new(),
+            shells: Vec::new(),
+            wires: Vec::new(),
+            vertices: Vec::new(),
+        }
+    }
+
+    /// Creates the compound from the solids, shells, wires and free vertices.
+    #[inline(always)]
+    pub const fn from_elements(
+        solids: Vec<Solid<P, C, S>>,
+        shells: Vec<Shell<P, C, S>>,
+        wires: Vec<Wire<P, C>>,
+        vertices: Vec<Vertex<P>>,
+    ) -> Self {
+        Compound {
+            solids,
+            shells,
+            wires,
+            vertices,
+        }
+    }
+
+    /// Returns the reference of the solids.
+    #[inline(always)]
+    pub const fn solids(&self) -> &Vec<Solid<P, C, S>> { &self.solids }
+    /// Returns the reference of the shells.
+    #[inline(always)]
+    pub const fn shells(&self) -> &Vec<Shell<P, C, S>> { &self.shells }
+    /// Returns the reference of the wires.
+    #[inline(always)]
+    pub const fn wires(&self) -> &Vec<Wire<P, C>> { &self.wires }
+    /// Returns the reference of the free vertices.
+    #[inline(always)]
+    pub const fn vertices(&self) -> &Vec<Vertex<P>> { &self.vertices }
+
+    /// Adds a solid to the compound.
+    #[inline(always)]
+    pub fn push_solid(&mut self, solid: Solid<P, C, S>) { self.solids.push(solid) }
+    /// Adds a shell to the compound.
+    #[inline(always)]
+    pub fn push_shell(&mut self, shell: Shell<P, C, S>) { self.shells.push(shell) }
+    /// Adds a wire to the compound.
+    #[inline(always)]
+    pub fn push_wire(&mut self, wire: Wire<P, C>) { self.wires.push(wire) }
+    /// Adds a free vertex to the compound.
+    #[inline(always)]
+    pub fn push_vertex(&mut self, vertex: Vertex<P>) { self.vertices.push(vertex) }
+
+    /// Returns the solids, shells, wires and free vertices.
+    #[inline(always)]
+    pub fn into_elements(self) -> CompoundElements<P, C, S> {
+        (self.solids, self.shells, self.wires, self.vertices)
+    }
+
+    /// Returns `true` if the compound has no elements.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.solids.is_empty()
+            && self.shells.is_empty()
+            && self.wires.is_empty()
+            && self.vertices.is_empty()
+    }
+
+    /// Returns an iterator over the faces of the solids and the shells.
+    #[inline(always)]
+    pub fn face_iter(&self) -> impl Iterator<Item = &Face<P, C, S>> {
+        let solid_faces = self.solids.iter().flat_map(Solid::face_iter);
+        solid_faces.chain(self.shells.iter().flatten())
+    }
+
+    /// Returns an iterator over the edges of the solids, the shells and the wires.
+    /// The edges shared by some faces are returned as many times as they are used.
+    #[inline(always)]
+    pub fn edge_iter(&self) -> impl Iterator<Item = Edge<P, C>> + '_ {
+        let face_edges = self.face_iter().flat_map(Face::boundaries).flatten();
+        face_edges.chain(self.wires.iter().flatten().cloned())
+    }
+
+    /// Returns an iterator over the vertices of all the elements.
+    /// The vertices shared by some edges are returned as many times as they are used.
+    #[inline(always)]
+    pub fn vertex_iter(&self) -> impl Iterator<Item = Vertex<P>> + '_ {
+        let face_vertices = self.face_iter().flat_map(Face::vertex_iter);
+        let wire_vertices = self.wires.iter().flat_map(Wire::vertex_iter);
+        face_vertices
+            .chain(wire_vertices)
+            .chain(self.vertices.iter().cloned())
+    }
+
+    /// Returns a new compound whose surfaces are mapped by `surface_mapping`,
+    /// curves are mapped by `curve_mapping` and points are mapped by `point_mapping`.
+    /// The elements are mapped independently, so the vertices and the edges shared by
+    /// two elements are not shared by the mapped elements.
+    /// # Remarks
+    /// Accessing geometry elements directly in the closure will result in a deadlock.
+    /// So, this method does not appear to the document.
+    #[doc(hidden)]
+    pub fn try_mapped<Q, D, T>(
+        &self,
+        mut point_mapping: impl FnMut(&P) -> Option<Q>,
+        mut curve_mapping: impl FnMut(&C) -> Option<D>,
+        mut surface_mapping: impl FnMut(&S) -> Option<T>,
+    ) -> Option<Compound<Q, D, T>> {
+        let solids = self
+            .solids
+            .iter()
+            .map(|solid| {
+                solid.try_mapped(&mut point_mapping, &mut curve_mapping, &mut surface_mapping)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let shells = self
+            .shells
+            .iter()
+            .map(|shell| {
+                shell.try_mapped(&mut point_mapping, &mut curve_mapping, &mut surface_mapping)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let wires = self
+            .wires
+            .iter()
+            .map(|wire| wire.try_mapped(&mut point_mapping, &mut curve_mapping))
+            .collect::<Option<Vec<_>>>()?;
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|vertex| vertex.try_mapped(&mut point_mapping))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Compound::from_elements(solids, shells, wires, vertices))
+    }
+
+    /// Returns a new compound whose surfaces are mapped by `surface_mapping`,
+    /// curves are mapped by `curve_mapping` and points are mapped by `point_mapping`.
+    /// The elements are mapped independently, so the vertices and the edges shared by
+    /// two elements are not shared by the mapped elements.
+    /// # Remarks
+    /// Accessing geometry elements directly in the closure will result in a deadlock.
+    /// So, this method does not appear to the document.
+    #[doc(hidden)]
+    pub fn mapped<Q, D, T>(
+        &self,
+        mut point_mapping: impl FnMut(&P) -> Q,
+        mut curve_mapping: impl FnMut(&C) -> D,
+        mut surface_mapping: impl FnMut(&S) -> T,
+    ) -> Compound<Q, D, T> {
+        let solids = self
+            .solids
+            .iter()
+            .map(|solid| solid.mapped(&mut point_mapping, &mut curve_mapping, &mut surface_mapping))
+            .collect();
+        let shells = self
+            .shells
+            .iter()
+            .map(|shell| shell.mapped(&mut point_mapping, &mut curve_mapping, &mut surface_mapping))
+            .collect();
+        let wires = self
+            .wires
+            .iter()
+            .map(|wire| wire.mapped(&mut point_mapping, &mut curve_mapping))
+            .collect();
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|vertex| vertex.mapped(&mut point_mapping))
+            .collect();
+        Compound::from_elements(solids, shells, wires, vertices)
+    }
+
+    /// Returns the consistence of the geometry of end vertices
+    /// and the geometry of edge.
+    #[inline(always)]
+    pub fn is_geometric_consistent(&self) -> bool
+    where
+        P: Tolerance,
+        C: BoundedCurve<Point = P>,
+        S: IncludeCurve<C>, {
+        self.solids.iter().all(Solid::is_geometric_consistent)
+            && self.shells.iter().all(Shell::is_geometric_consistent)
+            && self.wires.iter().all(Wire::is_geometric_consistent)
+    }
+
+    /// Returns the consistence of the geometry of end vertices
+    /// and the geometry of edge, including the curves of the edges having their own tolerances.
+    /// cf. [`Face::is_geometric_consistent_with_tolerances`]
+    #[inline(always)]
+    pub fn is_geometric_consistent_with_tolerances(&self) -> bool
+    where
+        P: Tolerance + MetricSpace<Metric = f64> + Copy,
+        C: BoundedCurve<Point = P>,
+        S: IncludeCurve<C> + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+    {
+        self.solids
+            .iter()
+            .all(Solid::is_geometric_consistent_with_tolerances)
+            && self
+                .shells
+                .iter()
+                .all(Shell::is_geometric_consistent_with_tolerances)
+            && self.wires.iter().all(Wire::is_geometric_consistent)
+    }
+}
+
+impl<P, C, S> Default for Compound<P, C, S> {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+impl<P, C, S> From<Solid<P, C, S>> for Compound<P, C, S> {
+    #[inline(always)]
+    fn from(solid: Solid<P, C, S>) -> Self {
+        Compound::from_elements(vec![solid], Vec::new(), Vec::new(), Vec::new())
+    }
+}
+
+impl<P, C, S> From<Shell<P, C, S>> for Compound<P, C, S> {
+    #[inline(always)]
+    fn from(shell: Shell<P, C, S>) -> Self {
+        Compound::from_elements(Vec::new(), vec![shell], Vec::new(), Vec::new())
+    }
+}
+
+impl<P, C, S> From<Wire<P, C>> for Compound<P, C, S> {
+    #[inline(always)]
+    fn from(wire: Wire<P, C>) -> Self {
+        Compound::from_elements(Vec::new(), Vec::new(), vec![wire], Vec::new())
+    }
+}
+
+impl<P, C, S> FromIterator<Solid<P, C, S>> for Compound<P, C, S> {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = Solid<P, C, S>>>(iter: I) -> Self {
+        Compound::from_elements(
+            iter.into_iter().collect(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+}
+
+impl<P, C, S> FromIterator<Shell<P, C, S>> for Compound<P, C, S> {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = Shell<P, C, S>>>(iter: I) -> Self {
+        Compound::from_elements(
+            Vec::new(),
+            iter.into_iter().collect(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+}
+
+impl<P, C, S> Extend<Solid<P, C, S>> for Compound<P, C, S> {
+    #[inline(always)]
+    fn extend<I: IntoIterator<Item = Solid<P, C, S>>>(&mut self, iter: I) {
+        self.solids.extend(iter)
+    }
+}
+
+impl<P, C, S> Extend<Shell<P, C, S>> for Compound<P, C, S> {
+    #[inline(always)]
+    fn extend<I: IntoIterator<Item = Shell<P, C, S>>>(&mut self, iter: I) {
+        self.shells.extend(iter)
+    }
+}
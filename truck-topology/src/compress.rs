@@ -19,17 +19,68 @@ pub struct CompressedEdge<C> {
     pub vertices: (usize, usize),
     /// curve geometry of the edge
     pub curve: C,
+    /// the tolerance of the edge, `None` if the global `TOLERANCE` is used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<f64>,
+    /// the stable id of the edge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stable_id: Option<StableID>,
 }
 
 impl<C> CompressedEdge<C> {
+    /// Creates a compressed edge without the tolerance and the stable id.
+    #[inline(always)]
+    pub fn new(vertices: (usize, usize), curve: C) -> Self {
+        Self {
+            vertices,
+            curve,
+            tolerance: None,
+            stable_id: None,
+        }
+    }
     #[inline(always)]
     fn create_edge<P>(self, v: &[Vertex<P>]) -> Result<Edge<P, C>> {
         let front = &v[self.vertices.0];
         let back = &v[self.vertices.1];
-        Edge::try_new(front, back, self.curve)
+        let edge = Edge::try_new(front, back, self.curve)?;
+        edge.set_tolerance(checked_tolerance(self.tolerance)?);
+        edge.set_stable_id(self.stable_id);
+        Ok(edge)
+    }
+}
+
+/// Returns `tolerance` if it is a valid local tolerance, which may be deserialized from
+/// untrusted data.
+#[inline(always)]
+fn checked_tolerance(tolerance: Option<f64>) -> Result<Option<f64>> {
+    match tolerance {
+        Some(tol) if tol.is_nan() || tol < TOLERANCE => Err(errors::Error::InvalidTolerance),
+        _ => Ok(tolerance),
     }
 }
 
+/// Creates the vertices and sets the tolerances and the stable ids.
+/// `tolerances` and `stable_ids` are empty or have the same length as `points`.
+fn create_vertices<P>(
+    points: Vec<P>,
+    tolerances: Vec<Option<f64>>,
+    stable_ids: Vec<Option<StableID>>,
+) -> Result<Vec<Vertex<P>>> {
+    let vertices: Vec<_> = points.into_iter().map(Vertex::new).collect();
+    vertices
+        .iter()
+        .zip(tolerances)
+        .try_for_each(|(vertex, tolerance)| {
+            vertex.set_tolerance(checked_tolerance(tolerance)?);
+            Ok(())
+        })?;
+    vertices
+        .iter()
+        .zip(stable_ids)
+        .for_each(|(vertex, stable_id)| vertex.set_stable_id(stable_id));
+    Ok(vertices)
+}
+
 /// The index of an edge in `CompressedShell`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CompressedEdgeIndex {
@@ -52,9 +103,22 @@ pub struct CompressedFace<S> {
     pub orientation: bool,
     /// surface geometry of the face
     pub surface: S,
+    /// the stable id of the face
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stable_id: Option<StableID>,
 }
 
 impl<S> CompressedFace<S> {
+    /// Creates a compressed face without the stable id.
+    #[inline(always)]
+    pub fn new(boundaries: Vec<Vec<CompressedEdgeIndex>>, orientation: bool, surface: S) -> Self {
+        Self {
+            boundaries,
+            orientation,
+            surface,
+            stable_id: None,
+        }
+    }
     fn create_face<P, C>(self, edges: &[Edge<P, C>]) -> Result<Face<P, C, S>> {
         let wires: Vec<Wire<P, C>> = self
             .boundaries
@@ -71,6 +135,7 @@ impl<S> CompressedFace<S> {
             })
             .collect();
         let mut face = Face::try_new(wires, self.surface)?;
+        face.set_stable_id(self.stable_id);
         if !self.orientation {
             face.invert();
         }
@@ -83,12 +148,41 @@ impl<S> CompressedFace<S> {
 pub struct CompressedShell<P, C, S> {
     /// all geometries of vertices
     pub vertices: Vec<P>,
+    /// the tolerances of vertices, empty if all the vertices use the global `TOLERANCE`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vertex_tolerances: Vec<Option<f64>>,
+    /// the stable ids of vertices, empty if no vertex has the stable id
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vertex_stable_ids: Vec<Option<StableID>>,
     /// all geometries and end vertices of edges
     pub edges: Vec<CompressedEdge<C>>,
     /// all geometries and boundaries of faces
     pub faces: Vec<CompressedFace<S>>,
 }
 
+impl<P, C, S> CompressedShell<P, C, S> {
+    /// Creates a compressed shell without the tolerances and the stable ids of vertices.
+    #[inline(always)]
+    pub fn new(
+        vertices: Vec<P>,
+        edges: Vec<CompressedEdge<C>>,
+        faces: Vec<CompressedFace<S>>,
+    ) -> Self {
+        Self {
+            vertices,
+            vertex_tolerances: Vec::new(),
+            vertex_stable_ids: Vec::new(),
+            edges,
+            faces,
+        }
+    }
+}
+
+impl<P, C, S> Default for CompressedShell<P, C, S> {
+    #[inline(always)]
+    fn default() -> Self { Self::new(Vec::new(), Vec::new(), Vec::new()) }
+}
+
 /// Serialized compressed solid
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CompressedSolid<P, C, S> {
@@ -96,8 +190,61 @@ pub struct CompressedSolid<P, C, S> {
     pub boundaries: Vec<CompressedShell<P, C, S>>,
 }
 
+/// Serialized compressed wire
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompressedWire<P, C> {
+    /// all geometries of vertices
+    pub vertices: Vec<P>,
+    /// the tolerances of vertices, empty if all the vertices use the global `TOLERANCE`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vertex_tolerances: Vec<Option<f64>>,
+    /// the stable ids of vertices, empty if no vertex has the stable id
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vertex_stable_ids: Vec<Option<StableID>>,
+    /// all geometries and end vertices of edges
+    pub edges: Vec<CompressedEdge<C>>,
+    /// the edges of the wire
+    pub wire: Vec<CompressedEdgeIndex>,
+}
+
+impl<P, C> CompressedWire<P, C> {
+    /// Creates a compressed wire without the tolerances and the stable ids of vertices.
+    #[inline(always)]
+    pub fn new(
+        vertices: Vec<P>,
+        edges: Vec<CompressedEdge<C>>,
+        wire: Vec<CompressedEdgeIndex>,
+    ) -> Self {
+        Self {
+            vertices,
+            vertex_tolerances: Vec::new(),
+            vertex_stable_ids: Vec::new(),
+            edges,
+            wire,
+        }
+    }
+}
+
+impl<P, C> Default for CompressedWire<P, C> {
+    #[inline(always)]
+    fn default() -> Self { Self::new(Vec::new(), Vec::new(), Vec::new()) }
+}
+
+/// Serialized compressed compound
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompressedCompound<P, C, S> {
+    /// all solids of compound
+    pub solids: Vec<CompressedSolid<P, C, S>>,
+    /// all shells of compound
+    pub shells: Vec<CompressedShell<P, C, S>>,
+    /// all wires of compound
+    pub wires: Vec<CompressedWire<P, C>>,
+    /// all geometries of free vertices
+    pub vertices: Vec<P>,
+}
+
 struct CompressDirector<P, C> {
-    vmap: HashMap<VertexID<P>, (usize, P)>,
+    vmap: HashMap<VertexID<P>, (usize, CompressedVertex<P>)>,
     emap: HashMap<EdgeID<C>, (usize, CompressedEdge<C>)>,
 }
 
@@ -114,7 +261,7 @@ impl<P: Clone, C: Clone> CompressDirector<P, C> {
         let id = self.vmap.len();
         self.vmap
             .entry(vertex.id())
-            .or_insert_with(|| (id, vertex.point()))
+            .or_insert_with(|| (id, (vertex.point(), vertex.tolerance(), vertex.stable_id())))
             .0
     }
 
@@ -130,6 +277,8 @@ impl<P: Clone, C: Clone> CompressDirector<P, C> {
                 let cedge = CompressedEdge {
                     vertices: (front_id, back_id),
                     curve,
+                    tolerance: edge.tolerance(),
+                    stable_id: edge.stable_id(),
                 };
                 self.emap.insert(edge.id(), (id, cedge));
                 (id, edge.orientation()).into()
@@ -152,52 +301,106 @@ impl<P: Clone, C: Clone> CompressDirector<P, C> {
                 .collect(),
             orientation: face.orientation(),
             surface: face.surface(),
+            stable_id: face.stable_id(),
         }
     }
 
     #[inline(always)]
     fn map2vec<K, T>(map: HashMap<K, (usize, T)>) -> Vec<T> {
         let mut vec: Vec<_> = map.into_iter().map(|entry| entry.1).collect();
-        vec.sort_by(|x, y| x.0.cmp(&y.0));
+        vec.sort_by_key(|x| x.0);
         vec.into_iter().map(|x| x.1).collect()
     }
 
     #[inline(always)]
-    fn vertices_edges(self) -> (Vec<P>, Vec<CompressedEdge<C>>) {
-        (Self::map2vec(self.vmap), Self::map2vec(self.emap))
+    fn vertices_edges(self) -> CompressedElements<P, C> {
+        fn none_to_empty<T>(vec: Vec<Option<T>>) -> Vec<Option<T>> {
+            match vec.iter().all(Option::is_none) {
+                true => Vec::new(),
+                false => vec,
+            }
+        }
+        let vmap = Self::map2vec(self.vmap);
+        let vertex_tolerances = none_to_empty(vmap.iter().map(|v| v.1).collect());
+        let vertex_stable_ids = none_to_empty(vmap.iter().map(|v| v.2).collect());
+        let vertices = vmap.into_iter().map(|v| v.0).collect();
+        CompressedElements {
+            vertices,
+            vertex_tolerances,
+            vertex_stable_ids,
+            edges: Self::map2vec(self.emap),
+        }
     }
 }
 
+type CompressedVertex<P> = (P, Option<f64>, Option<StableID>);
+struct CompressedElements<P, C> {
+    vertices: Vec<P>,
+    vertex_tolerances: Vec<Option<f64>>,
+    vertex_stable_ids: Vec<Option<StableID>>,
+    edges: Vec<CompressedEdge<C>>,
+}
+
+type CompressedShellWithIndices<P, C, S> = (
+    CompressedShell<P, C, S>,
+    HashMap<VertexID<P>, usize>,
+    HashMap<EdgeID<C>, usize>,
+);
+type ShellWithElements<P, C, S> = (Shell<P, C, S>, Vec<Vertex<P>>, Vec<Edge<P, C>>);
+
 impl<P: Clone, C: Clone, S: Clone> Shell<P, C, S> {
     /// Compresses the shell into the serialized compressed shell.
-    pub fn compress(&self) -> CompressedShell<P, C, S> {
+    pub fn compress(&self) -> CompressedShell<P, C, S> { self.compress_with_indices().0 }
+
+    /// Compresses the shell and returns the indices of vertices and edges in the compressed shell.
+    pub(crate) fn compress_with_indices(&self) -> CompressedShellWithIndices<P, C, S> {
         let mut director = CompressDirector::new();
         let mut face_closure = |face: &Face<P, C, S>| director.create_cface(face);
         let faces = self.iter().map(&mut face_closure).collect();
-        let (vertices, edges) = director.vertices_edges();
-        CompressedShell {
+        let vindices = director.vmap.iter().map(|(id, x)| (*id, x.0)).collect();
+        let eindices = director.emap.iter().map(|(id, x)| (*id, x.0)).collect();
+        let CompressedElements {
             vertices,
+            vertex_tolerances,
+            vertex_stable_ids,
+            edges,
+        } = director.vertices_edges();
+        let cshell = CompressedShell {
+            vertices,
+            vertex_tolerances,
+            vertex_stable_ids,
             edges,
             faces,
-        }
+        };
+        (cshell, vindices, eindices)
     }
 
     /// Extracts the serialized compressed shell into the shell.
     pub fn extract(cshell: CompressedShell<P, C, S>) -> Result<Self> {
+        Self::extract_with_elements(cshell).map(|x| x.0)
+    }
+
+    /// Extracts the compressed shell and returns the vertices and edges in the order of indices.
+    pub(crate) fn extract_with_elements(
+        cshell: CompressedShell<P, C, S>,
+    ) -> Result<ShellWithElements<P, C, S>> {
         let CompressedShell {
             vertices,
+            vertex_tolerances,
+            vertex_stable_ids,
             edges,
             faces,
         } = cshell;
-        let vertices: Vec<_> = vertices.into_iter().map(Vertex::new).collect();
+        let vertices = create_vertices(vertices, vertex_tolerances, vertex_stable_ids)?;
         let edges = edges
             .into_iter()
-            .map(move |edge| edge.create_edge(&vertices))
+            .map(|edge| edge.create_edge(&vertices))
             .collect::<Result<Vec<_>>>()?;
-        faces
+        let shell = faces
             .into_iter()
-            .map(move |face| face.create_face(&edges))
-            .collect()
+            .map(|face| face.create_face(&edges))
+            .collect::<Result<Self>>()?;
+        Ok((shell, vertices, edges))
     }
 }
 
@@ -221,6 +424,89 @@ impl<P: Clone, C: Clone, S: Clone> Solid<P, C, S> {
     }
 }
 
+impl<P: Clone, C: Clone> Wire<P, C> {
+    /// Compresses the wire into the serialized compressed wire.
+    pub fn compress(&self) -> CompressedWire<P, C> {
+        let mut director = CompressDirector::new();
+        let wire = director.create_boundary(self);
+        let CompressedElements {
+            vertices,
+            vertex_tolerances,
+            vertex_stable_ids,
+            edges,
+        } = director.vertices_edges();
+        CompressedWire {
+            vertices,
+            vertex_tolerances,
+            vertex_stable_ids,
+            edges,
+            wire,
+        }
+    }
+
+    /// Extracts the serialized compressed wire into the wire.
+    pub fn extract(cwire: CompressedWire<P, C>) -> Result<Self> {
+        let CompressedWire {
+            vertices,
+            vertex_tolerances,
+            vertex_stable_ids,
+            edges,
+            wire,
+        } = cwire;
+        let vertices = create_vertices(vertices, vertex_tolerances, vertex_stable_ids)?;
+        let edges = edges
+            .into_iter()
+            .map(|edge| edge.create_edge(&vertices))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(wire
+            .into_iter()
+            .map(
+                |CompressedEdgeIndex { index, orientation }| match orientation {
+                    true => edges[index].clone(),
+                    false => edges[index].inverse(),
+                },
+            )
+            .collect())
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> Compound<P, C, S> {
+    /// Compresses the compound into the serialized compressed compound.
+    pub fn compress(&self) -> CompressedCompound<P, C, S> {
+        CompressedCompound {
+            solids: self.solids().iter().map(Solid::compress).collect(),
+            shells: self.shells().iter().map(Shell::compress).collect(),
+            wires: self.wires().iter().map(Wire::compress).collect(),
+            vertices: self.vertices().iter().map(Vertex::point).collect(),
+        }
+    }
+
+    /// Extracts the serialized compressed compound into the compound.
+    pub fn extract(ccompound: CompressedCompound<P, C, S>) -> Result<Self> {
+        let CompressedCompound {
+            solids,
+            shells,
+            wires,
+            vertices,
+        } = ccompound;
+        Ok(Compound::from_elements(
+            solids
+                .into_iter()
+                .map(Solid::extract)
+                .collect::<Result<_>>()?,
+            shells
+                .into_iter()
+                .map(Shell::extract)
+                .collect::<Result<_>>()?,
+            wires
+                .into_iter()
+                .map(Wire::extract)
+                .collect::<Result<_>>()?,
+            vertices.into_iter().map(Vertex::new).collect(),
+        ))
+    }
+}
+
 // -------------------------- test -------------------------- //
 
 #[test]
@@ -380,3 +666,34 @@ where
         Shell::deserialize(deserializer).map(|mut shell| shell.pop().unwrap())
     }
 }
+
+impl<P, C, S> Serialize for Compound<P, C, S>
+where
+    P: Clone + Serialize,
+    C: Clone + Serialize,
+    S: Clone + Serialize,
+{
+    fn serialize<Serializer>(
+        &self,
+        serializer: Serializer,
+    ) -> std::result::Result<Serializer::Ok, Serializer::Error>
+    where
+        Serializer: serde::Serializer,
+    {
+        self.compress().serialize(serializer)
+    }
+}
+
+impl<'de, P, C, S> Deserialize<'de> for Compound<P, C, S>
+where
+    P: Clone + Deserialize<'de>,
+    C: Clone + Deserialize<'de>,
+    S: Clone + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where D: serde::Deserializer<'de> {
+        use serde::de::Error;
+        let compressed = CompressedCompound::<P, C, S>::deserialize(deserializer)?;
+        Compound::extract(compressed).map_err(D::Error::custom)
+    }
+}
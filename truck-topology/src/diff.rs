@@ -0,0 +1,502 @@
+//! Topological and geometric comparison of two shapes
+//!
+//! The methods `diff` and `diff_by` of [`Shell`] and [`Solid`] match the elements of two shapes
+//! and report the differences as a [`ShapeDiff`].
+//!
+//! - The vertices are matched by their points.
+//! - The edges are matched by their end vertices. The matched edges whose curves are different
+//!   are reported as modified.
+//! - The faces are matched by their boundary edges. The matched faces whose surfaces or boundary
+//!   curves are different, or whose orientations are opposite, are reported as modified.
+//!   The faces on the same surface sharing a boundary edge are also regarded as modified ones,
+//!   e.g. a face trimmed by a new edge.
+//!
+//! The elements are matched greedily in the order of the iterators of the shapes, and the identical
+//! pairs are matched before the modified ones. The other elements are reported as added or removed.
+
+use crate::*;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use truck_base::cgmath64::{EuclideanSpace, MetricSpace};
+
+/// the number of the divisions of each curve and of each parameter range for sampling points
+const DIFF_DIVISION: usize = 8;
+
+/// The differences of two shapes reported by `diff`.
+///
+/// The "removed" elements are the ones of `self` and the "added" elements are the ones of `other`.
+/// The modified pairs are given as the ids of `(self, other)`.
+pub struct ShapeDiff<C, S> {
+    /// the faces of `other` which have no counterpart in `self`
+    pub added_faces: Vec<FaceID<S>>,
+    /// the faces of `self` which have no counterpart in `other`
+    pub removed_faces: Vec<FaceID<S>>,
+    /// the pairs of the matched faces which are not identical
+    pub modified_faces: Vec<(FaceID<S>, FaceID<S>)>,
+    /// the edges of `other` which have no counterpart in `self`
+    pub added_edges: Vec<EdgeID<C>>,
+    /// the edges of `self` which have no counterpart in `other`
+    pub removed_edges: Vec<EdgeID<C>>,
+    /// the pairs of the matched edges which are not identical
+    pub modified_edges: Vec<(EdgeID<C>, EdgeID<C>)>,
+}
+
+impl<C, S> ShapeDiff<C, S> {
+    /// Returns `true` if the two shapes are topologically isomorphic and geometrically equal,
+    /// i.e. no face and no edge is added, removed or modified.
+    #[inline(always)]
+    pub fn is_equivalent(&self) -> bool {
+        self.added_faces.is_empty()
+            && self.removed_faces.is_empty()
+            && self.modified_faces.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.modified_edges.is_empty()
+    }
+}
+
+/// the counterpart of an edge
+struct EdgeMatch<C> {
+    id: EdgeID<C>,
+    reversed: bool,
+    identical: bool,
+}
+
+/// the numbers of the occurrences of the oriented edges in the boundaries of a face
+type BoundaryCount<C> = HashMap<(EdgeID<C>, bool), usize>;
+
+fn unique_edges<P, C>(edges: impl Iterator<Item = Edge<P, C>>) -> Vec<Edge<P, C>> {
+    let mut ids = HashSet::default();
+    edges.filter(|edge| ids.insert(edge.id())).collect()
+}
+
+fn unique_vertices<P, C>(edges: &[Edge<P, C>]) -> Vec<Vertex<P>> {
+    let mut ids = HashSet::default();
+    edges
+        .iter()
+        .flat_map(|edge| [edge.absolute_front(), edge.absolute_back()])
+        .filter(|vertex| ids.insert(vertex.id()))
+        .cloned()
+        .collect()
+}
+
+fn shape_diff<P, C, S>(
+    faces0: &[&Face<P, C, S>],
+    faces1: &[&Face<P, C, S>],
+    vertex_eq: impl Fn(&Vertex<P>, &Vertex<P>) -> bool,
+    edge_eq: impl Fn(&Edge<P, C>, &Edge<P, C>) -> bool,
+    face_eq: impl Fn(&Face<P, C, S>, &Face<P, C, S>) -> bool,
+) -> ShapeDiff<C, S> {
+    let edges0 = unique_edges(faces0.iter().flat_map(|face| face.edge_iter()));
+    let edges1 = unique_edges(faces1.iter().flat_map(|face| face.edge_iter()));
+
+    // vertices
+    let vertices1 = unique_vertices(&edges1);
+    let mut used = vec![false; vertices1.len()];
+    let mut vmap = HashMap::<VertexID<P>, VertexID<P>>::default();
+    for v0 in unique_vertices(&edges0) {
+        let found = (0..vertices1.len()).find(|i| !used[*i] && vertex_eq(&v0, &vertices1[*i]));
+        if let Some(i) = found {
+            used[i] = true;
+            vmap.insert(v0.id(), vertices1[i].id());
+        }
+    }
+
+    // edges
+    let mut ends = HashMap::<(VertexID<P>, VertexID<P>), Vec<usize>>::default();
+    edges1.iter().enumerate().for_each(|(i, edge)| {
+        let key = (edge.absolute_front().id(), edge.absolute_back().id());
+        ends.entry(key).or_default().push(i);
+    });
+    let candidates = |edge: &Edge<P, C>| -> Vec<(usize, bool)> {
+        let front = vmap.get(&edge.absolute_front().id());
+        let back = vmap.get(&edge.absolute_back().id());
+        let (Some(front), Some(back)) = (front, back) else {
+            return Vec::new();
+        };
+        let forward = ends.get(&(*front, *back)).into_iter().flatten();
+        let backward = ends.get(&(*back, *front)).into_iter().flatten();
+        let forward = forward.map(|i| (*i, false));
+        forward.chain(backward.map(|i| (*i, true))).collect()
+    };
+    let mut used = vec![false; edges1.len()];
+    let mut emap = HashMap::<EdgeID<C>, EdgeMatch<C>>::default();
+    for identical in [true, false] {
+        for e0 in &edges0 {
+            if emap.contains_key(&e0.id()) {
+                continue;
+            }
+            let found = candidates(e0)
+                .into_iter()
+                .find(|(i, _)| !used[*i] && (!identical || edge_eq(e0, &edges1[*i])));
+            if let Some((i, reversed)) = found {
+                used[i] = true;
+                let id = edges1[i].id();
+                let matched = EdgeMatch {
+                    id,
+                    reversed,
+                    identical,
+                };
+                emap.insert(e0.id(), matched);
+            }
+        }
+    }
+    let added_edges = (0..edges1.len())
+        .filter(|i| !used[*i])
+        .map(|i| edges1[i].id())
+        .collect();
+    let mut removed_edges = Vec::new();
+    let mut modified_edges = Vec::new();
+    edges0.iter().for_each(|edge| match emap.get(&edge.id()) {
+        None => removed_edges.push(edge.id()),
+        Some(matched) if !matched.identical => modified_edges.push((edge.id(), matched.id)),
+        Some(_) => {}
+    });
+
+    // faces
+    let mut edge_faces = HashMap::<EdgeID<C>, Vec<usize>>::default();
+    let counts1: Vec<BoundaryCount<C>> = faces1
+        .iter()
+        .enumerate()
+        .map(|(j, face)| {
+            let mut count = BoundaryCount::default();
+            face.edge_iter().for_each(|edge| {
+                *count.entry((edge.id(), edge.orientation())).or_default() += 1;
+                let faces = edge_faces.entry(edge.id()).or_default();
+                if faces.last() != Some(&j) {
+                    faces.push(j);
+                }
+            });
+            count
+        })
+        .collect();
+    // the boundary of the face mapped to `other`, and whether all the edges are identical
+    let mapped_boundaries: Vec<Option<(BoundaryCount<C>, bool)>> = faces0
+        .iter()
+        .map(|face| {
+            let mut count = BoundaryCount::default();
+            let mut identical = true;
+            for edge in face.edge_iter() {
+                let matched = emap.get(&edge.id())?;
+                let key = (matched.id, edge.orientation() != matched.reversed);
+                *count.entry(key).or_default() += 1;
+                identical &= matched.identical;
+            }
+            Some((count, identical))
+        })
+        .collect();
+    let mut used = vec![false; faces1.len()];
+    let mut fmap = vec![None; faces0.len()];
+    for pass in 0..3 {
+        for (i, f0) in faces0.iter().enumerate() {
+            if fmap[i].is_some() {
+                continue;
+            }
+            let mut candidates: Vec<usize> = f0
+                .edge_iter()
+                .filter_map(|edge| emap.get(&edge.id()))
+                .flat_map(|matched| edge_faces.get(&matched.id).into_iter().flatten())
+                .copied()
+                .collect();
+            candidates.sort_unstable();
+            candidates.dedup();
+            let corresponds = |j: usize| {
+                let boundary = mapped_boundaries[i].as_ref();
+                boundary.is_some_and(|(count, _)| *count == counts1[j])
+            };
+            let all_identical = mapped_boundaries[i]
+                .as_ref()
+                .is_some_and(|(_, identical)| *identical);
+            let found = candidates.into_iter().find(|j| {
+                !used[*j]
+                    && match pass {
+                        0 => all_identical && corresponds(*j) && face_eq(f0, faces1[*j]),
+                        1 => corresponds(*j),
+                        _ => face_eq(f0, faces1[*j]),
+                    }
+            });
+            if let Some(j) = found {
+                used[j] = true;
+                fmap[i] = Some((j, pass == 0));
+            }
+        }
+    }
+    let added_faces = (0..faces1.len())
+        .filter(|j| !used[*j])
+        .map(|j| faces1[j].id())
+        .collect();
+    let mut removed_faces = Vec::new();
+    let mut modified_faces = Vec::new();
+    faces0
+        .iter()
+        .zip(fmap)
+        .for_each(|(face, matched)| match matched {
+            None => removed_faces.push(face.id()),
+            Some((j, false)) => modified_faces.push((face.id(), faces1[j].id())),
+            Some(_) => {}
+        });
+
+    ShapeDiff {
+        added_faces,
+        removed_faces,
+        modified_faces,
+        added_edges,
+        removed_edges,
+        modified_edges,
+    }
+}
+
+fn diff_by<P, C, S>(
+    faces0: &[&Face<P, C, S>],
+    faces1: &[&Face<P, C, S>],
+    point_eq: impl Fn(&P, &P) -> bool,
+    curve_eq: impl Fn(&C, &C) -> bool,
+    surface_eq: impl Fn(&S, &S) -> bool,
+) -> ShapeDiff<C, S>
+where
+    P: Clone,
+    C: Clone,
+    S: Clone,
+{
+    shape_diff(
+        faces0,
+        faces1,
+        |v0, v1| point_eq(&v0.point(), &v1.point()),
+        |e0, e1| curve_eq(&e0.curve(), &e1.curve()),
+        |f0, f1| surface_eq(&f0.surface(), &f1.surface()),
+    )
+}
+
+fn sampling_points<C: BoundedCurve>(curve: &C) -> impl Iterator<Item = (f64, C::Point)> + '_ {
+    let (t0, t1) = curve.range_tuple();
+    (0..=DIFF_DIVISION).map(move |i| {
+        let t = t0 + (t1 - t0) * i as f64 / DIFF_DIVISION as f64;
+        (t, curve.subs(t))
+    })
+}
+
+/// Returns `true` if the points sampled from `curve0` are on `curve1` within `tol`.
+fn curve_on_curve<P, C>(curve0: &C, curve1: &C, tol: f64) -> bool
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>, {
+    let samples1: Vec<(f64, P)> = sampling_points(curve1).collect();
+    let (s0, s1) = curve1.range_tuple();
+    sampling_points(curve0).all(|(_, p)| {
+        let hint = samples1
+            .iter()
+            .min_by(|(_, q0), (_, q1)| p.distance2(*q0).total_cmp(&p.distance2(*q1)))
+            .map(|(s, _)| *s);
+        curve1
+            .search_nearest_parameter(p, hint, SEARCH_PARAMETER_TRIALS)
+            .is_some_and(|s| curve1.subs(s.clamp(s0, s1)).distance(p) <= tol)
+    })
+}
+
+/// Returns `true` if the points sampled from the surface of `face` around the face are on
+/// `surface` within `tol`.
+///
+/// The parameter range of the face is estimated by projecting the boundary to its surface.
+fn face_on_surface<P, C, S>(face: &Face<P, C, S>, surface: &S, tol: f64) -> bool
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P>,
+    S: Clone + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>, {
+    // The nearest parameter is searched globally if the search from the hint does not converge
+    // to a point on the surface.
+    let project = |surface: &S, p: P, hint: &mut Option<(f64, f64)>| {
+        let on_surface = |uv: &(f64, f64)| surface.subs(uv.0, uv.1).distance(p) <= tol;
+        let uv = hint
+            .and_then(|uv| surface.search_nearest_parameter(p, uv, SEARCH_PARAMETER_TRIALS))
+            .filter(on_surface)
+            .or_else(|| {
+                surface
+                    .search_nearest_parameter(p, SPHint2D::Global, SEARCH_PARAMETER_TRIALS)
+                    .filter(on_surface)
+            });
+        *hint = uv;
+        uv
+    };
+    let surface0 = face.surface();
+    let mut hint = None;
+    let (mut urange, mut vrange) = (
+        (f64::INFINITY, f64::NEG_INFINITY),
+        (f64::INFINITY, f64::NEG_INFINITY),
+    );
+    for edge in face.edge_iter() {
+        let curve = edge.curve();
+        for (_, p) in sampling_points(&curve) {
+            let Some((u, v)) = project(&surface0, p, &mut hint) else {
+                return false;
+            };
+            urange = (f64::min(urange.0, u), f64::max(urange.1, u));
+            vrange = (f64::min(vrange.0, v), f64::max(vrange.1, v));
+        }
+    }
+    if urange.0 > urange.1 {
+        (urange, vrange) = ((0.0, 1.0), (0.0, 1.0));
+    }
+    let mut hint = None;
+    let division = DIFF_DIVISION as f64;
+    (0..=DIFF_DIVISION)
+        .flat_map(|i| (0..=DIFF_DIVISION).map(move |j| (i as f64, j as f64)))
+        .all(|(i, j)| {
+            let u = urange.0 + (urange.1 - urange.0) * i / division;
+            let v = vrange.0 + (vrange.1 - vrange.0) * j / division;
+            let p = surface0.subs(u, v);
+            project(surface, p, &mut hint).is_some()
+        })
+}
+
+fn geometric_diff<P, C, S>(
+    faces0: &[&Face<P, C, S>],
+    faces1: &[&Face<P, C, S>],
+    tol: f64,
+) -> ShapeDiff<C, S>
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>,
+    S: Clone + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+{
+    nonpositive_tolerance!(tol);
+    shape_diff(
+        faces0,
+        faces1,
+        |v0, v1| v0.point().distance(v1.point()) <= tol,
+        |e0, e1| {
+            let (c0, c1) = (e0.curve(), e1.curve());
+            curve_on_curve(&c0, &c1, tol) && curve_on_curve(&c1, &c0, tol)
+        },
+        |f0, f1| face_on_surface(f0, &f1.surface(), tol) && face_on_surface(f1, &f0.surface(), tol),
+    )
+}
+
+impl<P: Clone, C: Clone, S: Clone> Shell<P, C, S> {
+    /// Compares the shell with `other` by the given equalities of the geometries.
+    ///
+    /// `curve_eq` is called for the curves of the edges whose end vertices are matched,
+    /// possibly in the opposite direction. See [the module document](self) for the matching.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news([0, 1, 2, 3]);
+    /// let edge = [
+    ///     Edge::new(&v[0], &v[1], 0),
+    ///     Edge::new(&v[1], &v[2], 1),
+    ///     Edge::new(&v[2], &v[3], 2),
+    ///     Edge::new(&v[3], &v[0], 3),
+    /// ];
+    /// let wire = Wire::from_iter(edge.clone());
+    /// let shell0: Shell<usize, usize, usize> = vec![Face::new(vec![wire], 0)].into();
+    ///
+    /// // the same shell constructed independently
+    /// let shell1 = shell0.mapped(|p| *p, |c| *c, |s| *s);
+    /// let diff = shell0.diff_by(&shell1, PartialEq::eq, PartialEq::eq, PartialEq::eq);
+    /// assert!(diff.is_equivalent());
+    ///
+    /// // the curve of an edge is modified
+    /// shell1[0].boundaries()[0][2].set_curve(5);
+    /// let diff = shell0.diff_by(&shell1, PartialEq::eq, PartialEq::eq, PartialEq::eq);
+    /// assert_eq!(diff.modified_edges, vec![(edge[2].id(), shell1[0].boundaries()[0][2].id())]);
+    /// assert_eq!(diff.modified_faces, vec![(shell0[0].id(), shell1[0].id())]);
+    /// assert!(diff.added_faces.is_empty() && diff.removed_edges.is_empty());
+    /// ```
+    pub fn diff_by(
+        &self,
+        other: &Self,
+        point_eq: impl Fn(&P, &P) -> bool,
+        curve_eq: impl Fn(&C, &C) -> bool,
+        surface_eq: impl Fn(&S, &S) -> bool,
+    ) -> ShapeDiff<C, S> {
+        let faces0: Vec<_> = self.face_iter().collect();
+        let faces1: Vec<_> = other.face_iter().collect();
+        diff_by(&faces0, &faces1, point_eq, curve_eq, surface_eq)
+    }
+}
+
+impl<P, C, S> Shell<P, C, S>
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>,
+    S: Clone + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+{
+    /// Compares the shell with `other` geometrically within `tol`.
+    ///
+    /// The curves and the surfaces are compared by the points sampled from them, in both directions.
+    /// The surfaces are sampled only around the faces, so the surfaces which coincide on the faces
+    /// are regarded as the same ones. See [the module document](self) for the matching.
+    pub fn diff(&self, other: &Self, tol: f64) -> ShapeDiff<C, S> {
+        let faces0: Vec<_> = self.face_iter().collect();
+        let faces1: Vec<_> = other.face_iter().collect();
+        geometric_diff(&faces0, &faces1, tol)
+    }
+}
+
+impl<P: Clone, C: Clone, S: Clone> Solid<P, C, S> {
+    /// Compares the faces of all the boundary shells with those of `other`
+    /// by the given equalities of the geometries. See [`Shell::diff_by`].
+    pub fn diff_by(
+        &self,
+        other: &Self,
+        point_eq: impl Fn(&P, &P) -> bool,
+        curve_eq: impl Fn(&C, &C) -> bool,
+        surface_eq: impl Fn(&S, &S) -> bool,
+    ) -> ShapeDiff<C, S> {
+        let faces0: Vec<_> = self.face_iter().collect();
+        let faces1: Vec<_> = other.face_iter().collect();
+        diff_by(&faces0, &faces1, point_eq, curve_eq, surface_eq)
+    }
+}
+
+impl<P, C, S> Solid<P, C, S>
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>,
+    S: Clone + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+{
+    /// Compares the faces of all the boundary shells with those of `other` geometrically within `tol`.
+    /// See [`Shell::diff`].
+    pub fn diff(&self, other: &Self, tol: f64) -> ShapeDiff<C, S> {
+        let faces0: Vec<_> = self.face_iter().collect();
+        let faces1: Vec<_> = other.face_iter().collect();
+        geometric_diff(&faces0, &faces1, tol)
+    }
+}
+
+impl<C, S> Clone for ShapeDiff<C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            added_faces: self.added_faces.clone(),
+            removed_faces: self.removed_faces.clone(),
+            modified_faces: self.modified_faces.clone(),
+            added_edges: self.added_edges.clone(),
+            removed_edges: self.removed_edges.clone(),
+            modified_edges: self.modified_edges.clone(),
+        }
+    }
+}
+
+impl<C, S> PartialEq for ShapeDiff<C, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.added_faces == other.added_faces
+            && self.removed_faces == other.removed_faces
+            && self.modified_faces == other.modified_faces
+            && self.added_edges == other.added_edges
+            && self.removed_edges == other.removed_edges
+            && self.modified_edges == other.modified_edges
+    }
+}
+
+impl<C, S> Eq for ShapeDiff<C, S> {}
+
+impl<C, S> Debug for ShapeDiff<C, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShapeDiff")
+            .field("added_faces", &self.added_faces)
+            .field("removed_faces", &self.removed_faces)
+            .field("modified_faces", &self.modified_faces)
+            .field("added_edges", &self.added_edges)
+            .field("removed_edges", &self.removed_edges)
+            .field("modified_edges", &self.modified_edges)
+            .finish()
+    }
+}
@@ -42,7 +42,7 @@ impl<P, C> Edge<P, C> {
         Edge {
             vertices: (front.clone(), back.clone()),
             orientation: true,
-            curve: Arc::new(Mutex::new(curve)),
+            curve: Geometry::new(curve, None),
         }
     }
 
@@ -277,6 +277,44 @@ impl<P, C> Edge<P, C> {
     #[inline(always)]
     pub fn set_curve(&self, curve: C) { *self.curve.lock() = curve; }
 
+    /// Returns the tolerance of the edge, `None` if the global `TOLERANCE` is used.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(), ()]);
+    /// let edge0 = Edge::new(&v[0], &v[1], ());
+    /// assert_eq!(edge0.tolerance(), None);
+    /// let edge1 = edge0.inverse();
+    /// edge1.set_tolerance(Some(1.0e-4));
+    /// assert_eq!(edge0.tolerance(), Some(1.0e-4));
+    /// ```
+    #[inline(always)]
+    pub fn tolerance(&self) -> Option<f64> { self.curve.tolerance() }
+
+    /// Sets the tolerance of the edge, which is shared by the same edges.
+    /// `None` resets the tolerance to the global `TOLERANCE`.
+    /// # Panics
+    /// Panics if the tolerance is less than `TOLERANCE`.
+    #[inline(always)]
+    pub fn set_tolerance(&self, tolerance: Option<f64>) { self.curve.set_tolerance(tolerance) }
+
+    /// Returns the tolerance of the edge, or the global `TOLERANCE` if it is not set.
+    #[inline(always)]
+    pub fn tolerance_or_default(&self) -> f64 { self.tolerance().unwrap_or(TOLERANCE) }
+
+    /// Returns the stable id of the edge, `None` if it has not been assigned.
+    /// See [`StableID`] for details.
+    #[inline(always)]
+    pub fn stable_id(&self) -> Option<StableID> { self.curve.stable_id() }
+
+    /// Sets the stable id of the edge, which is shared by the cloned and inverted edges.
+    #[inline(always)]
+    pub fn set_stable_id(&self, stable_id: Option<StableID>) { self.curve.set_stable_id(stable_id) }
+
+    /// Returns the stable id of the edge. If it has not been assigned, issues a new one.
+    #[inline(always)]
+    pub fn assign_stable_id(&self) -> StableID { self.curve.assign_stable_id() }
+
     /// Returns the id that does not depend on the direction of the edge.
     /// # Examples
     /// ```
@@ -288,7 +326,7 @@ impl<P, C> Edge<P, C> {
     /// assert_eq!(edge0.id(), edge1.id());
     /// ```
     #[inline(always)]
-    pub fn id(&self) -> EdgeID<C> { ID::new(Arc::as_ptr(&self.curve)) }
+    pub fn id(&self) -> EdgeID<C> { ID::new(&self.curve.entity) }
 
     /// Returns how many same edges.
     ///
@@ -340,6 +378,8 @@ impl<P, C> Edge<P, C> {
         let v1 = self.absolute_back().try_mapped(&mut point_mapping)?;
         let curve = curve_mapping(&*self.curve.lock())?;
         let mut edge = Edge::debug_new(&v0, &v1, curve);
+        edge.curve.set_tolerance(self.tolerance());
+        edge.curve.set_origin(&self.curve);
         if !self.orientation() {
             edge.invert();
         }
@@ -377,6 +417,8 @@ impl<P, C> Edge<P, C> {
         let v1 = self.absolute_back().mapped(&mut point_mapping);
         let curve = curve_mapping(&*self.curve.lock());
         let mut edge = Edge::debug_new(&v0, &v1, curve);
+        edge.curve.set_tolerance(self.tolerance());
+        edge.curve.set_origin(&self.curve);
         if edge.orientation() != self.orientation() {
             edge.invert();
         }
@@ -385,17 +427,25 @@ impl<P, C> Edge<P, C> {
 
     /// Returns the consistence of the geometry of end vertices
     /// and the geometry of edge.
+    ///
+    /// The end points of the curve are compared with the vertices by the larger one of
+    /// the tolerances of the edge and the vertex.
     #[inline(always)]
     pub fn is_geometric_consistent(&self) -> bool
     where
         P: Tolerance,
         C: BoundedCurve<Point = P>, {
+        let (front, back) = (self.absolute_front(), self.absolute_back());
+        let tol = self.tolerance_or_default();
+        let front_tol = f64::max(tol, front.tolerance_or_default());
+        let back_tol = f64::max(tol, back.tolerance_or_default());
         let curve = self.curve.lock();
         let geom_front = curve.front();
         let geom_back = curve.back();
-        let top_front = self.absolute_front().point.lock();
-        let top_back = self.absolute_back().point.lock();
-        geom_front.near(&*top_front) && geom_back.near(&*top_back)
+        let top_front = front.point.lock();
+        let top_back = back.point.lock();
+        geom_front.abs_diff_eq(&*top_front, front_tol)
+            && geom_back.abs_diff_eq(&*top_back, back_tol)
     }
 
     /// Cuts the edge at `vertex`.
@@ -417,12 +467,12 @@ impl<P, C> Edge<P, C> {
         let edge0 = Edge {
             vertices: (self.absolute_front().clone(), vertex.clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve0)),
+            curve: Geometry::new(curve0, self.tolerance()),
         };
         let edge1 = Edge {
             vertices: (vertex.clone(), self.absolute_back().clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve1)),
+            curve: Geometry::new(curve1, self.tolerance()),
         };
         match self.orientation {
             true => Some((edge0, edge1)),
@@ -449,12 +499,12 @@ impl<P, C> Edge<P, C> {
         let edge0 = Edge {
             vertices: (self.absolute_front().clone(), vertex.clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve0)),
+            curve: Geometry::new(curve0, self.tolerance()),
         };
         let edge1 = Edge {
             vertices: (vertex.clone(), self.absolute_back().clone()),
             orientation: self.orientation,
-            curve: Arc::new(Mutex::new(curve1)),
+            curve: Geometry::new(curve1, self.tolerance()),
         };
         match self.orientation {
             true => Some((edge0, edge1)),
@@ -482,7 +532,13 @@ impl<P, C> Edge<P, C> {
         let t1 = curve1.range_tuple().0;
         curve1.parameter_transform(1.0, t0 - t1);
         let curve = curve0.try_concat(&curve1)?;
-        Ok(Edge::debug_new(self.front(), rhs.back(), curve))
+        let edge = Edge::debug_new(self.front(), rhs.back(), curve);
+        let tolerance = match (self.tolerance(), rhs.tolerance()) {
+            (Some(tol0), Some(tol1)) => Some(f64::max(tol0, tol1)),
+            (tol0, tol1) => tol0.or(tol1),
+        };
+        edge.set_tolerance(tolerance);
+        Ok(edge)
     }
 
     /// Create display struct for debugging the edge.
@@ -583,7 +639,7 @@ impl<'a, P: Debug, C: Debug> Debug for DebugDisplay<'a, Edge<P, C>, EdgeDisplayF
         match self.format {
             EdgeDisplayFormat::Full { vertex_format } => f
                 .debug_struct("Edge")
-                .field("id", &Arc::as_ptr(&self.entity.curve))
+                .field("id", &self.entity.id())
                 .field(
                     "vertices",
                     &(
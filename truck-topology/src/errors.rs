@@ -113,6 +113,26 @@ pub enum Error {
     /// ```
     #[error("This shell is not a manifold.")]
     NotManifold,
+    /// The compressed attributes refer to an element which does not exist in the compressed shape.
+    #[error("The compressed attributes refer to an element which does not exist.")]
+    AttributeIndexOutOfRange,
+    /// The compressed shape has a tolerance which is less than `TOLERANCE` or is not a number.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// use truck_topology::errors::Error;
+    /// let v = Vertex::news([0, 1]);
+    /// let wire: Wire<usize, usize> = vec![Edge::new(&v[0], &v[1], 0)].into();
+    /// let mut cwire = wire.compress();
+    /// cwire.edges[0].tolerance = Some(0.0);
+    /// assert_eq!(Wire::extract(cwire.clone()).unwrap_err(), Error::InvalidTolerance);
+    ///
+    /// cwire.edges[0].tolerance = None;
+    /// cwire.vertex_tolerances = vec![Some(f64::NAN), None];
+    /// assert_eq!(Wire::extract(cwire).unwrap_err(), Error::InvalidTolerance);
+    /// ```
+    #[error("The tolerance must be no less than TOLERANCE.")]
+    InvalidTolerance,
 }
 
 #[test]
@@ -132,6 +152,13 @@ fn print_messages() {
     writeln!(&mut std::io::stderr(), "{}\n", Error::NotConnected).unwrap();
     writeln!(&mut std::io::stderr(), "{}\n", Error::NotClosedShell).unwrap();
     writeln!(&mut std::io::stderr(), "{}\n", Error::NotManifold).unwrap();
+    writeln!(
+        &mut std::io::stderr(),
+        "{}\n",
+        Error::AttributeIndexOutOfRange
+    )
+    .unwrap();
+    writeln!(&mut std::io::stderr(), "{}\n", Error::InvalidTolerance).unwrap();
     writeln!(
         &mut std::io::stderr(),
         "*******************************************************"
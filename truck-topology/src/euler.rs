@@ -0,0 +1,540 @@
+use crate::*;
+use rustc_hash::FxHashSet as HashSet;
+
+/// Sets the boundaries of `face` by the loops seen from the front side of `face`.
+fn set_oriented_boundaries<P, C, S>(face: &mut Face<P, C, S>, boundaries: Vec<Wire<P, C>>) {
+    face.boundaries = match face.orientation {
+        true => boundaries,
+        false => boundaries.iter().map(Wire::inverse).collect(),
+    };
+}
+
+impl<P, C, S> Shell<P, C, S> {
+    #[inline(always)]
+    fn includes_vertex(&self, vertex_id: VertexID<P>) -> bool {
+        self.vertex_iter().any(|v| v.id() == vertex_id)
+    }
+    #[inline(always)]
+    fn includes_edge(&self, edge_id: EdgeID<C>) -> bool {
+        self.edge_iter().any(|e| e.id() == edge_id)
+    }
+
+    /// Returns the Euler-Poincaré characteristic `V - E + F - R`, where `V`, `E`, `F` are the
+    /// numbers of vertices, edges and faces, and `R` is the number of rings, i.e. the boundary
+    /// wires other than the first one of each face.
+    ///
+    /// If `self` is connected and closed, it is equal to `2 * (1 - H)` where `H` is the genus.
+    /// The Euler operators `mev`, `kev`, `mef`, `kef`, `mekr`, `kemr` keep this number,
+    /// and `kfmrh` and `mfkrh` decrease and increase it by two, corresponding to the change of genus.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(); 4]);
+    /// let edge = [
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[0], &v[2], ()),
+    ///     Edge::new(&v[0], &v[3], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[1], &v[3], ()),
+    ///     Edge::new(&v[2], &v[3], ()),
+    /// ];
+    /// let wire = vec![
+    ///     Wire::from_iter(vec![&edge[0], &edge[3], &edge[1].inverse()]),
+    ///     Wire::from_iter(vec![&edge[1], &edge[5], &edge[2].inverse()]),
+    ///     Wire::from_iter(vec![&edge[2], &edge[4].inverse(), &edge[0].inverse()]),
+    ///     Wire::from_iter(vec![&edge[3], &edge[5], &edge[4].inverse()]),
+    /// ];
+    /// let shell: Shell<_, _, _> = wire.into_iter().map(|w| Face::new(vec![w], ())).collect();
+    /// // tetrahedron: 4 - 6 + 4 - 0
+    /// assert_eq!(shell.euler_characteristic(), 2);
+    /// ```
+    pub fn euler_characteristic(&self) -> isize {
+        let vertices: HashSet<_> = self.vertex_iter().map(|v| v.id()).collect();
+        let edges: HashSet<_> = self.edge_iter().map(|e| e.id()).collect();
+        let faces = self.len() as isize;
+        let loops = self.iter().map(|face| face.boundaries.len()).sum::<usize>() as isize;
+        vertices.len() as isize - edges.len() as isize + faces - (loops - faces)
+    }
+
+    /// MEV: makes an edge and a vertex.
+    ///
+    /// Inserts `edge` and its inverse into the boundary of the face with `face_id` at the
+    /// front vertex of `edge`. The back vertex of `edge` becomes a new vertex of degree one.
+    /// The boundary wire is not simple until the new vertex is connected by `mef` or `mekr`.
+    ///
+    /// # Failures
+    /// Returns `false` and does not edit `self` if:
+    /// - there is no face corresponding to `face_id` in the shell,
+    /// - the front vertex of `edge` is not in the boundary of the face, or
+    /// - the back vertex of `edge` or `edge` itself is already included in the shell.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(); 4]);
+    /// let wire = Wire::from(vec![
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[2], &v[0], ()),
+    /// ]);
+    /// let face = Face::new(vec![wire], ());
+    /// let face_id = face.id();
+    /// let mut shell: Shell<_, _, _> = vec![face].into();
+    ///
+    /// let edge = Edge::new(&v[1], &v[3], ());
+    /// assert!(shell.mev(face_id, edge.clone()));
+    /// assert_eq!(shell[0].boundaries()[0].len(), 5);
+    /// assert_eq!(shell.euler_characteristic(), 1);
+    ///
+    /// // inverse operation
+    /// assert_eq!(shell.kev(edge.id()), Some(edge));
+    /// assert_eq!(shell[0].boundaries()[0].len(), 3);
+    /// ```
+    pub fn mev(&mut self, face_id: FaceID<S>, edge: Edge<P, C>) -> bool {
+        if self.includes_vertex(edge.back().id()) || self.includes_edge(edge.id()) {
+            return false;
+        }
+        let face = match self.iter_mut().find(|face| face.id() == face_id) {
+            Some(face) => face,
+            None => return false,
+        };
+        let mut boundaries = face.boundaries();
+        let pos = boundaries.iter().enumerate().find_map(|(i, wire)| {
+            let j = wire.iter().position(|e| e.front() == edge.front())?;
+            Some((i, j))
+        });
+        let (i, j) = match pos {
+            Some(pos) => pos,
+            None => return false,
+        };
+        boundaries[i].insert(j, edge.inverse());
+        boundaries[i].insert(j, edge);
+        set_oriented_boundaries(face, boundaries);
+        true
+    }
+
+    /// KEV: kills an edge and a vertex. The inverse operation of [`Shell::mev`].
+    ///
+    /// # Returns
+    /// Returns the removed edge, whose back vertex is the removed vertex.
+    ///
+    /// # Failures
+    /// Returns `None` and does not edit `self` if there is no boundary wire in which the edge
+    /// with `edge_id` and its inverse are adjacent, i.e. the edge has no end of degree one,
+    /// or if the wire consists only of the edge and its inverse.
+    pub fn kev(&mut self, edge_id: EdgeID<C>) -> Option<Edge<P, C>> {
+        self.iter_mut().find_map(|face| {
+            let mut boundaries = face.boundaries();
+            let (i, j) = boundaries.iter().enumerate().find_map(|(i, wire)| {
+                let n = wire.len();
+                let j = (0..n)
+                    .find(|j| wire[*j].id() == edge_id && wire[(j + 1) % n].id() == edge_id)?;
+                Some((i, j))
+            })?;
+            let wire = &mut boundaries[i];
+            if wire.len() <= 2 {
+                return None;
+            }
+            let edge = if j + 1 < wire.len() {
+                let edge = wire.remove(j);
+                wire.remove(j);
+                edge
+            } else {
+                wire.pop_front();
+                wire.pop_back()
+            };
+            set_oriented_boundaries(face, boundaries);
+            edge
+        })
+    }
+
+    /// MEF: makes an edge and a face.
+    ///
+    /// Cuts the boundary wire of the face with `face_id` including both ends of `edge` into two wires.
+    /// The face with `face_id` is bounded by the wire including `edge`, and the new face, whose surface
+    /// is a clone of the original one, is bounded by the wire including the inverse of `edge`.
+    /// The other boundary wires remain on the face with `face_id`.
+    ///
+    /// # Returns
+    /// Returns the id of the new face.
+    ///
+    /// # Failures
+    /// Returns `None` and does not edit `self` if:
+    /// - there is no face corresponding to `face_id` in the shell,
+    /// - there is no boundary wire of the face including both ends of `edge`, or
+    /// - `edge` is already included in the shell.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(); 4]);
+    /// let wire = Wire::from(vec![
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[2], &v[3], ()),
+    ///     Edge::new(&v[3], &v[0], ()),
+    /// ]);
+    /// let face = Face::new(vec![wire], ());
+    /// let face_id = face.id();
+    /// let mut shell: Shell<_, _, _> = vec![face].into();
+    ///
+    /// let edge = Edge::new(&v[1], &v[3], ());
+    /// let new_face_id = shell.mef(face_id, edge.clone()).unwrap();
+    /// assert_eq!(shell.len(), 2);
+    /// assert_eq!(shell[0].id(), face_id);
+    /// assert_eq!(shell[1].id(), new_face_id);
+    /// let v0: Vec<_> = shell[0].boundaries()[0].vertex_iter().collect();
+    /// assert_eq!(v0, vec![v[3].clone(), v[0].clone(), v[1].clone()]);
+    /// let v1: Vec<_> = shell[1].boundaries()[0].vertex_iter().collect();
+    /// assert_eq!(v1, vec![v[1].clone(), v[2].clone(), v[3].clone()]);
+    /// assert_eq!(shell.euler_characteristic(), 1);
+    ///
+    /// // inverse operation
+    /// assert_eq!(shell.kef(edge.id()), Some(edge));
+    /// assert_eq!(shell.len(), 1);
+    /// assert_eq!(shell[0].boundaries()[0].len(), 4);
+    /// ```
+    pub fn mef(&mut self, face_id: FaceID<S>, edge: Edge<P, C>) -> Option<FaceID<S>>
+    where S: Clone {
+        if self.includes_edge(edge.id()) {
+            return None;
+        }
+        let face = self.iter_mut().find(|face| face.id() == face_id)?;
+        let mut boundaries = face.boundaries();
+        let pos = boundaries.iter().position(|wire| {
+            wire.iter().any(|e| e.front() == edge.front())
+                && wire.iter().any(|e| e.front() == edge.back())
+        })?;
+        let wire = &mut boundaries[pos];
+        let i = wire.iter().position(|e| e.front() == edge.back())?;
+        wire.rotate_left(i);
+        let j = wire.iter().position(|e| e.back() == edge.front())?;
+        let mut new_wire = wire.split_off(j + 1);
+        wire.push_back(edge.clone());
+        new_wire.push_back(edge.inverse());
+        set_oriented_boundaries(face, boundaries);
+        let mut new_face = Face {
+            boundaries: Vec::new(),
+            orientation: face.orientation,
+            surface: Geometry::new(face.surface(), None),
+        };
+        set_oriented_boundaries(&mut new_face, vec![new_wire]);
+        let new_face_id = new_face.id();
+        self.push(new_face);
+        Some(new_face_id)
+    }
+
+    /// KEF: kills an edge and a face. The inverse operation of [`Shell::mef`].
+    ///
+    /// The edge with `edge_id` must be shared by two different faces, and the boundary wires
+    /// including the edge are concatenated. The face including the edge in its original direction
+    /// remains and inherits the other boundary wires of the removed face.
+    ///
+    /// # Returns
+    /// Returns the removed edge.
+    ///
+    /// # Failures
+    /// Returns `None` and does not edit `self` if the edge with `edge_id` is not shared by
+    /// two different faces with the opposite directions.
+    pub fn kef(&mut self, edge_id: EdgeID<C>) -> Option<Edge<P, C>> {
+        let mut occurrences = Vec::new();
+        self.iter().enumerate().for_each(|(k, face)| {
+            face.boundaries().iter().enumerate().for_each(|(i, wire)| {
+                wire.iter().enumerate().for_each(|(j, edge)| {
+                    if edge.id() == edge_id {
+                        occurrences.push((k, i, j, edge.orientation()));
+                    }
+                })
+            })
+        });
+        if occurrences.len() != 2 || occurrences[0].3 == occurrences[1].3 {
+            return None;
+        }
+        occurrences.sort_by_key(|x| !x.3);
+        let ((k0, i0, j0, _), (k1, i1, j1, _)) = (occurrences[0], occurrences[1]);
+        if k0 == k1 {
+            return None;
+        }
+        let mut boundaries0 = self[k0].boundaries();
+        let mut boundaries1 = self[k1].boundaries();
+        let mut wire1 = boundaries1.remove(i1);
+        let wire0 = &mut boundaries0[i0];
+        wire0.rotate_left(j0);
+        wire1.rotate_left(j1);
+        let edge = wire0.pop_front();
+        wire1.pop_front();
+        wire0.append(&mut wire1);
+        boundaries0.extend(boundaries1);
+        set_oriented_boundaries(&mut self[k0], boundaries0);
+        self.remove(k1);
+        edge
+    }
+
+    /// MEKR: makes an edge and kills a ring.
+    ///
+    /// Concatenates two boundary wires of the face with `face_id` by `edge` and its inverse.
+    /// The front vertex of `edge` must be on one boundary wire and the back on another one.
+    /// The concatenated wire is not simple until the ring is made again by `kemr`.
+    ///
+    /// # Failures
+    /// Returns `false` and does not edit `self` if:
+    /// - there is no face corresponding to `face_id` in the shell,
+    /// - the ends of `edge` are not on two different boundary wires of the face, or
+    /// - `edge` is already included in the shell.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(); 6]);
+    /// let wire0 = Wire::from(vec![
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[2], &v[0], ()),
+    /// ]);
+    /// let wire1 = Wire::from(vec![
+    ///     Edge::new(&v[3], &v[5], ()),
+    ///     Edge::new(&v[5], &v[4], ()),
+    ///     Edge::new(&v[4], &v[3], ()),
+    /// ]);
+    /// let face = Face::new(vec![wire0, wire1], ());
+    /// let face_id = face.id();
+    /// let mut shell: Shell<_, _, _> = vec![face].into();
+    /// assert_eq!(shell.euler_characteristic(), 0);
+    ///
+    /// let edge = Edge::new(&v[0], &v[3], ());
+    /// assert!(shell.mekr(face_id, edge.clone()));
+    /// assert_eq!(shell[0].boundaries().len(), 1);
+    /// assert_eq!(shell[0].boundaries()[0].len(), 8);
+    /// assert_eq!(shell.euler_characteristic(), 0);
+    ///
+    /// // inverse operation
+    /// assert_eq!(shell.kemr(edge.id()), Some(edge));
+    /// assert_eq!(shell[0].boundaries().len(), 2);
+    /// ```
+    pub fn mekr(&mut self, face_id: FaceID<S>, edge: Edge<P, C>) -> bool {
+        if self.includes_edge(edge.id()) {
+            return false;
+        }
+        let face = match self.iter_mut().find(|face| face.id() == face_id) {
+            Some(face) => face,
+            None => return false,
+        };
+        let mut boundaries = face.boundaries();
+        let position = |vertex: &Vertex<P>, skip: Option<usize>| {
+            boundaries.iter().enumerate().find_map(|(i, wire)| {
+                if Some(i) == skip {
+                    return None;
+                }
+                let j = wire.iter().position(|e| e.front() == vertex)?;
+                Some((i, j))
+            })
+        };
+        let (i0, j0) = match position(edge.front(), None) {
+            Some(pos) => pos,
+            None => return false,
+        };
+        let (i1, j1) = match position(edge.back(), Some(i0)) {
+            Some(pos) => pos,
+            None => return false,
+        };
+        let mut wire1 = boundaries.remove(i1);
+        let wire0 = &mut boundaries[if i1 < i0 { i0 - 1 } else { i0 }];
+        wire0.rotate_left(j0);
+        wire1.rotate_left(j1);
+        wire0.push_back(edge.clone());
+        wire0.append(&mut wire1);
+        wire0.push_back(edge.inverse());
+        set_oriented_boundaries(face, boundaries);
+        true
+    }
+
+    /// KEMR: kills an edge and makes a ring. The inverse operation of [`Shell::mekr`].
+    ///
+    /// The edge with `edge_id` must appear twice, non-adjacently, in one boundary wire.
+    /// The wire is divided into two wires: the part between the appearances is added as a new ring,
+    /// and the rest stays at the original position.
+    ///
+    /// # Returns
+    /// Returns the removed edge.
+    ///
+    /// # Failures
+    /// Returns `None` and does not edit `self` if there is no such boundary wire.
+    pub fn kemr(&mut self, edge_id: EdgeID<C>) -> Option<Edge<P, C>> {
+        self.iter_mut().find_map(|face| {
+            let mut boundaries = face.boundaries();
+            let (i, j0, j1) = boundaries.iter().enumerate().find_map(|(i, wire)| {
+                let mut iter = wire.iter().enumerate().filter(|(_, e)| e.id() == edge_id);
+                let (j0, _) = iter.next()?;
+                let (j1, _) = iter.next()?;
+                if j1 - j0 == 1 || j1 - j0 + 1 == wire.len() {
+                    None
+                } else {
+                    Some((i, j0, j1))
+                }
+            })?;
+            let wire = &mut boundaries[i];
+            let mut ring = wire.split_off(j0);
+            let mut rest = ring.split_off(j1 - j0);
+            let edge = ring.pop_front();
+            rest.pop_front();
+            rest.append(wire);
+            *wire = rest;
+            boundaries.push(ring);
+            set_oriented_boundaries(face, boundaries);
+            edge
+        })
+    }
+
+    /// KFMRH: kills a face and makes a ring and a hole.
+    ///
+    /// Removes the face with `hole_face_id` from the shell and adds its boundary wires to the face
+    /// with `face_id` as rings. If the shell is closed, the genus is increased by one.
+    ///
+    /// # Returns
+    /// Returns the removed face.
+    ///
+    /// # Failures
+    /// Returns `None` and does not edit `self` if `face_id` and `hole_face_id` are the same,
+    /// or if one of them is not in the shell.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v = Vertex::news(&[(); 3]);
+    /// let wire = Wire::from(vec![
+    ///     Edge::new(&v[0], &v[1], ()),
+    ///     Edge::new(&v[1], &v[2], ()),
+    ///     Edge::new(&v[2], &v[0], ()),
+    /// ]);
+    /// // the sphere consisting of two triangles
+    /// let face0 = Face::new(vec![wire.clone()], ());
+    /// let face1 = Face::new(vec![wire.inverse()], ());
+    /// let (face_id0, face_id1) = (face0.id(), face1.id());
+    /// let mut shell: Shell<_, _, _> = vec![face0, face1].into();
+    /// assert_eq!(shell.euler_characteristic(), 2);
+    ///
+    /// // the torus
+    /// let hole = shell.kfmrh(face_id0, face_id1).unwrap();
+    /// assert_eq!(shell.len(), 1);
+    /// assert_eq!(shell[0].boundaries().len(), 2);
+    /// assert_eq!(shell.euler_characteristic(), 0);
+    ///
+    /// // inverse operation
+    /// let new_face_id = shell.mfkrh(face_id0, 1, hole.surface()).unwrap();
+    /// assert_eq!(shell.len(), 2);
+    /// assert_eq!(shell[1].id(), new_face_id);
+    /// assert_eq!(shell[1].boundaries(), hole.boundaries());
+    /// assert_eq!(shell.euler_characteristic(), 2);
+    /// ```
+    pub fn kfmrh(&mut self, face_id: FaceID<S>, hole_face_id: FaceID<S>) -> Option<Face<P, C, S>> {
+        if face_id == hole_face_id {
+            return None;
+        }
+        let k0 = self.iter().position(|face| face.id() == face_id)?;
+        let k1 = self.iter().position(|face| face.id() == hole_face_id)?;
+        let hole = self.remove(k1);
+        let face = &mut self[if k1 < k0 { k0 - 1 } else { k0 }];
+        let mut boundaries = face.boundaries();
+        boundaries.extend(hole.boundaries());
+        set_oriented_boundaries(face, boundaries);
+        Some(hole)
+    }
+
+    /// MFKRH: makes a face and kills a ring and a hole. The inverse operation of [`Shell::kfmrh`].
+    ///
+    /// Removes the `ring_index`th boundary wire of the face with `face_id`, and adds a new face
+    /// with `surface` whose boundary is the removed wire. The new face has the same orientation
+    /// as the face with `face_id`.
+    ///
+    /// # Returns
+    /// Returns the id of the new face.
+    ///
+    /// # Failures
+    /// Returns `None` and does not edit `self` if there is no face corresponding to `face_id`,
+    /// or if the face does not have the `ring_index`th boundary or has only one boundary.
+    pub fn mfkrh(
+        &mut self,
+        face_id: FaceID<S>,
+        ring_index: usize,
+        surface: S,
+    ) -> Option<FaceID<S>> {
+        let face = self.iter_mut().find(|face| face.id() == face_id)?;
+        let mut boundaries = face.boundaries();
+        if boundaries.len() < 2 || ring_index >= boundaries.len() {
+            return None;
+        }
+        let ring = boundaries.remove(ring_index);
+        set_oriented_boundaries(face, boundaries);
+        let mut new_face = Face {
+            boundaries: Vec::new(),
+            orientation: face.orientation,
+            surface: Geometry::new(surface, None),
+        };
+        set_oriented_boundaries(&mut new_face, vec![ring]);
+        let new_face_id = new_face.id();
+        self.push(new_face);
+        Some(new_face_id)
+    }
+}
+
+impl<P, C, S> Solid<P, C, S> {
+    /// MEV: makes an edge and a vertex. See [`Shell::mev`].
+    #[inline(always)]
+    pub fn mev(&mut self, face_id: FaceID<S>, edge: Edge<P, C>) -> bool {
+        self.boundaries
+            .iter_mut()
+            .any(|shell| shell.mev(face_id, edge.clone()))
+    }
+    /// KEV: kills an edge and a vertex. See [`Shell::kev`].
+    #[inline(always)]
+    pub fn kev(&mut self, edge_id: EdgeID<C>) -> Option<Edge<P, C>> {
+        self.boundaries
+            .iter_mut()
+            .find_map(|shell| shell.kev(edge_id))
+    }
+    /// MEF: makes an edge and a face. See [`Shell::mef`].
+    #[inline(always)]
+    pub fn mef(&mut self, face_id: FaceID<S>, edge: Edge<P, C>) -> Option<FaceID<S>>
+    where S: Clone {
+        self.boundaries
+            .iter_mut()
+            .find_map(|shell| shell.mef(face_id, edge.clone()))
+    }
+    /// KEF: kills an edge and a face. See [`Shell::kef`].
+    #[inline(always)]
+    pub fn kef(&mut self, edge_id: EdgeID<C>) -> Option<Edge<P, C>> {
+        self.boundaries
+            .iter_mut()
+            .find_map(|shell| shell.kef(edge_id))
+    }
+    /// MEKR: makes an edge and kills a ring. See [`Shell::mekr`].
+    #[inline(always)]
+    pub fn mekr(&mut self, face_id: FaceID<S>, edge: Edge<P, C>) -> bool {
+        self.boundaries
+            .iter_mut()
+            .any(|shell| shell.mekr(face_id, edge.clone()))
+    }
+    /// KEMR: kills an edge and makes a ring. See [`Shell::kemr`].
+    #[inline(always)]
+    pub fn kemr(&mut self, edge_id: EdgeID<C>) -> Option<Edge<P, C>> {
+        self.boundaries
+            .iter_mut()
+            .find_map(|shell| shell.kemr(edge_id))
+    }
+    /// KFMRH: kills a face and makes a ring and a hole. See [`Shell::kfmrh`].
+    #[inline(always)]
+    pub fn kfmrh(&mut self, face_id: FaceID<S>, hole_face_id: FaceID<S>) -> Option<Face<P, C, S>> {
+        self.boundaries
+            .iter_mut()
+            .find_map(|shell| shell.kfmrh(face_id, hole_face_id))
+    }
+    /// MFKRH: makes a face and kills a ring and a hole. See [`Shell::mfkrh`].
+    #[inline(always)]
+    pub fn mfkrh(
+        &mut self,
+        face_id: FaceID<S>,
+        ring_index: usize,
+        surface: S,
+    ) -> Option<FaceID<S>> {
+        self.boundaries
+            .iter_mut()
+            .find(|shell| shell.iter().any(|face| face.id() == face_id))?
+            .mfkrh(face_id, ring_index, surface)
+    }
+}
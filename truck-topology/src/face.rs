@@ -1,5 +1,6 @@
 use crate::{errors::Error, wire::EdgeIter, *};
 use rustc_hash::FxHashMap as HashMap;
+use truck_base::cgmath64::MetricSpace;
 
 impl<P, C, S> Face<P, C, S> {
     /// Creates a new face by a wire.
@@ -61,7 +62,7 @@ impl<P, C, S> Face<P, C, S> {
         Face {
             boundaries,
             orientation: true,
-            surface: Arc::new(Mutex::new(surface)),
+            surface: Geometry::new(surface, None),
         }
     }
 
@@ -215,7 +216,7 @@ impl<P, C, S> Face<P, C, S> {
     fn renew_pointer(&mut self)
     where S: Clone {
         let surface = self.surface();
-        self.surface = Arc::new(Mutex::new(surface));
+        self.surface = Geometry::new(surface, None);
     }
 
     /// Returns an iterator over the edges.
@@ -417,6 +418,7 @@ impl<P, C, S> Face<P, C, S> {
             .collect::<Option<Vec<_>>>()?;
         let surface = surface_mapping(&*self.surface.lock())?;
         let mut face = Face::debug_new(wires, surface);
+        face.surface.set_origin(&self.surface);
         if !self.orientation() {
             face.invert();
         }
@@ -489,6 +491,7 @@ impl<P, C, S> Face<P, C, S> {
             .collect();
         let surface = surface_mapping(&*self.surface.lock());
         let mut face = Face::debug_new(wires, surface);
+        face.surface.set_origin(&self.surface);
         if !self.orientation() {
             face.invert();
         }
@@ -607,7 +610,22 @@ impl<P, C, S> Face<P, C, S> {
     /// assert_ne!(face0.id(), face2.id());
     /// ```
     #[inline(always)]
-    pub fn id(&self) -> FaceID<S> { ID::new(Arc::as_ptr(&self.surface)) }
+    pub fn id(&self) -> FaceID<S> { ID::new(&self.surface.entity) }
+
+    /// Returns the stable id of the face, `None` if it has not been assigned.
+    /// See [`StableID`] for details.
+    #[inline(always)]
+    pub fn stable_id(&self) -> Option<StableID> { self.surface.stable_id() }
+
+    /// Sets the stable id of the face, which is shared by the cloned and inverted faces.
+    #[inline(always)]
+    pub fn set_stable_id(&self, stable_id: Option<StableID>) {
+        self.surface.set_stable_id(stable_id)
+    }
+
+    /// Returns the stable id of the face. If it has not been assigned, issues a new one.
+    #[inline(always)]
+    pub fn assign_stable_id(&self) -> StableID { self.surface.assign_stable_id() }
 
     /// Returns how many same faces.
     ///
@@ -797,7 +815,7 @@ impl<P, C, S> Face<P, C, S> {
         let mut face0 = Face {
             boundaries: self.boundaries.clone(),
             orientation: self.orientation,
-            surface: Arc::new(Mutex::new(self.surface())),
+            surface: Geometry::new(self.surface(), None),
         };
         let boundary = &mut face0.boundaries[0];
         let i = boundary
@@ -820,7 +838,7 @@ impl<P, C, S> Face<P, C, S> {
         let face1 = Face {
             boundaries: vec![new_wire],
             orientation: self.orientation,
-            surface: Arc::new(Mutex::new(self.surface())),
+            surface: Geometry::new(self.surface(), None),
         };
         Some((face0, face1))
     }
@@ -921,7 +939,7 @@ impl<P, C, S> Face<P, C, S> {
         Some(Face {
             boundaries,
             orientation: self.orientation(),
-            surface: Arc::new(Mutex::new(surface)),
+            surface: Geometry::new(surface, None),
         })
     }
 
@@ -1010,18 +1028,68 @@ where
 {
     /// Returns the consistence of the geometry of end vertices
     /// and the geometry of edge.
+    ///
+    /// Since [`IncludeCurve`] is judged by the global `TOLERANCE`, the inclusion of the curves
+    /// of the edges having their own tolerances is not checked.
+    /// Use [`Face::is_geometric_consistent_with_tolerances`] to check them.
     #[inline(always)]
     pub fn is_geometric_consistent(&self) -> bool {
+        let surface = &*self.surface.lock();
+        self.boundary_iters().into_iter().flatten().all(|edge| {
+            let edge_consist = edge.is_geometric_consistent();
+            let curve_consist = edge.tolerance().is_some() || surface.include(&*edge.curve.lock());
+            edge_consist && curve_consist
+        })
+    }
+}
+
+impl<P, C, S> Face<P, C, S>
+where
+    P: Tolerance + MetricSpace<Metric = f64> + Copy,
+    C: BoundedCurve<Point = P>,
+    S: IncludeCurve<C> + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+{
+    /// Returns the consistence of the geometry of end vertices
+    /// and the geometry of edge, including the curves of the edges having their own tolerances.
+    ///
+    /// The curves of such edges are checked by the distances from the points sampled on the curves
+    /// to the surface, instead of [`IncludeCurve`] judged by the global `TOLERANCE`.
+    #[inline(always)]
+    pub fn is_geometric_consistent_with_tolerances(&self) -> bool {
         let surface = &*self.surface.lock();
         self.boundary_iters().into_iter().flatten().all(|edge| {
             let edge_consist = edge.is_geometric_consistent();
             let curve = &*edge.curve.lock();
-            let curve_consist = surface.include(curve);
+            let curve_consist = match edge.tolerance() {
+                Some(tol) => curve_near_surface(curve, surface, tol),
+                None => surface.include(curve),
+            };
             edge_consist && curve_consist
         })
     }
 }
 
+/// Returns `true` if the points sampled on `curve` are within `tol` from `surface`.
+fn curve_near_surface<P, C, S>(curve: &C, surface: &S, tol: f64) -> bool
+where
+    P: MetricSpace<Metric = f64> + Copy,
+    C: BoundedCurve<Point = P>,
+    S: ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>, {
+    let mut hint = None;
+    validity::sampling_parameters(curve, true)
+        .into_iter()
+        .all(|t| {
+            let p = curve.subs(t);
+            let uv = hint
+                .and_then(|uv| surface.search_nearest_parameter(p, uv, SEARCH_PARAMETER_TRIALS))
+                .or_else(|| {
+                    surface.search_nearest_parameter(p, SPHint2D::Global, SEARCH_PARAMETER_TRIALS)
+                });
+            hint = uv;
+            uv.is_some_and(|(u, v)| surface.subs(u, v).distance(p) <= tol)
+        })
+}
+
 impl<P, C, S> Clone for Face<P, C, S> {
     #[inline(always)]
     fn clone(&self) -> Face<P, C, S> {
@@ -0,0 +1,324 @@
+//! History of modeling operations
+//!
+//! [`History`] records, for the vertices, edges and faces created by a modeling operation,
+//! how they are derived from the elements of the input shapes. The elements of the input shapes
+//! which remain in the result as they are have the same ids, so they are not recorded.
+//!
+//! # Examples
+//! ```
+//! use truck_topology::*;
+//! use truck_topology::history::*;
+//! let v = Vertex::news([(); 3]);
+//! let edge0 = Edge::<(), ()>::new(&v[0], &v[1], ());
+//! // `edge0` is divided at `v[2]`.
+//! let edge1 = Edge::new(&v[0], &v[2], ());
+//! let edge2 = Edge::new(&v[2], &v[1], ());
+//!
+//! let mut history = History::<(), (), ()>::new();
+//! history.set_edge(edge1.id(), Evolution::Modified, [Origin::Edge(edge0.id())]);
+//! history.set_edge(edge2.id(), Evolution::Modified, [Origin::Edge(edge0.id())]);
+//!
+//! let pieces: Vec<_> = history.edges_from(Origin::Edge(edge0.id())).collect();
+//! assert_eq!(pieces.len(), 2);
+//! assert!(pieces.contains(&(edge1.id(), Evolution::Modified)));
+//! ```
+
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+
+/// How an element is derived from its origins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Evolution {
+    /// The element is generated from lower dimensional elements,
+    /// e.g. a side face swept from an edge, or an intersection edge of two faces.
+    Generated,
+    /// The element is a transformed copy of the origin, e.g. the top face of an extrusion.
+    Copied,
+    /// The element is a part of the origin, e.g. a piece of a divided face.
+    Modified,
+}
+
+/// The id of an element from which another element is derived
+pub enum Origin<P, C, S> {
+    /// vertex
+    Vertex(VertexID<P>),
+    /// edge
+    Edge(EdgeID<C>),
+    /// face
+    Face(FaceID<S>),
+}
+
+/// How an element is derived and its origins
+pub struct HistoryEntry<P, C, S> {
+    /// how the element is derived
+    pub evolution: Evolution,
+    /// the origins of the element
+    pub origins: Vec<Origin<P, C, S>>,
+}
+
+/// History of a modeling operation
+pub struct History<P, C, S> {
+    vertices: HashMap<VertexID<P>, HistoryEntry<P, C, S>>,
+    edges: HashMap<EdgeID<C>, HistoryEntry<P, C, S>>,
+    faces: HashMap<FaceID<S>, HistoryEntry<P, C, S>>,
+}
+
+impl<P, C, S> History<P, C, S> {
+    /// Creates the empty history.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            vertices: HashMap::default(),
+            edges: HashMap::default(),
+            faces: HashMap::default(),
+        }
+    }
+    /// Returns `true` if nothing is recorded.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty() && self.edges.is_empty() && self.faces.is_empty()
+    }
+
+    /// Returns the history entry of the vertex.
+    #[inline(always)]
+    pub fn vertex(&self, id: VertexID<P>) -> Option<&HistoryEntry<P, C, S>> {
+        self.vertices.get(&id)
+    }
+    /// Returns the history entry of the edge.
+    #[inline(always)]
+    pub fn edge(&self, id: EdgeID<C>) -> Option<&HistoryEntry<P, C, S>> { self.edges.get(&id) }
+    /// Returns the history entry of the face.
+    #[inline(always)]
+    pub fn face(&self, id: FaceID<S>) -> Option<&HistoryEntry<P, C, S>> { self.faces.get(&id) }
+
+    /// Records the origins of the vertex.
+    #[inline(always)]
+    pub fn set_vertex(
+        &mut self,
+        id: VertexID<P>,
+        evolution: Evolution,
+        origins: impl IntoIterator<Item = Origin<P, C, S>>,
+    ) {
+        self.vertices
+            .insert(id, HistoryEntry::new(evolution, origins));
+    }
+    /// Records the origins of the edge.
+    #[inline(always)]
+    pub fn set_edge(
+        &mut self,
+        id: EdgeID<C>,
+        evolution: Evolution,
+        origins: impl IntoIterator<Item = Origin<P, C, S>>,
+    ) {
+        self.edges.insert(id, HistoryEntry::new(evolution, origins));
+    }
+    /// Records the origins of the face.
+    #[inline(always)]
+    pub fn set_face(
+        &mut self,
+        id: FaceID<S>,
+        evolution: Evolution,
+        origins: impl IntoIterator<Item = Origin<P, C, S>>,
+    ) {
+        self.faces.insert(id, HistoryEntry::new(evolution, origins));
+    }
+
+    /// Returns the iterator over the recorded vertices.
+    #[inline(always)]
+    pub fn vertex_iter(&self) -> impl Iterator<Item = (VertexID<P>, &HistoryEntry<P, C, S>)> {
+        self.vertices.iter().map(|(id, entry)| (*id, entry))
+    }
+    /// Returns the iterator over the recorded edges.
+    #[inline(always)]
+    pub fn edge_iter(&self) -> impl Iterator<Item = (EdgeID<C>, &HistoryEntry<P, C, S>)> {
+        self.edges.iter().map(|(id, entry)| (*id, entry))
+    }
+    /// Returns the iterator over the recorded faces.
+    #[inline(always)]
+    pub fn face_iter(&self) -> impl Iterator<Item = (FaceID<S>, &HistoryEntry<P, C, S>)> {
+        self.faces.iter().map(|(id, entry)| (*id, entry))
+    }
+
+    /// Returns the vertices derived from `origin`.
+    pub fn vertices_from(
+        &self,
+        origin: Origin<P, C, S>,
+    ) -> impl Iterator<Item = (VertexID<P>, Evolution)> + '_ {
+        self.vertices
+            .iter()
+            .filter(move |(_, entry)| entry.origins.contains(&origin))
+            .map(|(id, entry)| (*id, entry.evolution))
+    }
+    /// Returns the edges derived from `origin`.
+    pub fn edges_from(
+        &self,
+        origin: Origin<P, C, S>,
+    ) -> impl Iterator<Item = (EdgeID<C>, Evolution)> + '_ {
+        self.edges
+            .iter()
+            .filter(move |(_, entry)| entry.origins.contains(&origin))
+            .map(|(id, entry)| (*id, entry.evolution))
+    }
+    /// Returns the faces derived from `origin`.
+    pub fn faces_from(
+        &self,
+        origin: Origin<P, C, S>,
+    ) -> impl Iterator<Item = (FaceID<S>, Evolution)> + '_ {
+        self.faces
+            .iter()
+            .filter(move |(_, entry)| entry.origins.contains(&origin))
+            .map(|(id, entry)| (*id, entry.evolution))
+    }
+
+    /// Composes the history `later` of the operation applied to the result of `self`.
+    ///
+    /// The origins recorded in `later` are traced back to the origins recorded in `self`.
+    /// The elements derived by `Generated` or `Copied` keep the evolution even if
+    /// they are modified by the later operation.
+    pub fn compose(&self, later: &Self) -> Self {
+        let trace = |entry: &HistoryEntry<P, C, S>| {
+            let mut evolution = entry.evolution;
+            let mut origins = Vec::new();
+            entry.origins.iter().for_each(|origin| {
+                let prev = match origin {
+                    Origin::Vertex(id) => self.vertex(*id),
+                    Origin::Edge(id) => self.edge(*id),
+                    Origin::Face(id) => self.face(*id),
+                };
+                let traced = match prev {
+                    Some(prev) => {
+                        if evolution == Evolution::Modified {
+                            evolution = prev.evolution;
+                        }
+                        prev.origins.as_slice()
+                    }
+                    None => std::slice::from_ref(origin),
+                };
+                traced.iter().for_each(|origin| {
+                    if !origins.contains(origin) {
+                        origins.push(*origin);
+                    }
+                });
+            });
+            HistoryEntry { evolution, origins }
+        };
+        let mut res = Self {
+            vertices: self.vertices.clone(),
+            edges: self.edges.clone(),
+            faces: self.faces.clone(),
+        };
+        res.vertices
+            .extend(later.vertices.iter().map(|(id, entry)| (*id, trace(entry))));
+        res.edges
+            .extend(later.edges.iter().map(|(id, entry)| (*id, trace(entry))));
+        res.faces
+            .extend(later.faces.iter().map(|(id, entry)| (*id, trace(entry))));
+        res
+    }
+}
+
+impl<P, C, S> HistoryEntry<P, C, S> {
+    #[inline(always)]
+    fn new(evolution: Evolution, origins: impl IntoIterator<Item = Origin<P, C, S>>) -> Self {
+        Self {
+            evolution,
+            origins: origins.into_iter().collect(),
+        }
+    }
+}
+
+impl<P, C, S> Default for History<P, C, S> {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+impl<P, C, S> Clone for History<P, C, S> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            vertices: self.vertices.clone(),
+            edges: self.edges.clone(),
+            faces: self.faces.clone(),
+        }
+    }
+}
+
+impl<P, C, S> Debug for History<P, C, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("History")
+            .field("vertices", &self.vertices)
+            .field("edges", &self.edges)
+            .field("faces", &self.faces)
+            .finish()
+    }
+}
+
+impl<P, C, S> Clone for HistoryEntry<P, C, S> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            evolution: self.evolution,
+            origins: self.origins.clone(),
+        }
+    }
+}
+
+impl<P, C, S> PartialEq for HistoryEntry<P, C, S> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.evolution == other.evolution && self.origins == other.origins
+    }
+}
+
+impl<P, C, S> Eq for HistoryEntry<P, C, S> {}
+
+impl<P, C, S> Debug for HistoryEntry<P, C, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HistoryEntry")
+            .field("evolution", &self.evolution)
+            .field("origins", &self.origins)
+            .finish()
+    }
+}
+
+impl<P, C, S> Clone for Origin<P, C, S> {
+    #[inline(always)]
+    fn clone(&self) -> Self { *self }
+}
+
+impl<P, C, S> Copy for Origin<P, C, S> {}
+
+impl<P, C, S> PartialEq for Origin<P, C, S> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Origin::Vertex(x), Origin::Vertex(y)) => x == y,
+            (Origin::Edge(x), Origin::Edge(y)) => x == y,
+            (Origin::Face(x), Origin::Face(y)) => x == y,
+            _ => false,
+        }
+    }
+}
+
+impl<P, C, S> Eq for Origin<P, C, S> {}
+
+impl<P, C, S> Hash for Origin<P, C, S> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Origin::Vertex(id) => (0, id).hash(state),
+            Origin::Edge(id) => (1, id).hash(state),
+            Origin::Face(id) => (2, id).hash(state),
+        }
+    }
+}
+
+impl<P, C, S> Debug for Origin<P, C, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Origin::Vertex(id) => f.debug_tuple("Vertex").field(id).finish(),
+            Origin::Edge(id) => f.debug_tuple("Edge").field(id).finish(),
+            Origin::Face(id) => f.debug_tuple("Face").field(id).finish(),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! Undo and redo of the edits of shells and solids
+//!
+//! The geometries of vertices, edges and faces are shared by their clones, so the edits by
+//! [`Vertex::set_point`], [`Edge::set_curve`] and [`Face::set_surface`] are immediately visible
+//! to every holder, and the previous geometries are lost. [`Journal`] owns a shell or a solid,
+//! records the edits made through [`Transaction`]s, and rolls them back by [`Journal::undo`]
+//! and replays them by [`Journal::redo`].
+//!
+//! The geometric edits are recorded by [`Transaction::set_point`], [`Transaction::set_curve`] and
+//! [`Transaction::set_surface`]. The topological edits are recorded by [`Transaction::edit`],
+//! which takes the snapshots of the shape before and after the edit. Since the snapshots share
+//! the vertices, edges and faces with the shape, the geometries modified directly inside the
+//! closure of [`Transaction::edit`] are not recorded.
+//!
+//! # Examples
+//! ```
+//! use truck_topology::*;
+//! use truck_topology::journal::*;
+//! let v = Vertex::news([0, 1, 2, 3]);
+//! let edge = [
+//!     Edge::new(&v[0], &v[1], 0),
+//!     Edge::new(&v[1], &v[2], 1),
+//!     Edge::new(&v[2], &v[0], 2),
+//!     Edge::new(&v[1], &v[3], 3),
+//!     Edge::new(&v[3], &v[2], 4),
+//! ];
+//! let wire0 = Wire::from(vec![edge[0].clone(), edge[1].clone(), edge[2].clone()]);
+//! let wire1 = Wire::from(vec![edge[3].clone(), edge[4].clone(), edge[1].inverse()]);
+//! let shell: Shell<_, _, _> = vec![Face::new(vec![wire0], 0)].into();
+//! let mut journal = Journal::new(shell);
+//!
+//! let mut transaction = journal.transaction();
+//! transaction.set_point(&v[0], 10);
+//! transaction.edit(|shell| shell.push(Face::new(vec![wire1], 1)));
+//! transaction.set_curve(&edge[1], 5);
+//! transaction.commit();
+//! assert_eq!(v[0].point(), 10);
+//! assert_eq!(journal.len(), 2);
+//!
+//! assert!(journal.undo());
+//! assert_eq!(v[0].point(), 0);
+//! assert_eq!(edge[1].curve(), 1);
+//! assert_eq!(journal.len(), 1);
+//!
+//! assert!(journal.redo());
+//! assert_eq!(v[0].point(), 10);
+//! assert_eq!(edge[1].curve(), 5);
+//! assert_eq!(journal.len(), 2);
+//! ```
+
+use crate::*;
+
+/// Shapes whose edits can be recorded by [`Journal`], i.e. [`Shell`] and [`Solid`].
+pub trait Journaled: Clone + PartialEq {
+    /// the point of the vertices
+    type Point;
+    /// the curve of the edges
+    type Curve;
+    /// the surface of the faces
+    type Surface;
+}
+
+impl<P, C, S> Journaled for Shell<P, C, S> {
+    type Point = P;
+    type Curve = C;
+    type Surface = S;
+}
+
+impl<P: Clone, C: Clone, S: Clone> Journaled for Solid<P, C, S> {
+    type Point = P;
+    type Curve = C;
+    type Surface = S;
+}
+
+/// A recorded edit, which has the states before and after the edit.
+enum Change<T: Journaled> {
+    Point(Vertex<T::Point>, T::Point, T::Point),
+    Curve(Edge<T::Point, T::Curve>, T::Curve, T::Curve),
+    Surface(Face<T::Point, T::Curve, T::Surface>, T::Surface, T::Surface),
+    Topology(T, T),
+}
+
+impl<T: Journaled> Change<T>
+where
+    T::Point: Clone,
+    T::Curve: Clone,
+    T::Surface: Clone,
+{
+    fn revert(&self, shape: &mut T) {
+        match self {
+            Change::Point(vertex, before, _) => vertex.set_point(before.clone()),
+            Change::Curve(edge, before, _) => edge.set_curve(before.clone()),
+            Change::Surface(face, before, _) => face.set_surface(before.clone()),
+            Change::Topology(before, _) => *shape = before.clone(),
+        }
+    }
+    fn apply(&self, shape: &mut T) {
+        match self {
+            Change::Point(vertex, _, after) => vertex.set_point(after.clone()),
+            Change::Curve(edge, _, after) => edge.set_curve(after.clone()),
+            Change::Surface(face, _, after) => face.set_surface(after.clone()),
+            Change::Topology(_, after) => *shape = after.clone(),
+        }
+    }
+}
+
+/// A shell or a solid with the undo and redo histories of its edits
+///
+/// The shape can be referred by `Deref`, and edited through [`Journal::transaction`].
+pub struct Journal<T: Journaled> {
+    shape: T,
+    undo_stack: Vec<Vec<Change<T>>>,
+    redo_stack: Vec<Vec<Change<T>>>,
+}
+
+impl<T: Journaled> Journal<T>
+where
+    T::Point: Clone,
+    T::Curve: Clone,
+    T::Surface: Clone,
+{
+    /// Creates the journal of `shape` with the empty histories.
+    #[inline(always)]
+    pub fn new(shape: T) -> Self {
+        Self {
+            shape,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+    /// Returns the shape and discards the histories.
+    #[inline(always)]
+    pub fn into_inner(self) -> T { self.shape }
+    /// Starts a transaction. The edits through the transaction are recorded as one step of
+    /// the undo history when the transaction is committed, and rolled back when the transaction
+    /// is dropped without committing.
+    #[inline(always)]
+    pub fn transaction(&mut self) -> Transaction<'_, T> {
+        Transaction {
+            journal: self,
+            changes: Vec::new(),
+        }
+    }
+    /// Returns the number of the transactions which can be undone.
+    #[inline(always)]
+    pub fn undo_len(&self) -> usize { self.undo_stack.len() }
+    /// Returns the number of the transactions which can be redone.
+    #[inline(always)]
+    pub fn redo_len(&self) -> usize { self.redo_stack.len() }
+    /// Rolls back the last committed transaction.
+    /// Returns `false` if there is no transaction to be undone.
+    pub fn undo(&mut self) -> bool {
+        let Some(changes) = self.undo_stack.pop() else {
+            return false;
+        };
+        changes
+            .iter()
+            .rev()
+            .for_each(|change| change.revert(&mut self.shape));
+        self.redo_stack.push(changes);
+        true
+    }
+    /// Replays the last undone transaction.
+    /// Returns `false` if there is no transaction to be redone.
+    pub fn redo(&mut self) -> bool {
+        let Some(changes) = self.redo_stack.pop() else {
+            return false;
+        };
+        changes
+            .iter()
+            .for_each(|change| change.apply(&mut self.shape));
+        self.undo_stack.push(changes);
+        true
+    }
+    /// Discards the undo and redo histories. The current shape is kept.
+    #[inline(always)]
+    pub fn clear_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
+
+impl<T: Journaled> std::ops::Deref for Journal<T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T { &self.shape }
+}
+
+impl<T: Journaled + Debug> Debug for Journal<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Journal")
+            .field("shape", &self.shape)
+            .field("undo_len", &self.undo_stack.len())
+            .field("redo_len", &self.redo_stack.len())
+            .finish()
+    }
+}
+
+/// A sequence of edits recorded as one step of the history of [`Journal`]
+///
+/// The edits are applied to the shape immediately. If the transaction is dropped without
+/// [`Transaction::commit`], the edits are rolled back.
+pub struct Transaction<'a, T: Journaled>
+where
+    T::Point: Clone,
+    T::Curve: Clone,
+    T::Surface: Clone, {
+    journal: &'a mut Journal<T>,
+    changes: Vec<Change<T>>,
+}
+
+impl<'a, T: Journaled> Transaction<'a, T>
+where
+    T::Point: Clone,
+    T::Curve: Clone,
+    T::Surface: Clone,
+{
+    /// Returns the shape in editing.
+    #[inline(always)]
+    pub fn shape(&self) -> &T { &self.journal.shape }
+    /// Returns `true` if no edit is recorded.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool { self.changes.is_empty() }
+    /// Sets the point of `vertex` and records the change.
+    pub fn set_point(&mut self, vertex: &Vertex<T::Point>, point: T::Point) {
+        let before = vertex.point();
+        vertex.set_point(point.clone());
+        self.changes
+            .push(Change::Point(vertex.clone(), before, point));
+    }
+    /// Sets the curve of `edge` and records the change.
+    /// As [`Edge::set_curve`], the curve is not inverted even if `edge` is inverted.
+    pub fn set_curve(&mut self, edge: &Edge<T::Point, T::Curve>, curve: T::Curve) {
+        let before = edge.curve();
+        edge.set_curve(curve.clone());
+        self.changes
+            .push(Change::Curve(edge.clone(), before, curve));
+    }
+    /// Sets the surface of `face` and records the change.
+    /// As [`Face::set_surface`], the surface is not inverted even if `face` is inverted.
+    pub fn set_surface(
+        &mut self,
+        face: &Face<T::Point, T::Curve, T::Surface>,
+        surface: T::Surface,
+    ) {
+        let before = face.surface();
+        face.set_surface(surface.clone());
+        self.changes
+            .push(Change::Surface(face.clone(), before, surface));
+    }
+    /// Edits the topology of the shape by `edit` and records the change.
+    /// Nothing is recorded if the shape is not changed.
+    ///
+    /// Only the topology is recorded: the geometries must be modified by [`Transaction::set_point`],
+    /// [`Transaction::set_curve`] and [`Transaction::set_surface`] before or after `edit`.
+    /// A geometry modified inside `edit` is kept by [`Journal::undo`].
+    pub fn edit<R>(&mut self, edit: impl FnOnce(&mut T) -> R) -> R {
+        let before = self.journal.shape.clone();
+        let res = edit(&mut self.journal.shape);
+        if self.journal.shape != before {
+            let after = self.journal.shape.clone();
+            self.changes.push(Change::Topology(before, after));
+        }
+        res
+    }
+    /// Records the edits as one step of the undo history, and discards the redo history.
+    /// Nothing is recorded if the transaction is empty.
+    pub fn commit(mut self) {
+        let changes = std::mem::take(&mut self.changes);
+        if !changes.is_empty() {
+            self.journal.undo_stack.push(changes);
+            self.journal.redo_stack.clear();
+        }
+    }
+    /// Rolls back the edits. Same as dropping the transaction.
+    #[inline(always)]
+    pub fn rollback(self) {}
+}
+
+impl<'a, T: Journaled> Drop for Transaction<'a, T>
+where
+    T::Point: Clone,
+    T::Curve: Clone,
+    T::Surface: Clone,
+{
+    fn drop(&mut self) {
+        let shape = &mut self.journal.shape;
+        self.changes
+            .iter()
+            .rev()
+            .for_each(|change| change.revert(shape));
+    }
+}
+
+impl<'a, T: Journaled> Debug for Transaction<'a, T>
+where
+    T::Point: Clone,
+    T::Curve: Clone,
+    T::Surface: Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transaction")
+            .field("changes", &self.changes.len())
+            .finish()
+    }
+}
@@ -81,9 +81,12 @@
 )]
 
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
+use std::num::NonZeroU64;
+use std::sync::atomic::{AtomicU64, Ordering};
 use truck_base::{id::ID, tolerance::*};
 use truck_geotrait::*;
 
@@ -94,6 +97,87 @@ use std::sync::Arc;
 
 const SEARCH_PARAMETER_TRIALS: usize = 100;
 
+/// The geometry of a vertex, an edge or a face with the tolerance and the stable id of the element.
+#[derive(Debug)]
+struct Geometry<T> {
+    entity: Mutex<T>,
+    // the bits of the tolerance, `0` if the tolerance is not set.
+    tolerance: AtomicU64,
+    // the stable id, `0` if the stable id is not set.
+    stable_id: AtomicU64,
+    // the stable id of the element from which the element is mapped, `0` if there is no such id.
+    origin: AtomicU64,
+}
+
+impl<T> Geometry<T> {
+    #[inline(always)]
+    fn new(entity: T, tolerance: Option<f64>) -> Arc<Self> {
+        Arc::new(Geometry {
+            entity: Mutex::new(entity),
+            tolerance: AtomicU64::new(tolerance.map_or(0, f64::to_bits)),
+            stable_id: AtomicU64::new(StableID::auto_issued()),
+            origin: AtomicU64::new(0),
+        })
+    }
+    #[inline(always)]
+    fn tolerance(&self) -> Option<f64> {
+        match self.tolerance.load(Ordering::Relaxed) {
+            0 => None,
+            bits => Some(f64::from_bits(bits)),
+        }
+    }
+    #[inline(always)]
+    fn set_tolerance(&self, tolerance: Option<f64>) {
+        if let Some(tol) = tolerance {
+            nonpositive_tolerance!(tol);
+        }
+        let bits = tolerance.map_or(0, f64::to_bits);
+        self.tolerance.store(bits, Ordering::Relaxed)
+    }
+    #[inline(always)]
+    fn stable_id(&self) -> Option<StableID> {
+        NonZeroU64::new(self.stable_id.load(Ordering::Relaxed)).map(StableID)
+    }
+    #[inline(always)]
+    fn set_stable_id(&self, stable_id: Option<StableID>) {
+        let id = stable_id.map_or(0, StableID::get);
+        self.stable_id.store(id, Ordering::Relaxed)
+    }
+    #[inline(always)]
+    fn assign_stable_id(&self) -> StableID {
+        if let Some(stable_id) = self.stable_id() {
+            return stable_id;
+        }
+        let issued = StableID::issue();
+        match self
+            .stable_id
+            .compare_exchange(0, issued.get(), Ordering::Relaxed, Ordering::Relaxed)
+        {
+            Ok(_) => issued,
+            Err(id) => StableID(NonZeroU64::new(id).unwrap()),
+        }
+    }
+    #[inline(always)]
+    fn origin(&self) -> Option<StableID> {
+        NonZeroU64::new(self.origin.load(Ordering::Relaxed)).map(StableID)
+    }
+    /// Records `source` as the origin, or the origin of `source` if it has no stable id.
+    #[inline(always)]
+    fn set_origin<U>(&self, source: &Geometry<U>) {
+        let id = match source.stable_id.load(Ordering::Relaxed) {
+            0 => source.origin.load(Ordering::Relaxed),
+            id => id,
+        };
+        self.origin.store(id, Ordering::Relaxed)
+    }
+}
+
+impl<T> std::ops::Deref for Geometry<T> {
+    type Target = Mutex<T>;
+    #[inline(always)]
+    fn deref(&self) -> &Mutex<T> { &self.entity }
+}
+
 /// Vertex, the minimum topological unit.
 ///
 /// The constructor `Vertex::new()` creates a different vertex each time.
@@ -106,7 +190,7 @@ const SEARCH_PARAMETER_TRIALS: usize = 100;
 /// ```
 #[derive(Debug)]
 pub struct Vertex<P> {
-    point: Arc<Mutex<P>>,
+    point: Arc<Geometry<P>>,
 }
 
 /// Edge, which consists two vertices.
@@ -125,7 +209,7 @@ pub struct Vertex<P> {
 pub struct Edge<P, C> {
     vertices: (Vertex<P>, Vertex<P>),
     orientation: bool,
-    curve: Arc<Mutex<C>>,
+    curve: Arc<Geometry<C>>,
 }
 
 /// Wire, a path or cycle which consists some edges.
@@ -156,7 +240,7 @@ pub struct Wire<P, C> {
 pub struct Face<P, C, S> {
     boundaries: Vec<Wire<P, C>>,
     orientation: bool,
-    surface: Arc<Mutex<S>>,
+    surface: Arc<Geometry<S>>,
 }
 
 /// Shell, a connected compounded faces.
@@ -174,6 +258,18 @@ pub struct Solid<P, C, S> {
     boundaries: Vec<Shell<P, C, S>>,
 }
 
+/// Compound, a collection of solids, shells, wires and free vertices.
+///
+/// The elements are not required to be connected or disjoint, e.g. a compound can hold
+/// all the pieces resulting from a boolean operation as a single value.
+#[derive(Clone, Debug)]
+pub struct Compound<P, C, S> {
+    solids: Vec<Solid<P, C, S>>,
+    shells: Vec<Shell<P, C, S>>,
+    wires: Vec<Wire<P, C>>,
+    vertices: Vec<Vertex<P>>,
+}
+
 /// `Result` with crate's errors.
 pub type Result<T> = std::result::Result<T, errors::Error>;
 
@@ -251,6 +347,35 @@ pub type EdgeID<C> = ID<Mutex<C>>;
 /// ```
 pub type FaceID<S> = ID<Mutex<S>>;
 
+/// The id of a vertex, an edge or a face which does not depend on the memory address.
+///
+/// Unlike [`VertexID`], [`EdgeID`] and [`FaceID`], the stable id is preserved by compression,
+/// extraction and serialization, so it can be used to refer the elements from outside of the
+/// process. The elements have no stable ids unless they are set explicitly, assigned by e.g.
+/// [`Shell::assign_stable_ids`], or issued on creation by [`StableID::set_auto_issue`].
+/// ```
+/// use truck_topology::*;
+/// let v = Vertex::news(&[(); 3]);
+/// let wire = Wire::from(vec![
+///     Edge::new(&v[0], &v[1], ()),
+///     Edge::new(&v[1], &v[2], ()),
+///     Edge::new(&v[2], &v[0], ()),
+/// ]);
+/// let face = Face::new(vec![wire], ());
+/// let shell: Shell<_, _, _> = vec![face.clone(), face.inverse()].into();
+/// assert_eq!(face.stable_id(), None);
+///
+/// shell.assign_stable_ids();
+/// let stable_id = face.stable_id().unwrap();
+/// assert_eq!(face.inverse().stable_id(), Some(stable_id));
+///
+/// let extracted = Shell::extract(shell.compress()).unwrap();
+/// assert_eq!(extracted[0].stable_id(), Some(stable_id));
+/// assert_ne!(extracted[0].id(), face.id());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct StableID(NonZeroU64);
+
 /// configuration for vertex display format.
 #[derive(Clone, Copy, Debug)]
 pub enum VertexDisplayFormat {
@@ -383,15 +508,32 @@ pub enum SolidDisplayFormat {
     },
 }
 
+pub mod adjacency;
+/// Typed attributes attached to vertices, edges and faces
+pub mod attributes;
+pub mod binary;
+mod compound;
 pub mod compress;
+pub mod diff;
 mod edge;
 /// classifies the errors that can occur in this crate.
 pub mod errors;
+mod euler;
 /// Defines the boundary iterator.
 pub mod face;
+/// Records how the elements are derived by modeling operations.
+pub mod history;
+pub mod journal;
+pub mod mass_properties;
+/// Represents non-manifold cellular solids sharing faces.
+pub mod non_manifold;
+mod sew;
 /// classifies shell conditions and defines the face iterators.
 pub mod shell;
 mod solid;
+mod stable_id;
+mod unify;
+pub mod validity;
 mod vertex;
 /// define the edge iterators and the vertex iterator.
 pub mod wire;
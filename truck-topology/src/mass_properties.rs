@@ -0,0 +1,405 @@
+//! Mass properties of shells and solids
+//!
+//! [`MassProperties`] has the volume, the surface area, the centroid and the inertia tensor of a
+//! shape with the unit density. Unlike `CalcVolume` of `truck-meshalgo`, they are computed without
+//! tessellation: the integrals over the solid are reduced to the integrals over the boundary
+//! faces by the divergence theorem, and the integrals over the trimmed faces are reduced to the
+//! line integrals along the boundaries in the parameter spaces of the surfaces by Green's theorem.
+//! The integrals are evaluated by the composite Gauss–Legendre quadrature, and the divisions are
+//! doubled until the results converge.
+//!
+//! The principal moments and axes are obtained by [`MassProperties::principal_axes`].
+//! ```
+//! use truck_topology::mass_properties::MassProperties;
+//! use truck_base::{assert_near, cgmath64::*, tolerance::*};
+//! // the cuboid [0, 1] x [0, 2] x [0, 3] rotated by 90 degrees around the z-axis
+//! let props = MassProperties {
+//!     volume: 6.0,
+//!     area: 22.0,
+//!     centroid: Point3::new(-1.0, 0.5, 1.5),
+//!     inertia: Matrix3::from_diagonal(Vector3::new(5.0, 6.5, 2.5)),
+//! };
+//! let (moments, axes) = props.principal_axes();
+//! assert_near!(moments[0], 2.5);
+//! assert_near!(axes[0].z.abs(), 1.0);
+//! assert_near!(moments[2], 6.5);
+//! assert_near!(axes[2].y.abs(), 1.0);
+//!
+//! // the moment of inertia about the edge on the z-axis
+//! let inertia = props.inertia_about(Point3::new(0.0, 0.0, 1.5));
+//! assert_near!(inertia.z.z, 2.5 + 6.0 * 1.25);
+//! ```
+
+use crate::*;
+use truck_base::{bounding_box::BoundingBox, cgmath64::*};
+
+/// the nodes and the weights of the 8-point Gauss–Legendre quadrature on `[-1, 1]`
+const GAUSS_LEGENDRE: [(f64, f64); 8] = [
+    (-0.960_289_856_497_536_3, 0.101_228_536_290_376_3),
+    (-0.796_666_477_413_626_7, 0.222_381_034_453_374_5),
+    (-0.525_532_409_916_329, 0.313_706_645_877_887_3),
+    (-0.183_434_642_495_649_8, 0.362_683_783_378_362),
+    (0.183_434_642_495_649_8, 0.362_683_783_378_362),
+    (0.525_532_409_916_329, 0.313_706_645_877_887_3),
+    (0.796_666_477_413_626_7, 0.222_381_034_453_374_5),
+    (0.960_289_856_497_536_3, 0.101_228_536_290_376_3),
+];
+
+/// the maximum number of the divisions of the quadrature
+const MAX_DIVISION: usize = 128;
+
+/// The integrals of the area, the volume, the first moments `x`, `y`, `z` and the second moments
+/// `xx`, `yy`, `zz`, `xy`, `yz`, `zx`.
+type Integrals = [f64; 11];
+
+/// the dimensions of the lengths of the integrals
+const DIMENSIONS: Integrals = [2.0, 3.0, 4.0, 4.0, 4.0, 5.0, 5.0, 5.0, 5.0, 5.0, 5.0];
+
+/// Mass properties of a shape with the unit density
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+    /// the volume enclosed by the shape
+    pub volume: f64,
+    /// the total area of the faces
+    pub area: f64,
+    /// the centroid of the volume
+    pub centroid: Point3,
+    /// the inertia tensor about the centroid
+    pub inertia: Matrix3,
+}
+
+impl MassProperties {
+    fn new(integrals: Integrals) -> Self {
+        let [area, volume, x, y, z, xx, yy, zz, xy, yz, zx] = integrals;
+        let centroid = Point3::new(x, y, z) / volume;
+        let inertia_at_origin =
+            Matrix3::new(yy + zz, -xy, -zx, -xy, zz + xx, -yz, -zx, -yz, xx + yy);
+        let mut res = Self {
+            volume,
+            area,
+            centroid,
+            inertia: inertia_at_origin,
+        };
+        res.inertia = inertia_at_origin - res.parallel_axis_term(Point3::origin());
+        res
+    }
+
+    // the difference of the inertia tensors about `point` and about the centroid
+    fn parallel_axis_term(&self, point: Point3) -> Matrix3 {
+        let d = point - self.centroid;
+        let outer = Matrix3::from_cols(d * d.x, d * d.y, d * d.z);
+        (Matrix3::identity() * d.magnitude2() - outer) * self.volume
+    }
+
+    /// Returns the inertia tensor about `point` by the parallel axis theorem.
+    #[inline(always)]
+    pub fn inertia_about(&self, point: Point3) -> Matrix3 {
+        self.inertia + self.parallel_axis_term(point)
+    }
+
+    /// Returns the principal moments of inertia in ascending order, and the matrix whose columns
+    /// are the corresponding principal axes.
+    pub fn principal_axes(&self) -> ([f64; 3], Matrix3) {
+        let (a, v) = jacobi_eigen(self.inertia);
+        let mut idx = [0, 1, 2];
+        idx.sort_by(|i, j| a[*i][*i].total_cmp(&a[*j][*j]));
+        let moments = idx.map(|i| a[i][i]);
+        let axes = Matrix3::from_cols(v[idx[0]], v[idx[1]], v[idx[2]]);
+        (moments, axes)
+    }
+}
+
+/// Diagonalizes the symmetric matrix by the Jacobi method.
+/// Returns the diagonal matrix and the orthogonal matrix of the eigenvectors.
+fn jacobi_eigen(mut a: Matrix3) -> (Matrix3, Matrix3) {
+    let mut v = Matrix3::identity();
+    let scale = (0..3).map(|i| a[i][i].abs()).fold(0.0, f64::max);
+    for _ in 0..50 {
+        let off_diagonal = a[0][1].abs() + a[1][2].abs() + a[2][0].abs();
+        if off_diagonal <= f64::EPSILON * scale {
+            break;
+        }
+        for (p, q) in [(0, 1), (1, 2), (0, 2)] {
+            if a[q][p] == 0.0 {
+                continue;
+            }
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[q][p]);
+            let t = theta.signum() / (theta.abs() + f64::sqrt(theta * theta + 1.0));
+            let c = 1.0 / f64::sqrt(t * t + 1.0);
+            let mut rotation = Matrix3::identity();
+            rotation[p][p] = c;
+            rotation[q][q] = c;
+            rotation[q][p] = t * c;
+            rotation[p][q] = -t * c;
+            a = rotation.transpose() * a * rotation;
+            v = v * rotation;
+        }
+    }
+    (a, v)
+}
+
+/// Integrates `f` on `[a, b]` by the composite Gauss–Legendre quadrature with `division` segments.
+fn gauss_legendre(
+    a: f64,
+    b: f64,
+    division: usize,
+    mut f: impl FnMut(f64) -> Option<Integrals>,
+) -> Option<Integrals> {
+    let h = (b - a) / division as f64;
+    let mut res = [0.0; 11];
+    for i in 0..division {
+        let mid = a + h * (i as f64 + 0.5);
+        for (x, w) in GAUSS_LEGENDRE {
+            let value = f(mid + h * x / 2.0)?;
+            res.iter_mut()
+                .zip(value)
+                .for_each(|(r, value)| *r += value * w * h / 2.0);
+        }
+    }
+    Some(res)
+}
+
+/// The integrands whose divergences are the integrands of the integrals, dotted with the normal.
+fn integrands(point: Point3, normal: Vector3) -> Integrals {
+    let (x, y, z) = (point.x, point.y, point.z);
+    [
+        normal.magnitude(),
+        point.to_vec().dot(normal) / 3.0,
+        x * x * normal.x / 2.0,
+        y * y * normal.y / 2.0,
+        z * z * normal.z / 2.0,
+        x * x * x * normal.x / 3.0,
+        y * y * y * normal.y / 3.0,
+        z * z * z * normal.z / 3.0,
+        x * x * y * normal.x / 2.0,
+        y * y * z * normal.y / 2.0,
+        z * z * x * normal.z / 2.0,
+    ]
+}
+
+/// Shifts the parameter by the periods of `surface` so that it is the nearest to `reference`.
+fn nearest_period<S: ParametricSurface>(
+    surface: &S,
+    (mut u, mut v): (f64, f64),
+    (u1, v1): (f64, f64),
+) -> (f64, f64) {
+    if let Some(period) = surface.u_period() {
+        u += period * ((u1 - u) / period).round();
+    }
+    if let Some(period) = surface.v_period() {
+        v += period * ((v1 - v) / period).round();
+    }
+    (u, v)
+}
+
+/// Returns the parameter of `point` on `surface`, nearest to `hint` modulo the periods.
+fn search_parameter<S>(surface: &S, point: Point3, hint: Option<(f64, f64)>) -> Option<(f64, f64)>
+where S: ParametricSurface3D
+        + SearchParameter<D2, Point = Point3>
+        + SearchNearestParameter<D2, Point = Point3> {
+    let search = |hint: SPHint2D| {
+        surface
+            .search_parameter(point, hint, SEARCH_PARAMETER_TRIALS)
+            .or_else(|| surface.search_nearest_parameter(point, hint, SEARCH_PARAMETER_TRIALS))
+            .filter(|(u, v)| surface.subs(*u, *v).near(&point))
+    };
+    let uv = hint
+        .and_then(|hint| search(hint.into()))
+        .or_else(|| search(SPHint2D::Global))?;
+    Some(match hint {
+        Some(hint) => nearest_period(surface, uv, hint),
+        None => uv,
+    })
+}
+
+/// Returns the parameter of the end of an edge, where `node` is the parameter of the nearest
+/// quadrature node on the edge. At a singular point of `surface`, e.g. a pole of a sphere,
+/// the degenerate coordinate is taken from `node`.
+fn end_parameter<S>(surface: &S, point: Point3, node: (f64, f64)) -> (f64, f64)
+where S: ParametricSurface3D
+        + SearchParameter<D2, Point = Point3>
+        + SearchNearestParameter<D2, Point = Point3> {
+    let Some((u, v)) = search_parameter(surface, point, Some(node)) else {
+        return node;
+    };
+    match (surface.uder(u, v).so_small(), surface.vder(u, v).so_small()) {
+        (true, _) => (node.0, v),
+        (_, true) => (u, node.1),
+        _ => (u, v),
+    }
+}
+
+/// Integrates over the face with `division` segments for each edge.
+/// The area is signed by the orientation of the face in the parameter space.
+///
+/// By Green's theorem, the integral of `dF/du` over the domain is the integral of `F dv` along the
+/// boundary, where `F(u, v)` is the integral of the integrand from `u0` to `u`. The gaps between
+/// the ends of the edges in the parameter space, e.g. at the poles, are connected by segments.
+fn face_integrals<C, S>(face: &Face<Point3, C, S>, division: usize) -> Option<Integrals>
+where
+    C: ParametricCurve3D + BoundedCurve,
+    S: ParametricSurface3D
+        + SearchParameter<D2, Point = Point3>
+        + SearchNearestParameter<D2, Point = Point3>, {
+    let surface = face.surface();
+    // `F(u, v) dv`
+    let line_element = |u0: f64, (u, v): (f64, f64), dv: f64| {
+        let f = gauss_legendre(u0, u, division, |s| {
+            let normal = surface.uder(s, v).cross(surface.vder(s, v));
+            Some(integrands(surface.subs(s, v), normal))
+        })?;
+        Some(f.map(|x| x * dv))
+    };
+    let segment = |u0: f64, (u, v): (f64, f64), (u1, v1): (f64, f64)| {
+        if (v1 - v).so_small() {
+            return Some([0.0; 11]);
+        }
+        gauss_legendre(0.0, 1.0, division, |s| {
+            let p = (u + (u1 - u) * s, v + (v1 - v) * s);
+            line_element(u0, p, v1 - v)
+        })
+    };
+    let mut u0 = None;
+    let mut res = [0.0; 11];
+    let mut add = |integrals: Integrals| {
+        res.iter_mut().zip(integrals).for_each(|(r, x)| *r += x);
+    };
+    for wire in face.boundaries() {
+        // The projection of the middle point of an edge is more stable than the one of a vertex,
+        // which may be at a pole.
+        let initial_hint = wire.front().and_then(|edge| {
+            let curve = edge.curve();
+            let (t0, t1) = curve.range_tuple();
+            search_parameter(&surface, curve.subs((t0 + t1) / 2.0), None)
+        });
+        let (mut first, mut last) = (None, None);
+        for edge in wire.iter() {
+            let curve = edge.curve();
+            let (t0, t1) = match edge.orientation() {
+                true => curve.range_tuple(),
+                false => (curve.range_tuple().1, curve.range_tuple().0),
+            };
+            let (mut first_node, mut hint) = (None, last.or(initial_hint));
+            let integrals = gauss_legendre(t0, t1, division, |t| {
+                let (u, v) = search_parameter(&surface, curve.subs(t), hint)?;
+                hint = Some((u, v));
+                first_node.get_or_insert((u, v));
+                let u0 = *u0.get_or_insert(u);
+                let (a, b, c) = (surface.uder(u, v), surface.vder(u, v), curve.der(t));
+                let (aa, ab, bb) = (a.dot(a), a.dot(b), b.dot(b));
+                let det = aa * bb - ab * ab;
+                if det.so_small2() {
+                    return Some([0.0; 11]);
+                }
+                let dv = (aa * b.dot(c) - ab * a.dot(c)) / det;
+                line_element(u0, (u, v), dv)
+            })?;
+            add(integrals);
+            let start = end_parameter(&surface, curve.subs(t0), first_node?);
+            let end = end_parameter(&surface, curve.subs(t1), hint?);
+            if let Some(last) = last {
+                add(segment(u0?, last, start)?);
+            }
+            first.get_or_insert(start);
+            last = Some(end);
+        }
+        if let (Some(first), Some(last)) = (first, last) {
+            add(segment(u0?, last, nearest_period(&surface, first, last))?);
+        }
+    }
+    Some(res)
+}
+
+/// Integrates over the face, doubling the divisions until the integrals converge.
+fn converged_face_integrals<C, S>(face: &Face<Point3, C, S>, tol: f64) -> Option<Integrals>
+where
+    C: ParametricCurve3D + BoundedCurve,
+    S: ParametricSurface3D
+        + SearchParameter<D2, Point = Point3>
+        + SearchNearestParameter<D2, Point = Point3>, {
+    // the size of the face estimated from the points on the boundary
+    let bdb: BoundingBox<Point3> = face
+        .edge_iter()
+        .flat_map(|edge| {
+            let curve = edge.curve();
+            let (t0, t1) = curve.range_tuple();
+            [t0, (t0 + t1) / 2.0, t1].map(|t| curve.subs(t))
+        })
+        .collect();
+    let size = bdb.diameter().max(tol);
+    let mut division = 2;
+    let mut res = face_integrals(face, division)?;
+    while division < MAX_DIVISION {
+        division *= 2;
+        let next = face_integrals(face, division)?;
+        let converged = res
+            .iter()
+            .zip(&next)
+            .zip(DIMENSIONS)
+            .all(|((x, y), dim)| (x - y).abs() <= tol * size.powf(dim - 1.0));
+        res = next;
+        if converged {
+            break;
+        }
+    }
+    res[0] = res[0].abs();
+    Some(res)
+}
+
+fn mass_properties<'a, C, S>(
+    faces: impl Iterator<Item = &'a Face<Point3, C, S>>,
+    tol: f64,
+) -> Option<MassProperties>
+where
+    C: ParametricCurve3D + BoundedCurve + 'a,
+    S: ParametricSurface3D
+        + SearchParameter<D2, Point = Point3>
+        + SearchNearestParameter<D2, Point = Point3>
+        + 'a,
+{
+    nonpositive_tolerance!(tol);
+    let mut integrals = [0.0; 11];
+    for face in faces {
+        let res = converged_face_integrals(face, tol)?;
+        integrals.iter_mut().zip(res).for_each(|(x, y)| *x += y);
+    }
+    Some(MassProperties::new(integrals))
+}
+
+impl<C, S> Shell<Point3, C, S>
+where
+    C: ParametricCurve3D + BoundedCurve,
+    S: ParametricSurface3D
+        + SearchParameter<D2, Point = Point3>
+        + SearchNearestParameter<D2, Point = Point3>,
+{
+    /// Returns the mass properties of the region enclosed by the shell.
+    /// See [the module document](self) for the details.
+    ///
+    /// The errors of the integrals are of the order of the changes by moving the faces by `tol`.
+    /// The volume, the centroid and the inertia tensor make sense only if the shell is closed
+    /// and oriented. Returns `None` if a point on the boundary of a face cannot be projected to
+    /// the surface.
+    #[inline(always)]
+    pub fn mass_properties(&self, tol: f64) -> Option<MassProperties> {
+        mass_properties(self.face_iter(), tol)
+    }
+}
+
+impl<C, S> Solid<Point3, C, S>
+where
+    C: ParametricCurve3D + BoundedCurve,
+    S: ParametricSurface3D
+        + SearchParameter<D2, Point = Point3>
+        + SearchNearestParameter<D2, Point = Point3>,
+{
+    /// Returns the mass properties of the solid.
+    /// See [the module document](self) for the details.
+    ///
+    /// The errors of the integrals are of the order of the changes by moving the faces by `tol`.
+    /// Returns `None` if a point on the boundary of a face cannot be projected to the surface.
+    #[inline(always)]
+    pub fn mass_properties(&self, tol: f64) -> Option<MassProperties> {
+        mass_properties(self.face_iter(), tol)
+    }
+}
@@ -0,0 +1,243 @@
+//! Non-manifold boundary representation
+//!
+//! [`CellComplex`] is a set of cells, i.e. solids, which may share their boundary faces,
+//! together with dangling faces and edges which bound no cell. For example, the regions of
+//! different materials sharing internal faces can be represented as a cell complex.
+//!
+//! Since the face id does not depend on the orientation, a face of a solid and the inverse face
+//! of another solid are registered as one face. Such faces are created, e.g., by sweeping a
+//! boundary face of a solid, or by [`Shell::sew`].
+
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use serde::{Deserialize, Serialize};
+
+/// The face in the boundary of a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CellFace {
+    /// the index of the face in the cell complex
+    pub index: usize,
+    /// `true` if the cell uses the registered face as is, `false` if uses its inverse.
+    pub orientation: bool,
+}
+
+/// Non-manifold boundary representation consisting of cells sharing faces.
+#[derive(Clone, Debug)]
+pub struct CellComplex<P, C, S> {
+    faces: Vec<Face<P, C, S>>,
+    face_indices: HashMap<FaceID<S>, usize>,
+    cells: Vec<Vec<Vec<CellFace>>>,
+    edges: Vec<Edge<P, C>>,
+}
+
+impl<P, C, S> CellComplex<P, C, S> {
+    /// Creates the empty cell complex.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            faces: Vec::new(),
+            face_indices: HashMap::default(),
+            cells: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Returns all the faces, including the dangling faces.
+    #[inline(always)]
+    pub fn faces(&self) -> &[Face<P, C, S>] { &self.faces }
+    /// Returns the cells. A cell is represented by the boundary shells of faces.
+    #[inline(always)]
+    pub fn cells(&self) -> &[Vec<Vec<CellFace>>] { &self.cells }
+    /// Returns the dangling edges.
+    #[inline(always)]
+    pub fn dangling_edges(&self) -> &[Edge<P, C>] { &self.edges }
+    /// Returns the index of the face.
+    #[inline(always)]
+    pub fn face_index(&self, face_id: FaceID<S>) -> Option<usize> {
+        self.face_indices.get(&face_id).copied()
+    }
+
+    fn register_face(&mut self, face: &Face<P, C, S>) -> CellFace {
+        match self.face_indices.get(&face.id()) {
+            Some(&index) => CellFace {
+                index,
+                orientation: self.faces[index].orientation() == face.orientation(),
+            },
+            None => {
+                let index = self.faces.len();
+                self.faces.push(face.clone());
+                self.face_indices.insert(face.id(), index);
+                CellFace {
+                    index,
+                    orientation: true,
+                }
+            }
+        }
+    }
+
+    /// Adds a solid as a new cell, and returns the index of the cell.
+    ///
+    /// The faces already registered in the cell complex are shared with the new cell.
+    pub fn push_solid(&mut self, solid: &Solid<P, C, S>) -> usize {
+        let cell = solid
+            .boundaries()
+            .iter()
+            .map(|shell| shell.iter().map(|face| self.register_face(face)).collect())
+            .collect();
+        self.cells.push(cell);
+        self.cells.len() - 1
+    }
+
+    /// Adds a face and returns the index of the face.
+    ///
+    /// The face is dangling unless a cell added later has the face in its boundary.
+    #[inline(always)]
+    pub fn push_face(&mut self, face: &Face<P, C, S>) -> usize { self.register_face(face).index }
+
+    /// Adds a dangling edge.
+    #[inline(always)]
+    pub fn push_edge(&mut self, edge: Edge<P, C>) { self.edges.push(edge) }
+
+    /// Returns the face in the orientation used by a cell.
+    #[inline(always)]
+    pub fn cell_face(&self, cell_face: CellFace) -> Face<P, C, S> {
+        match cell_face.orientation {
+            true => self.faces[cell_face.index].clone(),
+            false => self.faces[cell_face.index].inverse(),
+        }
+    }
+
+    /// Returns the cells using the face, and the orientations of the face in the cells.
+    pub fn face_cells(&self, face_index: usize) -> Vec<(usize, bool)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .flat_map(|(i, cell)| {
+                cell.iter()
+                    .flatten()
+                    .filter(move |cell_face| cell_face.index == face_index)
+                    .map(move |cell_face| (i, cell_face.orientation))
+            })
+            .collect()
+    }
+
+    /// Returns the indices of the faces shared by more than one cell.
+    pub fn internal_faces(&self) -> Vec<usize> { self.faces_by_usage(|count| count > 1) }
+
+    /// Returns the indices of the faces in the boundary of no cell.
+    pub fn dangling_faces(&self) -> Vec<usize> { self.faces_by_usage(|count| count == 0) }
+
+    fn faces_by_usage(&self, filter: impl Fn(usize) -> bool) -> Vec<usize> {
+        let mut counts = vec![0; self.faces.len()];
+        self.cells
+            .iter()
+            .flatten()
+            .flatten()
+            .for_each(|cell_face| counts[cell_face.index] += 1);
+        (0..counts.len()).filter(|i| filter(counts[*i])).collect()
+    }
+
+    /// Returns the edges shared by more than two faces, including the dangling faces.
+    pub fn non_manifold_edges(&self) -> Vec<Edge<P, C>> {
+        let mut counts = HashMap::<EdgeID<C>, (usize, Edge<P, C>)>::default();
+        self.faces
+            .iter()
+            .flat_map(Face::absolute_boundaries)
+            .flatten()
+            .for_each(|edge| counts.entry(edge.id()).or_insert((0, edge.clone())).0 += 1);
+        let mut edges: Vec<_> = self
+            .faces
+            .iter()
+            .flat_map(Face::absolute_boundaries)
+            .flatten()
+            .filter_map(|edge| match counts.remove(&edge.id()) {
+                Some((count, edge)) if count > 2 => Some(edge),
+                _ => None,
+            })
+            .collect();
+        edges.iter_mut().for_each(|edge| {
+            if !edge.orientation() {
+                edge.invert();
+            }
+        });
+        edges
+    }
+
+    /// Returns the `index`-th cell as a solid.
+    /// # Failures
+    /// Returns an error if the boundaries of the cell are not closed manifold shells.
+    pub fn cell_solid(&self, index: usize) -> Result<Solid<P, C, S>> {
+        let boundaries = self.cells[index]
+            .iter()
+            .map(|shell| {
+                shell
+                    .iter()
+                    .map(|cell_face| self.cell_face(*cell_face))
+                    .collect()
+            })
+            .collect();
+        Solid::try_new(boundaries)
+    }
+
+    /// Returns all the cells as solids.
+    /// # Failures
+    /// Returns an error if the boundaries of a cell are not closed manifold shells.
+    pub fn to_solids(&self) -> Result<Vec<Solid<P, C, S>>> {
+        (0..self.cells.len()).map(|i| self.cell_solid(i)).collect()
+    }
+
+    /// Returns the union of `cells` as a solid.
+    ///
+    /// The faces shared by two of `cells` in the opposite orientations are removed,
+    /// and the remaining faces are divided into the connected boundary shells.
+    /// # Failures
+    /// Returns an error if the remaining faces do not form closed manifold shells.
+    pub fn merged_solid(&self, cells: impl IntoIterator<Item = usize>) -> Result<Solid<P, C, S>> {
+        let mut cell_faces = Vec::<CellFace>::new();
+        cells
+            .into_iter()
+            .flat_map(|i| self.cells[i].iter().flatten())
+            .for_each(|cell_face| {
+                let opposite = CellFace {
+                    index: cell_face.index,
+                    orientation: !cell_face.orientation,
+                };
+                match cell_faces.iter().position(|x| *x == opposite) {
+                    Some(i) => {
+                        cell_faces.swap_remove(i);
+                    }
+                    None => cell_faces.push(*cell_face),
+                }
+            });
+        let shell: Shell<P, C, S> = cell_faces
+            .into_iter()
+            .map(|cell_face| self.cell_face(cell_face))
+            .collect();
+        Solid::try_new(shell.connected_components())
+    }
+}
+
+impl<P, C, S> Default for CellComplex<P, C, S> {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+impl<P, C, S> From<Solid<P, C, S>> for CellComplex<P, C, S> {
+    #[inline(always)]
+    fn from(solid: Solid<P, C, S>) -> Self {
+        let mut complex = Self::new();
+        complex.push_solid(&solid);
+        complex
+    }
+}
+
+impl<P, C, S> FromIterator<Solid<P, C, S>> for CellComplex<P, C, S> {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = Solid<P, C, S>>>(iter: I) -> Self {
+        let mut complex = Self::new();
+        iter.into_iter().for_each(|solid| {
+            complex.push_solid(&solid);
+        });
+        complex
+    }
+}
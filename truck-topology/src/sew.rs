@@ -0,0 +1,201 @@
+use crate::*;
+use rustc_hash::FxHashMap as HashMap;
+use truck_base::cgmath64::{EuclideanSpace, MetricSpace};
+
+pub(crate) fn find_root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+/// Merges the vertices closer than `tol`.
+/// Returns the map from the ids to the indices of the representatives.
+fn merge_vertices<P>(vertices: &[Vertex<P>], tol: f64) -> HashMap<VertexID<P>, usize>
+where P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64> {
+    let points: Vec<P> = vertices.iter().map(Vertex::point).collect();
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    indices.sort_by(|i, j| points[*i][0].total_cmp(&points[*j][0]));
+    let mut parents: Vec<usize> = (0..points.len()).collect();
+    for (k, i) in indices.iter().enumerate() {
+        for j in &indices[k + 1..] {
+            if points[*j][0] - points[*i][0] > tol {
+                break;
+            }
+            if points[*i].distance(points[*j]) <= tol {
+                let (a, b) = (find_root(&mut parents, *i), find_root(&mut parents, *j));
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+    }
+    (0..vertices.len())
+        .map(|i| (vertices[i].id(), find_root(&mut parents, i)))
+        .collect()
+}
+
+impl<P, C, S> Shell<P, C, S>
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>,
+    S: Clone,
+{
+    /// Sews independently created faces into a shell sharing vertices and edges.
+    ///
+    /// The vertices closer than `tol` are merged into one vertex. Then, the edges whose end vertices
+    /// are merged and whose curves are closer than `tol` at the middle points are merged into one edge.
+    /// The edges collapsed into a point are removed from the boundaries, and the faces whose outer
+    /// boundaries collapse are removed from the shell.
+    ///
+    /// The geometries of the first vertices and edges are adopted as the ones of merged elements.
+    /// The faces are recreated with the same surfaces and orientations.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// use truck_base::cgmath64::*;
+    /// use truck_geotrait::*;
+    /// # use std::ops::Bound;
+    /// # #[derive(Clone, Debug)]
+    /// # struct Line(Point3, Point3);
+    /// # impl ParametricCurve for Line {
+    /// #     type Point = Point3;
+    /// #     type Vector = Vector3;
+    /// #     fn subs(&self, t: f64) -> Point3 { self.0 + (self.1 - self.0) * t }
+    /// #     fn der(&self, _: f64) -> Vector3 { self.1 - self.0 }
+    /// #     fn der2(&self, _: f64) -> Vector3 { Vector3::zero() }
+    /// #     fn parameter_range(&self) -> ParameterRange {
+    /// #         (Bound::Included(0.0), Bound::Included(1.0))
+    /// #     }
+    /// # }
+    /// # impl BoundedCurve for Line {}
+    /// # impl SearchNearestParameter<D1> for Line {
+    /// #     type Point = Point3;
+    /// #     fn search_nearest_parameter<H: Into<SPHint1D>>(&self, p: Point3, _: H, _: usize) -> Option<f64> {
+    /// #         let v = self.1 - self.0;
+    /// #         Some(f64::clamp((p - self.0).dot(v) / v.magnitude2(), 0.0, 1.0))
+    /// #     }
+    /// # }
+    /// // creates a triangle face with independent vertices and edges
+    /// let triangle = |p: [Point3; 3]| {
+    ///     let v = Vertex::news(p);
+    ///     let wire: Wire<_, _> = (0..3)
+    ///         .map(|i| Edge::new(&v[i], &v[(i + 1) % 3], Line(p[i], p[(i + 1) % 3])))
+    ///         .collect();
+    ///     Face::new(vec![wire], ())
+    /// };
+    /// let p = [
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(1.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 1.0),
+    /// ];
+    /// // the vertices are slightly different.
+    /// let q = p.map(|p| p + Vector3::new(1.0e-7, 0.0, 0.0));
+    /// let faces = vec![
+    ///     triangle([p[0], p[2], p[1]]),
+    ///     triangle([q[0], q[1], q[3]]),
+    ///     triangle([p[1], p[2], p[3]]),
+    ///     triangle([q[0], q[3], q[2]]),
+    /// ];
+    ///
+    /// let shell: Shell<_, _, _> = faces.clone().into();
+    /// assert_ne!(shell.shell_condition(), shell::ShellCondition::Closed);
+    ///
+    /// let shell = Shell::sew(faces, 1.0e-6);
+    /// assert_eq!(shell.shell_condition(), shell::ShellCondition::Closed);
+    /// assert_eq!(shell.vertex_iter().count(), 12);
+    /// ```
+    pub fn sew(faces: impl IntoIterator<Item = Face<P, C, S>>, tol: f64) -> Self {
+        nonpositive_tolerance!(tol);
+        let faces: Vec<Face<P, C, S>> = faces.into_iter().collect();
+        let mut vertices = Vec::<Vertex<P>>::new();
+        let mut vertex_ids = HashMap::<VertexID<P>, ()>::default();
+        faces
+            .iter()
+            .flat_map(|face| face.boundaries.iter().flatten())
+            .for_each(|edge| {
+                [edge.absolute_front(), edge.absolute_back()]
+                    .into_iter()
+                    .for_each(|v| {
+                        if vertex_ids.insert(v.id(), ()).is_none() {
+                            vertices.push(v.clone());
+                        }
+                    })
+            });
+        let vmap = merge_vertices(&vertices, tol);
+
+        // the edges merged into the representative edges, keyed by the indices of end vertices
+        let mut merged = HashMap::<(usize, usize), Vec<(C, Edge<P, C>)>>::default();
+        let mut emap = HashMap::<EdgeID<C>, Option<Edge<P, C>>>::default();
+        let mut sew_edge = |edge: &Edge<P, C>| -> Option<Edge<P, C>> {
+            if let Some(new_edge) = emap.get(&edge.id()) {
+                return new_edge.clone();
+            }
+            let front_idx = vmap[&edge.absolute_front().id()];
+            let back_idx = vmap[&edge.absolute_back().id()];
+            if front_idx == back_idx {
+                emap.insert(edge.id(), None);
+                return None;
+            }
+            let (front, back) = (&vertices[front_idx], &vertices[back_idx]);
+            let curve = edge.curve();
+            let (t0, t1) = curve.range_tuple();
+            let middle = curve.subs((t0 + t1) / 2.0);
+            let candidates = merged
+                .entry((front_idx.min(back_idx), front_idx.max(back_idx)))
+                .or_default();
+            let found = candidates.iter().find(|(c, _)| {
+                c.search_nearest_parameter(middle, None, 100)
+                    .map(|t| c.subs(t).distance(middle) <= tol)
+                    .unwrap_or(false)
+            });
+            let new_edge = match found {
+                Some((_, e)) if e.absolute_front() == front => e.clone(),
+                Some((_, e)) => e.inverse(),
+                None => {
+                    let new_edge =
+                        match edge.absolute_front() == front && edge.absolute_back() == back {
+                            true => edge.absolute_clone(),
+                            false => Edge::new_unchecked(front, back, curve.clone()),
+                        };
+                    candidates.push((curve, new_edge.clone()));
+                    new_edge
+                }
+            };
+            emap.insert(edge.id(), Some(new_edge.clone()));
+            Some(new_edge)
+        };
+
+        faces
+            .iter()
+            .filter_map(|face| {
+                let boundaries: Vec<Wire<P, C>> = face
+                    .boundaries
+                    .iter()
+                    .map(|wire| {
+                        wire.iter()
+                            .filter_map(|edge| {
+                                let new_edge = sew_edge(edge)?;
+                                Some(match edge.orientation() {
+                                    true => new_edge,
+                                    false => new_edge.inverse(),
+                                })
+                            })
+                            .collect::<Wire<P, C>>()
+                    })
+                    .enumerate()
+                    .filter(|(i, wire)| *i == 0 || !wire.is_empty())
+                    .map(|(_, wire)| wire)
+                    .collect();
+                if boundaries[0].is_empty() {
+                    return None;
+                }
+                let mut new_face = Face::debug_new(boundaries, face.surface());
+                if !face.orientation() {
+                    new_face.invert();
+                }
+                Some(new_face)
+            })
+            .collect()
+    }
+}
@@ -2,6 +2,7 @@ use crate::*;
 use rayon::prelude::*;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use std::vec::Vec;
+use truck_base::cgmath64::MetricSpace;
 use truck_base::entry_map::FxEntryMap as EntryMap;
 
 type FaceAdjacencyMap<'a, P, C, S> = HashMap<&'a Face<P, C, S>, Vec<&'a Face<P, C, S>>>;
@@ -248,6 +249,8 @@ impl<P, C, S> Shell<P, C, S> {
     /// assert_eq!(face_adjacency[&shell[2]].len(), 1);
     /// assert_eq!(face_adjacency[&shell[3]].len(), 3);
     /// ```
+    // faces are hashed by their ids, not by the mutable surfaces
+    #[allow(clippy::mutable_key_type)]
     pub fn face_adjacency(&self) -> FaceAdjacencyMap<'_, P, C, S> {
         let mut adjacency = EntryMap::new(|x| x, |_| Vec::new());
         let mut edge_face_map = EntryMap::new(|x| x, |_| Vec::new());
@@ -358,6 +361,8 @@ impl<P, C, S> Shell<P, C, S> {
     /// assert!(shell.is_connected());
     /// assert_eq!(shell.connected_components().len(), 2);
     /// ```
+    // faces are hashed by their ids, not by the mutable surfaces
+    #[allow(clippy::mutable_key_type)]
     pub fn connected_components(&self) -> Vec<Shell<P, C, S>> {
         let mut adjacency = self.face_adjacency();
         let components = create_components(&mut adjacency);
@@ -481,6 +486,7 @@ impl<P, C, S> Shell<P, C, S> {
                     .collect::<Option<Vec<_>>>()?;
                 let surface = surface_mapping(&*face.surface.lock())?;
                 let mut new_face = Face::debug_new(wires, surface);
+                new_face.surface.set_origin(&face.surface);
                 if !face.orientation() {
                     new_face.invert();
                 }
@@ -575,6 +581,7 @@ impl<P, C, S> Shell<P, C, S> {
                     .collect();
                 let surface = surface_mapping(&*face.surface.lock());
                 let mut new_face = Face::debug_new(wires, surface);
+                new_face.surface.set_origin(&face.surface);
                 if !face.orientation() {
                     new_face.invert();
                 }
@@ -594,6 +601,20 @@ impl<P, C, S> Shell<P, C, S> {
         self.iter().all(|face| face.is_geometric_consistent())
     }
 
+    /// Returns the consistence of the geometry of end vertices
+    /// and the geometry of edge, including the curves of the edges having their own tolerances.
+    /// cf. [`Face::is_geometric_consistent_with_tolerances`]
+    #[inline(always)]
+    pub fn is_geometric_consistent_with_tolerances(&self) -> bool
+    where
+        P: Tolerance + MetricSpace<Metric = f64> + Copy,
+        C: BoundedCurve<Point = P>,
+        S: IncludeCurve<C> + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+    {
+        self.iter()
+            .all(|face| face.is_geometric_consistent_with_tolerances())
+    }
+
     /// Cuts one edge into two edges at vertex.
     ///
     /// # Returns
@@ -2,6 +2,7 @@ use crate::errors::Error;
 use crate::shell::ShellCondition;
 use crate::*;
 use std::vec::Vec;
+use truck_base::cgmath64::MetricSpace;
 
 impl<P, C, S> Solid<P, C, S> {
     /// create the shell whose boundaries is boundary.
@@ -146,6 +147,21 @@ impl<P, C, S> Solid<P, C, S> {
             .all(|shell| shell.is_geometric_consistent())
     }
 
+    /// Returns the consistence of the geometry of end vertices
+    /// and the geometry of edge, including the curves of the edges having their own tolerances.
+    /// cf. [`Face::is_geometric_consistent_with_tolerances`]
+    #[inline(always)]
+    pub fn is_geometric_consistent_with_tolerances(&self) -> bool
+    where
+        P: Tolerance + MetricSpace<Metric = f64> + Copy,
+        C: BoundedCurve<Point = P>,
+        S: IncludeCurve<C> + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+    {
+        self.boundaries()
+            .iter()
+            .all(|shell| shell.is_geometric_consistent_with_tolerances())
+    }
+
     /// Cuts one edge into two edges at vertex.
     #[inline(always)]
     pub fn cut_edge(
@@ -0,0 +1,148 @@
+use crate::*;
+use std::sync::atomic::AtomicBool;
+
+static NEXT_STABLE_ID: AtomicU64 = AtomicU64::new(1);
+static AUTO_ISSUE: AtomicBool = AtomicBool::new(false);
+
+impl StableID {
+    /// Creates the stable id from the raw value. Returns `None` if `id == 0`.
+    ///
+    /// The ids issued after this call are greater than `id`,
+    /// so that the issued ids do not collide with the ids restored from the serialized data.
+    /// ```
+    /// use truck_topology::StableID;
+    /// let id = StableID::new(100).unwrap();
+    /// assert_eq!(id.get(), 100);
+    /// assert!(StableID::issue() > id);
+    /// assert_eq!(StableID::new(0), None);
+    /// ```
+    #[inline(always)]
+    pub fn new(id: u64) -> Option<StableID> {
+        let id = NonZeroU64::new(id)?;
+        NEXT_STABLE_ID.fetch_max(id.get().saturating_add(1), Ordering::Relaxed);
+        Some(StableID(id))
+    }
+
+    /// Returns the raw value of the id.
+    #[inline(always)]
+    pub fn get(self) -> u64 { self.0.get() }
+
+    /// Issues a new stable id from the counter of the process.
+    #[inline(always)]
+    pub fn issue() -> StableID {
+        let id = NEXT_STABLE_ID.fetch_add(1, Ordering::Relaxed);
+        StableID(NonZeroU64::new(id).unwrap())
+    }
+
+    /// Raises the counter so that the ids issued next are no less than `next`.
+    ///
+    /// The counter is never lowered, so that the ids already issued or restored are not issued again.
+    /// Raising the counter at the beginning of a modeling process to a value greater than the ids
+    /// in use makes the issued ids reproducible, as long as the elements are created in the same order.
+    /// The counter is shared by all threads, so the ids issued in parallel are not reproducible.
+    /// ```
+    /// use truck_topology::StableID;
+    /// let id = StableID::issue();
+    /// StableID::set_next(1);
+    /// assert!(StableID::issue() > id);
+    /// StableID::set_next(id.get() + 100);
+    /// assert_eq!(StableID::issue().get(), id.get() + 100);
+    /// ```
+    /// # Panics
+    /// Panics if `next == 0`.
+    #[inline(always)]
+    pub fn set_next(next: u64) {
+        assert_ne!(next, 0, "the stable id must not be zero.");
+        NEXT_STABLE_ID.fetch_max(next, Ordering::Relaxed);
+    }
+
+    /// Switches whether the stable ids are issued on creating vertices, edges and faces.
+    /// Off by default. The switch is shared by all threads.
+    #[inline(always)]
+    pub fn set_auto_issue(auto_issue: bool) { AUTO_ISSUE.store(auto_issue, Ordering::Relaxed) }
+
+    /// Returns `true` if the stable ids are issued on creating vertices, edges and faces.
+    #[inline(always)]
+    pub fn is_auto_issue() -> bool { AUTO_ISSUE.load(Ordering::Relaxed) }
+
+    /// Returns the raw value of a new id if the auto issuing is on, `0` otherwise.
+    #[inline(always)]
+    pub(crate) fn auto_issued() -> u64 {
+        match Self::is_auto_issue() {
+            true => Self::issue().get(),
+            false => 0,
+        }
+    }
+}
+
+/// The deserialized ids are restored by [`StableID::new`], so that they are not issued again.
+impl<'de> Deserialize<'de> for StableID {
+    #[inline(always)]
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let id = NonZeroU64::deserialize(deserializer)?;
+        Ok(StableID::new(id.get()).unwrap())
+    }
+}
+
+impl std::fmt::Display for StableID {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result { std::fmt::Display::fmt(&self.0, f) }
+}
+
+fn assign_stable_ids<'a, P: 'a, C: 'a, S: 'a>(
+    faces: impl Iterator<Item = &'a Face<P, C, S>>,
+    edges: impl Iterator<Item = Edge<P, C>>,
+    vertices: impl Iterator<Item = Vertex<P>>,
+) {
+    faces.for_each(|face| {
+        face.assign_stable_id();
+    });
+    edges.for_each(|edge| {
+        edge.assign_stable_id();
+    });
+    vertices.for_each(|vertex| {
+        vertex.assign_stable_id();
+    });
+}
+
+impl<P, C> Wire<P, C> {
+    /// Issues the stable ids to the edges and vertices which have no stable ids,
+    /// in the order of [`Wire::edge_iter`] and [`Wire::vertex_iter`].
+    #[inline(always)]
+    pub fn assign_stable_ids(&self) {
+        let faces = std::iter::empty::<&Face<P, C, ()>>();
+        assign_stable_ids(faces, self.edge_iter().cloned(), self.vertex_iter());
+    }
+}
+
+impl<P, C, S> Shell<P, C, S> {
+    /// Issues the stable ids to the faces, edges and vertices which have no stable ids.
+    ///
+    /// The ids are issued to the faces, the edges and the vertices in this order,
+    /// each in the order of [`Shell::face_iter`], [`Shell::edge_iter`] and [`Shell::vertex_iter`],
+    /// so that the same shell is always numbered in the same way from the same counter.
+    #[inline(always)]
+    pub fn assign_stable_ids(&self) {
+        assign_stable_ids(self.face_iter(), self.edge_iter(), self.vertex_iter());
+    }
+}
+
+impl<P, C, S> Solid<P, C, S> {
+    /// Issues the stable ids to the faces, edges and vertices which have no stable ids.
+    /// See [`Shell::assign_stable_ids`] for the order.
+    #[inline(always)]
+    pub fn assign_stable_ids(&self) {
+        assign_stable_ids(self.face_iter(), self.edge_iter(), self.vertex_iter());
+    }
+}
+
+impl<P, C, S> Compound<P, C, S> {
+    /// Issues the stable ids to the faces, edges and vertices which have no stable ids.
+    /// See [`Shell::assign_stable_ids`] for the order.
+    #[inline(always)]
+    pub fn assign_stable_ids(&self) {
+        assign_stable_ids(self.face_iter(), self.edge_iter(), self.vertex_iter());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::*;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use sew::find_root;
+
+/// Merges the faces into one face bounded by the edges not shared by two of the faces.
+/// Returns `None` if the remaining edges do not form a valid boundary.
+fn merge_faces<P, C, S>(faces: &[&Face<P, C, S>]) -> Option<Face<P, C, S>>
+where S: Clone + Invertible {
+    let mut edge_faces = HashMap::<EdgeID<C>, HashSet<usize>>::default();
+    faces.iter().enumerate().for_each(|(i, face)| {
+        face.boundaries.iter().flatten().for_each(|edge| {
+            edge_faces.entry(edge.id()).or_default().insert(i);
+        })
+    });
+    let mut edges: Vec<Edge<P, C>> = faces
+        .iter()
+        .flat_map(|face| face.boundaries())
+        .flatten()
+        .filter(|edge| edge_faces[&edge.id()].len() == 1)
+        .collect();
+    edges.reverse();
+    let mut boundaries = Vec::new();
+    while let Some(edge) = edges.pop() {
+        let mut wire = Wire::from(vec![edge]);
+        while wire.front_vertex() != wire.back_vertex() {
+            let back = wire.back_vertex().unwrap();
+            let idx = edges.iter().rposition(|edge| edge.front() == back)?;
+            wire.push_back(edges.remove(idx));
+        }
+        boundaries.push(wire);
+    }
+    Face::try_new(boundaries, faces[0].oriented_surface()).ok()
+}
+
+impl<P, C, S> Shell<P, C, S>
+where
+    P: Debug,
+    C: SameDomain + Concat<C, Point = P, Output = C> + Invertible + ParameterTransform,
+    S: SameDomain + Clone + Invertible,
+{
+    /// Merges the adjacent faces lying on the same surface, and concatenates the edges lying on
+    /// the same curve by [`Shell::remove_vertex_by_concat_edges`].
+    ///
+    /// Two faces sharing an edge are merged if their oriented surfaces are on the same domain within
+    /// `tol`. The merged face is bounded by the edges which are not shared by the merged faces, and
+    /// has the oriented surface of the first face. The faces whose merged boundaries are not simple
+    /// wires are left as they are. Then, a vertex shared by exactly two edges on the same domain is
+    /// removed by concatenating the edges.
+    ///
+    /// The merged faces and concatenated edges are created as new elements,
+    /// i.e. they have new ids.
+    pub fn unify_same_domain(&mut self, tol: f64) {
+        nonpositive_tolerance!(tol);
+        self.unify_faces(tol);
+        self.unify_edges(tol);
+    }
+
+    fn unify_faces(&mut self, tol: f64) {
+        let surfaces: Vec<S> = self.iter().map(Face::oriented_surface).collect();
+        let mut edge_faces = HashMap::<EdgeID<C>, Vec<usize>>::default();
+        self.iter().enumerate().for_each(|(i, face)| {
+            face.boundaries.iter().flatten().for_each(|edge| {
+                edge_faces.entry(edge.id()).or_default().push(i);
+            })
+        });
+        let mut parents: Vec<usize> = (0..self.len()).collect();
+        edge_faces.values().for_each(|faces| {
+            if let [i, j] = faces[..] {
+                if i != j && surfaces[i].same_domain(&surfaces[j], tol) {
+                    let (a, b) = (find_root(&mut parents, i), find_root(&mut parents, j));
+                    parents[a.max(b)] = a.min(b);
+                }
+            }
+        });
+
+        let mut groups = HashMap::<usize, Vec<&Face<P, C, S>>>::default();
+        (0..self.len()).for_each(|i| {
+            let root = find_root(&mut parents, i);
+            groups.entry(root).or_default().push(&self[i]);
+        });
+        let merged: HashMap<usize, Face<P, C, S>> = groups
+            .into_iter()
+            .filter(|(_, faces)| faces.len() > 1)
+            .filter_map(|(root, faces)| Some((root, merge_faces(&faces)?)))
+            .collect();
+        if merged.is_empty() {
+            return;
+        }
+        let faces = std::mem::take(&mut self.face_list);
+        self.face_list = faces
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, face)| {
+                let root = find_root(&mut parents, i);
+                match merged.get(&root) {
+                    Some(merged) if i == root => Some(merged.clone()),
+                    Some(_) => None,
+                    None => Some(face),
+                }
+            })
+            .collect();
+    }
+
+    fn unify_edges(&mut self, tol: f64) {
+        let mut vertex_edges = HashMap::<VertexID<P>, Vec<Edge<P, C>>>::default();
+        let mut edge_ids = HashSet::<EdgeID<C>>::default();
+        let mut vertices = Vec::new();
+        self.edge_iter().for_each(|edge| {
+            if edge_ids.insert(edge.id()) {
+                [edge.absolute_front(), edge.absolute_back()]
+                    .into_iter()
+                    .for_each(|v| {
+                        let edges = vertex_edges.entry(v.id()).or_insert_with(|| {
+                            vertices.push(v.id());
+                            Vec::new()
+                        });
+                        edges.push(edge.absolute_clone());
+                    })
+            }
+        });
+        vertices.into_iter().for_each(|vertex_id| {
+            let [edge0, edge1] = match &vertex_edges[&vertex_id][..] {
+                [edge0, edge1] => [edge0.clone(), edge1.clone()],
+                _ => return,
+            };
+            if edge0.id() == edge1.id() || !edge0.curve().same_domain(&edge1.curve(), tol) {
+                return;
+            }
+            let Some(new_edge) = self.remove_vertex_by_concat_edges(vertex_id) else {
+                return;
+            };
+            let new_edge = new_edge.absolute_clone();
+            [edge0, edge1].into_iter().for_each(|edge| {
+                let end = match edge.absolute_front().id() == vertex_id {
+                    true => edge.absolute_back(),
+                    false => edge.absolute_front(),
+                };
+                if let Some(edges) = vertex_edges.get_mut(&end.id()) {
+                    edges
+                        .iter_mut()
+                        .filter(|e| e.id() == edge.id())
+                        .for_each(|e| *e = new_edge.clone());
+                }
+            });
+        });
+    }
+}
+
+impl<P, C, S> Solid<P, C, S>
+where
+    P: Debug,
+    C: SameDomain + Concat<C, Point = P, Output = C> + Invertible + ParameterTransform,
+    S: SameDomain + Clone + Invertible,
+{
+    /// Merges the adjacent faces lying on the same surface, and concatenates the edges lying on
+    /// the same curve. See [`Shell::unify_same_domain`] for details.
+    #[inline(always)]
+    pub fn unify_same_domain(&mut self, tol: f64) {
+        self.boundaries
+            .iter_mut()
+            .for_each(|shell| shell.unify_same_domain(tol));
+        #[cfg(debug_assertions)]
+        Solid::new(self.boundaries.clone());
+    }
+}
@@ -0,0 +1,550 @@
+//! Validity check of topology and geometry
+//!
+//! `is_geometric_consistent` answers only whether the whole shape is consistent or not.
+//! The methods `check` of [`Edge`], [`Face`], [`Shell`] and [`Solid`] report every detected
+//! problem as a [`ValidityIssue`] with the ids of the failed elements.
+//!
+//! The geometric checks are done on the points sampled from the curves, so the issues smaller
+//! than the sampling interval may be overlooked.
+
+use crate::{shell::ShellCondition, *};
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use truck_base::bounding_box::{Bounded, BoundingBox};
+use truck_base::cgmath64::{EuclideanSpace, MetricSpace};
+
+/// the number of the divisions of each edge for sampling points
+const CHECK_DIVISION: usize = 8;
+/// the number of the iterations of the alternating projections between two curves
+const PROJECTION_ITERATIONS: usize = 16;
+
+/// A problem of topology or geometry detected by `check`.
+pub enum ValidityIssue<P, C, S> {
+    /// The end vertex of the edge is farther than the tolerance from the end of the curve.
+    VertexNotOnCurve(EdgeID<C>, VertexID<P>),
+    /// The curve of the edge is farther than the tolerance from the surface of the face.
+    EdgeNotOnSurface(FaceID<S>, EdgeID<C>),
+    /// The `usize`-th boundary wire of the face is not closed.
+    OpenWire(FaceID<S>, usize),
+    /// The `usize`-th boundary wire of the face passes through the same vertex twice,
+    /// or its edges intersect each other.
+    SelfIntersectingWire(FaceID<S>, usize),
+    /// Two boundary wires of the face share a vertex or intersect each other.
+    IntersectingWires(FaceID<S>, usize, usize),
+    /// In the parameter space of the surface, the outer boundary of the face is not
+    /// counter-clockwise, or the `usize`-th inner boundary is not clockwise.
+    WrongLoopOrientation(FaceID<S>, usize),
+    /// The edge is shared by more than two faces.
+    NonManifoldEdge(EdgeID<C>),
+    /// The two faces sharing the edge traverse it in the same direction.
+    InconsistentOrientation(EdgeID<C>),
+    /// The faces around the vertex are not connected by edges.
+    NonManifoldVertex(VertexID<P>),
+    /// The `usize`-th boundary shell of the solid is not closed.
+    OpenShell(usize),
+    /// The `usize`-th boundary shell of the solid is not contained in the outer shell.
+    InnerShellOutsideOuter(usize),
+}
+
+/// The parameters of the sampling points in the direction of the edge, including both ends.
+pub(crate) fn sampling_parameters<P, C: BoundedCurve<Point = P>>(
+    curve: &C,
+    orientation: bool,
+) -> Vec<f64> {
+    let (t0, t1) = curve.range_tuple();
+    (0..=CHECK_DIVISION)
+        .map(|i| {
+            let i = match orientation {
+                true => i,
+                false => CHECK_DIVISION - i,
+            };
+            t0 + (t1 - t0) * i as f64 / CHECK_DIVISION as f64
+        })
+        .collect()
+}
+
+/// Moves `x` by the multiple of `period` so that it is the nearest to `prev`.
+fn unwrap_parameter(prev: f64, x: f64, period: Option<f64>) -> f64 {
+    match period {
+        Some(period) => x - period * ((x - prev) / period).round(),
+        None => x,
+    }
+}
+
+fn edge_issues<P, C, S>(edge: &Edge<P, C>, tol: f64, issues: &mut Vec<ValidityIssue<P, C, S>>)
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P>, {
+    let curve = edge.curve();
+    let ends = [
+        (edge.absolute_front(), curve.front()),
+        (edge.absolute_back(), curve.back()),
+    ];
+    ends.into_iter().for_each(|(vertex, point)| {
+        if vertex.point().distance(point) > tol {
+            issues.push(ValidityIssue::VertexNotOnCurve(edge.id(), vertex.id()));
+        }
+    });
+}
+
+/// Returns `true` if the curves come closer than `tol`.
+///
+/// Starting from the sampled points, the points are alternately projected to the other curve
+/// so that they converge to the nearest pair.
+fn curves_intersect<P, C>(curve0: &C, curve1: &C, samples1: &[(f64, P)], tol: f64) -> bool
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>, {
+    let (r0, r1) = (curve0.range_tuple(), curve1.range_tuple());
+    sampling_parameters(curve0, true).into_iter().any(|mut t| {
+        let mut p = curve0.subs(t);
+        let hint = samples1
+            .iter()
+            .min_by(|(_, q0), (_, q1)| p.distance2(*q0).partial_cmp(&p.distance2(*q1)).unwrap())
+            .map(|(s, _)| *s);
+        let Some(mut s) = curve1.search_nearest_parameter(p, hint, SEARCH_PARAMETER_TRIALS) else {
+            return false;
+        };
+        for _ in 0..PROJECTION_ITERATIONS {
+            s = s.clamp(r1.0, r1.1);
+            let q = curve1.subs(s);
+            if p.distance(q) <= tol {
+                return true;
+            }
+            match curve0.search_nearest_parameter(q, Some(t), SEARCH_PARAMETER_TRIALS) {
+                Some(t1) => t = t1.clamp(r0.0, r0.1),
+                None => return false,
+            }
+            p = curve0.subs(t);
+            match curve1.search_nearest_parameter(p, Some(s), SEARCH_PARAMETER_TRIALS) {
+                Some(s1) => s = s1,
+                None => return false,
+            }
+        }
+        false
+    })
+}
+
+fn face_issues<P, C, S>(face: &Face<P, C, S>, tol: f64, issues: &mut Vec<ValidityIssue<P, C, S>>)
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>,
+    S: Clone + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>, {
+    let surface = face.surface();
+    let wires = face.absolute_boundaries();
+    let mut push = |issue: ValidityIssue<P, C, S>| {
+        if !issues.contains(&issue) {
+            issues.push(issue);
+        }
+    };
+
+    // topological conditions of wires
+    let mut all_closed = true;
+    wires.iter().enumerate().for_each(|(i, wire)| {
+        if !wire.is_closed() {
+            all_closed = false;
+            push(ValidityIssue::OpenWire(face.id(), i));
+        } else if !wire.is_simple() {
+            push(ValidityIssue::SelfIntersectingWire(face.id(), i));
+        }
+    });
+    let vertex_sets: Vec<HashSet<VertexID<P>>> = wires
+        .iter()
+        .map(|wire| wire.vertex_iter().map(|v| v.id()).collect())
+        .collect();
+    (0..wires.len()).for_each(|i| {
+        (i + 1..wires.len()).for_each(|j| {
+            if !vertex_sets[i].is_disjoint(&vertex_sets[j]) {
+                push(ValidityIssue::IntersectingWires(face.id(), i, j));
+            }
+        })
+    });
+
+    // sampled points, and their parameters on the surface
+    let (u_period, v_period) = (surface.u_period(), surface.v_period());
+    let mut samples = Vec::<(usize, Edge<P, C>, C, Vec<(f64, P)>)>::new();
+    let mut areas = Vec::<Option<f64>>::new();
+    wires.iter().enumerate().for_each(|(i, wire)| {
+        let mut uvs = Vec::<(f64, f64)>::new();
+        let mut projected = true;
+        wire.iter().for_each(|edge| {
+            let curve = edge.curve();
+            let params = sampling_parameters(&curve, edge.orientation());
+            let points: Vec<(f64, P)> = params.iter().map(|t| (*t, curve.subs(*t))).collect();
+            let mut on_surface = true;
+            points.iter().for_each(|(_, p)| {
+                let uv = uvs
+                    .last()
+                    .and_then(|uv| {
+                        surface.search_nearest_parameter(*p, *uv, SEARCH_PARAMETER_TRIALS)
+                    })
+                    .or_else(|| {
+                        surface.search_nearest_parameter(
+                            *p,
+                            SPHint2D::Global,
+                            SEARCH_PARAMETER_TRIALS,
+                        )
+                    });
+                match uv {
+                    Some((u, v)) => {
+                        if surface.subs(u, v).distance(*p) > tol {
+                            on_surface = false;
+                        }
+                        let uv = match uvs.last() {
+                            Some(prev) => (
+                                unwrap_parameter(prev.0, u, u_period),
+                                unwrap_parameter(prev.1, v, v_period),
+                            ),
+                            None => (u, v),
+                        };
+                        uvs.push(uv);
+                    }
+                    None => {
+                        on_surface = false;
+                        projected = false;
+                    }
+                }
+            });
+            if !on_surface {
+                push(ValidityIssue::EdgeNotOnSurface(face.id(), edge.id()));
+            }
+            samples.push((i, edge.clone(), curve, points));
+        });
+        let winding = |x0: f64, x1: f64, period: Option<f64>| match period {
+            Some(period) => (x1 - x0).abs() > period / 2.0,
+            None => false,
+        };
+        let area = match (uvs.first(), uvs.last()) {
+            (Some(first), Some(last))
+                if projected
+                    && !winding(first.0, last.0, u_period)
+                    && !winding(first.1, last.1, v_period) =>
+            {
+                let closed = uvs.iter().chain(std::iter::once(first));
+                let area = closed
+                    .clone()
+                    .zip(closed.skip(1))
+                    .map(|(p, q)| p.0 * q.1 - p.1 * q.0)
+                    .sum::<f64>();
+                Some(area / 2.0)
+            }
+            _ => None,
+        };
+        areas.push(area);
+    });
+
+    // orientations of loops, decided only if all the loops are closed in the parameter space.
+    let areas: Option<Vec<f64>> = areas.into_iter().collect();
+    if let (true, Some(areas)) = (all_closed, areas) {
+        let outer = (0..areas.len())
+            .max_by(|i, j| areas[*i].abs().partial_cmp(&areas[*j].abs()).unwrap())
+            .unwrap_or(0);
+        areas.iter().enumerate().for_each(|(i, area)| {
+            if (i == outer) != (*area > 0.0) {
+                push(ValidityIssue::WrongLoopOrientation(face.id(), i));
+            }
+        });
+    }
+
+    // geometric intersections of edges without common vertices
+    let chord = |points: &[(f64, P)]| {
+        points
+            .windows(2)
+            .map(|p| p[0].1.distance(p[1].1))
+            .fold(0.0, f64::max)
+    };
+    let chords: Vec<f64> = samples
+        .iter()
+        .map(|(_, _, _, points)| chord(points))
+        .collect();
+    samples
+        .iter()
+        .enumerate()
+        .for_each(|(k, (i, edge0, curve0, points0))| {
+            samples[k + 1..].iter().zip(&chords[k + 1..]).for_each(
+                |((j, edge1, curve1, points1), chord1)| {
+                    let ends0 = [edge0.front().id(), edge0.back().id()];
+                    if edge0.id() == edge1.id()
+                        || ends0.contains(&edge1.front().id())
+                        || ends0.contains(&edge1.back().id())
+                    {
+                        return;
+                    }
+                    let distance = points0
+                        .iter()
+                        .flat_map(|(_, p)| points1.iter().map(move |(_, q)| p.distance(*q)))
+                        .fold(f64::INFINITY, f64::min);
+                    if distance > chords[k] + chord1 + tol
+                        || !curves_intersect(curve0, curve1, points1, tol)
+                    {
+                        return;
+                    }
+                    match i == j {
+                        true => push(ValidityIssue::SelfIntersectingWire(face.id(), *i)),
+                        false => push(ValidityIssue::IntersectingWires(face.id(), *i, *j)),
+                    }
+                },
+            )
+        });
+}
+
+fn shell_issues<P, C, S>(
+    shell: &Shell<P, C, S>,
+    tol: f64,
+    issues: &mut Vec<ValidityIssue<P, C, S>>,
+) where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>,
+    S: Clone + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+{
+    let mut checked = HashSet::<EdgeID<C>>::default();
+    shell.edge_iter().for_each(|edge| {
+        if checked.insert(edge.id()) {
+            edge_issues(&edge, tol, issues);
+        }
+    });
+    shell.iter().for_each(|face| face_issues(face, tol, issues));
+
+    let mut orientations = HashMap::<EdgeID<C>, Vec<bool>>::default();
+    shell.edge_iter().for_each(|edge| {
+        orientations
+            .entry(edge.id())
+            .or_default()
+            .push(edge.orientation())
+    });
+    checked.clear();
+    shell.edge_iter().for_each(|edge| {
+        if !checked.insert(edge.id()) {
+            return;
+        }
+        match orientations[&edge.id()].as_slice() {
+            [_, _, _, ..] => issues.push(ValidityIssue::NonManifoldEdge(edge.id())),
+            [x, y] if x == y => issues.push(ValidityIssue::InconsistentOrientation(edge.id())),
+            _ => {}
+        }
+    });
+    issues.extend(
+        shell
+            .singular_vertices()
+            .into_iter()
+            .map(|v| ValidityIssue::NonManifoldVertex(v.id())),
+    );
+}
+
+impl<P, C> Edge<P, C>
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P>,
+{
+    /// Checks whether the end vertices are on the ends of the curve within `tol`.
+    ///
+    /// Returns the empty vector if the edge is valid.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// use truck_topology::validity::ValidityIssue;
+    /// use truck_base::cgmath64::*;
+    /// use truck_geotrait::*;
+    /// # use std::ops::Bound;
+    /// # #[derive(Clone, Debug)]
+    /// # struct Line(Point3, Point3);
+    /// # impl ParametricCurve for Line {
+    /// #     type Point = Point3;
+    /// #     type Vector = Vector3;
+    /// #     fn subs(&self, t: f64) -> Point3 { self.0 + (self.1 - self.0) * t }
+    /// #     fn der(&self, _: f64) -> Vector3 { self.1 - self.0 }
+    /// #     fn der2(&self, _: f64) -> Vector3 { Vector3::zero() }
+    /// #     fn parameter_range(&self) -> ParameterRange {
+    /// #         (Bound::Included(0.0), Bound::Included(1.0))
+    /// #     }
+    /// # }
+    /// # impl BoundedCurve for Line {}
+    /// let p = [Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0)];
+    /// let v = Vertex::news(p);
+    /// let edge = Edge::new(&v[0], &v[1], Line(p[0], p[1]));
+    /// assert!(edge.check::<()>(1.0e-6).is_empty());
+    ///
+    /// v[1].set_point(Point3::new(1.0, 0.1, 0.0));
+    /// assert_eq!(
+    ///     edge.check::<()>(1.0e-6),
+    ///     vec![ValidityIssue::VertexNotOnCurve(edge.id(), v[1].id())],
+    /// );
+    /// ```
+    pub fn check<S>(&self, tol: f64) -> Vec<ValidityIssue<P, C, S>> {
+        nonpositive_tolerance!(tol);
+        let mut issues = Vec::new();
+        edge_issues(self, tol, &mut issues);
+        issues
+    }
+}
+
+impl<P, C, S> Face<P, C, S>
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>,
+    S: Clone + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+{
+    /// Checks the edges and the boundary wires of the face.
+    ///
+    /// In addition to [`Edge::check`], reports the edges not on the surface, the boundaries which
+    /// are open or intersect each other, and the boundaries with wrong orientations.
+    /// The orientations are checked only if all the boundaries are closed loops in the parameter space.
+    /// The boundary enclosing the largest area is regarded as the outer boundary.
+    ///
+    /// Returns the empty vector if the face is valid.
+    pub fn check(&self, tol: f64) -> Vec<ValidityIssue<P, C, S>> {
+        nonpositive_tolerance!(tol);
+        let mut issues = Vec::new();
+        let mut checked = HashSet::<EdgeID<C>>::default();
+        self.edge_iter().for_each(|edge| {
+            if checked.insert(edge.id()) {
+                edge_issues(&edge, tol, &mut issues);
+            }
+        });
+        face_issues(self, tol, &mut issues);
+        issues
+    }
+}
+
+impl<P, C, S> Shell<P, C, S>
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64>,
+    C: Clone + BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>,
+    S: Clone + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+{
+    /// Checks the faces, and the manifold condition and the orientation of the shell.
+    ///
+    /// In addition to [`Face::check`], reports the edges shared by more than two faces, the edges
+    /// traversed in the same direction by two faces, and the non-manifold vertices.
+    /// The open shell is not reported since a shell may have boundaries.
+    ///
+    /// Returns the empty vector if the shell is valid.
+    pub fn check(&self, tol: f64) -> Vec<ValidityIssue<P, C, S>> {
+        nonpositive_tolerance!(tol);
+        let mut issues = Vec::new();
+        shell_issues(self, tol, &mut issues);
+        issues
+    }
+}
+
+impl<P, C, S> Solid<P, C, S>
+where
+    P: EuclideanSpace<Scalar = f64> + MetricSpace<Metric = f64> + Bounded<Scalar = f64>,
+    C: Clone + BoundedCurve<Point = P> + SearchNearestParameter<D1, Point = P>,
+    S: Clone + ParametricSurface<Point = P> + SearchNearestParameter<D2, Point = P>,
+{
+    /// Checks the boundary shells of the solid.
+    ///
+    /// In addition to [`Shell::check`], reports the open shells and the inner shells outside
+    /// the outer shell. The shell with the largest bounding box is regarded as the outer shell,
+    /// and the others are compared with it by the bounding boxes of the sampled points.
+    ///
+    /// Returns the empty vector if the solid is valid.
+    pub fn check(&self, tol: f64) -> Vec<ValidityIssue<P, C, S>> {
+        nonpositive_tolerance!(tol);
+        let mut issues = Vec::new();
+        self.boundaries().iter().enumerate().for_each(|(i, shell)| {
+            shell_issues(shell, tol, &mut issues);
+            if shell.shell_condition() != ShellCondition::Closed {
+                issues.push(ValidityIssue::OpenShell(i));
+            }
+        });
+        let bdbs: Vec<BoundingBox<P>> = self
+            .boundaries()
+            .iter()
+            .map(|shell| {
+                shell
+                    .edge_iter()
+                    .flat_map(|edge| {
+                        let curve = edge.curve();
+                        sampling_parameters(&curve, true)
+                            .into_iter()
+                            .map(move |t| curve.subs(t))
+                    })
+                    .collect()
+            })
+            .collect();
+        let outer = (0..bdbs.len())
+            .max_by(|i, j| {
+                bdbs[*i]
+                    .diameter()
+                    .partial_cmp(&bdbs[*j].diameter())
+                    .unwrap()
+            })
+            .unwrap_or(0);
+        bdbs.iter().enumerate().for_each(|(i, bdb)| {
+            if i != outer && bdbs[outer] + bdb != bdbs[outer] {
+                issues.push(ValidityIssue::InnerShellOutsideOuter(i));
+            }
+        });
+        issues
+    }
+}
+
+impl<P, C, S> Clone for ValidityIssue<P, C, S> {
+    #[inline(always)]
+    fn clone(&self) -> Self { *self }
+}
+
+impl<P, C, S> Copy for ValidityIssue<P, C, S> {}
+
+impl<P, C, S> PartialEq for ValidityIssue<P, C, S> {
+    fn eq(&self, other: &Self) -> bool {
+        use ValidityIssue::*;
+        match (self, other) {
+            (VertexNotOnCurve(e0, v0), VertexNotOnCurve(e1, v1)) => e0 == e1 && v0 == v1,
+            (EdgeNotOnSurface(f0, e0), EdgeNotOnSurface(f1, e1)) => f0 == f1 && e0 == e1,
+            (OpenWire(f0, i0), OpenWire(f1, i1)) => f0 == f1 && i0 == i1,
+            (SelfIntersectingWire(f0, i0), SelfIntersectingWire(f1, i1)) => f0 == f1 && i0 == i1,
+            (IntersectingWires(f0, i0, j0), IntersectingWires(f1, i1, j1)) => {
+                f0 == f1 && i0 == i1 && j0 == j1
+            }
+            (WrongLoopOrientation(f0, i0), WrongLoopOrientation(f1, i1)) => f0 == f1 && i0 == i1,
+            (NonManifoldEdge(e0), NonManifoldEdge(e1)) => e0 == e1,
+            (InconsistentOrientation(e0), InconsistentOrientation(e1)) => e0 == e1,
+            (NonManifoldVertex(v0), NonManifoldVertex(v1)) => v0 == v1,
+            (OpenShell(i0), OpenShell(i1)) => i0 == i1,
+            (InnerShellOutsideOuter(i0), InnerShellOutsideOuter(i1)) => i0 == i1,
+            _ => false,
+        }
+    }
+}
+
+impl<P, C, S> Eq for ValidityIssue<P, C, S> {}
+
+impl<P, C, S> Debug for ValidityIssue<P, C, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use ValidityIssue::*;
+        match self {
+            VertexNotOnCurve(e, v) => f.debug_tuple("VertexNotOnCurve").field(e).field(v).finish(),
+            EdgeNotOnSurface(face, e) => f
+                .debug_tuple("EdgeNotOnSurface")
+                .field(face)
+                .field(e)
+                .finish(),
+            OpenWire(face, i) => f.debug_tuple("OpenWire").field(face).field(i).finish(),
+            SelfIntersectingWire(face, i) => f
+                .debug_tuple("SelfIntersectingWire")
+                .field(face)
+                .field(i)
+                .finish(),
+            IntersectingWires(face, i, j) => f
+                .debug_tuple("IntersectingWires")
+                .field(face)
+                .field(i)
+                .field(j)
+                .finish(),
+            WrongLoopOrientation(face, i) => f
+                .debug_tuple("WrongLoopOrientation")
+                .field(face)
+                .field(i)
+                .finish(),
+            NonManifoldEdge(e) => f.debug_tuple("NonManifoldEdge").field(e).finish(),
+            InconsistentOrientation(e) => {
+                f.debug_tuple("InconsistentOrientation").field(e).finish()
+            }
+            NonManifoldVertex(v) => f.debug_tuple("NonManifoldVertex").field(v).finish(),
+            OpenShell(i) => f.debug_tuple("OpenShell").field(i).finish(),
+            InnerShellOutsideOuter(i) => f.debug_tuple("InnerShellOutsideOuter").field(i).finish(),
+        }
+    }
+}
@@ -14,7 +14,7 @@ impl<P> Vertex<P> {
     #[inline(always)]
     pub fn new(point: P) -> Vertex<P> {
         Vertex {
-            point: Arc::new(Mutex::new(point)),
+            point: Geometry::new(point, None),
         }
     }
 
@@ -70,7 +70,10 @@ impl<P> Vertex<P> {
         &self,
         mut point_mapping: impl FnMut(&P) -> Option<Q>,
     ) -> Option<Vertex<Q>> {
-        Some(Vertex::new(point_mapping(&*self.point.lock())?))
+        let vertex = Vertex::new(point_mapping(&*self.point.lock())?);
+        vertex.point.set_tolerance(self.tolerance());
+        vertex.point.set_origin(&self.point);
+        Some(vertex)
     }
 
     /// Returns vertex whose point is converted by `point_mapping`.
@@ -87,12 +90,52 @@ impl<P> Vertex<P> {
     #[doc(hidden)]
     #[inline(always)]
     pub fn mapped<Q>(&self, mut point_mapping: impl FnMut(&P) -> Q) -> Vertex<Q> {
-        Vertex::new(point_mapping(&*self.point.lock()))
+        let vertex = Vertex::new(point_mapping(&*self.point.lock()));
+        vertex.point.set_tolerance(self.tolerance());
+        vertex.point.set_origin(&self.point);
+        vertex
     }
 
+    /// Returns the tolerance of the vertex, `None` if the global `TOLERANCE` is used.
+    /// # Examples
+    /// ```
+    /// use truck_topology::*;
+    /// let v0 = Vertex::new(());
+    /// assert_eq!(v0.tolerance(), None);
+    /// let v1 = v0.clone();
+    /// v1.set_tolerance(Some(1.0e-4));
+    /// assert_eq!(v0.tolerance(), Some(1.0e-4));
+    /// ```
+    #[inline(always)]
+    pub fn tolerance(&self) -> Option<f64> { self.point.tolerance() }
+
+    /// Sets the tolerance of the vertex, which is shared by the cloned vertices.
+    /// `None` resets the tolerance to the global `TOLERANCE`.
+    /// # Panics
+    /// Panics if the tolerance is less than `TOLERANCE`.
+    #[inline(always)]
+    pub fn set_tolerance(&self, tolerance: Option<f64>) { self.point.set_tolerance(tolerance) }
+
+    /// Returns the tolerance of the vertex, or the global `TOLERANCE` if it is not set.
+    #[inline(always)]
+    pub fn tolerance_or_default(&self) -> f64 { self.tolerance().unwrap_or(TOLERANCE) }
+
+    /// Returns the stable id of the vertex, `None` if it has not been assigned.
+    /// See [`StableID`] for details.
+    #[inline(always)]
+    pub fn stable_id(&self) -> Option<StableID> { self.point.stable_id() }
+
+    /// Sets the stable id of the vertex, which is shared by the cloned vertices.
+    #[inline(always)]
+    pub fn set_stable_id(&self, stable_id: Option<StableID>) { self.point.set_stable_id(stable_id) }
+
+    /// Returns the stable id of the vertex. If it has not been assigned, issues a new one.
+    #[inline(always)]
+    pub fn assign_stable_id(&self) -> StableID { self.point.assign_stable_id() }
+
     /// Returns the id of the vertex.
     #[inline(always)]
-    pub fn id(&self) -> VertexID<P> { ID::new(Arc::as_ptr(&self.point)) }
+    pub fn id(&self) -> VertexID<P> { ID::new(&self.point.entity) }
 
     /// Returns how many same vertices.
     ///
@@ -177,7 +220,7 @@ impl<'a, P: Debug> Debug for DebugDisplay<'a, Vertex<P>, VertexDisplayFormat> {
         match self.format {
             VertexDisplayFormat::Full => f
                 .debug_struct("Vertex")
-                .field("id", &Arc::as_ptr(&self.entity.point))
+                .field("id", &self.entity.id())
                 .field("entity", &MutexFmt(&self.entity.point))
                 .finish(),
             VertexDisplayFormat::IDTuple => {
@@ -560,7 +560,9 @@ where
         let vb = edge.absolute_back();
         let vertex1 = vertex_map.entry_or_insert(vb).clone()?;
         let curve = curve_mapping(&*edge.curve.lock())?;
-        Some(Edge::debug_new(&vertex0, &vertex1, curve))
+        let new_edge = Edge::debug_new(&vertex0, &vertex1, curve);
+        new_edge.curve.set_origin(&edge.curve);
+        Some(new_edge)
     }
 }
 
@@ -578,7 +580,9 @@ where
         let vb = edge.absolute_back();
         let vertex1 = vertex_map.entry_or_insert(vb).clone();
         let curve = curve_mapping(&*edge.curve.lock());
-        Edge::debug_new(&vertex0, &vertex1, curve)
+        let new_edge = Edge::debug_new(&vertex0, &vertex1, curve);
+        new_edge.curve.set_origin(&edge.curve);
+        new_edge
     }
 }
 
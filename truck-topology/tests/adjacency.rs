@@ -0,0 +1,122 @@
+use std::f64::consts::PI;
+use truck_geometry::prelude::*;
+use truck_topology::{adjacency::Convexity, *};
+
+type Face = truck_topology::Face<Point3, Line<Point3>, Plane>;
+
+/// the prism of height 1 over the counter-clockwise polygon, and the vertices of the bottom face
+fn prism(polygon: &[(f64, f64)]) -> (Solid<Point3, Line<Point3>, Plane>, Vec<Vertex<Point3>>) {
+    let n = polygon.len();
+    let bottom: Vec<Point3> = polygon
+        .iter()
+        .map(|(x, y)| Point3::new(*x, *y, 0.0))
+        .collect();
+    let top: Vec<Point3> = polygon
+        .iter()
+        .map(|(x, y)| Point3::new(*x, *y, 1.0))
+        .collect();
+    let (b, t) = (Vertex::news(&bottom), Vertex::news(&top));
+    let line =
+        |v0: &Vertex<Point3>, v1: &Vertex<Point3>| Edge::new(v0, v1, Line(v0.point(), v1.point()));
+    let bottom_edges: Vec<_> = (0..n).map(|i| line(&b[i], &b[(i + 1) % n])).collect();
+    let top_edges: Vec<_> = (0..n).map(|i| line(&t[i], &t[(i + 1) % n])).collect();
+    let vertical_edges: Vec<_> = (0..n).map(|i| line(&b[i], &t[i])).collect();
+
+    let wire: Wire<_, _> = bottom_edges.iter().rev().map(Edge::inverse).collect();
+    let (x, y) = (Vector3::unit_x(), Vector3::unit_y());
+    let mut faces = vec![Face::new(
+        vec![wire],
+        Plane::new(bottom[0], bottom[0] + y, bottom[0] + x),
+    )];
+    faces.extend((0..n).map(|i| {
+        let wire: Wire<_, _> = vec![
+            bottom_edges[i].clone(),
+            vertical_edges[(i + 1) % n].clone(),
+            top_edges[i].inverse(),
+            vertical_edges[i].inverse(),
+        ]
+        .into();
+        let plane = Plane::new(bottom[i], bottom[(i + 1) % n], top[i]);
+        Face::new(vec![wire], plane)
+    }));
+    let wire: Wire<_, _> = top_edges.into();
+    faces.push(Face::new(
+        vec![wire],
+        Plane::new(top[0], top[0] + x, top[0] + y),
+    ));
+    (Solid::new(vec![faces.into()]), b)
+}
+
+#[test]
+fn l_shape() {
+    let polygon = [
+        (0.0, 0.0),
+        (2.0, 0.0),
+        (2.0, 1.0),
+        (1.0, 1.0),
+        (1.0, 2.0),
+        (0.0, 2.0),
+    ];
+    let (l_shape, v) = prism(&polygon);
+    let bottom = &l_shape.boundaries()[0][0];
+
+    let mut graph = l_shape.face_adjacency_graph();
+    assert_eq!((graph.faces().len(), graph.edges().len()), (8, 18));
+    assert!(graph.edges().iter().all(|edge| edge.convexity.is_none()));
+    assert_eq!(graph.faces_around_vertex(v[3].id()).len(), 3);
+    assert_eq!(graph.adjacent_faces(bottom.id()).len(), 6);
+    let loops = graph.edge_loops(bottom.id());
+    assert_eq!(loops.len(), 1);
+    assert_eq!(loops[0].len(), 6);
+
+    graph.compute_dihedral_angles(TOLERANCE);
+    let concave: Vec<_> = graph
+        .edges()
+        .iter()
+        .filter(|edge| edge.convexity == Some(Convexity::Concave))
+        .collect();
+    assert_eq!(concave.len(), 1);
+    let ends = [concave[0].edge.front(), concave[0].edge.back()];
+    assert!(ends.iter().any(|vertex| vertex.id() == v[3].id()));
+    assert_near!(concave[0].dihedral_angle.unwrap(), 1.5 * PI);
+    graph.edges().iter().for_each(|edge| {
+        if edge.convexity == Some(Convexity::Convex) {
+            assert_near!(edge.dihedral_angle.unwrap(), 0.5 * PI);
+        }
+    });
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("graph {\n") && dot.ends_with("}\n"));
+    assert_eq!(dot.matches("[label=").count(), 8 + 18);
+    assert_eq!(dot.matches(" -- ").count(), 18);
+    assert_eq!(dot.matches("[label=\"90.0\"];").count(), 17);
+    assert_eq!(dot.matches("[label=\"270.0\", style=dashed];").count(), 1);
+    assert!(!dot.contains("style=dotted"));
+    // the bottom face is adjacent to the all side faces
+    (1..=6).for_each(|i| assert!(dot.contains(&format!("    f0 -- f{i} [label=\"90.0\"];"))));
+
+    l_shape.assign_stable_ids();
+    let dot = l_shape.face_adjacency_graph().to_dot();
+    let id = bottom.stable_id().unwrap();
+    assert!(dot.contains(&format!("    f0 [label=\"#{id}\"];")));
+    assert!(dot.contains("    f0 -- f1;"));
+}
+
+#[test]
+fn smooth_edge() {
+    // the side faces on the edges (0, 0)-(1, 0) and (1, 0)-(2, 0) are on the same plane.
+    let polygon = [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)];
+    let (prism, v) = prism(&polygon);
+    let mut graph = prism.face_adjacency_graph();
+    graph.compute_dihedral_angles(TOLERANCE);
+    graph.edges().iter().for_each(|edge| {
+        let ends = [edge.edge.front(), edge.edge.back()];
+        let convexity = match ends.iter().any(|vertex| vertex.id() == v[1].id()) {
+            true if edge.edge.front().point().z != edge.edge.back().point().z => Convexity::Smooth,
+            _ => Convexity::Convex,
+        };
+        assert_eq!(edge.convexity, Some(convexity));
+    });
+    let dot = graph.to_dot();
+    assert_eq!(dot.matches("[label=\"180.0\", style=dotted];").count(), 1);
+}
@@ -0,0 +1,142 @@
+use truck_topology::{attributes::*, errors::Error, *};
+
+mod common;
+use common::tetrahedron;
+
+fn attributes_by_geometry(
+    solid: &Solid<usize, usize, usize>,
+    attributes: &AttributeStore<usize, usize, usize, String>,
+) -> [Vec<(usize, String)>; 3] {
+    let mut vertices: Vec<_> = solid
+        .vertex_iter()
+        .filter_map(|v| Some((v.point(), attributes.vertex(&v)?.clone())))
+        .collect();
+    let mut edges: Vec<_> = solid
+        .edge_iter()
+        .filter_map(|e| Some((e.curve(), attributes.edge(&e)?.clone())))
+        .collect();
+    let mut faces: Vec<_> = solid
+        .face_iter()
+        .filter_map(|f| Some((f.surface(), attributes.face(f)?.clone())))
+        .collect();
+    [&mut vertices, &mut edges, &mut faces]
+        .into_iter()
+        .for_each(|vec| {
+            vec.sort();
+            vec.dedup();
+        });
+    [vertices, edges, faces]
+}
+
+#[test]
+fn compress_extract_attributes() {
+    let solid = tetrahedron();
+    let mut attributes = AttributeStore::new();
+    solid.vertex_iter().for_each(|v| {
+        attributes.set_vertex(&v, format!("vertex{}", v.point()));
+    });
+    solid
+        .edge_iter()
+        .filter(|e| e.curve() % 2 == 0)
+        .for_each(|e| {
+            attributes.set_edge(&e, format!("edge{}", e.curve()));
+        });
+    let face = &solid.boundaries()[0][2];
+    attributes.set_face(face, "face".to_string());
+    // the attributes of the elements out of the solid are ignored.
+    let other = Vertex::new(10);
+    attributes.set_vertex(&other, "other".to_string());
+
+    let (csolid, cattrs) = solid.compress_with_attributes(&attributes);
+    assert_eq!(cattrs[0].vertices.len(), 4);
+    assert_eq!(cattrs[0].edges.len(), 3);
+    assert_eq!(cattrs[0].faces, vec![(2, "face".to_string())]);
+
+    let (solid1, attributes1) = Solid::extract_with_attributes(csolid, cattrs).unwrap();
+    assert_eq!(
+        attributes_by_geometry(&solid, &attributes),
+        attributes_by_geometry(&solid1, &attributes1),
+    );
+
+    // invalid index
+    let (csolid, mut cattrs) = solid.compress_with_attributes(&attributes);
+    cattrs[0].faces.push((4, "none".to_string()));
+    assert_eq!(
+        Solid::extract_with_attributes(csolid, cattrs).unwrap_err(),
+        Error::AttributeIndexOutOfRange,
+    );
+}
+
+#[test]
+fn inherit_and_retain_attributes() {
+    let solid = tetrahedron();
+    let mut attributes = AttributeStore::new();
+    solid.edge_iter().for_each(|e| {
+        attributes.set_edge(&e, e.curve());
+    });
+    solid.face_iter().for_each(|f| {
+        attributes.set_face(f, f.surface());
+    });
+
+    let copy = solid.mapped(|p| *p, |c| *c, |s| *s);
+    let mapped = attributes.mapped(solid.face_iter(), copy.face_iter());
+    copy.edge_iter()
+        .for_each(|e| assert_eq!(mapped.edge(&e), Some(&e.curve())));
+    copy.face_iter()
+        .for_each(|f| assert_eq!(mapped.face(f), Some(&f.surface())));
+
+    attributes.inherit(solid.face_iter(), copy.face_iter());
+    attributes.retain_elements(copy.face_iter());
+    assert_eq!(attributes.edge_iter().count(), 6);
+    assert_eq!(attributes.face_iter().count(), 4);
+    assert!(solid.face_iter().all(|f| attributes.face(f).is_none()));
+}
+
+#[test]
+fn attributes_through_mapped() {
+    let solid = tetrahedron();
+    let mut attributes = AttributeStore::new();
+    solid.vertex_iter().for_each(|v| {
+        attributes.set_vertex(&v, v.point());
+    });
+    solid.face_iter().for_each(|f| {
+        attributes.set_face(f, f.surface());
+    });
+
+    // the copies of copies refer to the attributes of the original elements
+    let copy = solid.mapped(|p| *p, |c| *c, |s| *s);
+    let copy = copy.mapped(|p| *p, |c| *c, |s| *s);
+    copy.vertex_iter()
+        .for_each(|v| assert_eq!(attributes.vertex(&v), Some(&v.point())));
+    copy.face_iter()
+        .for_each(|f| assert_eq!(attributes.face(f), Some(&f.surface())));
+    let face = copy.face_iter().next().unwrap();
+    let edge = face.edge_iter().next().unwrap();
+    let copied = edge.mapped(|p| *p, |c| *c);
+    assert_eq!(
+        attributes.vertex(copied.front()),
+        attributes.vertex(edge.front())
+    );
+
+    // the own attribute precedes the one of the origin
+    attributes.set_face(face, 100);
+    assert_eq!(attributes.face(face), Some(&100));
+    assert_eq!(attributes.face(&solid.boundaries()[0][0]), Some(&0));
+
+    // the attributes of the origins are kept while they are referred to
+    attributes.retain_elements(copy.face_iter());
+    assert_eq!(attributes.vertex_iter().count(), 4);
+    assert_eq!(attributes.face_iter().count(), 4);
+    let (_, cattrs) = copy.compress_with_attributes(&attributes);
+    assert_eq!(cattrs[0].vertices.len(), 4);
+    assert_eq!(cattrs[0].faces, vec![(0, 100), (1, 1), (2, 2), (3, 3)]);
+
+    // the stable ids of dropped elements are not reused
+    let other = Vertex::new(10);
+    attributes.set_vertex(&other, 10);
+    let stable_id = other.stable_id();
+    drop(other);
+    let vertex = Vertex::new(11);
+    assert_eq!(attributes.vertex(&vertex), None);
+    assert_ne!(vertex.stable_id(), stable_id);
+}
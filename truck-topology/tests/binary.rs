@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use truck_topology::binary::*;
+use truck_topology::{compress::CompressedSolid, *};
+
+mod common;
+use common::tetrahedron;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+enum Geometry {
+    Empty,
+    Point([f64; 3]),
+    Segment([f64; 3], [f64; 3]),
+    Circle { radius: f32, name: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Sample {
+    geometries: Vec<Geometry>,
+    offset: i64,
+    label: Option<char>,
+    names: HashMap<String, (u8, bool)>,
+    data: Vec<Option<u64>>,
+    floats: Vec<f64>,
+}
+
+#[test]
+fn round_trip() {
+    let solid = tetrahedron();
+    let csolid = solid.compress();
+    for compression in [Compression::None, Compression::Lz] {
+        let bytes = to_vec(&csolid, compression).unwrap();
+        let restored: CompressedSolid<usize, usize, usize> = from_slice(&bytes).unwrap();
+        assert_eq!(restored, csolid);
+        let restored: Solid<usize, usize, usize> = from_slice(&bytes).unwrap();
+        assert_eq!(restored.compress(), csolid);
+    }
+
+    let sample = Sample {
+        geometries: vec![
+            Geometry::Empty,
+            Geometry::Point([1.0, -2.5, f64::MAX]),
+            Geometry::Segment([0.0; 3], [1.0e-300, 2.0, 3.0]),
+            Geometry::Circle {
+                radius: 0.5,
+                name: "circle".into(),
+            },
+            Geometry::Circle {
+                radius: -1.5,
+                name: "circle".into(),
+            },
+        ],
+        offset: i64::MIN,
+        label: Some('あ'),
+        names: [("a".into(), (255, true)), ("b".into(), (0, false))].into(),
+        data: vec![None, Some(u64::MAX), Some(0)],
+        floats: vec![
+            -0.0,
+            0.5,
+            -7.0,
+            1.0e15,
+            2.0f64.powi(60),
+            0.1,
+            f64::MIN_POSITIVE,
+        ],
+    };
+    let bytes = to_vec(&sample, Compression::Lz).unwrap();
+    let restored = from_slice::<Sample>(&bytes).unwrap();
+    assert_eq!(restored, sample);
+    assert!(restored.floats[0].is_sign_negative());
+}
+
+#[test]
+fn stream() {
+    let solid = tetrahedron();
+    let mut writer = Writer::new(Vec::new(), Compression::Lz);
+    (0..10).for_each(|_| writer.write(&solid).unwrap());
+    writer.write(&solid.boundaries()[0]).unwrap();
+    let bytes = writer.into_inner();
+
+    let mut reader = Reader::new(bytes.as_slice());
+    assert!(reader.skip().unwrap());
+    let solids: Vec<Solid<usize, usize, usize>> = reader
+        .iter()
+        .take(9)
+        .collect::<binary::Result<_>>()
+        .unwrap();
+    assert_eq!(solids.len(), 9);
+    let shell: Shell<usize, usize, usize> = reader.read().unwrap().unwrap();
+    assert_eq!(shell.len(), 4);
+    assert!(reader
+        .read::<Solid<usize, usize, usize>>()
+        .unwrap()
+        .is_none());
+    assert!(!reader.skip().unwrap());
+}
+
+#[test]
+fn rejected_data() {
+    let bytes = to_vec(&tetrahedron(), Compression::None).unwrap();
+    type S = Solid<usize, usize, usize>;
+
+    let mut invalid = bytes.clone();
+    invalid[0] = b'{';
+    assert!(matches!(
+        from_slice::<S>(&invalid),
+        Err(Error::InvalidMagic)
+    ));
+
+    let mut newer = bytes.clone();
+    newer[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    assert!(matches!(
+        from_slice::<S>(&newer),
+        Err(Error::UnsupportedVersion { found }) if found == FORMAT_VERSION + 1,
+    ));
+
+    let truncated = &bytes[..bytes.len() - 1];
+    assert!(matches!(
+        from_slice::<S>(truncated),
+        Err(Error::Corrupted(_))
+    ));
+    assert!(matches!(
+        from_slice::<S>(&bytes[..10]),
+        Err(Error::Corrupted(_))
+    ));
+
+    let shell = to_vec(&tetrahedron().boundaries()[0], Compression::None).unwrap();
+    assert!(from_slice::<S>(&shell).is_err());
+}
+
+#[derive(Serialize)]
+struct OldPart {
+    name: String,
+    obsolete: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewPart {
+    name: String,
+    #[serde(default)]
+    revision: u32,
+}
+
+#[test]
+fn schema_evolution() {
+    let old = OldPart {
+        name: "part".into(),
+        obsolete: 3,
+    };
+    let bytes = to_vec(&old, Compression::None).unwrap();
+    let new: NewPart = from_slice(&bytes).unwrap();
+    assert_eq!(new.name, "part");
+    assert_eq!(new.revision, 0);
+}
+
+#[test]
+fn smaller_than_json() {
+    use truck_geometry::prelude::*;
+    type Solid = truck_topology::Solid<Point3, BSplineCurve<Point3>, BSplineSurface<Point3>>;
+    let p = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+    ];
+    let v = Vertex::news(p);
+    // the wavy curve with many control points
+    let curve = |i: usize, j: usize| {
+        let control_points = (0..=20)
+            .map(|k| {
+                let t = k as f64 / 20.0;
+                p[i] + (p[j] - p[i]) * t + Vector3::new(0.0, 0.0, 0.1 * f64::sin(k as f64))
+            })
+            .collect();
+        BSplineCurve::new(KnotVec::uniform_knot(3, 18), control_points)
+    };
+    let edge = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]
+        .map(|(i, j)| Edge::new(&v[i], &v[j], curve(i, j)));
+    let surface = |i: usize, j: usize, k: usize| {
+        let control_points = vec![vec![p[i], p[j]], vec![p[k], p[k]]];
+        let knot_vecs = (KnotVec::bezier_knot(1), KnotVec::bezier_knot(1));
+        BSplineSurface::new(knot_vecs, control_points)
+    };
+    let faces: Vec<_> = [
+        (vec![&edge[0], &edge[3], &edge[1].inverse()], (0, 1, 2)),
+        (vec![&edge[1], &edge[5], &edge[2].inverse()], (0, 2, 3)),
+        (
+            vec![&edge[2], &edge[4].inverse(), &edge[0].inverse()],
+            (0, 3, 1),
+        ),
+        (vec![&edge[3], &edge[5], &edge[4].inverse()], (1, 2, 3)),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(n, (wire, (i, j, k)))| {
+        let face = Face::new(vec![Wire::from_iter(wire)], surface(i, j, k));
+        match n {
+            3 => face.inverse(),
+            _ => face,
+        }
+    })
+    .collect();
+    let solid = Solid::new(vec![faces.into()]);
+
+    let json = serde_json::to_vec(&solid).unwrap();
+    let bytes = to_vec(&solid, Compression::None).unwrap();
+    let compressed = to_vec(&solid, Compression::Lz).unwrap();
+    assert!(bytes.len() < json.len());
+    assert!(compressed.len() * 3 < json.len());
+
+    let restored: Solid = from_slice(&compressed).unwrap();
+    assert_eq!(serde_json::to_vec(&restored).unwrap(), json);
+}
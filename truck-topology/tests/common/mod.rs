@@ -0,0 +1,79 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use truck_geometry::prelude::*;
+use truck_topology::*;
+
+/// the tetrahedron whose vertices, edges and faces have the geometries `0, 1, 2, ...`
+pub fn tetrahedron() -> Solid<usize, usize, usize> { offset_tetrahedron(0) }
+
+/// the tetrahedron whose vertices, edges and faces have the geometries `offset, offset + 1, ...`
+pub fn offset_tetrahedron(offset: usize) -> Solid<usize, usize, usize> {
+    let v = Vertex::news([0, 1, 2, 3].map(|i| i + offset));
+    let edge = [
+        Edge::new(&v[0], &v[1], offset),
+        Edge::new(&v[0], &v[2], offset + 1),
+        Edge::new(&v[0], &v[3], offset + 2),
+        Edge::new(&v[1], &v[2], offset + 3),
+        Edge::new(&v[1], &v[3], offset + 4),
+        Edge::new(&v[2], &v[3], offset + 5),
+    ];
+    let wire = vec![
+        Wire::from_iter(vec![&edge[0], &edge[3], &edge[1].inverse()]),
+        Wire::from_iter(vec![&edge[1], &edge[5], &edge[2].inverse()]),
+        Wire::from_iter(vec![&edge[2], &edge[4].inverse(), &edge[0].inverse()]),
+        Wire::from_iter(vec![&edge[3], &edge[5], &edge[4].inverse()]),
+    ];
+    let mut faces: Vec<Face<_, _, _>> = wire
+        .into_iter()
+        .enumerate()
+        .map(|(i, w)| Face::new(vec![w], i + offset))
+        .collect();
+    faces[3].invert();
+    Solid::new(vec![faces.into()])
+}
+
+/// the cube `[origin, origin + size]^3` sharing the vertices and edges
+pub fn cube(origin: Point3, size: f64) -> Shell<Point3, Line<Point3>, Plane> {
+    let p = [
+        (0.0, 0.0, 0.0),
+        (1.0, 0.0, 0.0),
+        (1.0, 1.0, 0.0),
+        (0.0, 1.0, 0.0),
+        (0.0, 0.0, 1.0),
+        (1.0, 0.0, 1.0),
+        (1.0, 1.0, 1.0),
+        (0.0, 1.0, 1.0),
+    ]
+    .map(|(x, y, z)| origin + size * Vector3::new(x, y, z));
+    let v = Vertex::news(p);
+    let indices = [
+        [0, 3, 2, 1],
+        [0, 1, 5, 4],
+        [1, 2, 6, 5],
+        [2, 3, 7, 6],
+        [3, 0, 4, 7],
+        [4, 5, 6, 7],
+    ];
+    let mut edges = HashMap::<(usize, usize), Edge<_, _>>::new();
+    indices
+        .iter()
+        .map(|idx| {
+            let wire: Wire<_, _> = (0..4)
+                .map(|k| {
+                    let (i, j) = (idx[k], idx[(k + 1) % 4]);
+                    match edges.get(&(j, i)) {
+                        Some(edge) => edge.inverse(),
+                        None => {
+                            let edge = Edge::new(&v[i], &v[j], Line(p[i], p[j]));
+                            edges.insert((i, j), edge.clone());
+                            edge
+                        }
+                    }
+                })
+                .collect();
+            let plane = Plane::new(p[idx[0]], p[idx[1]], p[idx[3]]);
+            Face::new(vec![wire], plane)
+        })
+        .collect()
+}
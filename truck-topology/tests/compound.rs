@@ -0,0 +1,69 @@
+use truck_topology::*;
+
+mod common;
+use common::offset_tetrahedron;
+
+fn sample_compound() -> Compound<usize, usize, usize> {
+    let mut compound: Compound<_, _, _> = [offset_tetrahedron(0), offset_tetrahedron(10)]
+        .into_iter()
+        .collect();
+    let mut shell = offset_tetrahedron(20).into_boundaries().pop().unwrap();
+    shell.pop();
+    compound.push_shell(shell);
+    let v = Vertex::news([30, 31, 32]);
+    let wire: Wire<_, _> = vec![
+        Edge::new(&v[0], &v[1], 30),
+        Edge::new(&v[2], &v[1], 31).inverse(),
+    ]
+    .into();
+    compound.push_wire(wire);
+    compound.push_vertex(Vertex::new(40));
+    compound
+}
+
+#[test]
+fn compound_elements() {
+    let compound = sample_compound();
+    assert!(!compound.is_empty());
+    assert!(Compound::<(), (), ()>::new().is_empty());
+    assert_eq!(compound.solids().len(), 2);
+    assert_eq!(compound.shells().len(), 1);
+    assert_eq!(compound.face_iter().count(), 11);
+    assert_eq!(compound.edge_iter().count(), 35);
+    let mut points: Vec<usize> = compound.vertex_iter().map(|v| v.point()).collect();
+    points.sort();
+    points.dedup();
+    assert_eq!(
+        points,
+        vec![0, 1, 2, 3, 10, 11, 12, 13, 20, 21, 22, 23, 30, 31, 32, 40]
+    );
+}
+
+#[test]
+fn compress_extract_compound() {
+    let compound = sample_compound();
+    let ccompound = compound.compress();
+    assert_eq!(ccompound.solids.len(), 2);
+    assert_eq!(ccompound.shells.len(), 1);
+    assert_eq!(ccompound.wires[0].vertices, vec![30, 31, 32]);
+    assert_eq!(ccompound.vertices, vec![40]);
+
+    let extracted = Compound::extract(ccompound.clone()).unwrap();
+    assert_eq!(extracted.compress(), ccompound);
+    let wire = &extracted.wires()[0];
+    assert!(wire.is_continuous());
+    assert_eq!(wire.back_vertex().unwrap().point(), 32);
+}
+
+#[test]
+fn mapped_compound() {
+    let compound = sample_compound();
+    let mapped = compound.mapped(|p| *p as f64, |c| *c as f64 * 2.0, |s| *s as f64 * 3.0);
+    let ccompound = mapped.compress();
+    assert_eq!(ccompound.vertices, vec![40.0]);
+    assert_eq!(ccompound.wires[0].edges[1].curve, 62.0);
+    assert_eq!(ccompound.solids[1].boundaries[0].faces[0].surface, 30.0);
+
+    let failed = compound.try_mapped(|p| Some(*p), |c| Some(*c), |s| (*s < 20).then_some(*s));
+    assert!(failed.is_none());
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use truck_geometry::prelude::*;
+use truck_topology::{diff::ShapeDiff, *};
+
+type Curve = BSplineCurve<Point3>;
+
+fn two_squares() -> Shell<usize, usize, usize> {
+    let v = Vertex::news([0, 1, 2, 3, 4, 5]);
+    let edge = [
+        Edge::new(&v[0], &v[1], 0),
+        Edge::new(&v[1], &v[2], 1),
+        Edge::new(&v[3], &v[4], 2),
+        Edge::new(&v[4], &v[5], 3),
+        Edge::new(&v[0], &v[3], 4),
+        Edge::new(&v[1], &v[4], 5),
+        Edge::new(&v[2], &v[5], 6),
+    ];
+    let wire0 = Wire::from(vec![
+        edge[0].clone(),
+        edge[5].clone(),
+        edge[2].inverse(),
+        edge[4].inverse(),
+    ]);
+    let wire1 = Wire::from(vec![
+        edge[1].clone(),
+        edge[6].clone(),
+        edge[3].inverse(),
+        edge[5].inverse(),
+    ]);
+    vec![Face::new(vec![wire0], 0), Face::new(vec![wire1], 1)].into()
+}
+
+fn compare(
+    shell0: &Shell<usize, usize, usize>,
+    shell1: &Shell<usize, usize, usize>,
+) -> diff::ShapeDiff<usize, usize> {
+    shell0.diff_by(shell1, PartialEq::eq, PartialEq::eq, PartialEq::eq)
+}
+
+#[test]
+fn equivalent_shells() {
+    let shell0 = two_squares();
+    let shell1 = two_squares();
+    assert!(compare(&shell0, &shell1).is_equivalent());
+    assert!(compare(&shell0, &shell0).is_equivalent());
+}
+
+#[test]
+fn moved_vertex() {
+    let shell0 = two_squares();
+    let shell1 = two_squares();
+    let edges1: Vec<_> = shell1[1].boundaries()[0].iter().cloned().collect();
+    edges1[0].back().set_point(10);
+
+    let diff = compare(&shell0, &shell1);
+    assert!(!diff.is_equivalent());
+    let edges0: Vec<_> = shell0[1].boundaries()[0].iter().cloned().collect();
+    assert_eq!(diff.removed_edges, vec![edges0[0].id(), edges0[1].id()]);
+    assert_eq!(diff.added_edges, vec![edges1[0].id(), edges1[1].id()]);
+    assert!(diff.modified_edges.is_empty());
+    assert_eq!(diff.modified_faces, vec![(shell0[1].id(), shell1[1].id())]);
+    assert!(diff.added_faces.is_empty() && diff.removed_faces.is_empty());
+}
+
+#[test]
+fn modified_geometries() {
+    let shell0 = two_squares();
+    let mut shell1 = two_squares();
+    shell1[0].set_surface(7);
+    shell1[1].invert();
+    let diff = compare(&shell0, &shell1);
+    assert_eq!(
+        diff.modified_faces,
+        vec![
+            (shell0[0].id(), shell1[0].id()),
+            (shell0[1].id(), shell1[1].id())
+        ],
+    );
+    assert!(diff.modified_edges.is_empty());
+
+    let shell1 = two_squares();
+    let edge0 = shell0[0].boundaries()[0][1].clone();
+    let edge1 = shell1[0].boundaries()[0][1].clone();
+    edge1.set_curve(7);
+    let diff = compare(&shell0, &shell1);
+    assert_eq!(diff.modified_edges, vec![(edge0.id(), edge1.id())]);
+    assert_eq!(
+        diff.modified_faces,
+        vec![
+            (shell0[0].id(), shell1[0].id()),
+            (shell0[1].id(), shell1[1].id())
+        ],
+    );
+}
+
+#[test]
+fn removed_face() {
+    let shell0 = two_squares();
+    let mut shell1 = two_squares();
+    let face = shell1.pop().unwrap();
+    let diff = compare(&shell0, &shell1);
+    assert_eq!(diff.removed_faces, vec![shell0[1].id()]);
+    assert_eq!(diff.removed_edges.len(), 3);
+    assert!(diff.modified_faces.is_empty() && diff.added_faces.is_empty());
+
+    shell1.push(face);
+    assert!(compare(&shell0, &shell1).is_equivalent());
+}
+
+fn line(p: Point3, q: Point3) -> Curve { BSplineCurve::new(KnotVec::bezier_knot(1), vec![p, q]) }
+
+/// the cuboid `[0, 1] x [0, 1] x [0, height]`
+fn cuboid(height: f64) -> Solid<Point3, Curve, Plane> {
+    let p = [
+        (0.0, 0.0, 0.0),
+        (1.0, 0.0, 0.0),
+        (1.0, 1.0, 0.0),
+        (0.0, 1.0, 0.0),
+        (0.0, 0.0, height),
+        (1.0, 0.0, height),
+        (1.0, 1.0, height),
+        (0.0, 1.0, height),
+    ]
+    .map(|(x, y, z)| Point3::new(x, y, z));
+    let v = Vertex::news(p);
+    let indices = [
+        [0, 3, 2, 1],
+        [0, 1, 5, 4],
+        [1, 2, 6, 5],
+        [2, 3, 7, 6],
+        [3, 0, 4, 7],
+        [4, 5, 6, 7],
+    ];
+    let mut edges = HashMap::<(usize, usize), Edge<Point3, Curve>>::new();
+    let shell: Shell<_, _, _> = indices
+        .iter()
+        .map(|idx| {
+            let wire: Wire<_, _> = (0..4)
+                .map(|k| {
+                    let (i, j) = (idx[k], idx[(k + 1) % 4]);
+                    match edges.get(&(j, i)) {
+                        Some(edge) => edge.inverse(),
+                        None => {
+                            let edge = Edge::new(&v[i], &v[j], line(p[i], p[j]));
+                            edges.insert((i, j), edge.clone());
+                            edge
+                        }
+                    }
+                })
+                .collect();
+            let plane = Plane::new(p[idx[0]], p[idx[1]], p[idx[3]]);
+            Face::new(vec![wire], plane)
+        })
+        .collect();
+    Solid::new(vec![shell])
+}
+
+#[test]
+fn shape_diff() {
+    let cube = cuboid(1.0);
+    assert!(cube.diff(&cuboid(1.0), TOLERANCE).is_equivalent());
+
+    let diff = cube.diff(&cuboid(2.0), TOLERANCE);
+    let face_counts = |diff: &ShapeDiff<Curve, Plane>| {
+        let (added, removed) = (diff.added_faces.len(), diff.removed_faces.len());
+        (added, removed, diff.modified_faces.len())
+    };
+    assert_eq!(face_counts(&diff), (1, 1, 4));
+    assert_eq!((diff.added_edges.len(), diff.removed_edges.len()), (8, 8));
+    assert!(diff.modified_edges.is_empty());
+
+    // The line is replaced by the same line in another representation.
+    let other = cuboid(1.0);
+    let edge = other.edge_iter().next().unwrap();
+    let (p0, p1) = (edge.absolute_front().point(), edge.absolute_back().point());
+    let mut curve = line(p0, p1);
+    curve.elevate_degree().add_knot(0.5);
+    edge.set_curve(curve);
+    assert!(cube.diff(&other, TOLERANCE).is_equivalent());
+
+    let middle = p0.midpoint(p1) + Vector3::new(0.1, 0.1, 0.1);
+    edge.set_curve(BSplineCurve::new(
+        KnotVec::bezier_knot(2),
+        vec![p0, middle, p1],
+    ));
+    let diff = cube.diff(&other, TOLERANCE);
+    let original = cube.edge_iter().next().unwrap();
+    assert_eq!(diff.modified_edges, vec![(original.id(), edge.id())]);
+    assert_eq!(face_counts(&diff), (0, 0, 2));
+}
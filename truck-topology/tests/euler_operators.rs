@@ -201,3 +201,102 @@ fn solid_cut_edge() {
     let count = tri.edge_iter().count();
     assert_eq!(count, 12);
 }
+
+#[test]
+fn euler_operators_round_trip() {
+    let v = Vertex::news([(); 4]);
+    let edge = [
+        Edge::new(&v[0], &v[1], ()),
+        Edge::new(&v[0], &v[2], ()),
+        Edge::new(&v[0], &v[3], ()),
+        Edge::new(&v[1], &v[2], ()),
+        Edge::new(&v[1], &v[3], ()),
+        Edge::new(&v[2], &v[3], ()),
+    ];
+    let wire = vec![
+        Wire::from_iter(vec![&edge[0], &edge[3], &edge[1].inverse()]),
+        Wire::from_iter(vec![&edge[1], &edge[5], &edge[2].inverse()]),
+        Wire::from_iter(vec![&edge[2], &edge[4].inverse(), &edge[0].inverse()]),
+        Wire::from_iter(vec![&edge[3], &edge[5], &edge[4].inverse()]),
+    ];
+    let mut faces: Vec<Face<(), (), ()>> =
+        wire.into_iter().map(|w| Face::new(vec![w], ())).collect();
+    faces[3].invert();
+    let face_id = faces[0].id();
+    let mut solid = Solid::new(vec![faces.into()]);
+    let org_solid = solid.clone();
+
+    // put a new vertex on the face and connect it to the other vertex.
+    let new_vertex = Vertex::new(());
+    let edge0 = Edge::new(&v[0], &new_vertex, ());
+    let edge1 = Edge::new(&new_vertex, &v[2], ());
+    assert!(!solid.mev(face_id, Edge::new(&v[0], &v[1], ())));
+    assert!(solid.mev(face_id, edge0.clone()));
+    assert_eq!(solid.boundaries()[0].euler_characteristic(), 2);
+    let new_face_id = solid.mef(face_id, edge1.clone()).unwrap();
+    assert_eq!(solid.boundaries()[0].euler_characteristic(), 2);
+    assert_eq!(solid.boundaries()[0].len(), 5);
+    assert!(Solid::try_new(solid.boundaries().clone()).is_ok());
+    assert!(solid
+        .boundaries()
+        .iter()
+        .flat_map(Shell::face_iter)
+        .all(|face| Face::try_new(face.boundaries(), ()).is_ok()));
+    assert!(solid.face_iter().any(|face| face.id() == new_face_id));
+
+    // inverse operations
+    assert_eq!(solid.kev(edge1.id()), None);
+    assert_eq!(solid.kef(edge1.id()), Some(edge1));
+    assert_eq!(solid.kev(edge0.id()), Some(edge0));
+    assert_eq!(solid.boundaries()[0].euler_characteristic(), 2);
+    // the boundary wires are the same up to rotations.
+    let face_edges = |solid: &Solid<(), (), ()>| {
+        solid
+            .face_iter()
+            .map(|face| {
+                let mut ids: Vec<_> = face
+                    .edge_iter()
+                    .map(|e| (e.id(), e.orientation()))
+                    .collect();
+                let i = (0..ids.len())
+                    .min_by_key(|i| format!("{:?}", ids[*i]))
+                    .unwrap();
+                ids.rotate_left(i);
+                (face.id(), ids)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(face_edges(&solid), face_edges(&org_solid));
+}
+
+#[test]
+fn euler_operators_make_hole() {
+    let v = Vertex::news([(); 3]);
+    let wire = Wire::from(vec![
+        Edge::new(&v[0], &v[1], ()),
+        Edge::new(&v[1], &v[2], ()),
+        Edge::new(&v[2], &v[0], ()),
+    ]);
+    let face0: Face<(), (), ()> = Face::new(vec![wire.clone()], ());
+    let face1 = Face::new(vec![wire.inverse()], ());
+    let (face_id0, face_id1) = (face0.id(), face1.id());
+    let mut shell: Shell<_, _, _> = vec![face0, face1].into();
+
+    let hole = shell.kfmrh(face_id0, face_id1).unwrap();
+    assert_eq!(hole.id(), face_id1);
+    assert_eq!(shell.euler_characteristic(), 0);
+    assert_eq!(shell.shell_condition(), shell::ShellCondition::Closed);
+
+    // connect the two rings and separate them again.
+    let edge = Edge::new(&v[0], &v[1], ());
+    assert!(shell.mekr(face_id0, edge.clone()));
+    assert_eq!(shell[0].boundaries().len(), 1);
+    assert_eq!(shell.euler_characteristic(), 0);
+    assert_eq!(shell.shell_condition(), shell::ShellCondition::Closed);
+    assert_eq!(shell.kemr(edge.id()), Some(edge));
+    assert_eq!(shell[0].boundaries().len(), 2);
+
+    shell.mfkrh(face_id0, 1, ()).unwrap();
+    assert_eq!(shell.euler_characteristic(), 2);
+    assert_eq!(shell.shell_condition(), shell::ShellCondition::Closed);
+}
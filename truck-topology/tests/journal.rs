@@ -0,0 +1,151 @@
+use truck_topology::journal::*;
+use truck_topology::*;
+
+mod common;
+use common::tetrahedron;
+
+fn triangle() -> (Vec<Vertex<usize>>, Shell<usize, usize, usize>) {
+    let v = Vertex::news([0, 1, 2]);
+    let wire = Wire::from(vec![
+        Edge::new(&v[0], &v[1], 0),
+        Edge::new(&v[1], &v[2], 1),
+        Edge::new(&v[2], &v[0], 2),
+    ]);
+    (v, vec![Face::new(vec![wire], 0)].into())
+}
+
+#[test]
+fn undo_redo_geometry() {
+    let (v, shell) = triangle();
+    let edge = shell[0].boundaries()[0][1].clone();
+    let face = shell[0].clone();
+    let mut journal = Journal::new(shell);
+
+    let mut transaction = journal.transaction();
+    transaction.set_point(&v[0], 10);
+    transaction.set_point(&v[0], 20);
+    transaction.commit();
+    let mut transaction = journal.transaction();
+    transaction.set_curve(&edge, 11);
+    transaction.set_surface(&face, 12);
+    transaction.commit();
+    assert_eq!((journal.undo_len(), journal.redo_len()), (2, 0));
+
+    assert!(journal.undo());
+    assert_eq!((v[0].point(), edge.curve(), face.surface()), (20, 1, 0));
+    assert!(journal.undo());
+    assert_eq!(v[0].point(), 0);
+    assert!(!journal.undo());
+    assert_eq!((journal.undo_len(), journal.redo_len()), (0, 2));
+
+    assert!(journal.redo());
+    assert_eq!(v[0].point(), 20);
+    assert!(journal.redo());
+    assert_eq!((edge.curve(), face.surface()), (11, 12));
+    assert!(!journal.redo());
+}
+
+#[test]
+fn undo_redo_topology() {
+    let solid = tetrahedron();
+    let original = solid.clone();
+    let mut journal = Journal::new(solid);
+
+    let mut transaction = journal.transaction();
+    transaction.edit(Solid::not);
+    let inverted = transaction.shape().clone();
+    let face = inverted.boundaries()[0][0].clone();
+    transaction.set_surface(&face, 5);
+    transaction.commit();
+    assert_eq!(*journal, inverted);
+
+    assert!(journal.undo());
+    assert_eq!(*journal, original);
+    assert_eq!(face.surface(), 0);
+    assert!(journal.redo());
+    assert_eq!(*journal, inverted);
+    assert_eq!(face.surface(), 5);
+    assert_eq!(journal.into_inner(), inverted);
+}
+
+#[test]
+fn rollback() {
+    let (v, shell) = triangle();
+    let mut journal = Journal::new(shell.clone());
+
+    let mut transaction = journal.transaction();
+    transaction.set_point(&v[1], 10);
+    transaction.edit(|shell| {
+        shell[0].invert();
+    });
+    transaction.set_point(&v[1], 20);
+    transaction.rollback();
+    assert_eq!(v[1].point(), 1);
+    assert_eq!(*journal, shell);
+
+    {
+        let mut transaction = journal.transaction();
+        transaction.set_point(&v[2], 10);
+    }
+    assert_eq!(v[2].point(), 2);
+    assert_eq!(journal.undo_len(), 0);
+
+    // empty transactions are not recorded
+    let mut transaction = journal.transaction();
+    transaction.edit(|_| {});
+    assert!(transaction.is_empty());
+    transaction.commit();
+    assert_eq!(journal.undo_len(), 0);
+}
+
+#[test]
+fn commit_discards_redo() {
+    let (v, shell) = triangle();
+    let mut journal = Journal::new(shell);
+    let mut transaction = journal.transaction();
+    transaction.set_point(&v[0], 10);
+    transaction.commit();
+    assert!(journal.undo());
+    assert_eq!(journal.redo_len(), 1);
+
+    let mut transaction = journal.transaction();
+    transaction.set_point(&v[0], 20);
+    transaction.commit();
+    assert_eq!((journal.undo_len(), journal.redo_len()), (1, 0));
+    assert!(!journal.redo());
+    assert!(journal.undo());
+    assert_eq!(v[0].point(), 0);
+
+    journal.clear_history();
+    assert_eq!((journal.undo_len(), journal.redo_len()), (0, 0));
+    assert_eq!(v[0].point(), 0);
+}
+
+#[test]
+fn geometry_outside_edit() {
+    let (v, shell) = triangle();
+    let mut journal = Journal::new(shell.clone());
+
+    let mut transaction = journal.transaction();
+    transaction.edit(|shell| {
+        shell[0].invert();
+    });
+    transaction.set_point(&v[0], 10);
+    transaction.commit();
+    assert!(journal.undo());
+    assert_eq!(*journal, shell);
+    assert_eq!(v[0].point(), 0);
+
+    // the geometries modified inside `edit` are not recorded
+    let mut transaction = journal.transaction();
+    transaction.edit(|_| v[1].set_point(10));
+    assert!(transaction.is_empty());
+    transaction.edit(|shell| {
+        shell[0].invert();
+        v[1].set_point(20);
+    });
+    transaction.commit();
+    assert!(journal.undo());
+    assert_eq!(*journal, shell);
+    assert_eq!(v[1].point(), 20);
+}
@@ -0,0 +1,88 @@
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+use truck_base::{assert_near, tolerance::*};
+use truck_geometry::prelude::*;
+use truck_topology::*;
+
+mod common;
+use common::cube;
+
+type Solid = truck_topology::Solid<Point3, Line<Point3>, Plane>;
+
+/// the sphere divided into four faces by the meridians
+fn sphere(
+    center: Point3,
+    radius: f64,
+) -> truck_topology::Solid<Point3, NurbsCurve<Vector4>, Sphere> {
+    let north = Vertex::new(center + radius * Vector3::unit_z());
+    let south = Vertex::new(center - radius * Vector3::unit_z());
+    let meridians: Vec<_> = (0..4)
+        .map(|i| {
+            let v = i as f64 * PI / 2.0;
+            let (c, s) = (f64::cos(v), f64::sin(v));
+            let weighted = |z: f64| Vector4::new(c, s, z, 1.0) * FRAC_1_SQRT_2;
+            let control_points = vec![
+                Vector4::new(0.0, 0.0, 1.0, 1.0),
+                weighted(1.0),
+                Vector4::new(c, s, 0.0, 1.0),
+                weighted(-1.0),
+                Vector4::new(0.0, 0.0, -1.0, 1.0),
+            ]
+            .into_iter()
+            .map(|p| {
+                let q = center.to_vec() * p.w + radius * p.truncate();
+                Vector4::new(q.x, q.y, q.z, p.w)
+            })
+            .collect();
+            let knots = KnotVec::from(vec![0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 2.0]);
+            let curve = NurbsCurve::new(BSplineCurve::new(knots, control_points));
+            Edge::new(&north, &south, curve)
+        })
+        .collect();
+    let shell: Shell<_, _, _> = (0..4)
+        .map(|i| {
+            let wire = vec![meridians[i].clone(), meridians[(i + 1) % 4].inverse()];
+            Face::new(vec![wire.into()], Sphere::new(center, radius))
+        })
+        .collect();
+    truck_topology::Solid::new(vec![shell])
+}
+
+#[test]
+fn cube_mass_properties() {
+    let solid = Solid::new(vec![cube(Point3::new(1.0, -2.0, 3.0), 2.0)]);
+    let props = solid.mass_properties(TOLERANCE).unwrap();
+    assert_near!(props.volume, 8.0);
+    assert_near!(props.area, 24.0);
+    assert_near!(props.centroid, Point3::new(2.0, -1.0, 4.0));
+    // the moment of inertia of the cube with the side `a` is `a^5 / 6`
+    assert_near!(props.inertia, Matrix3::identity() * 32.0 / 6.0);
+
+    // the cavity is subtracted
+    let mut cavity = cube(Point3::new(1.0, -2.0, 3.0), 1.0);
+    cavity.iter_mut().for_each(|face| {
+        face.invert();
+    });
+    let hollow = Solid::new(vec![cube(Point3::new(1.0, -2.0, 3.0), 2.0), cavity]);
+    let props = hollow.mass_properties(TOLERANCE).unwrap();
+    assert_near!(props.volume, 7.0);
+    assert_near!(props.area, 30.0);
+    // the centroids of the whole cube and the cavity are (2, -1, 4) and (1.5, -1.5, 3.5)
+    let centroid = Point3::new(2.0, -1.0, 4.0) + (Vector3::new(0.5, 0.5, 0.5) / 7.0);
+    assert_near!(props.centroid, centroid);
+}
+
+#[test]
+fn sphere_mass_properties() {
+    let center = Point3::new(1.0, -2.0, 3.0);
+    let solid = sphere(center, 2.0);
+    let props = solid.mass_properties(TOLERANCE).unwrap();
+    assert_near!(props.volume, 4.0 * PI * 8.0 / 3.0);
+    assert_near!(props.area, 4.0 * PI * 4.0);
+    assert_near!(props.centroid, center);
+    // the moment of inertia of the ball with the radius `r` is `8 PI r^5 / 15`
+    assert_near!(props.inertia, Matrix3::identity() * 8.0 * PI * 32.0 / 15.0);
+    let (moments, _) = props.principal_axes();
+    moments
+        .iter()
+        .for_each(|moment| assert_near!(*moment, 8.0 * PI * 32.0 / 15.0));
+}
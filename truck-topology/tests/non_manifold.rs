@@ -0,0 +1,86 @@
+use truck_topology::{non_manifold::*, *};
+
+// Two tetrahedra sharing the triangle (0, 1, 2), with the apexes 3 and 4.
+fn bipyramid() -> (Solid<usize, usize, usize>, Solid<usize, usize, usize>) {
+    let v = Vertex::news([0, 1, 2, 3, 4]);
+    let mut edges = std::collections::HashMap::new();
+    let mut edge = |i: usize, j: usize| -> Edge<usize, usize> {
+        let (a, b) = (usize::min(i, j), usize::max(i, j));
+        let edge = edges
+            .entry((a, b))
+            .or_insert_with(|| Edge::new(&v[a], &v[b], 10 * a + b))
+            .clone();
+        match i < j {
+            true => edge,
+            false => edge.inverse(),
+        }
+    };
+    let mut triangle = |[i, j, k]: [usize; 3]| {
+        let wire: Wire<_, _> = vec![edge(i, j), edge(j, k), edge(k, i)].into();
+        Face::new(vec![wire], 100 * i + 10 * j + k)
+    };
+    let shared = triangle([0, 1, 2]);
+    let upper = vec![
+        shared.inverse(),
+        triangle([0, 1, 3]),
+        triangle([1, 2, 3]),
+        triangle([2, 0, 3]),
+    ];
+    let lower = vec![
+        shared,
+        triangle([1, 0, 4]),
+        triangle([2, 1, 4]),
+        triangle([0, 2, 4]),
+    ];
+    (
+        Solid::new(vec![upper.into()]),
+        Solid::new(vec![lower.into()]),
+    )
+}
+
+#[test]
+fn cells_sharing_face() {
+    let (upper, lower) = bipyramid();
+    let complex: CellComplex<_, _, _> = [upper.clone(), lower.clone()].into_iter().collect();
+    assert_eq!(complex.faces().len(), 7);
+    assert_eq!(complex.cells().len(), 2);
+    assert_eq!(complex.internal_faces(), vec![0]);
+    assert!(complex.dangling_faces().is_empty());
+    assert_eq!(complex.face_cells(0), vec![(0, true), (1, false)]);
+    assert_eq!(complex.face_index(upper.boundaries()[0][0].id()), Some(0));
+    assert_eq!(complex.non_manifold_edges().len(), 3);
+
+    let solids = complex.to_solids().unwrap();
+    assert_eq!(solids[0].boundaries()[0], upper.boundaries()[0]);
+    assert_eq!(solids[1].boundaries()[0], lower.boundaries()[0]);
+
+    let merged = complex.merged_solid([0, 1]).unwrap();
+    assert_eq!(merged.boundaries().len(), 1);
+    assert_eq!(merged.boundaries()[0].len(), 6);
+    let merged = CellComplex::from(merged);
+    assert!(merged.internal_faces().is_empty());
+    assert!(merged.non_manifold_edges().is_empty());
+}
+
+#[test]
+fn dangling_elements() {
+    let (upper, _) = bipyramid();
+    let mut complex = CellComplex::from(upper.clone());
+    let v = Vertex::news([5, 6]);
+    let (v0, v1) = (
+        upper.vertex_iter().next().unwrap(),
+        upper.vertex_iter().nth(1).unwrap(),
+    );
+    let wire: Wire<_, _> = vec![
+        upper.boundaries()[0][0].boundaries()[0][0].inverse(),
+        Edge::new(&v0, &v[0], 50),
+        Edge::new(&v[0], &v1, 51),
+    ]
+    .into();
+    let index = complex.push_face(&Face::new(vec![wire], 500));
+    complex.push_edge(Edge::new(&v[0], &v[1], 60));
+    assert_eq!(complex.dangling_faces(), vec![index]);
+    assert_eq!(complex.dangling_edges().len(), 1);
+    assert_eq!(complex.non_manifold_edges().len(), 1);
+    assert!(complex.merged_solid([0]).is_ok());
+}
@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+use truck_geometry::prelude::*;
+use truck_topology::{shell::ShellCondition, *};
+
+type Face = truck_topology::Face<Point3, Line<Point3>, Plane>;
+
+/// the quadrangle face whose vertices and edges are not shared with other faces
+fn quadrangle(p: [Point3; 4]) -> Face {
+    let v = Vertex::news(p);
+    let wire: Wire<_, _> = (0..4)
+        .map(|i| Edge::new(&v[i], &v[(i + 1) % 4], Line(p[i], p[(i + 1) % 4])))
+        .collect();
+    Face::new(vec![wire], Plane::new(p[0], p[1], p[3]))
+}
+
+#[test]
+fn sew_separately_attached_planes() {
+    let p = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(1.0, 0.0, 1.0),
+        Point3::new(1.0, 1.0, 1.0),
+        Point3::new(0.0, 1.0, 1.0),
+    ];
+    let indices = [
+        [0, 3, 2, 1],
+        [0, 1, 5, 4],
+        [1, 2, 6, 5],
+        [2, 3, 7, 6],
+        [3, 0, 4, 7],
+        [4, 5, 6, 7],
+    ];
+    let faces: Vec<Face> = indices
+        .iter()
+        .map(|idx| quadrangle(idx.map(|i| p[i])))
+        .collect();
+    let shell: Shell<_, _, _> = faces.clone().into();
+    assert_ne!(shell.shell_condition(), ShellCondition::Closed);
+    assert_eq!(shell.vertex_iter().count(), 24);
+
+    let shell = Shell::sew(faces, 1.0e-6);
+    assert_eq!(shell.shell_condition(), ShellCondition::Closed);
+    assert_eq!(shell.edge_iter().count(), 24);
+    let vertex_ids: HashSet<_> = shell.vertex_iter().map(|v| v.id()).collect();
+    let edge_ids: HashSet<_> = shell.edge_iter().map(|e| e.id()).collect();
+    assert_eq!((vertex_ids.len(), edge_ids.len()), (8, 12));
+    let solid = Solid::try_new(vec![shell]).unwrap();
+    assert!(solid.check(1.0e-6).is_empty());
+}
@@ -0,0 +1,128 @@
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use truck_topology::*;
+
+/// The counter and the auto issuing of stable ids are shared by the process,
+/// so the tests issuing ids hold this lock and start from the default state.
+/// The counter is never lowered, so the ids are compared with the next id at the beginning.
+static STATE: Mutex<()> = Mutex::new(());
+
+/// Restores the default state on drop, even if the test panics.
+struct StateGuard {
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Drop for StateGuard {
+    fn drop(&mut self) { StableID::set_auto_issue(false) }
+}
+
+fn lock_state() -> StateGuard {
+    let guard = STATE.lock().unwrap_or_else(PoisonError::into_inner);
+    StableID::set_auto_issue(false);
+    StateGuard { _lock: guard }
+}
+
+fn triangle_shell() -> Shell<usize, usize, usize> {
+    let v = Vertex::news([0, 1, 2]);
+    let wire: Wire<_, _> = vec![
+        Edge::new(&v[0], &v[1], 0),
+        Edge::new(&v[1], &v[2], 1),
+        Edge::new(&v[2], &v[0], 2),
+    ]
+    .into();
+    let face = Face::new(vec![wire], 0);
+    vec![face.clone(), face.inverse()].into()
+}
+
+fn stable_ids(shell: &Shell<usize, usize, usize>) -> Vec<Option<StableID>> {
+    let faces = shell.face_iter().map(Face::stable_id);
+    let edges = shell.edge_iter().map(|edge| edge.stable_id());
+    let vertices = shell.vertex_iter().map(|vertex| vertex.stable_id());
+    faces.chain(edges).chain(vertices).collect()
+}
+
+#[test]
+fn stable_ids_are_reproducible() {
+    let _state = lock_state();
+    let base = StableID::issue().get();
+    let shell = triangle_shell();
+    assert!(stable_ids(&shell).iter().all(Option::is_none));
+    let cshell = shell.compress();
+    assert!(cshell.vertex_stable_ids.is_empty());
+    assert!(cshell.faces.iter().all(|face| face.stable_id.is_none()));
+
+    // assigned in the order of faces, edges and vertices
+    shell.assign_stable_ids();
+    let ids: Vec<_> = stable_ids(&shell)
+        .into_iter()
+        .map(|id| id.unwrap().get() - base)
+        .collect();
+    assert_eq!(ids, vec![1, 1, 2, 3, 4, 4, 3, 2, 5, 6, 7, 5, 7, 6]);
+
+    // assigning again changes nothing
+    shell.assign_stable_ids();
+    assert_eq!(StableID::issue().get() - base, 8);
+}
+
+#[test]
+fn stable_ids_through_compression() {
+    let _state = lock_state();
+    let shell = triangle_shell();
+    shell.assign_stable_ids();
+    let extracted = Shell::extract(shell.compress()).unwrap();
+    assert_eq!(stable_ids(&extracted), stable_ids(&shell));
+    assert_ne!(extracted[0].id(), shell[0].id());
+
+    // restored ids are not issued again
+    let restored = StableID::issue().get() + 100;
+    let mut cshell = triangle_shell().compress();
+    cshell.faces[0].stable_id = StableID::new(restored);
+    Shell::extract(cshell).unwrap();
+    assert_eq!(StableID::issue().get(), restored + 1);
+}
+
+#[test]
+fn stable_ids_through_serialization() {
+    let _state = lock_state();
+    let solid = Solid::new(vec![triangle_shell()]);
+    solid.assign_stable_ids();
+    let bytes = binary::to_vec(&solid, binary::Compression::None).unwrap();
+    let restored: Solid<usize, usize, usize> = binary::from_slice(&bytes).unwrap();
+    let ids = |solid: &Solid<usize, usize, usize>| stable_ids(&solid.boundaries()[0]);
+    assert!(ids(&restored).iter().all(Option::is_some));
+    assert_eq!(ids(&solid), ids(&restored));
+}
+
+#[test]
+fn stable_ids_issued_on_creation() {
+    let _state = lock_state();
+    StableID::set_auto_issue(true);
+    let base0 = StableID::issue().get();
+    let shell0 = triangle_shell();
+    let base1 = StableID::issue().get();
+    let shell1 = triangle_shell();
+    StableID::set_auto_issue(false);
+    assert!(stable_ids(&shell0).iter().all(Option::is_some));
+    let relative_ids = |shell, base| -> Vec<u64> {
+        let ids = stable_ids(shell).into_iter();
+        ids.map(|id| id.unwrap().get() - base).collect()
+    };
+    assert_eq!(relative_ids(&shell0, base0), relative_ids(&shell1, base1));
+    assert!(triangle_shell()[0].stable_id().is_none());
+}
+
+#[test]
+fn deserialized_stable_ids_are_not_issued_again() {
+    let _state = lock_state();
+    let restored = StableID::issue().get() + 100;
+    let id: StableID = serde_json::from_str(&restored.to_string()).unwrap();
+    assert_eq!(id.get(), restored);
+
+    // reading the stable id does not move the counter
+    let vertex = Vertex::new(0);
+    vertex.set_stable_id(Some(id));
+    let next = StableID::issue().get();
+    assert_eq!(next, restored + 1);
+    assert_eq!(vertex.stable_id(), Some(id));
+    assert_eq!(vertex.assign_stable_id(), id);
+    assert_eq!(StableID::issue().get(), next + 1);
+}
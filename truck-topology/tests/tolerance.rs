@@ -0,0 +1,96 @@
+use truck_topology::*;
+
+#[test]
+fn compress_extract_tolerances() {
+    let v = Vertex::news([0, 1, 2]);
+    let edge = [
+        Edge::new(&v[0], &v[1], 0),
+        Edge::new(&v[1], &v[2], 1),
+        Edge::new(&v[2], &v[0], 2),
+    ];
+    let face = Face::new(vec![edge.iter().collect()], 0);
+    let shell: Shell<_, _, _> = vec![face.clone(), face.inverse()].into();
+
+    let cshell = shell.compress();
+    assert!(cshell.vertex_tolerances.is_empty());
+    assert!(cshell.edges.iter().all(|edge| edge.tolerance.is_none()));
+
+    v[1].set_tolerance(Some(1.0e-3));
+    edge[2].set_tolerance(Some(1.0e-2));
+    let cshell = shell.compress();
+    assert_eq!(cshell.vertex_tolerances, vec![None, Some(1.0e-3), None]);
+    assert_eq!(cshell.edges[2].tolerance, Some(1.0e-2));
+
+    let extracted = Shell::extract(cshell.clone()).unwrap();
+    assert_eq!(extracted.compress(), cshell);
+    let extracted_edge = &extracted[0].boundaries()[0][2];
+    assert_eq!(extracted_edge.tolerance(), Some(1.0e-2));
+    assert_eq!(extracted_edge.back().tolerance(), None);
+    assert_eq!(extracted_edge.front().tolerance(), None);
+    assert_eq!(
+        extracted[0].boundaries()[0][0].back().tolerance(),
+        Some(1.0e-3)
+    );
+
+    let mapped = shell.mapped(|p| *p as f64, |c| *c as f64, |s| *s as f64);
+    assert_eq!(
+        mapped.compress().vertex_tolerances,
+        vec![None, Some(1.0e-3), None]
+    );
+}
+
+#[test]
+fn local_tolerances() {
+    use truck_geometry::prelude::*;
+    let v0 = Vertex::new(Point3::new(0.0, 0.0, 0.0));
+    let v1 = Vertex::new(Point3::new(1.0, 0.0, 0.0));
+    let edge = Edge::new(&v0, &v1, Line(v0.point(), v1.point()));
+    assert!(edge.is_geometric_consistent());
+
+    v1.set_point(Point3::new(1.0, 1.0e-4, 0.0));
+    assert!(!edge.is_geometric_consistent());
+    v1.set_tolerance(Some(1.0e-3));
+    assert!(edge.is_geometric_consistent());
+    v1.set_tolerance(None);
+    edge.set_tolerance(Some(1.0e-3));
+    assert!(edge.is_geometric_consistent());
+}
+
+#[test]
+fn toleranced_edge_off_surface() {
+    use truck_geometry::prelude::*;
+    let p = [
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ];
+    let v = Vertex::news(p);
+    let line = |p: Point3, q: Point3| BSplineCurve::new(KnotVec::bezier_knot(1), vec![p, q]);
+    // the curve bulges out of the plane by 0.25 in the middle
+    let bulge = BSplineCurve::new(
+        KnotVec::bezier_knot(2),
+        vec![p[0], Point3::new(0.5, 0.0, 0.5), p[1]],
+    );
+    let edges = [
+        Edge::new(&v[0], &v[1], bulge),
+        Edge::new(&v[1], &v[2], line(p[1], p[2])),
+        Edge::new(&v[2], &v[3], line(p[2], p[3])),
+        Edge::new(&v[3], &v[0], line(p[3], p[0])),
+    ];
+    let face = Face::new(vec![edges.iter().collect()], Plane::new(p[0], p[1], p[3]));
+    assert!(!face.is_geometric_consistent());
+    assert!(!face.is_geometric_consistent_with_tolerances());
+
+    // the curve is out of its tolerance, though the edge itself is consistent.
+    edges[0].set_tolerance(Some(1.0e-3));
+    assert!(edges[0].is_geometric_consistent());
+    // `is_geometric_consistent` does not check the curves of the toleranced edges.
+    assert!(face.is_geometric_consistent());
+    assert!(!face.is_geometric_consistent_with_tolerances());
+
+    edges[0].set_tolerance(Some(0.3));
+    assert!(face.is_geometric_consistent_with_tolerances());
+    edges[1].set_tolerance(Some(1.0e-3));
+    assert!(face.is_geometric_consistent_with_tolerances());
+}
@@ -0,0 +1,122 @@
+use truck_geometry::prelude::*;
+use truck_topology::{validity::ValidityIssue, *};
+
+mod common;
+use common::cube;
+
+type Edge = truck_topology::Edge<Point3, Line<Point3>>;
+type Wire = truck_topology::Wire<Point3, Line<Point3>>;
+type Face = truck_topology::Face<Point3, Line<Point3>, Plane>;
+type Solid = truck_topology::Solid<Point3, Line<Point3>, Plane>;
+
+const TOL: f64 = 1.0e-6;
+
+/// the closed wire through the vertices
+fn polygon(v: &[Vertex<Point3>]) -> Wire {
+    (0..v.len())
+        .map(|i| {
+            let (v0, v1) = (&v[i], &v[(i + 1) % v.len()]);
+            Edge::new(v0, v1, Line(v0.point(), v1.point()))
+        })
+        .collect()
+}
+
+/// the plane on which the points of `p` run counter-clockwise
+fn plane(p: [Point3; 3]) -> Plane { Plane::new(p[0], p[1], p[2]) }
+
+#[test]
+fn valid_cube() {
+    let cube = Solid::new(vec![cube(Point3::origin(), 1.0)]);
+    assert!(cube.check(TOL).is_empty());
+}
+
+#[test]
+fn wrong_loop_orientation() {
+    // the inner boundary has the same orientation as the outer one.
+    let square = |x0: f64, x1: f64| {
+        let v = Vertex::news(
+            [(x0, x0), (x1, x0), (x1, x1), (x0, x1)].map(|(x, y)| Point3::new(x, y, 0.0)),
+        );
+        polygon(&v)
+    };
+    let xy = plane([
+        Point3::origin(),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ]);
+    let face = Face::new(vec![square(0.0, 3.0), square(1.0, 2.0)], xy);
+    assert_eq!(
+        face.check(TOL),
+        vec![ValidityIssue::WrongLoopOrientation(face.id(), 1)],
+    );
+    let face = Face::new(vec![square(0.0, 3.0), square(1.0, 2.0).inverse()], xy);
+    assert!(face.check(TOL).is_empty());
+}
+
+#[test]
+fn self_intersecting_wire() {
+    // bowtie
+    let v = Vertex::news(
+        [(0.0, 0.0), (1.0, 1.0), (1.0, 0.0), (0.0, 1.0)].map(|(x, y)| Point3::new(x, y, 0.0)),
+    );
+    let xy = plane([
+        Point3::origin(),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    ]);
+    let face = Face::new(vec![polygon(&v)], xy);
+    let issues = face.check(TOL);
+    assert!(issues.contains(&ValidityIssue::SelfIntersectingWire(face.id(), 0)));
+}
+
+#[test]
+fn vertex_not_on_curve() {
+    let shell = cube(Point3::origin(), 1.0);
+    let vertex = shell.vertex_iter().next().unwrap();
+    vertex.set_point(Point3::new(-0.1, -0.1, -0.1));
+    let issues = shell.check(TOL);
+    assert_eq!(issues.len(), 3);
+    assert!(issues.iter().all(|issue| matches!(
+        issue,
+        ValidityIssue::VertexNotOnCurve(_, v) if *v == vertex.id()
+    )));
+    vertex.set_point(Point3::origin());
+    assert!(shell.check(TOL).is_empty());
+}
+
+#[test]
+fn inconsistent_orientation() {
+    let mut shell = cube(Point3::origin(), 1.0);
+    shell[0].invert();
+    let issues = shell.check(TOL);
+    assert_eq!(issues.len(), 4);
+    let edges: Vec<_> = shell[0].edge_iter().map(|edge| edge.id()).collect();
+    assert!(issues.iter().all(|issue| matches!(
+        issue,
+        ValidityIssue::InconsistentOrientation(e) if edges.contains(e)
+    )));
+}
+
+#[test]
+fn inner_shell_outside_outer() {
+    let with_cavity = |origin: Point3| {
+        let mut inner = cube(origin, 1.0);
+        inner.iter_mut().for_each(|face| {
+            face.invert();
+        });
+        Solid::new_unchecked(vec![cube(Point3::origin(), 2.0), inner])
+    };
+    let solid = with_cavity(Point3::new(0.5, 0.5, 0.5));
+    assert!(solid.check(TOL).is_empty());
+    let solid = with_cavity(Point3::new(0.0, 0.0, 1.5));
+    assert_eq!(
+        solid.check(TOL),
+        vec![ValidityIssue::InnerShellOutsideOuter(1)],
+    );
+
+    // open shell
+    let mut shell = cube(Point3::origin(), 1.0);
+    shell.pop();
+    let solid = Solid::new_unchecked(vec![shell]);
+    assert_eq!(solid.check(TOL), vec![ValidityIssue::OpenShell(0)]);
+}